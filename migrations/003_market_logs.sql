@@ -29,6 +29,10 @@ ALTER TABLE market_logs ADD COLUMN IF NOT EXISTS edge_down_sell DECIMAL(10, 6);
 ALTER TABLE market_logs ADD COLUMN IF NOT EXISTS bid_size_up NUMERIC(20, 6);
 ALTER TABLE market_logs ADD COLUMN IF NOT EXISTS bid_size_down NUMERIC(20, 6);
 
+-- Add the model's Wilson bounds on P(UP) (what the bot enters on, replayed by btc-strategy-sim)
+ALTER TABLE market_logs ADD COLUMN IF NOT EXISTS p_up_wilson_lower DECIMAL(10, 6);
+ALTER TABLE market_logs ADD COLUMN IF NOT EXISTS p_up_wilson_upper DECIMAL(10, 6);
+
 -- Index for time-based queries
 CREATE INDEX IF NOT EXISTS idx_market_logs_timestamp ON market_logs (timestamp DESC);
 
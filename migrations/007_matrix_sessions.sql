@@ -0,0 +1,65 @@
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- BTC 15-Minute Bot - Session-Conditioned Matrix Snapshots
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- Tags each snapshot with the session it was built from ('all' = pooled matrix).
+-- One active snapshot per session; the bot picks the session matching the
+-- current time and falls back to 'all' for sparse cells.
+
+ALTER TABLE matrix_snapshots ADD COLUMN IF NOT EXISTS session TEXT NOT NULL DEFAULT 'all';
+ALTER TABLE matrix_snapshots ADD COLUMN IF NOT EXISTS session_json JSONB;  -- Session definition (hours/days)
+
+CREATE INDEX IF NOT EXISTS idx_matrix_snapshots_session ON matrix_snapshots(session, is_active, created_at DESC);
+
+-- Save a matrix for one session (marks that session's previous snapshot inactive)
+CREATE OR REPLACE FUNCTION save_session_matrix(
+    p_session TEXT,
+    p_session_json JSONB,
+    p_matrix_json JSONB,
+    p_total_windows INTEGER,
+    p_data_start TIMESTAMPTZ,
+    p_data_end TIMESTAMPTZ
+) RETURNS INTEGER AS $$
+DECLARE
+    v_id INTEGER;
+BEGIN
+    UPDATE matrix_snapshots SET is_active = FALSE WHERE is_active = TRUE AND session = p_session;
+
+    INSERT INTO matrix_snapshots (matrix_json, total_windows, data_start, data_end, is_active, session, session_json)
+    VALUES (p_matrix_json, p_total_windows, p_data_start, p_data_end, TRUE, p_session, p_session_json)
+    RETURNING id INTO v_id;
+
+    -- Cleanup: keep only last 10 snapshots per session
+    DELETE FROM matrix_snapshots
+    WHERE session = p_session
+      AND id NOT IN (
+        SELECT id FROM matrix_snapshots WHERE session = p_session ORDER BY created_at DESC LIMIT 10
+    );
+
+    RETURN v_id;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Pooled matrix: same signature as before, now only replaces the 'all' snapshot
+CREATE OR REPLACE FUNCTION save_matrix(
+    p_matrix_json JSONB,
+    p_total_windows INTEGER,
+    p_data_start TIMESTAMPTZ,
+    p_data_end TIMESTAMPTZ
+) RETURNS INTEGER AS $$
+BEGIN
+    RETURN save_session_matrix('all', NULL, p_matrix_json, p_total_windows, p_data_start, p_data_end);
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION get_latest_matrix()
+RETURNS JSONB AS $$
+BEGIN
+    RETURN (
+        SELECT matrix_json
+        FROM matrix_snapshots
+        WHERE is_active = TRUE AND session = 'all'
+        ORDER BY created_at DESC
+        LIMIT 1
+    );
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,14 @@
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- BTC 15-Minute Bot - Raw Matrix Counts
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- Latest raw-count state of each matrix (terminal, first passage, price reach,
+-- price crossing, sessions) so `btc-probability-matrix update` can add the
+-- windows completed since the last run instead of rebuilding from scratch.
+-- data_end is the start of the last ingested window.
+
+CREATE TABLE IF NOT EXISTS matrix_counts (
+    name            TEXT PRIMARY KEY,              -- 'terminal', 'first_passage', ..., 'session:<name>'
+    matrix_json     JSONB NOT NULL,                -- Full matrix including raw counts
+    data_end        TIMESTAMPTZ,                   -- Last ingested window start
+    updated_at      TIMESTAMPTZ DEFAULT NOW()
+);
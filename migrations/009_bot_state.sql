@@ -0,0 +1,25 @@
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- BTC 15-Minute Bot - Persisted Risk State
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- Durable copy of the bot's risk state (bankroll, daily P&L, streaks, per-window
+-- bet counts, cooldowns, open positions). Redis holds the live snapshot; every
+-- version is also written here so a pod can rebuild when Redis is empty.
+
+CREATE TABLE IF NOT EXISTS bot_state_snapshots (
+    version BIGINT PRIMARY KEY,
+    state_json JSONB NOT NULL,
+    pod_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- What changed between consecutive versions (audit trail, not replayed)
+CREATE TABLE IF NOT EXISTS bot_state_events (
+    id BIGSERIAL PRIMARY KEY,
+    version BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    event_json JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_bot_state_events_version ON bot_state_events(version DESC);
+CREATE INDEX IF NOT EXISTS idx_bot_state_events_kind ON bot_state_events(kind, created_at DESC);
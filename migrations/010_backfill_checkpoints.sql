@@ -0,0 +1,20 @@
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- Backfill Checkpoints
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- One row per (job, 15-minute window) once that window has been fetched and
+-- upserted. Reruns skip checkpointed windows, so an interrupted backfill
+-- resumes where it stopped; windows with no data are recorded as 'empty' so
+-- they are not refetched on every run. Windows that hit the trade page cap
+-- are recorded as 'truncated' (rows missing; refetch with --force).
+
+CREATE TABLE IF NOT EXISTS backfill_windows (
+    job TEXT NOT NULL,                          -- e.g. 'polymarket_prices'
+    window_timestamp BIGINT NOT NULL,           -- Unix timestamp of window start
+    status TEXT NOT NULL,                       -- 'done', 'empty' or 'truncated'
+    rows_upserted INTEGER NOT NULL DEFAULT 0,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (job, window_timestamp)
+);
+
+CREATE INDEX IF NOT EXISTS idx_backfill_windows_completed
+ON backfill_windows (job, completed_at DESC);
@@ -0,0 +1,30 @@
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- Binance Kline Intervals and Aggregate Trades
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- binance_klines holds one series per interval ('1m', '1s'). Existing rows are
+-- 1-minute klines. The primary key on (symbol, timestamp) is replaced by a
+-- unique index that includes the interval so both series can coexist.
+
+ALTER TABLE binance_klines
+ADD COLUMN IF NOT EXISTS kline_interval VARCHAR(4) NOT NULL DEFAULT '1m';
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_binance_klines_symbol_interval_time
+ON binance_klines (symbol, kline_interval, timestamp);
+
+ALTER TABLE binance_klines DROP CONSTRAINT IF EXISTS binance_klines_pkey;
+
+-- Aggregate trades (GET /api/v3/aggTrades): every fill, for tick-level paths
+CREATE TABLE IF NOT EXISTS binance_agg_trades (
+    symbol VARCHAR(20) NOT NULL,
+    agg_trade_id BIGINT NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL,
+    price NUMERIC(20, 8) NOT NULL,
+    quantity NUMERIC(30, 8) NOT NULL,
+    first_trade_id BIGINT NOT NULL,
+    last_trade_id BIGINT NOT NULL,
+    is_buyer_maker BOOLEAN NOT NULL,
+    PRIMARY KEY (symbol, agg_trade_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_binance_agg_trades_symbol_time
+ON binance_agg_trades (symbol, timestamp);
@@ -0,0 +1,24 @@
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- BTC 15-Minute Bot - Hedged Pair Settlements
+-- ═══════════════════════════════════════════════════════════════════════════════
+-- One row per UP + DOWN pair at window settlement. The legs are in
+-- bot_executions (position_id = pair_id); this is the pair's realized P&L.
+
+CREATE TABLE IF NOT EXISTS bot_hedged_pairs (
+    id              SERIAL PRIMARY KEY,
+    pair_id         VARCHAR(64) NOT NULL UNIQUE,
+    origin          VARCHAR(10) NOT NULL,            -- 'PAIR', 'COMPLETE' or 'HEDGE'
+    window_start    TIMESTAMPTZ NOT NULL,
+    up_shares       NUMERIC(20, 8) NOT NULL,
+    up_price        NUMERIC(10, 6) NOT NULL,
+    down_shares     NUMERIC(20, 8) NOT NULL,
+    down_price      NUMERIC(10, 6) NOT NULL,
+    cost_usdc       NUMERIC(20, 8) NOT NULL,
+    payout_usdc     NUMERIC(20, 8) NOT NULL,
+    pnl_usdc        NUMERIC(20, 8) NOT NULL,
+    locked_pnl_usdc NUMERIC(20, 8) NOT NULL,
+    outcome         VARCHAR(10),                     -- 'UP', 'DOWN' or NULL if unknown
+    settled_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_bot_hedged_pairs_window ON bot_hedged_pairs(window_start DESC);
@@ -0,0 +1,700 @@
+//! Polymarket history backfills (replaces the scripts/*.py backfills)
+//!
+//! Every job walks settled 15-minute windows oldest first: fetch one window,
+//! upsert its rows and checkpoint it in `backfill_windows`, all in one
+//! transaction. A run only fetches missing windows - no rows in the job's
+//! table and no checkpoint - so an interrupted backfill resumes where it
+//! stopped and the hourly sync is the same command with a shorter lookback.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Client, Transaction};
+use tracing::{info, warn};
+
+use crate::http_client::HttpClient;
+
+const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com";
+const CLOB_API_URL: &str = "https://clob.polymarket.com";
+const DATA_API_URL: &str = "https://data-api.polymarket.com";
+
+/// BTC up/down markets open every 15 minutes
+pub const WINDOW_SECS: i64 = 900;
+
+/// Only windows that ended at least this long ago are backfilled (market
+/// resolved, trades final), so a checkpoint is never taken on partial data
+const SETTLE_SECS: i64 = 600;
+
+/// Trades are paged from the data API this many at a time
+const TRADES_PAGE_SIZE: usize = 500;
+
+/// Safety cap on trade pages per window
+const MAX_TRADE_PAGES: usize = 40;
+
+// ============================================================================
+// Jobs and Schema
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Job {
+    /// UP/DOWN price history per window (CLOB /prices-history)
+    PolymarketPrices,
+    /// Individual fills per window (data API /trades)
+    PolymarketTrades,
+}
+
+impl Job {
+    pub const ALL: [Job; 2] = [Job::PolymarketPrices, Job::PolymarketTrades];
+
+    /// Target table, also the checkpoint key
+    pub fn table(self) -> &'static str {
+        match self {
+            Job::PolymarketPrices => "polymarket_prices",
+            Job::PolymarketTrades => "polymarket_trades",
+        }
+    }
+}
+
+/// Columns the upserts and gap queries rely on
+const EXPECTED_COLUMNS: &[(&str, &[&str])] = &[
+    ("polymarket_prices", &["window_timestamp", "token_type", "token_id", "timestamp", "price"]),
+    (
+        "polymarket_trades",
+        &[
+            "transaction_hash", "condition_id", "window_timestamp", "slug", "timestamp", "outcome", "side",
+            "price", "size", "asset", "proxy_wallet",
+        ],
+    ),
+    ("backfill_windows", &["job", "window_timestamp", "status", "rows_upserted", "completed_at"]),
+];
+
+/// Create the job and checkpoint tables, then check their columns (tables
+/// created by older tooling are not altered by CREATE TABLE IF NOT EXISTS)
+pub async fn run_migrations(client: &Client) -> Result<()> {
+    client.batch_execute(include_str!("../migrations/005_polymarket_prices.sql")).await?;
+    client.batch_execute(include_str!("../migrations/006_polymarket_trades.sql")).await?;
+    client.batch_execute(include_str!("../migrations/010_backfill_checkpoints.sql")).await?;
+
+    for (table, expected) in EXPECTED_COLUMNS {
+        let rows = client
+            .query(
+                "SELECT column_name::text FROM information_schema.columns
+                 WHERE table_schema = current_schema() AND table_name = $1",
+                &[table],
+            )
+            .await?;
+        let columns: Vec<String> = rows.iter().map(|r| r.get(0)).collect();
+        let missing: Vec<&str> = expected.iter().copied().filter(|c| !columns.iter().any(|col| col == c)).collect();
+        if !missing.is_empty() {
+            return Err(anyhow!(
+                "Table {} does not match migrations/ (missing columns: {})",
+                table,
+                missing.join(", ")
+            ));
+        }
+    }
+    info!("Backfill migrations complete");
+    Ok(())
+}
+
+// ============================================================================
+// Windows, Checkpoints and Gaps
+// ============================================================================
+
+pub fn window_slug(window_timestamp: i64) -> String {
+    format!("btc-updown-15m-{}", window_timestamp)
+}
+
+/// First and last window start to backfill for a lookback ending `now`: from
+/// the window containing `now - lookback_secs` to the last settled window.
+/// Empty when first > last.
+pub fn window_range(now: i64, lookback_secs: i64) -> (i64, i64) {
+    let first = (now - lookback_secs).div_euclid(WINDOW_SECS) * WINDOW_SECS;
+    let last = (now - SETTLE_SECS - WINDOW_SECS).div_euclid(WINDOW_SECS) * WINDOW_SECS;
+    (first, last)
+}
+
+/// Windows in [first, last] with neither rows in the job's table nor a
+/// checkpoint (rows written by the old scripts count as done)
+pub async fn find_missing_windows(client: &Client, job: Job, first: i64, last: i64) -> Result<Vec<i64>> {
+    let query = format!(
+        r#"
+        SELECT w
+        FROM generate_series($1::bigint, $2::bigint, {step}) AS w
+        WHERE NOT EXISTS (
+            SELECT 1 FROM backfill_windows b WHERE b.job = $3 AND b.window_timestamp = w
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM {table} t WHERE t.window_timestamp = w
+        )
+        ORDER BY w
+        "#,
+        step = WINDOW_SECS,
+        table = job.table()
+    );
+    let rows = client.query(&query, &[&first, &last, &job.table()]).await?;
+    Ok(rows.iter().map(|r| r.get(0)).collect())
+}
+
+/// Contiguous runs of missing windows as (first, last) window starts
+pub fn group_gaps(windows: &[i64]) -> Vec<(i64, i64)> {
+    let mut gaps: Vec<(i64, i64)> = Vec::new();
+    for &w in windows {
+        match gaps.last_mut() {
+            Some((_, end)) if w == *end + WINDOW_SECS => *end = w,
+            _ => gaps.push((w, w)),
+        }
+    }
+    gaps
+}
+
+/// Checkpoint stats of a job
+#[derive(Debug, Default)]
+pub struct CheckpointStats {
+    pub done: i64,
+    pub empty: i64,
+    /// Stored with the trade page cap hit (some rows missing)
+    pub truncated: i64,
+    pub last_completed: Option<DateTime<Utc>>,
+}
+
+pub async fn checkpoint_stats(client: &Client, job: Job) -> Result<CheckpointStats> {
+    let row = client
+        .query_one(
+            "SELECT COUNT(*) FILTER (WHERE status = 'done'), COUNT(*) FILTER (WHERE status = 'empty'),
+                    COUNT(*) FILTER (WHERE status = 'truncated'), MAX(completed_at)
+             FROM backfill_windows WHERE job = $1",
+            &[&job.table()],
+        )
+        .await?;
+    Ok(CheckpointStats { done: row.get(0), empty: row.get(1), truncated: row.get(2), last_completed: row.get(3) })
+}
+
+/// Window starts in [first, last] checkpointed as truncated
+pub async fn find_truncated_windows(client: &Client, job: Job, first: i64, last: i64) -> Result<Vec<i64>> {
+    let rows = client
+        .query(
+            "SELECT window_timestamp FROM backfill_windows
+             WHERE job = $1 AND status = 'truncated' AND window_timestamp BETWEEN $2 AND $3
+             ORDER BY window_timestamp",
+            &[&job.table(), &first, &last],
+        )
+        .await?;
+    Ok(rows.iter().map(|r| r.get(0)).collect())
+}
+
+/// Checkpoint status of a fetched window
+fn window_status(rows: u64, truncated: bool) -> &'static str {
+    if truncated {
+        "truncated"
+    } else if rows > 0 {
+        "done"
+    } else {
+        "empty"
+    }
+}
+
+async fn checkpoint(tx: &Transaction<'_>, job: Job, window_timestamp: i64, rows: u64, truncated: bool) -> Result<()> {
+    let status = window_status(rows, truncated);
+    tx.execute(
+        "INSERT INTO backfill_windows (job, window_timestamp, status, rows_upserted)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (job, window_timestamp) DO UPDATE
+         SET status = EXCLUDED.status, rows_upserted = EXCLUDED.rows_upserted, completed_at = NOW()",
+        &[&job.table(), &window_timestamp, &status, &(rows as i32)],
+    )
+    .await?;
+    Ok(())
+}
+
+// ============================================================================
+// API Responses
+// ============================================================================
+
+/// The market of one window (Gamma API)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowMarket {
+    pub window_timestamp: i64,
+    pub slug: String,
+    pub condition_id: String,
+    pub up_token_id: String,
+    pub down_token_id: String,
+}
+
+impl WindowMarket {
+    /// Parse a Gamma `/markets?slug=` response (None: no market for this window)
+    pub fn from_gamma(window_timestamp: i64, response: &Value) -> Result<Option<Self>> {
+        let Some(market) = response.as_array().and_then(|markets| markets.first()) else {
+            return Ok(None);
+        };
+        let condition_id = market["conditionId"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Market without conditionId"))?;
+        let tokens = json_string_list(&market["clobTokenIds"]).context("Invalid clobTokenIds")?;
+        let outcomes = json_string_list(&market["outcomes"]).context("Invalid outcomes")?;
+        if tokens.len() < 2 || outcomes.len() != tokens.len() {
+            return Err(anyhow!("Expected 2 tokens and outcomes, got {} / {}", tokens.len(), outcomes.len()));
+        }
+
+        let index = |name: &str| outcomes.iter().position(|o| o.eq_ignore_ascii_case(name));
+        Ok(Some(Self {
+            window_timestamp,
+            slug: window_slug(window_timestamp),
+            condition_id: condition_id.to_string(),
+            up_token_id: tokens[index("up").unwrap_or(0)].clone(),
+            down_token_id: tokens[index("down").unwrap_or(1)].clone(),
+        }))
+    }
+}
+
+/// Gamma encodes lists as JSON strings (`"[\"a\", \"b\"]"`); accept arrays too
+fn json_string_list(value: &Value) -> Result<Vec<String>> {
+    let parsed;
+    let list = match value {
+        Value::String(s) => {
+            parsed = serde_json::from_str::<Value>(s)?;
+            &parsed
+        }
+        other => other,
+    };
+    list.as_array()
+        .ok_or_else(|| anyhow!("Not a list: {}", value))?
+        .iter()
+        .map(|v| v.as_str().map(str::to_string).ok_or_else(|| anyhow!("Not a string: {}", v)))
+        .collect()
+}
+
+/// A decimal the APIs send either as a JSON number or as a string
+fn json_decimal(value: &Value) -> Option<Decimal> {
+    let text = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => return None,
+    };
+    Decimal::from_str(&text).or_else(|_| Decimal::from_scientific(&text)).ok()
+}
+
+fn unix_time(value: &Value) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(value.as_i64()?, 0).single()
+}
+
+/// One point of a token's price history
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryPoint {
+    pub timestamp: DateTime<Utc>,
+    pub price: Decimal,
+}
+
+/// Parse a CLOB `/prices-history` response, ordered and one point per timestamp
+pub fn parse_price_history(response: &Value) -> Result<Vec<HistoryPoint>> {
+    let history = response["history"]
+        .as_array()
+        .ok_or_else(|| anyhow!("prices-history response without history"))?;
+    let mut points: Vec<HistoryPoint> = history
+        .iter()
+        .map(|point| {
+            Some(HistoryPoint { timestamp: unix_time(&point["t"])?, price: json_decimal(&point["p"])? })
+        })
+        .collect::<Option<_>>()
+        .ok_or_else(|| anyhow!("Invalid price history point"))?;
+    points.sort_by_key(|p| p.timestamp);
+    points.dedup_by_key(|p| p.timestamp);
+    Ok(points)
+}
+
+/// One fill from the data API
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeRow {
+    pub transaction_hash: String,
+    pub timestamp: DateTime<Utc>,
+    pub outcome: String,
+    pub side: String,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub asset: String,
+    pub proxy_wallet: String,
+}
+
+/// Parse a data API `/trades` page (entries without a transaction hash are skipped)
+pub fn parse_trades(response: &Value) -> Result<Vec<TradeRow>> {
+    let trades = response.as_array().ok_or_else(|| anyhow!("trades response is not a list"))?;
+    let text = |trade: &Value, key: &str| trade[key].as_str().unwrap_or_default().to_string();
+    Ok(trades
+        .iter()
+        .filter_map(|trade| {
+            let transaction_hash = trade["transactionHash"].as_str().filter(|h| !h.is_empty())?;
+            Some(TradeRow {
+                transaction_hash: transaction_hash.to_string(),
+                timestamp: unix_time(&trade["timestamp"])?,
+                outcome: text(trade, "outcome"),
+                side: text(trade, "side"),
+                price: json_decimal(&trade["price"])?,
+                size: json_decimal(&trade["size"])?,
+                asset: text(trade, "asset"),
+                proxy_wallet: text(trade, "proxyWallet"),
+            })
+        })
+        .collect())
+}
+
+// ============================================================================
+// API Client
+// ============================================================================
+
+/// Gamma, CLOB and data API reads for past windows
+pub struct PolymarketHistoryClient {
+    http: HttpClient,
+    gamma_url: String,
+    clob_url: String,
+    data_url: String,
+}
+
+impl PolymarketHistoryClient {
+    pub fn new(timeout_ms: u64) -> Self {
+        let env = |key: &str, default: &str| std::env::var(key).unwrap_or_else(|_| default.to_string());
+        Self {
+            http: HttpClient::new("polymarket-backfill", Duration::from_millis(timeout_ms)),
+            gamma_url: env("POLYMARKET_GAMMA_URL", GAMMA_API_URL),
+            clob_url: env("POLYMARKET_CLOB_URL", CLOB_API_URL),
+            data_url: env("POLYMARKET_DATA_URL", DATA_API_URL),
+        }
+    }
+
+    async fn get_json(&self, url: &str) -> Result<Value> {
+        let response = self.http.get(url).send().await.with_context(|| format!("Failed to fetch {}", url))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Request to {} failed: {} - {}", url, status, text));
+        }
+        response.json().await.with_context(|| format!("Failed to parse response from {}", url))
+    }
+
+    pub async fn market(&self, window_timestamp: i64) -> Result<Option<WindowMarket>> {
+        let url = format!("{}/markets?slug={}", self.gamma_url, window_slug(window_timestamp));
+        WindowMarket::from_gamma(window_timestamp, &self.get_json(&url).await?)
+    }
+
+    pub async fn price_history(&self, token_id: &str) -> Result<Vec<HistoryPoint>> {
+        let url = format!("{}/prices-history?market={}&interval=max", self.clob_url, token_id);
+        parse_price_history(&self.get_json(&url).await?)
+    }
+
+    /// All trades of a market, paging until a short page. The flag is set
+    /// when MAX_TRADE_PAGES was reached and later trades were left out.
+    pub async fn trades(&self, condition_id: &str) -> Result<(Vec<TradeRow>, bool)> {
+        let mut trades = Vec::new();
+        for page in 0..MAX_TRADE_PAGES {
+            let url = format!(
+                "{}/trades?market={}&limit={}&offset={}",
+                self.data_url,
+                condition_id,
+                TRADES_PAGE_SIZE,
+                page * TRADES_PAGE_SIZE
+            );
+            let response = self.get_json(&url).await?;
+            let page_len = response.as_array().map_or(0, |a| a.len());
+            trades.extend(parse_trades(&response)?);
+            if page_len < TRADES_PAGE_SIZE {
+                return Ok((trades, false));
+            }
+        }
+        warn!("Market {} has more than {} trades, stored the first {} (checkpointed as truncated)", condition_id,
+            MAX_TRADE_PAGES * TRADES_PAGE_SIZE, trades.len());
+        Ok((trades, true))
+    }
+}
+
+// ============================================================================
+// Upserts
+// ============================================================================
+
+/// Multi-row INSERT of `rows` (each `width` params) with `conflict` appended
+async fn insert_rows(
+    tx: &Transaction<'_>,
+    insert: &str,
+    width: usize,
+    conflict: &str,
+    rows: &[Vec<Box<dyn ToSql + Sync + Send>>],
+) -> Result<u64> {
+    let mut affected = 0u64;
+    // Postgres allows at most 65535 parameters per statement
+    for chunk in rows.chunks(60_000 / width) {
+        let mut query = format!("{} VALUES ", insert);
+        for i in 0..chunk.len() {
+            if i > 0 {
+                query.push_str(", ");
+            }
+            let placeholders: Vec<String> = (1..=width).map(|j| format!("${}", i * width + j)).collect();
+            query.push_str(&format!("({})", placeholders.join(", ")));
+        }
+        query.push(' ');
+        query.push_str(conflict);
+
+        let params: Vec<&(dyn ToSql + Sync)> =
+            chunk.iter().flatten().map(|p| p.as_ref() as &(dyn ToSql + Sync)).collect();
+        affected += tx.execute(&query, &params).await?;
+    }
+    Ok(affected)
+}
+
+async fn upsert_prices(
+    tx: &Transaction<'_>,
+    market: &WindowMarket,
+    up: &[HistoryPoint],
+    down: &[HistoryPoint],
+) -> Result<u64> {
+    let rows: Vec<Vec<Box<dyn ToSql + Sync + Send>>> = [("UP", &market.up_token_id, up), ("DOWN", &market.down_token_id, down)]
+        .into_iter()
+        .flat_map(|(token_type, token_id, points)| {
+            points.iter().map(move |p| -> Vec<Box<dyn ToSql + Sync + Send>> {
+                vec![
+                    Box::new(market.window_timestamp),
+                    Box::new(token_type),
+                    Box::new(token_id.clone()),
+                    Box::new(p.timestamp),
+                    Box::new(p.price),
+                ]
+            })
+        })
+        .collect();
+
+    insert_rows(
+        tx,
+        "INSERT INTO polymarket_prices (window_timestamp, token_type, token_id, timestamp, price)",
+        5,
+        "ON CONFLICT (window_timestamp, token_type, timestamp) DO UPDATE
+         SET token_id = EXCLUDED.token_id, price = EXCLUDED.price",
+        &rows,
+    )
+    .await
+}
+
+async fn upsert_trades(tx: &Transaction<'_>, market: &WindowMarket, trades: &[TradeRow]) -> Result<u64> {
+    let rows: Vec<Vec<Box<dyn ToSql + Sync + Send>>> = trades
+        .iter()
+        .map(|t| -> Vec<Box<dyn ToSql + Sync + Send>> {
+            vec![
+                Box::new(t.transaction_hash.clone()),
+                Box::new(market.condition_id.clone()),
+                Box::new(market.window_timestamp),
+                Box::new(market.slug.clone()),
+                Box::new(t.timestamp),
+                Box::new(t.outcome.clone()),
+                Box::new(t.side.clone()),
+                Box::new(t.price),
+                Box::new(t.size),
+                Box::new(t.asset.clone()),
+                Box::new(t.proxy_wallet.clone()),
+            ]
+        })
+        .collect();
+
+    // Fills are immutable; one transaction hash is stored once (table constraint)
+    insert_rows(
+        tx,
+        "INSERT INTO polymarket_trades (transaction_hash, condition_id, window_timestamp, slug, timestamp, \
+         outcome, side, price, size, asset, proxy_wallet)",
+        11,
+        "ON CONFLICT (transaction_hash) DO NOTHING",
+        &rows,
+    )
+    .await
+}
+
+// ============================================================================
+// Runner
+// ============================================================================
+
+/// What one backfill run did
+#[derive(Debug, Default)]
+pub struct RunSummary {
+    pub windows: usize,
+    pub with_data: usize,
+    pub empty: usize,
+    /// Windows that errored; left as gaps for the next run
+    pub failed: usize,
+    /// Windows stored with the trade page cap hit
+    pub truncated: usize,
+    pub rows: u64,
+}
+
+/// Fetch, upsert and checkpoint one window. Returns rows upserted (0 = empty)
+/// and whether the fetch was truncated.
+async fn backfill_window(
+    client: &mut Client,
+    api: &PolymarketHistoryClient,
+    job: Job,
+    window_timestamp: i64,
+) -> Result<(u64, bool)> {
+    let market = api.market(window_timestamp).await?;
+
+    // Fetch everything before opening the transaction
+    enum Fetched {
+        Nothing,
+        Prices(WindowMarket, Vec<HistoryPoint>, Vec<HistoryPoint>),
+        Trades(WindowMarket, Vec<TradeRow>, bool),
+    }
+    let fetched = match (job, market) {
+        (_, None) => Fetched::Nothing,
+        (Job::PolymarketPrices, Some(market)) => {
+            let up = api.price_history(&market.up_token_id).await?;
+            let down = api.price_history(&market.down_token_id).await?;
+            Fetched::Prices(market, up, down)
+        }
+        (Job::PolymarketTrades, Some(market)) => {
+            let (trades, truncated) = api.trades(&market.condition_id).await?;
+            Fetched::Trades(market, trades, truncated)
+        }
+    };
+
+    let tx = client.transaction().await?;
+    let rows = match &fetched {
+        Fetched::Nothing => 0,
+        Fetched::Prices(market, up, down) => upsert_prices(&tx, market, up, down).await?,
+        Fetched::Trades(market, trades, _) => upsert_trades(&tx, market, trades).await?,
+    };
+    // A window whose rows were all already stored (trades DO NOTHING) still counts as done
+    let has_data = match &fetched {
+        Fetched::Nothing => false,
+        Fetched::Prices(_, up, down) => !up.is_empty() || !down.is_empty(),
+        Fetched::Trades(_, trades, _) => !trades.is_empty(),
+    };
+    let truncated = matches!(fetched, Fetched::Trades(_, _, true));
+    let rows = if has_data { rows.max(1) } else { 0 };
+    checkpoint(&tx, job, window_timestamp, rows, truncated).await?;
+    tx.commit().await?;
+    Ok((rows, truncated))
+}
+
+/// Backfill the settled windows of the last `lookback_secs`: only missing
+/// ones, or every window in the range with `force` (upserts make that safe)
+pub async fn run(
+    client: &mut Client,
+    api: &PolymarketHistoryClient,
+    job: Job,
+    lookback_secs: i64,
+    force: bool,
+) -> Result<RunSummary> {
+    let (first, last) = window_range(Utc::now().timestamp(), lookback_secs);
+    if first > last {
+        return Ok(RunSummary::default());
+    }
+    let windows: Vec<i64> = if force {
+        (first..=last).step_by(WINDOW_SECS as usize).collect()
+    } else {
+        find_missing_windows(client, job, first, last).await?
+    };
+
+    let total_windows = (last - first) / WINDOW_SECS + 1;
+    info!("{}: {} of {} windows to fetch", job.table(), windows.len(), total_windows);
+
+    let mut summary = RunSummary { windows: windows.len(), ..Default::default() };
+    for (i, &window) in windows.iter().enumerate() {
+        match backfill_window(client, api, job, window).await {
+            Ok((0, _)) => summary.empty += 1,
+            Ok((rows, truncated)) => {
+                summary.with_data += 1;
+                summary.rows += rows;
+                if truncated {
+                    summary.truncated += 1;
+                }
+            }
+            Err(e) => {
+                summary.failed += 1;
+                warn!("{} {}: {:#} (left as a gap)", job.table(), window_slug(window), e);
+            }
+        }
+        if (i + 1) % 100 == 0 || i + 1 == windows.len() {
+            info!(
+                "{}: {}/{} windows ({} with data, {} empty, {} failed, {} rows)",
+                job.table(), i + 1, windows.len(), summary.with_data, summary.empty, summary.failed, summary.rows
+            );
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_window_status() {
+        assert_eq!(window_status(12, false), "done");
+        assert_eq!(window_status(0, false), "empty");
+        assert_eq!(window_status(MAX_TRADE_PAGES as u64 * TRADES_PAGE_SIZE as u64, true), "truncated");
+    }
+
+    #[test]
+    fn test_window_range_and_gaps() {
+        // 100s into window 1000: window 999 ended 100s ago (not settled), 998 is the last
+        let now = 1000 * WINDOW_SECS + 100;
+        assert_eq!(window_range(now, 3600), (996 * WINDOW_SECS, 998 * WINDOW_SECS));
+        // 700s in: window 999 ended 700s ago and is settled
+        assert_eq!(window_range(1000 * WINDOW_SECS + 700, 0).1, 999 * WINDOW_SECS);
+        let (first, last) = window_range(now, 60);
+        assert!(first > last);
+
+        let w = |i: i64| i * WINDOW_SECS;
+        assert_eq!(group_gaps(&[]), vec![]);
+        assert_eq!(
+            group_gaps(&[w(1), w(2), w(3), w(5), w(7), w(8)]),
+            vec![(w(1), w(3)), (w(5), w(5)), (w(7), w(8))]
+        );
+        assert_eq!(window_slug(1765400400), "btc-updown-15m-1765400400");
+    }
+
+    #[test]
+    fn test_parse_gamma_market_and_price_history() {
+        let gamma = json!([{
+            "conditionId": "0xabc",
+            "clobTokenIds": "[\"111\", \"222\"]",
+            "outcomes": "[\"Down\", \"Up\"]"
+        }]);
+        let market = WindowMarket::from_gamma(1765400400, &gamma).unwrap().unwrap();
+        assert_eq!(market.up_token_id, "222");
+        assert_eq!(market.down_token_id, "111");
+        assert_eq!(market.condition_id, "0xabc");
+        assert_eq!(market.slug, "btc-updown-15m-1765400400");
+        assert_eq!(WindowMarket::from_gamma(0, &json!([])).unwrap(), None);
+        assert!(WindowMarket::from_gamma(0, &json!([{"conditionId": "0x1", "clobTokenIds": "[\"1\"]", "outcomes": "[\"Up\"]"}])).is_err());
+
+        let history = json!({"history": [
+            {"t": 1765400460, "p": 0.52},
+            {"t": 1765400400, "p": "0.5"},
+            {"t": 1765400460, "p": 0.52}
+        ]});
+        let points = parse_price_history(&history).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].timestamp.timestamp(), 1765400400);
+        assert_eq!(points[0].price, Decimal::from_str("0.5").unwrap());
+        assert_eq!(points[1].price, Decimal::from_str("0.52").unwrap());
+        assert!(parse_price_history(&json!({"history": [{"t": 1, "p": null}]})).is_err());
+        assert!(parse_price_history(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_parse_trades() {
+        let page = json!([
+            {"transactionHash": "0x01", "timestamp": 1765400412, "outcome": "Up", "side": "BUY",
+             "price": 0.47, "size": 10.5, "asset": "222", "proxyWallet": "0xw"},
+            {"transactionHash": "", "timestamp": 1765400413, "price": 0.5, "size": 1},
+            {"transactionHash": "0x02", "timestamp": 1765400414, "outcome": "Down", "side": "SELL",
+             "price": "0.53", "size": "2"}
+        ]);
+        let trades = parse_trades(&page).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].price, Decimal::from_str("0.47").unwrap());
+        assert_eq!(trades[0].size, Decimal::from_str("10.5").unwrap());
+        assert_eq!(trades[0].proxy_wallet, "0xw");
+        assert_eq!(trades[1].side, "SELL");
+        assert_eq!(trades[1].asset, "");
+        assert!(parse_trades(&json!({"error": "x"})).is_err());
+    }
+}
@@ -894,6 +894,7 @@ fn rolling_stddev(values: &[f64]) -> f64 {
 // Database Loading
 // =============================================================================
 
+/// 1s closes from `binance_klines` (run `binance fetch --interval 1s` first)
 async fn load_prices_from_db(
     start_date: DateTime<Utc>,
     end_date: DateTime<Utc>,
@@ -924,8 +925,9 @@ async fn load_prices_from_db(
     let rows = client
         .query(
             "SELECT timestamp, close_price
-             FROM binance_prices
+             FROM binance_klines
              WHERE symbol = 'BTCUSDT'
+               AND kline_interval = '1s'
                AND timestamp >= $1
                AND timestamp < $2
              ORDER BY timestamp ASC",
@@ -0,0 +1,609 @@
+//! Monte Carlo Bankroll Simulator for BTC 15-Minute Markets
+//!
+//! Bootstraps per-trade returns (live trade_results or a btc-strategy-sim trade
+//! export) into many synthetic trading histories, sizes every bet with the bot's
+//! fractional Kelly rules, and reports:
+//! - Terminal bankroll distribution (percentiles)
+//! - Max drawdown distribution
+//! - Probability of hitting the daily loss limit
+//! - Risk of ruin
+//!
+//! Usage:
+//!   btc-bankroll-sim --days-history 30
+//!   btc-bankroll-sim --trades-csv trades.csv --trades-per-day 40
+//!   btc-bankroll-sim --compare-kelly "0.10,0.15,0.25,0.50" --horizon-days 60
+
+#[path = "../bot/config.rs"]
+#[allow(dead_code)]
+mod config;
+
+#[path = "../stop_loss.rs"]
+#[allow(dead_code)]
+mod stop_loss;
+
+#[path = "../strategies.rs"]
+#[allow(dead_code)]
+mod strategies;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use config::BotConfig;
+use native_tls::TlsConnector;
+use postgres_native_tls::MakeTlsConnector;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::prelude::*;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use tokio_postgres::Client;
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+// ============================================================================
+// CLI Arguments
+// ============================================================================
+
+#[derive(Parser, Debug)]
+#[command(name = "btc-bankroll-sim")]
+#[command(about = "Monte Carlo bankroll simulation and risk-of-ruin analysis")]
+struct Args {
+    /// Bot config file (betting + risk sections are used for sizing)
+    #[arg(short, long, default_value = "config/bot_config.yaml")]
+    config: String,
+
+    /// Per-trade CSV from btc-strategy-sim --trades-output (instead of live trades)
+    #[arg(long)]
+    trades_csv: Option<String>,
+
+    /// Days of live trade_results to sample from (when no CSV is given)
+    #[arg(long, default_value = "30")]
+    days_history: u32,
+
+    /// Starting bankroll in USDC
+    #[arg(short, long, default_value = "1000.0")]
+    bankroll: f64,
+
+    /// Number of days to simulate per path
+    #[arg(long, default_value = "30")]
+    horizon_days: u32,
+
+    /// Trades per simulated day (default: observed rate, or 24 for CSV input)
+    #[arg(long)]
+    trades_per_day: Option<u32>,
+
+    /// Number of Monte Carlo paths
+    #[arg(short = 'n', long, default_value = "10000")]
+    paths: u32,
+
+    /// Ruin threshold as a fraction of starting bankroll (e.g., 0.5 = lost half)
+    #[arg(long, default_value = "0.5")]
+    ruin_pct: f64,
+
+    /// Confidence level assumed for trades without one (CSV input)
+    #[arg(long, default_value = "Moderate")]
+    default_confidence: String,
+
+    /// Compare multiple Kelly fractions (comma-separated)
+    #[arg(long)]
+    compare_kelly: Option<String>,
+
+    /// RNG seed (for reproducible runs)
+    #[arg(long, default_value = "42")]
+    seed: u64,
+
+    /// Output CSV file (optional)
+    #[arg(short = 'o', long)]
+    output: Option<String>,
+}
+
+// ============================================================================
+// Database
+// ============================================================================
+
+struct DbConfig {
+    host: String,
+    port: u16,
+    user: String,
+    password: String,
+    database: String,
+}
+
+impl Default for DbConfig {
+    fn default() -> Self {
+        Self {
+            host: std::env::var("DB_HOST")
+                .unwrap_or_else(|_| "zd4409065-postgresql.crysaioqovvg.eu-west-1.rds.amazonaws.com".to_string()),
+            port: 5432,
+            user: std::env::var("DB_USER").unwrap_or_else(|_| "qoveryadmin".to_string()),
+            password: std::env::var("DB_PASSWORD")
+                .unwrap_or_else(|_| "xP-R3PMRO0dNuFOgqDm5HYuwMV-kK3Lp".to_string()),
+            database: std::env::var("DB_NAME").unwrap_or_else(|_| "polymarket".to_string()),
+        }
+    }
+}
+
+async fn connect_db(config: &DbConfig) -> Result<Client> {
+    let connection_string = format!(
+        "host={} port={} user={} password={} dbname={}",
+        config.host, config.port, config.user, config.password, config.database
+    );
+
+    let tls_connector = TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .build()?;
+    let connector = MakeTlsConnector::new(tls_connector);
+
+    let (client, connection) = tokio_postgres::connect(&connection_string, connector).await?;
+
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("Database connection error: {}", e);
+        }
+    });
+
+    Ok(client)
+}
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// One historical trade, reduced to what the sizing and P&L need
+#[derive(Debug, Clone)]
+struct TradeSample {
+    /// Market probability at entry (price paid)
+    market_prob: f64,
+    /// Edge at entry (relative, as used by the bot's Kelly sizing)
+    edge: f64,
+    /// Matrix confidence at entry ("Strong", "Moderate", "Weak")
+    confidence: String,
+    /// Return per $1 staked (e.g., 0.25 = +25%, -1.0 = total loss)
+    return_frac: f64,
+}
+
+/// Row format written by btc-strategy-sim --trades-output
+#[derive(Debug, Deserialize)]
+struct CsvTrade {
+    entry_price: f64,
+    edge: f64,
+    confidence: Option<String>,
+    return_pct: f64,
+}
+
+/// Parameters for one simulation run
+#[derive(Debug, Clone)]
+struct SimParams {
+    starting_bankroll: f64,
+    horizon_days: u32,
+    trades_per_day: u32,
+    paths: u32,
+    ruin_pct: f64,
+}
+
+/// Outcome of a single simulated path
+#[derive(Debug, Clone, Default)]
+struct PathResult {
+    terminal_bankroll: f64,
+    max_drawdown_pct: f64,
+    days_limit_hit: u32,
+    ruined: bool,
+    bets_placed: u32,
+}
+
+/// Aggregated results for one Kelly fraction
+#[derive(Debug, Clone, Default)]
+struct SimSummary {
+    kelly_fraction: f64,
+    terminal: Vec<f64>,
+    drawdowns: Vec<f64>,
+    paths_hit_limit: u32,
+    days_hit_limit: u32,
+    ruined: u32,
+    total_days: u32,
+    total_bets: u64,
+}
+
+impl SimSummary {
+    fn paths(&self) -> usize {
+        self.terminal.len()
+    }
+
+    fn terminal_pct(&self, p: f64) -> f64 {
+        percentile(&self.terminal, p)
+    }
+
+    fn drawdown_pct(&self, p: f64) -> f64 {
+        percentile(&self.drawdowns, p)
+    }
+
+    fn median_growth_pct(&self, starting: f64) -> f64 {
+        (self.terminal_pct(0.50) / starting - 1.0) * 100.0
+    }
+
+    fn prob_loss(&self, starting: f64) -> f64 {
+        if self.terminal.is_empty() { 0.0 } else {
+            self.terminal.iter().filter(|&&b| b < starting).count() as f64 / self.paths() as f64 * 100.0
+        }
+    }
+
+    fn prob_path_hit_limit(&self) -> f64 {
+        if self.terminal.is_empty() { 0.0 } else { self.paths_hit_limit as f64 / self.paths() as f64 * 100.0 }
+    }
+
+    fn prob_day_hit_limit(&self) -> f64 {
+        if self.total_days == 0 { 0.0 } else { self.days_hit_limit as f64 / self.total_days as f64 * 100.0 }
+    }
+
+    fn risk_of_ruin(&self) -> f64 {
+        if self.terminal.is_empty() { 0.0 } else { self.ruined as f64 / self.paths() as f64 * 100.0 }
+    }
+
+    fn avg_bets(&self) -> f64 {
+        if self.terminal.is_empty() { 0.0 } else { self.total_bets as f64 / self.paths() as f64 }
+    }
+}
+
+/// Percentile of a sorted slice (p in 0.0-1.0, nearest rank)
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let idx = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[idx.min(sorted.len() - 1)]
+}
+
+// ============================================================================
+// Data Loading
+// ============================================================================
+
+/// Load settled bot trades from trade_results.
+/// Returns the samples and the observed number of trades per active day.
+async fn load_live_trades(client: &Client, days: u32) -> Result<(Vec<TradeSample>, f64)> {
+    info!("Loading trade results from last {} days...", days);
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(days as i64);
+
+    let query = r#"
+        SELECT t.market_probability,
+               t.edge_pct,
+               t.confidence_level,
+               r.roi_pct,
+               DATE(t.window_start) AS trade_date
+        FROM bot_trades t
+        JOIN trade_results r ON t.id = r.trade_id
+        WHERE t.window_start > $1
+        ORDER BY t.window_start
+    "#;
+
+    let rows = client.query(query, &[&cutoff]).await?;
+
+    let mut samples = Vec::with_capacity(rows.len());
+    let mut dates = HashSet::new();
+
+    for row in rows {
+        let date: chrono::NaiveDate = row.get("trade_date");
+        dates.insert(date);
+
+        samples.push(TradeSample {
+            market_prob: row.get::<_, Decimal>("market_probability").to_f64().unwrap_or(0.5),
+            edge: row.get::<_, Decimal>("edge_pct").to_f64().unwrap_or(0.0),
+            confidence: row.get("confidence_level"),
+            return_frac: row.get::<_, Decimal>("roi_pct").to_f64().unwrap_or(0.0) / 100.0,
+        });
+    }
+
+    let per_day = if dates.is_empty() { 0.0 } else { samples.len() as f64 / dates.len() as f64 };
+    info!("Loaded {} trades over {} active days", samples.len(), dates.len());
+
+    Ok((samples, per_day))
+}
+
+fn load_csv_trades(path: &str, default_confidence: &str) -> Result<Vec<TradeSample>> {
+    let mut reader = csv::Reader::from_path(path)
+        .with_context(|| format!("Failed to open trades CSV: {}", path))?;
+
+    let mut samples = Vec::new();
+    for row in reader.deserialize() {
+        let t: CsvTrade = row?;
+        let confidence = t.confidence
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| default_confidence.to_string());
+
+        samples.push(TradeSample {
+            market_prob: t.entry_price,
+            edge: t.edge,
+            confidence,
+            return_frac: t.return_pct / 100.0,
+        });
+    }
+
+    info!("Loaded {} trades from {}", samples.len(), path);
+    Ok(samples)
+}
+
+// ============================================================================
+// Simulation
+// ============================================================================
+
+/// Simulate one bankroll path, resampling trades with replacement.
+///
+/// Mirrors the live bot: bets are sized from the current bankroll with
+/// BotConfig::kelly_bet_size, losing streaks shrink size via loss_reduction_factor,
+/// and trading stops for the day once the daily loss limit is reached.
+fn simulate_path(samples: &[TradeSample], config: &BotConfig, params: &SimParams, rng: &mut StdRng) -> PathResult {
+    let mut bankroll = params.starting_bankroll;
+    let mut peak = bankroll;
+    let mut max_drawdown = 0.0_f64;
+    let mut consecutive_losses = 0u32;
+    let mut result = PathResult::default();
+    let ruin_level = params.starting_bankroll * params.ruin_pct;
+
+    'days: for _ in 0..params.horizon_days {
+        let mut daily_pnl = 0.0;
+
+        for _ in 0..params.trades_per_day {
+            // Same check as StrategyContext::decide (limit relative to current bankroll)
+            let daily_loss_limit = bankroll * config.risk.daily_loss_limit_pct;
+            if daily_pnl < 0.0 && daily_pnl.abs() >= daily_loss_limit {
+                result.days_limit_hit += 1;
+                break;
+            }
+
+            let trade = &samples[rng.gen_range(0..samples.len())];
+            let bet = config.kelly_bet_size(
+                bankroll,
+                trade.edge,
+                trade.market_prob,
+                &trade.confidence,
+                consecutive_losses,
+            );
+
+            if bet < config.betting.min_bet_usdc {
+                continue;
+            }
+
+            let pnl = bet * trade.return_frac;
+            bankroll += pnl;
+            daily_pnl += pnl;
+            result.bets_placed += 1;
+
+            if pnl > 0.0 {
+                consecutive_losses = 0;
+            } else {
+                consecutive_losses += 1;
+            }
+
+            if bankroll > peak {
+                peak = bankroll;
+            }
+            let drawdown = (peak - bankroll) / peak * 100.0;
+            if drawdown > max_drawdown {
+                max_drawdown = drawdown;
+            }
+
+            if bankroll <= ruin_level {
+                result.ruined = true;
+                break 'days;
+            }
+        }
+    }
+
+    result.terminal_bankroll = bankroll;
+    result.max_drawdown_pct = max_drawdown;
+    result
+}
+
+fn run_simulation(samples: &[TradeSample], config: &BotConfig, params: &SimParams, seed: u64) -> SimSummary {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut summary = SimSummary {
+        kelly_fraction: config.betting.kelly_fraction,
+        ..Default::default()
+    };
+
+    for _ in 0..params.paths {
+        let path = simulate_path(samples, config, params, &mut rng);
+
+        summary.terminal.push(path.terminal_bankroll);
+        summary.drawdowns.push(path.max_drawdown_pct);
+        summary.days_hit_limit += path.days_limit_hit;
+        summary.total_days += params.horizon_days;
+        summary.total_bets += path.bets_placed as u64;
+        if path.days_limit_hit > 0 {
+            summary.paths_hit_limit += 1;
+        }
+        if path.ruined {
+            summary.ruined += 1;
+        }
+    }
+
+    summary.terminal.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    summary.drawdowns.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    summary
+}
+
+// ============================================================================
+// Output Formatting
+// ============================================================================
+
+fn print_sample_stats(samples: &[TradeSample]) {
+    let wins = samples.iter().filter(|s| s.return_frac > 0.0).count();
+    let avg_return = samples.iter().map(|s| s.return_frac).sum::<f64>() / samples.len() as f64;
+    let avg_edge = samples.iter().map(|s| s.edge).sum::<f64>() / samples.len() as f64;
+
+    println!("\nTrade sample:");
+    println!("  Trades:          {}", samples.len());
+    println!("  Win rate:        {:.1}%", wins as f64 / samples.len() as f64 * 100.0);
+    println!("  Avg return/$:    {:+.2}%", avg_return * 100.0);
+    println!("  Avg entry edge:  {:.2}%", avg_edge * 100.0);
+}
+
+fn print_comparison(summaries: &[SimSummary], params: &SimParams, daily_loss_limit_pct: f64) {
+    let starting = params.starting_bankroll;
+
+    println!("\n{}", "=".repeat(150));
+    println!("{:^150}", format!(
+        "BANKROLL SIMULATION (${:.0} start, {} days x {} trades, {} paths)",
+        starting, params.horizon_days, params.trades_per_day, params.paths
+    ));
+    println!("{}", "=".repeat(150));
+    println!();
+
+    println!(
+        "{:>7} | {:>10} {:>10} {:>10} {:>10} {:>10} {:>9} | {:>8} {:>8} | {:>8} {:>9} {:>9} | {:>8}",
+        "Kelly", "P5", "P25", "Median", "P75", "P95", "Growth", "DD med", "DD P95",
+        "P(loss)", "P(limit)", "Limit/day", "P(ruin)"
+    );
+    println!("{}", "-".repeat(150));
+
+    for s in summaries {
+        println!(
+            "{:>6.0}% | {:>10.2} {:>10.2} {:>10.2} {:>10.2} {:>10.2} {:>8.1}% | {:>7.1}% {:>7.1}% | {:>7.1}% {:>8.1}% {:>8.2}% | {:>7.2}%",
+            s.kelly_fraction * 100.0,
+            s.terminal_pct(0.05), s.terminal_pct(0.25), s.terminal_pct(0.50),
+            s.terminal_pct(0.75), s.terminal_pct(0.95),
+            s.median_growth_pct(starting),
+            s.drawdown_pct(0.50), s.drawdown_pct(0.95),
+            s.prob_loss(starting), s.prob_path_hit_limit(), s.prob_day_hit_limit(),
+            s.risk_of_ruin()
+        );
+    }
+
+    println!("{}", "-".repeat(150));
+    println!("P(limit) = share of paths that hit the {:.0}% daily loss limit at least once; Limit/day = share of days.",
+        daily_loss_limit_pct * 100.0);
+    println!("P(ruin)  = share of paths whose bankroll fell to {:.0}% of the start.", params.ruin_pct * 100.0);
+    if let Some(s) = summaries.first() {
+        println!("Avg bets placed per path: {:.0} (bets below min_bet_usdc are skipped)", s.avg_bets());
+    }
+}
+
+fn write_csv(summaries: &[SimSummary], params: &SimParams, path: &str) -> Result<()> {
+    let mut file = File::create(path)?;
+
+    writeln!(file, "kelly_fraction,p5,p25,median,p75,p95,median_growth_pct,dd_median_pct,dd_p95_pct,prob_loss_pct,prob_limit_path_pct,prob_limit_day_pct,risk_of_ruin_pct,avg_bets")?;
+
+    for s in summaries {
+        writeln!(
+            file,
+            "{:.4},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.1}",
+            s.kelly_fraction,
+            s.terminal_pct(0.05), s.terminal_pct(0.25), s.terminal_pct(0.50),
+            s.terminal_pct(0.75), s.terminal_pct(0.95),
+            s.median_growth_pct(params.starting_bankroll),
+            s.drawdown_pct(0.50), s.drawdown_pct(0.95),
+            s.prob_loss(params.starting_bankroll), s.prob_path_hit_limit(), s.prob_day_hit_limit(),
+            s.risk_of_ruin(), s.avg_bets()
+        )?;
+    }
+
+    info!("Results written to {}", path);
+    Ok(())
+}
+
+// ============================================================================
+// Main
+// ============================================================================
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env().add_directive("bankroll_simulator=info".parse()?))
+        .init();
+
+    let args = Args::parse();
+
+    println!();
+    println!("{}", "=".repeat(80));
+    println!("{:^80}", "BTC 15-MINUTE BANKROLL SIMULATOR");
+    println!("{:^80}", "(Monte Carlo bootstrap + Kelly sizing + risk of ruin)");
+    println!("{}", "=".repeat(80));
+    println!();
+
+    let config_path = Path::new(&args.config);
+    let base_config = if config_path.exists() {
+        BotConfig::load(config_path)?
+    } else {
+        info!("Config {} not found, using defaults", args.config);
+        BotConfig::default()
+    };
+
+    let (samples, observed_per_day) = match &args.trades_csv {
+        Some(path) => (load_csv_trades(path, &args.default_confidence)?, 0.0),
+        None => {
+            let db_config = DbConfig::default();
+            info!("Connecting to database...");
+            let client = connect_db(&db_config).await?;
+            info!("Connected!");
+            load_live_trades(&client, args.days_history).await?
+        }
+    };
+
+    if samples.is_empty() {
+        println!("No trades to sample from");
+        return Ok(());
+    }
+
+    let trades_per_day = args.trades_per_day.unwrap_or_else(|| {
+        if observed_per_day > 0.0 { observed_per_day.round().max(1.0) as u32 } else { 24 }
+    });
+
+    let params = SimParams {
+        starting_bankroll: args.bankroll,
+        horizon_days: args.horizon_days,
+        trades_per_day,
+        paths: args.paths,
+        ruin_pct: args.ruin_pct,
+    };
+
+    let kelly_fractions: Vec<f64> = args.compare_kelly
+        .as_ref()
+        .map(|s| s.split(',').filter_map(|x| x.trim().parse().ok()).collect())
+        .unwrap_or_else(|| vec![base_config.betting.kelly_fraction]);
+
+    print_sample_stats(&samples);
+
+    println!("\nSizing (from {}):", args.config);
+    println!("  Max bet:         {:.1}% of bankroll, ${:.0} cap, ${:.2} min",
+        base_config.betting.max_bet_pct * 100.0, base_config.betting.max_bet_usdc, base_config.betting.min_bet_usdc);
+    println!("  Confidence mult: strong={:.2} moderate={:.2} weak={:.2}",
+        base_config.betting.confidence_multipliers.strong,
+        base_config.betting.confidence_multipliers.moderate,
+        base_config.betting.confidence_multipliers.weak);
+    println!("  Loss reduction:  {:.2}x per consecutive loss", base_config.risk.loss_reduction_factor);
+    println!("  Daily limit:     {:.0}% of bankroll", base_config.risk.daily_loss_limit_pct * 100.0);
+
+    // Same seed for every fraction so they see identical trade sequences
+    let mut summaries = Vec::new();
+    for &fraction in &kelly_fractions {
+        let mut config = base_config.clone();
+        config.betting.kelly_fraction = fraction;
+        info!("Simulating kelly_fraction={:.2}...", fraction);
+        summaries.push(run_simulation(&samples, &config, &params, args.seed));
+    }
+
+    print_comparison(&summaries, &params, base_config.risk.daily_loss_limit_pct);
+
+    if kelly_fractions.len() > 1 {
+        if let Some(best) = summaries.iter().max_by(|a, b| a.terminal_pct(0.50).partial_cmp(&b.terminal_pct(0.50)).unwrap()) {
+            println!("\nBEST MEDIAN BANKROLL: {:.0}% Kelly (${:.2})", best.kelly_fraction * 100.0, best.terminal_pct(0.50));
+        }
+        if let Some(best) = summaries.iter().min_by(|a, b| a.risk_of_ruin().partial_cmp(&b.risk_of_ruin()).unwrap()) {
+            println!("LOWEST RISK OF RUIN:  {:.0}% Kelly ({:.2}%)", best.kelly_fraction * 100.0, best.risk_of_ruin());
+        }
+    }
+
+    if let Some(output_path) = &args.output {
+        write_csv(&summaries, &params, output_path)?;
+    }
+
+    println!("\n{}", "=".repeat(80));
+    println!("{:^80}", "SIMULATION COMPLETE");
+    println!("{}", "=".repeat(80));
+    println!();
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 //! Cheap Buy Scenario - Buy at $0.10, sell at $0.70
 //!
 //! Test: Buy when price hits $0.10 in first 3 minutes, sell if hits $0.70
+//!
+//! Live version: the `cheap_buy` plugin strategy (src/strategies.rs), backtested
+//! with its bot config via `btc-strategy-sim --plugin cheap_buy`.
 
 use anyhow::Result;
 use chrono::Utc;
@@ -9,6 +9,8 @@ mod models;
 #[path = "../stats.rs"]
 mod stats;
 
+#[path = "../http_client.rs"]
+mod http_client;
 #[path = "../bot/binance.rs"]
 mod binance;
 #[path = "../bot/polymarket.rs"]
@@ -8,10 +8,15 @@
 mod models;
 #[path = "../stats.rs"]
 mod stats;
+#[path = "../http_client.rs"]
+mod http_client;
 #[path = "../bot/binance.rs"]
 mod binance;
 #[path = "../bot/polymarket.rs"]
 mod polymarket;
+#[path = "../metrics.rs"]
+#[allow(dead_code)]
+mod metrics;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -137,9 +142,14 @@ async fn binance_ws_task(
     running: Arc<AtomicBool>,
 ) -> Result<()> {
     let url = "wss://stream.binance.com:9443/ws/btcusdt@trade";
+    let mut first_attempt = true;
 
     while running.load(Ordering::SeqCst) {
         info!("Connecting to Binance WebSocket...");
+        if !first_attempt {
+            metrics::feed_reconnect("binance");
+        }
+        first_attempt = false;
 
         match connect_async(url).await {
             Ok((ws_stream, _)) => {
@@ -149,6 +159,7 @@ async fn binance_ws_task(
                 while running.load(Ordering::SeqCst) {
                     match tokio::time::timeout(Duration::from_secs(30), read.next()).await {
                         Ok(Some(Ok(Message::Text(text)))) => {
+                            metrics::feed_message("binance");
                             if let Ok(trade) = serde_json::from_str::<BinanceTrade>(&text) {
                                 if let Ok(price) = trade.price.parse::<f64>() {
                                     let mut state = state.write().await;
@@ -257,6 +268,7 @@ async fn polymarket_ws_task(
     running: Arc<AtomicBool>,
 ) -> Result<()> {
     let url = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+    let mut first_attempt = true;
 
     while running.load(Ordering::SeqCst) {
         // Get current token IDs
@@ -272,6 +284,10 @@ async fn polymarket_ws_task(
         }
 
         info!("Connecting to Polymarket WebSocket...");
+        if !first_attempt {
+            metrics::feed_reconnect("polymarket");
+        }
+        first_attempt = false;
 
         match connect_async(url).await {
             Ok((ws_stream, _)) => {
@@ -323,6 +339,7 @@ async fn polymarket_ws_task(
                         msg = read.next() => {
                             match msg {
                                 Some(Ok(Message::Text(text))) => {
+                                    metrics::feed_message("polymarket");
                                     // Log raw message for debugging (full message)
                                     debug!("Polymarket WS raw: {}", text);
 
@@ -603,16 +620,8 @@ fn load_matrix_from_file() -> Result<ProbabilityMatrix> {
     Ok(matrix)
 }
 
-/// Returns (edge_up_buy, edge_down_buy, edge_up_sell, edge_down_sell)
-fn calculate_edges(
-    matrix: &ProbabilityMatrix,
-    time_elapsed: u32,
-    price_delta: f64,
-    ask_up: f64,
-    ask_down: f64,
-    bid_up: f64,
-    bid_down: f64,
-) -> (Option<f64>, Option<f64>, Option<f64>, Option<f64>) {
+/// Wilson bounds on P(UP) for the current cell: (lower, upper), None if too few samples
+fn wilson_bounds(matrix: &ProbabilityMatrix, time_elapsed: u32, price_delta: f64) -> Option<(f64, f64)> {
     let time_bucket = (time_elapsed / 15).min(59) as u8;
     let delta_bucket = delta_to_bucket(
         Decimal::try_from(price_delta).unwrap_or_default()
@@ -621,12 +630,26 @@ fn calculate_edges(
     let cell = matrix.get(time_bucket, delta_bucket);
 
     // Must match bot's timing.min_samples_in_bucket (30)
-    if cell.total() < 30 {
-        return (None, None, None, None);
+    if cell.sample_size() < 30.0 {
+        return None;
     }
+    Some((cell.p_up_wilson_lower, cell.p_up_wilson_upper))
+}
 
-    let our_p_up = cell.p_up_wilson_lower;
-    let our_p_down = 1.0 - cell.p_up_wilson_upper;
+/// Returns (edge_up_buy, edge_down_buy, edge_up_sell, edge_down_sell)
+fn calculate_edges(
+    bounds: Option<(f64, f64)>,
+    ask_up: f64,
+    ask_down: f64,
+    bid_up: f64,
+    bid_down: f64,
+) -> (Option<f64>, Option<f64>, Option<f64>, Option<f64>) {
+    let Some((wilson_lower, wilson_upper)) = bounds else {
+        return (None, None, None, None);
+    };
+
+    let our_p_up = wilson_lower;
+    let our_p_down = 1.0 - wilson_upper;
 
     // Buy edges: positive when our probability > market price (good to buy)
     let edge_up_buy = if ask_up > 0.01 {
@@ -685,10 +708,9 @@ async fn logger_task(
         let price_delta = s.btc_price - s.window_open_price;
 
         // Calculate edges (buy and sell)
+        let bounds = wilson_bounds(&matrix, time_elapsed as u32, price_delta);
         let (edge_up, edge_down, edge_up_sell, edge_down_sell) = calculate_edges(
-            &matrix,
-            time_elapsed as u32,
-            price_delta,
+            bounds,
             s.up_best_ask,
             s.down_best_ask,
             s.up_best_bid,
@@ -706,8 +728,8 @@ async fn logger_task(
                 price_up, price_down, size_up, size_down,
                 edge_up, edge_down, btc_price, time_elapsed, price_delta, error_message,
                 bid_up, bid_down, edge_up_sell, edge_down_sell,
-                bid_size_up, bid_size_down
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+                bid_size_up, bid_size_down, p_up_wilson_lower, p_up_wilson_upper
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22)
             "#,
             &[
                 &timestamp,
@@ -730,6 +752,8 @@ async fn logger_task(
                 &edge_down_sell.map(f64_to_dec),
                 &(if s.up_best_bid_size > 0.0 { Some(f64_to_dec(s.up_best_bid_size)) } else { None }),
                 &(if s.down_best_bid_size > 0.0 { Some(f64_to_dec(s.down_best_bid_size)) } else { None }),
+                &bounds.map(|(lower, _)| f64_to_dec(lower)),
+                &bounds.map(|(_, upper)| f64_to_dec(upper)),
             ],
         ).await;
 
@@ -739,6 +763,7 @@ async fn logger_task(
             }
             Err(e) => {
                 error!("Failed to insert log: {}", e);
+                metrics::db_error();
                 error_count += 1;
             }
         }
@@ -800,6 +825,17 @@ async fn main() -> Result<()> {
         r.store(false, Ordering::SeqCst);
     });
 
+    // Start HTTP server for Prometheus scraping (/metrics)
+    let metrics_port: u16 = std::env::var("METRICS_PORT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(9100);
+    tokio::spawn(async move {
+        if let Err(e) = metrics::serve(metrics_port, metrics::router()).await {
+            error!("HTTP server failed: {}", e);
+        }
+    });
+
     // Connect to database
     let db_config = DbConfig::default();
     let db_client = connect_db(&db_config).await?;
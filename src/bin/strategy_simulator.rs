@@ -6,12 +6,17 @@
 //! - Min profit requirements
 //! - Max trades per window
 //! - Cooldown periods
+//! - Stop-loss modes (fixed, percent, trailing, model)
+//! - Plugin strategies from the bot config (same parameters as live)
 //!
 //! Usage:
 //!   btc-strategy-sim --hours 4
 //!   btc-strategy-sim --hours 24 --compare-buy-edges "0.05,0.07,0.10"
 //!   btc-strategy-sim --hours 24 --compare-sell-edges "0.05,0.10,0.15"
 //!   btc-strategy-sim --hours 24 --compare-min-profits "0.0,0.10,0.20,0.50"
+//!   btc-strategy-sim --hours 168 --trades-output trades.csv
+//!   btc-strategy-sim --hours 168 --compare-stop-modes "off,percent,trailing,model"
+//!   btc-strategy-sim --hours 168 --plugin cheap_buy --config config/bot_config.yaml
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -20,6 +25,7 @@ use native_tls::TlsConnector;
 use postgres_native_tls::MakeTlsConnector;
 use rust_decimal::prelude::*;
 use rust_decimal::Decimal;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
@@ -27,6 +33,17 @@ use tokio_postgres::Client;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
+#[path = "../stop_loss.rs"]
+#[allow(dead_code)]
+mod stop_loss;
+
+#[path = "../strategies.rs"]
+#[allow(dead_code)]
+mod strategies;
+
+use stop_loss::{StopLossConfig, StopLossMode, StopTracker};
+use strategies::{MarketView, Side, SideQuote, StrategyRegistry, StrategySlotConfig};
+
 // ============================================================================
 // CLI Arguments
 // ============================================================================
@@ -99,6 +116,68 @@ struct Args {
     /// Compare hold-to-expiration vs early-exit strategies
     #[arg(long)]
     compare_hold_vs_sell: bool,
+
+    /// Write individual trades of the best scenario to CSV
+    /// (input for btc-bankroll-sim --trades-csv)
+    #[arg(long)]
+    trades_output: Option<String>,
+
+    /// Stop-loss mode: off, fixed, percent, trailing, model
+    #[arg(long, default_value = "off")]
+    stop_mode: String,
+
+    /// Compare multiple stop-loss modes (comma-separated)
+    #[arg(long)]
+    compare_stop_modes: Option<String>,
+
+    /// Fixed stop: sell when bid <= this price
+    #[arg(long, default_value = "0.15")]
+    stop_price: f64,
+
+    /// Percent stop: sell when bid is this fraction below entry
+    #[arg(long, default_value = "0.50")]
+    max_loss_pct: f64,
+
+    /// Trailing stop: sell when bid is this fraction below its peak since entry
+    #[arg(long, default_value = "0.30")]
+    trailing_pct: f64,
+
+    /// Model stop: sell when our probability < bid - margin
+    #[arg(long, default_value = "0.05")]
+    model_margin: f64,
+
+    /// Backtest a plugin strategy from the bot config instead of the edge rules
+    /// (e.g. cheap_buy; its `strategies:` entry supplies limits and params)
+    #[arg(long)]
+    plugin: Option<String>,
+
+    /// Bot config to read plugin strategies from
+    #[arg(long, default_value = "config/bot_config.yaml")]
+    config: String,
+}
+
+/// The part of the bot config the simulator needs
+#[derive(Debug, Deserialize)]
+struct PluginConfigFile {
+    #[serde(default)]
+    strategies: Vec<StrategySlotConfig>,
+}
+
+/// Plugin slot by name from the bot config (defaults if not listed), enabled
+/// for the backtest even when it is switched off live
+fn load_plugin_slot(path: &str, name: &str) -> Result<StrategySlotConfig> {
+    let yaml = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+    let file: PluginConfigFile = serde_yaml::from_str(&yaml).with_context(|| format!("Failed to parse {}", path))?;
+    let mut slot = file
+        .strategies
+        .into_iter()
+        .find(|s| s.name == name)
+        .unwrap_or_else(|| StrategySlotConfig::new(name));
+    slot.enabled = true;
+
+    // Fail fast on unknown names / bad params
+    StrategyRegistry::from_config(std::slice::from_ref(&slot))?;
+    Ok(slot)
 }
 
 // ============================================================================
@@ -171,6 +250,49 @@ struct MarketSnapshot {
     spread_down: f64,
     // Delta (for alignment filter)
     price_delta: f64,   // BTC price change from window start
+    // Bid sizes in shares (None for rows logged before they were recorded)
+    bid_size_up: Option<f64>,
+    bid_size_down: Option<f64>,
+    // Ask sizes in shares
+    ask_size_up: f64,
+    ask_size_down: f64,
+    // Logged Wilson bounds on P(UP) (None for older rows or thin cells)
+    p_up_wilson_lower: Option<f64>,
+    p_up_wilson_upper: Option<f64>,
+}
+
+impl MarketSnapshot {
+    /// View handed to plugin strategies. Model P is the logged Wilson bound the
+    /// live bot uses (lower for UP, 1 - upper for DOWN); rows logged before the
+    /// bounds were recorded fall back to the buy edge = (p - ask) / ask.
+    fn market_view(&self) -> MarketView {
+        let from_edge = |ask: f64, edge: f64| (ask > 0.0).then(|| (ask * (1.0 + edge)).clamp(0.0, 1.0));
+        let (model_p_up, model_p_down) = match (self.p_up_wilson_lower, self.p_up_wilson_upper) {
+            (Some(lower), Some(upper)) => (Some(lower), Some(1.0 - upper)),
+            _ => (from_edge(self.price_up, self.edge_up), from_edge(self.price_down, self.edge_down)),
+        };
+        let elapsed = self.time_elapsed.max(0) as u32;
+        MarketView {
+            now_ms: self.time_elapsed as i64 * 1000,
+            seconds_elapsed: elapsed,
+            seconds_remaining: 900u32.saturating_sub(elapsed),
+            price_delta: self.price_delta,
+            up: SideQuote {
+                bid: self.bid_up,
+                ask: self.price_up,
+                bid_size: self.bid_size_up.unwrap_or(0.0),
+                ask_size: self.ask_size_up,
+            },
+            down: SideQuote {
+                bid: self.bid_down,
+                ask: self.price_down,
+                bid_size: self.bid_size_down.unwrap_or(0.0),
+                ask_size: self.ask_size_down,
+            },
+            model_p_up,
+            model_p_down,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -206,6 +328,10 @@ struct Position {
     direction: Direction,
     entry_price: f64,
     entry_time: i32,
+    entry_edge: f64,
+    size_frac: f64,     // Fraction of the bet still held (< 1 after a liquidity-capped stop)
+    stop: StopTracker,
+    exit_target: Option<f64>,  // Plugin sell target (None = edge rules / hold)
 }
 
 #[derive(Debug, Clone)]
@@ -213,15 +339,17 @@ struct Trade {
     direction: Direction,
     entry_price: f64,
     entry_time: i32,
+    entry_edge: f64,
     exit_price: f64,
     exit_time: i32,
-    exit_reason: String,  // "SELL_EDGE", "EXPIRATION_WIN", "EXPIRATION_LOSS"
+    exit_reason: String,  // "SELL_EDGE", "EXIT_TARGET", "STOP_LOSS", "EXPIRATION_WIN", "EXPIRATION_LOSS"
     pnl_pct: f64,
+    size_frac: f64,       // Fraction of the bet this exit closed
 }
 
 impl Trade {
     fn pnl(&self, bet_amount: f64) -> f64 {
-        bet_amount * self.pnl_pct / 100.0
+        bet_amount * self.size_frac * self.pnl_pct / 100.0
     }
 
     fn is_win(&self) -> bool {
@@ -243,6 +371,10 @@ struct ScenarioConfig {
     require_delta_alignment: bool,
     /// If true, disable selling (hold to expiration)
     hold_to_expiration: bool,
+    /// Stop-loss applied to every position (mode off = none)
+    stop_loss: StopLossConfig,
+    /// Plugin strategy replacing the edge buy/sell rules
+    plugin: Option<StrategySlotConfig>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -254,6 +386,7 @@ struct ScenarioResult {
     total_pnl: f64,
     markets_traded: u32,
     sell_edge_exits: u32,
+    stop_exits: u32,
     expiration_wins: u32,
     expiration_losses: u32,
     // Capital metrics (simple = total bets placed)
@@ -310,6 +443,7 @@ struct MarketResult {
     exp_wins: u32,
     exp_losses: u32,
     capital_invested: f64,  // Capital used in this market
+    trade_log: Vec<Trade>,  // Individual trades (for --trades-output)
 }
 
 // ============================================================================
@@ -368,7 +502,13 @@ async fn load_markets(client: &Client, hours: u32) -> Result<Vec<Market>> {
             edge_down,
             edge_up_sell,
             edge_down_sell,
-            price_delta
+            price_delta,
+            bid_size_up,
+            bid_size_down,
+            size_up,
+            size_down,
+            p_up_wilson_lower,
+            p_up_wilson_upper
         FROM market_logs
         WHERE timestamp > $1
           AND time_elapsed <= 885
@@ -408,6 +548,12 @@ async fn load_markets(client: &Client, hours: u32) -> Result<Vec<Market>> {
             spread_up,
             spread_down,
             price_delta: row.get::<_, Decimal>("price_delta").to_f64().unwrap_or(0.0),
+            bid_size_up: row.get::<_, Option<Decimal>>("bid_size_up").and_then(|d| d.to_f64()),
+            bid_size_down: row.get::<_, Option<Decimal>>("bid_size_down").and_then(|d| d.to_f64()),
+            ask_size_up: row.get::<_, Decimal>("size_up").to_f64().unwrap_or(0.0),
+            ask_size_down: row.get::<_, Decimal>("size_down").to_f64().unwrap_or(0.0),
+            p_up_wilson_lower: row.get::<_, Option<Decimal>>("p_up_wilson_lower").and_then(|d| d.to_f64()),
+            p_up_wilson_upper: row.get::<_, Option<Decimal>>("p_up_wilson_upper").and_then(|d| d.to_f64()),
         };
 
         markets_map.entry(slug).or_default().push(snapshot);
@@ -445,7 +591,135 @@ fn simulate_market(market: &Market, config: &ScenarioConfig, bet_amount: f64) ->
     let mut current_capital = 0.0;         // Real: currently held
     let mut peak_capital = 0.0;            // Real: max concurrent
 
+    // Fresh registry per market = per-window budgets reset
+    let mut registry = config
+        .plugin
+        .as_ref()
+        .map(|slot| StrategyRegistry::from_config(std::slice::from_ref(slot)).expect("plugin validated in main"));
+
     for snapshot in &market.snapshots {
+        // ═══════════════════════════════════════════════════════════════
+        // STEP 0: Check STOP-LOSS for each position (applies even when holding
+        // to expiration). Sells are capped at the logged bid size, shared by all
+        // positions stopped out in this snapshot; the remainder stays open.
+        // ═══════════════════════════════════════════════════════════════
+        if config.stop_loss.mode != StopLossMode::Off && !open_positions.is_empty() {
+            let mut bid_left_up = snapshot.bid_size_up;
+            let mut bid_left_down = snapshot.bid_size_down;
+
+            for pos in open_positions.iter_mut() {
+                let (bid, sell_edge, bid_left) = match pos.direction {
+                    Direction::Up => (snapshot.bid_up, snapshot.edge_up_sell, &mut bid_left_up),
+                    Direction::Down => (snapshot.bid_down, snapshot.edge_down_sell, &mut bid_left_down),
+                };
+                // Logged sell edge = (bid - our_p) / bid
+                let our_prob = bid * (1.0 - sell_edge);
+                if pos.stop.check(bid, our_prob).is_none() {
+                    continue;
+                }
+
+                let shares = pos.size_frac * bet_amount / pos.entry_price;
+                let sold = stop_loss::fillable_shares(shares, *bid_left);
+                if sold < 0.01 {
+                    continue;
+                }
+                if let Some(left) = bid_left.as_mut() {
+                    *left -= sold;
+                }
+
+                let sold_frac = pos.size_frac * sold / shares;
+                pos.size_frac -= sold_frac;
+                trades.push(Trade {
+                    direction: pos.direction,
+                    entry_price: pos.entry_price,
+                    entry_time: pos.entry_time,
+                    entry_edge: pos.entry_edge,
+                    exit_price: bid,
+                    exit_time: snapshot.time_elapsed,
+                    exit_reason: "STOP_LOSS".to_string(),
+                    pnl_pct: (bid - pos.entry_price) / pos.entry_price * 100.0,
+                    size_frac: sold_frac,
+                });
+                current_capital -= sold_frac * bet_amount;
+            }
+
+            open_positions.retain(|p| p.size_frac > 1e-6);
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // STEP 0b: Plugin exit targets - sell at the bid once it reaches the
+        // target the strategy set at entry
+        // ═══════════════════════════════════════════════════════════════
+        if !config.hold_to_expiration {
+            let mut i = 0;
+            while i < open_positions.len() {
+                let pos = &open_positions[i];
+                let bid = match pos.direction {
+                    Direction::Up => snapshot.bid_up,
+                    Direction::Down => snapshot.bid_down,
+                };
+                if !pos.exit_target.is_some_and(|t| bid > 0.0 && bid >= t) {
+                    i += 1;
+                    continue;
+                }
+
+                let pos = open_positions.remove(i);
+                trades.push(Trade {
+                    direction: pos.direction,
+                    entry_price: pos.entry_price,
+                    entry_time: pos.entry_time,
+                    entry_edge: pos.entry_edge,
+                    exit_price: bid,
+                    exit_time: snapshot.time_elapsed,
+                    exit_reason: "EXIT_TARGET".to_string(),
+                    pnl_pct: (bid - pos.entry_price) / pos.entry_price * 100.0,
+                    size_frac: pos.size_frac,
+                });
+                current_capital -= pos.size_frac * bet_amount;
+            }
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // PLUGIN MODE: the strategy decides entries (limits enforced by the
+        // registry, exactly as in the bot); edge rules are skipped
+        // ═══════════════════════════════════════════════════════════════
+        if let Some(ref mut registry) = registry {
+            let view = snapshot.market_view();
+            let held = open_positions.len() as u32;
+            let intents = registry.evaluate(&view, &|_| held);
+
+            for intent in intents {
+                let direction = match intent.side {
+                    Side::Up => Direction::Up,
+                    Side::Down => Direction::Down,
+                };
+                open_positions.push(Position {
+                    direction,
+                    entry_price: intent.price,
+                    entry_time: snapshot.time_elapsed,
+                    entry_edge: intent.edge,
+                    size_frac: intent.amount_usdc / bet_amount,
+                    stop: StopTracker::new(&config.stop_loss, intent.price),
+                    exit_target: intent.exit_target,
+                });
+                registry.on_fill(&strategies::Fill {
+                    strategy: intent.strategy.clone(),
+                    side: intent.side,
+                    price: intent.price,
+                    shares: intent.amount_usdc / intent.price,
+                    amount_usdc: intent.amount_usdc,
+                    now_ms: view.now_ms,
+                });
+
+                total_bets_placed += intent.amount_usdc;
+                current_capital += intent.amount_usdc;
+                if current_capital > peak_capital {
+                    peak_capital = current_capital;
+                }
+            }
+            continue;
+        }
+
         // ═══════════════════════════════════════════════════════════════
         // STEP 1: Check SELL conditions if we have ANY positions
         // (Skip if hold_to_expiration is true)
@@ -467,21 +741,23 @@ fn simulate_market(market: &Market, config: &ScenarioConfig, bet_amount: f64) ->
 
                 if profit_pct >= config.min_profit {
                     // Sell ALL positions at current bid - CAPITAL FREED!
-                    let positions_sold = open_positions.len();
+                    let fraction_sold: f64 = open_positions.iter().map(|p| p.size_frac).sum();
                     for pos in open_positions.drain(..) {
                         let pos_profit_pct = (bid_price - pos.entry_price) / pos.entry_price * 100.0;
                         trades.push(Trade {
                             direction: pos.direction,
                             entry_price: pos.entry_price,
                             entry_time: pos.entry_time,
+                            entry_edge: pos.entry_edge,
                             exit_price: bid_price,
                             exit_time: snapshot.time_elapsed,
                             exit_reason: "SELL_EDGE".to_string(),
                             pnl_pct: pos_profit_pct,
+                            size_frac: pos.size_frac,
                         });
                     }
                     // Capital comes back when we sell!
-                    current_capital -= positions_sold as f64 * bet_amount;
+                    current_capital -= fraction_sold * bet_amount;
                     // Don't continue - allow buying again in same snapshot if edge exists
                 }
             }
@@ -532,6 +808,10 @@ fn simulate_market(market: &Market, config: &ScenarioConfig, bet_amount: f64) ->
                         direction: Direction::Down,
                         entry_price: snapshot.price_down,
                         entry_time: snapshot.time_elapsed,
+                        entry_edge: snapshot.edge_down,
+                        size_frac: 1.0,
+                        stop: StopTracker::new(&config.stop_loss, snapshot.price_down),
+                        exit_target: None,
                     });
                     last_entry_time = Some(snapshot.time_elapsed);
                     trades_count += 1;
@@ -542,6 +822,10 @@ fn simulate_market(market: &Market, config: &ScenarioConfig, bet_amount: f64) ->
                         direction: Direction::Up,
                         entry_price: snapshot.price_up,
                         entry_time: snapshot.time_elapsed,
+                        entry_edge: snapshot.edge_up,
+                        size_frac: 1.0,
+                        stop: StopTracker::new(&config.stop_loss, snapshot.price_up),
+                        exit_target: None,
                     });
                     last_entry_time = Some(snapshot.time_elapsed);
                     trades_count += 1;
@@ -556,6 +840,10 @@ fn simulate_market(market: &Market, config: &ScenarioConfig, bet_amount: f64) ->
                     direction: Direction::Down,
                     entry_price: snapshot.price_down,
                     entry_time: snapshot.time_elapsed,
+                    entry_edge: snapshot.edge_down,
+                    size_frac: 1.0,
+                    stop: StopTracker::new(&config.stop_loss, snapshot.price_down),
+                    exit_target: None,
                 });
                 last_entry_time = Some(snapshot.time_elapsed);
                 trades_count += 1;
@@ -565,6 +853,10 @@ fn simulate_market(market: &Market, config: &ScenarioConfig, bet_amount: f64) ->
                     direction: Direction::Up,
                     entry_price: snapshot.price_up,
                     entry_time: snapshot.time_elapsed,
+                    entry_edge: snapshot.edge_up,
+                    size_frac: 1.0,
+                    stop: StopTracker::new(&config.stop_loss, snapshot.price_up),
+                    exit_target: None,
                 });
                 last_entry_time = Some(snapshot.time_elapsed);
                 trades_count += 1;
@@ -602,10 +894,12 @@ fn simulate_market(market: &Market, config: &ScenarioConfig, bet_amount: f64) ->
             direction: pos.direction,
             entry_price: pos.entry_price,
             entry_time: pos.entry_time,
+            entry_edge: pos.entry_edge,
             exit_price: if won { 1.0 } else { 0.0 },
             exit_time: 900,
             exit_reason: if won { "EXPIRATION_WIN".to_string() } else { "EXPIRATION_LOSS".to_string() },
             pnl_pct,
+            size_frac: pos.size_frac,
         });
     }
 
@@ -641,6 +935,7 @@ fn run_scenario(markets: &[Market], config: &ScenarioConfig, bet_amount: f64) ->
             exp_wins: 0,
             exp_losses: 0,
             capital_invested: capital_info.simple_capital,
+            trade_log: Vec::new(),
         };
 
         for trade in &trades {
@@ -657,7 +952,8 @@ fn run_scenario(markets: &[Market], config: &ScenarioConfig, bet_amount: f64) ->
             }
 
             match trade.exit_reason.as_str() {
-                "SELL_EDGE" => { mr.sell_exits += 1; result.sell_edge_exits += 1; }
+                "SELL_EDGE" | "EXIT_TARGET" => { mr.sell_exits += 1; result.sell_edge_exits += 1; }
+                "STOP_LOSS" => { mr.sell_exits += 1; result.stop_exits += 1; }
                 "EXPIRATION_WIN" => { mr.exp_wins += 1; result.expiration_wins += 1; }
                 "EXPIRATION_LOSS" => { mr.exp_losses += 1; result.expiration_losses += 1; }
                 _ => {}
@@ -679,6 +975,7 @@ fn run_scenario(markets: &[Market], config: &ScenarioConfig, bet_amount: f64) ->
         }
 
         result.markets_traded += 1;
+        mr.trade_log = trades;
         market_results.push(mr);
     }
 
@@ -697,17 +994,17 @@ fn print_scenario_comparison(results: &[ScenarioResult], bet_amount: f64) {
 
     // Header with both simple and real capital
     println!(
-        "{:<30} {:>6} {:>6} {:>8} | {:>10} {:>8} {:>8} | {:>10} {:>8} {:>8} | {:>7} {:>7} {:>7}",
+        "{:<38} {:>6} {:>6} {:>8} | {:>10} {:>8} {:>8} | {:>10} {:>8} {:>8} | {:>7} {:>7} {:>7} {:>7}",
         "Scenario", "Trades", "Win%", "P&L",
         "SimpleCap", "Return%", "MaxCap/W",
         "RealCap", "RealRet%", "RealMax/W",
-        "SellEx", "ExpWin", "ExpLoss"
+        "SellEx", "StopEx", "ExpWin", "ExpLoss"
     );
     println!("{}", "-".repeat(200));
 
     for r in results {
         println!(
-            "{:<30} {:>6} {:>5.1}% {:>7.0}$ | {:>9.0}$ {:>7.1}% {:>7.0}$ | {:>9.0}$ {:>7.1}% {:>8.0}$ | {:>7} {:>7} {:>7}",
+            "{:<38} {:>6} {:>5.1}% {:>7.0}$ | {:>9.0}$ {:>7.1}% {:>7.0}$ | {:>9.0}$ {:>7.1}% {:>8.0}$ | {:>7} {:>7} {:>7} {:>7}",
             r.name,
             r.total_trades,
             r.win_rate(),
@@ -722,6 +1019,7 @@ fn print_scenario_comparison(results: &[ScenarioResult], bet_amount: f64) {
             r.max_real_capital_per_window,
             // Exit types
             r.sell_edge_exits,
+            r.stop_exits,
             r.expiration_wins,
             r.expiration_losses,
         );
@@ -768,19 +1066,19 @@ fn print_market_breakdown(market_results: &[MarketResult]) {
 fn write_csv(results: &[ScenarioResult], path: &str) -> Result<()> {
     let mut file = File::create(path)?;
 
-    writeln!(file, "scenario,trades,wins,losses,win_pct,total_pnl,simple_capital,simple_return_pct,simple_max_cap,real_capital,real_return_pct,real_max_cap,pnl_per_trade,sell_exits,exp_wins,exp_losses,markets")?;
+    writeln!(file, "scenario,trades,wins,losses,win_pct,total_pnl,simple_capital,simple_return_pct,simple_max_cap,real_capital,real_return_pct,real_max_cap,pnl_per_trade,sell_exits,stop_exits,exp_wins,exp_losses,markets")?;
 
     for r in results {
         writeln!(
             file,
-            "{},{},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.4},{},{},{},{}",
+            "{},{},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.4},{},{},{},{},{}",
             r.name, r.total_trades, r.wins, r.losses, r.win_rate(),
             r.total_pnl,
             // Simple capital
             r.total_capital_invested, r.total_return_pct(), r.max_capital_per_window,
             // Real capital
             r.total_real_capital, r.real_return_pct(), r.max_real_capital_per_window,
-            r.pnl_per_trade(), r.sell_edge_exits,
+            r.pnl_per_trade(), r.sell_edge_exits, r.stop_exits,
             r.expiration_wins, r.expiration_losses, r.markets_traded
         )?;
     }
@@ -789,6 +1087,56 @@ fn write_csv(results: &[ScenarioResult], path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Write per-trade returns in the format read by btc-bankroll-sim.
+/// Confidence is left empty: market_logs does not record the matrix cell confidence.
+/// One row per position: partial (liquidity-capped) exits of the same entry
+/// are merged, with the return weighted by the fraction each exit closed
+fn position_trades(trades: &[Trade]) -> Vec<Trade> {
+    let mut merged: Vec<Trade> = Vec::new();
+    for t in trades {
+        let same_position = |m: &Trade| {
+            m.direction == t.direction && m.entry_time == t.entry_time && m.entry_price == t.entry_price
+        };
+        match merged.iter_mut().find(|m| same_position(m)) {
+            Some(m) => {
+                let total = m.size_frac + t.size_frac;
+                m.pnl_pct = (m.pnl_pct * m.size_frac + t.pnl_pct * t.size_frac) / total;
+                m.size_frac = total;
+                m.exit_price = t.exit_price;
+                m.exit_time = t.exit_time;
+                if !m.exit_reason.split('+').any(|r| r == t.exit_reason) {
+                    m.exit_reason = format!("{}+{}", m.exit_reason, t.exit_reason);
+                }
+            }
+            None => merged.push(t.clone()),
+        }
+    }
+    merged
+}
+
+fn write_trades_csv(market_results: &[MarketResult], path: &str) -> Result<()> {
+    let mut file = File::create(path)?;
+
+    // return_pct is per dollar staked on the position; size_frac is the stake as a fraction of the bet
+    writeln!(file, "market_slug,direction,entry_time,entry_price,edge,confidence,exit_reason,return_pct,size_frac")?;
+
+    let mut count = 0;
+    for mr in market_results {
+        for t in position_trades(&mr.trade_log) {
+            writeln!(
+                file,
+                "{},{},{},{:.4},{:.4},,{},{:.4},{:.4}",
+                mr.market_slug, t.direction, t.entry_time, t.entry_price,
+                t.entry_edge, t.exit_reason, t.pnl_pct, t.size_frac
+            )?;
+            count += 1;
+        }
+    }
+
+    info!("{} trades written to {}", count, path);
+    Ok(())
+}
+
 // ============================================================================
 // Main
 // ============================================================================
@@ -863,39 +1211,97 @@ async fn main() -> Result<()> {
         vec![false]  // Default: allow selling
     };
 
+    // Stop-loss options
+    let stop_modes: Vec<StopLossMode> = args.compare_stop_modes
+        .as_deref()
+        .unwrap_or(&args.stop_mode)
+        .split(',')
+        .map(|x| StopLossMode::parse(x).with_context(|| format!("Unknown stop mode: {}", x)))
+        .collect::<Result<_>>()?;
+
     // Build scenarios
     let mut scenarios: Vec<ScenarioConfig> = Vec::new();
 
-    for &buy_edge in &buy_edges {
+    let stop_config = |mode: StopLossMode| StopLossConfig {
+        mode,
+        stop_price: args.stop_price,
+        max_loss_pct: args.max_loss_pct,
+        trailing_pct: args.trailing_pct,
+        model_margin: args.model_margin,
+    };
+
+    // Plugin mode: one scenario per stop mode (and hold vs sell); edge options don't apply
+    let plugin_slot = match args.plugin.as_deref() {
+        Some(name) => Some(load_plugin_slot(&args.config, name)?),
+        None => None,
+    };
+    let edge_buy_edges: &[f64] = if plugin_slot.is_some() { &[] } else { &buy_edges };
+
+    if let Some(ref slot) = plugin_slot {
+        info!("Plugin '{}' from {}: params {:?}", slot.name, args.config, slot.params);
+        for &hold in &hold_options {
+            for &stop_mode in &stop_modes {
+                let mut name = format!("PLUGIN_{}", slot.name);
+                if hold {
+                    name.push_str("_HOLD");
+                }
+                if stop_mode != StopLossMode::Off {
+                    name.push_str(&format!("_SL-{}", stop_mode.as_str()));
+                }
+                scenarios.push(ScenarioConfig {
+                    name,
+                    min_buy_edge: 0.0,
+                    min_sell_edge: 0.0,
+                    min_profit: 0.0,
+                    max_spread: args.max_spread,
+                    cooldown_seconds: 0,
+                    max_trades_per_market: None,
+                    require_delta_alignment: false,
+                    hold_to_expiration: hold,
+                    stop_loss: stop_config(stop_mode),
+                    plugin: Some(slot.clone()),
+                });
+            }
+        }
+    }
+
+    for &buy_edge in edge_buy_edges {
         for &sell_edge in &sell_edges {
             for &min_profit in &min_profits {
                 for &cooldown in &cooldowns {
                     for &max_trade in &max_trades {
                         for &delta_align in &delta_alignments {
                             for &hold in &hold_options {
-                                // Build scenario name
-                                let align_str = if delta_align { "DA" } else { "noDA" };
-                                let exit_str = if hold { "HOLD" } else { "SELL" };
-                                let name = format!(
-                                    "B{:.0}%_S{:.0}%_P{:.0}%_{}_{}",
-                                    buy_edge * 100.0,
-                                    sell_edge * 100.0,
-                                    min_profit * 100.0,
-                                    align_str,
-                                    exit_str
-                                );
-
-                                scenarios.push(ScenarioConfig {
-                                    name,
-                                    min_buy_edge: buy_edge,
-                                    min_sell_edge: sell_edge,
-                                    min_profit,
-                                    max_spread: args.max_spread,
-                                    cooldown_seconds: cooldown,
-                                    max_trades_per_market: max_trade,
-                                    require_delta_alignment: delta_align,
-                                    hold_to_expiration: hold,
-                                });
+                                for &stop_mode in &stop_modes {
+                                    // Build scenario name
+                                    let align_str = if delta_align { "DA" } else { "noDA" };
+                                    let exit_str = if hold { "HOLD" } else { "SELL" };
+                                    let mut name = format!(
+                                        "B{:.0}%_S{:.0}%_P{:.0}%_{}_{}",
+                                        buy_edge * 100.0,
+                                        sell_edge * 100.0,
+                                        min_profit * 100.0,
+                                        align_str,
+                                        exit_str
+                                    );
+                                    if stop_mode != StopLossMode::Off {
+                                        name.push_str(&format!("_SL-{}", stop_mode.as_str()));
+                                    }
+
+                                    scenarios.push(ScenarioConfig {
+                                        name,
+                                        min_buy_edge: buy_edge,
+                                        min_sell_edge: sell_edge,
+                                        min_profit,
+                                        max_spread: args.max_spread,
+                                        cooldown_seconds: cooldown,
+                                        max_trades_per_market: max_trade,
+                                        require_delta_alignment: delta_align,
+                                        hold_to_expiration: hold,
+                                        stop_loss: stop_config(stop_mode),
+                                        plugin: None,
+                                    });
+                                }
                             }
                         }
                     }
@@ -946,6 +1352,12 @@ async fn main() -> Result<()> {
         write_csv(&results, output_path)?;
     }
 
+    if let Some(trades_path) = &args.trades_output {
+        if let Some(ref mr) = best_market_results {
+            write_trades_csv(mr, trades_path)?;
+        }
+    }
+
     println!("\n{}", "=".repeat(80));
     println!("{:^80}", "SIMULATION COMPLETE");
     println!("{}", "=".repeat(80));
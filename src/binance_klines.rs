@@ -1,20 +1,77 @@
 //! Binance Klines Fetcher
 //!
-//! Fetches historical BTC/USDT klines (candlesticks) from Binance REST API
-//! and stores them in PostgreSQL for matrix building.
+//! Fetches historical BTC/USDT klines (candlesticks) and aggregate trades
+//! from Binance REST API and stores them in PostgreSQL for matrix building.
+//! Klines are stored per interval (1m, 1s) so matrices can be built at the
+//! resolution the bot trades at.
 
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Duration, TimeZone, Utc};
 use rust_decimal::Decimal;
-use serde::Deserialize;
+use std::fmt;
 use std::str::FromStr;
 use tokio_postgres::Client;
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
+
+use crate::http_client::HttpClient;
 
 const BINANCE_API_URL: &str = "https://api.binance.com";
 const SYMBOL: &str = "BTCUSDT";
 const DB_SYMBOL: &str = "BTCUSDT";
 
+/// Binance limit for both /klines and /aggTrades
+const PAGE_LIMIT: usize = 1000;
+
+/// Kline interval stored in the `kline_interval` column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KlineInterval {
+    OneSecond,
+    #[default]
+    OneMinute,
+}
+
+impl KlineInterval {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            KlineInterval::OneSecond => "1s",
+            KlineInterval::OneMinute => "1m",
+        }
+    }
+
+    pub fn step(self) -> Duration {
+        match self {
+            KlineInterval::OneSecond => Duration::seconds(1),
+            KlineInterval::OneMinute => Duration::minutes(1),
+        }
+    }
+
+    /// History fetched when nothing is stored yet (1s is ~600k rows a week)
+    pub fn default_lookback_days(self) -> u32 {
+        match self {
+            KlineInterval::OneSecond => 7,
+            KlineInterval::OneMinute => 180,
+        }
+    }
+}
+
+impl fmt::Display for KlineInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for KlineInterval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1s" => Ok(KlineInterval::OneSecond),
+            "1m" => Ok(KlineInterval::OneMinute),
+            other => Err(anyhow!("Unsupported kline interval '{}' (expected 1s or 1m)", other)),
+        }
+    }
+}
+
 /// Kline data from Binance API
 #[derive(Debug, Clone)]
 pub struct Kline {
@@ -29,16 +86,26 @@ pub struct Kline {
     pub num_trades: i64,
 }
 
+/// Aggregate trade from Binance API (fills at one price from one taker order)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggTrade {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub first_trade_id: i64,
+    pub last_trade_id: i64,
+    pub is_buyer_maker: bool,
+}
+
 /// Binance klines client
 pub struct BinanceKlinesClient {
-    http: reqwest::Client,
+    http: HttpClient,
 }
 
 impl BinanceKlinesClient {
     pub fn new(timeout_ms: u64) -> Result<Self> {
-        let http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(timeout_ms))
-            .build()?;
+        let http = HttpClient::new("binance-klines", std::time::Duration::from_millis(timeout_ms));
 
         Ok(Self { http })
     }
@@ -49,13 +116,16 @@ impl BinanceKlinesClient {
         &self,
         start_time: DateTime<Utc>,
         end_time: DateTime<Utc>,
+        interval: KlineInterval,
     ) -> Result<Vec<Kline>> {
         let url = format!(
-            "{}/api/v3/klines?symbol={}&interval=1m&startTime={}&endTime={}&limit=1000",
+            "{}/api/v3/klines?symbol={}&interval={}&startTime={}&endTime={}&limit={}",
             BINANCE_API_URL,
             SYMBOL,
+            interval,
             start_time.timestamp_millis(),
-            end_time.timestamp_millis()
+            end_time.timestamp_millis(),
+            PAGE_LIMIT
         );
 
         let response = self.http.get(&url).send().await?;
@@ -71,89 +141,105 @@ impl BinanceKlinesClient {
 
         let mut klines = Vec::with_capacity(data.len());
         for row in data {
-            if row.len() < 11 {
-                continue;
+            if let Some(kline) = parse_kline(&row)? {
+                klines.push(kline);
             }
-
-            let open_time_ms = row[0].as_i64().unwrap_or(0);
-            let close_time_ms = row[6].as_i64().unwrap_or(0);
-
-            let kline = Kline {
-                open_time: Utc.timestamp_millis_opt(open_time_ms).unwrap(),
-                open: parse_decimal(&row[1])?,
-                high: parse_decimal(&row[2])?,
-                low: parse_decimal(&row[3])?,
-                close: parse_decimal(&row[4])?,
-                volume: parse_decimal(&row[5])?,
-                close_time: Utc.timestamp_millis_opt(close_time_ms).unwrap(),
-                quote_volume: parse_decimal(&row[7])?,
-                num_trades: row[8].as_i64().unwrap_or(0),
-            };
-
-            klines.push(kline);
         }
 
         Ok(klines)
     }
 
-    /// Fetch all klines from start_date to end_date
-    /// Handles pagination automatically (1000 klines per request)
-    pub async fn fetch_all_klines(
-        &self,
-        start_date: DateTime<Utc>,
-        end_date: DateTime<Utc>,
-        progress_callback: Option<&dyn Fn(usize, usize)>,
-    ) -> Result<Vec<Kline>> {
-        let total_minutes = (end_date - start_date).num_minutes() as usize;
-        let total_requests = (total_minutes + 999) / 1000;
-
-        info!(
-            "Fetching {} minutes of klines ({} requests)",
-            total_minutes, total_requests
+    /// Fetch up to 1000 aggregate trades starting at `from_id`
+    pub async fn fetch_agg_trades(&self, from_id: i64) -> Result<Vec<AggTrade>> {
+        let url = format!(
+            "{}/api/v3/aggTrades?symbol={}&fromId={}&limit={}",
+            BINANCE_API_URL, SYMBOL, from_id, PAGE_LIMIT
         );
+        self.get_agg_trades(&url).await
+    }
 
-        let mut all_klines = Vec::new();
-        let mut current_start = start_date;
-        let mut request_count = 0;
-
-        while current_start < end_date {
-            // Each request fetches up to 1000 1-minute klines
-            let batch_end = (current_start + Duration::minutes(1000)).min(end_date);
-
-            let klines = self.fetch_klines(current_start, batch_end).await?;
-            let count = klines.len();
-            all_klines.extend(klines);
-
-            request_count += 1;
-            if let Some(cb) = progress_callback {
-                cb(request_count, total_requests);
+    /// ID of the first aggregate trade in [start, end), if any
+    /// Binance only accepts startTime/endTime spans of up to one hour
+    pub async fn first_agg_trade_id(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Option<i64>> {
+        let mut current_start = start;
+        while current_start < end {
+            let batch_end = (current_start + Duration::hours(1)).min(end);
+            let url = format!(
+                "{}/api/v3/aggTrades?symbol={}&startTime={}&endTime={}&limit=1",
+                BINANCE_API_URL,
+                SYMBOL,
+                current_start.timestamp_millis(),
+                batch_end.timestamp_millis() - 1
+            );
+            if let Some(trade) = self.get_agg_trades(&url).await?.first() {
+                return Ok(Some(trade.id));
             }
+            current_start = batch_end;
+        }
+        Ok(None)
+    }
 
-            if count == 0 {
-                warn!("No klines returned for range {:?} to {:?}", current_start, batch_end);
-                current_start = batch_end;
-            } else {
-                // Move to next batch (use last kline's time + 1 minute to avoid duplicates)
-                current_start = batch_end;
-            }
+    async fn get_agg_trades(&self, url: &str) -> Result<Vec<AggTrade>> {
+        let response = self.http.get(url).send().await?;
 
-            // Rate limiting: 1200 requests/minute, so ~20/second max
-            // Be conservative: 5 requests/second
-            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Binance API error: {} - {}", status, text));
         }
 
-        info!("Fetched {} total klines", all_klines.len());
-        Ok(all_klines)
+        let data: Vec<serde_json::Value> = response.json().await?;
+        data.iter().map(parse_agg_trade).collect()
     }
 }
 
+/// Parse one /klines row; rows with fewer than 11 fields are skipped
+fn parse_kline(row: &[serde_json::Value]) -> Result<Option<Kline>> {
+    if row.len() < 11 {
+        return Ok(None);
+    }
+
+    let open_time_ms = row[0].as_i64().unwrap_or(0);
+    let close_time_ms = row[6].as_i64().unwrap_or(0);
+
+    Ok(Some(Kline {
+        open_time: Utc.timestamp_millis_opt(open_time_ms).unwrap(),
+        open: parse_decimal(&row[1])?,
+        high: parse_decimal(&row[2])?,
+        low: parse_decimal(&row[3])?,
+        close: parse_decimal(&row[4])?,
+        volume: parse_decimal(&row[5])?,
+        close_time: Utc.timestamp_millis_opt(close_time_ms).unwrap(),
+        quote_volume: parse_decimal(&row[7])?,
+        num_trades: row[8].as_i64().unwrap_or(0),
+    }))
+}
+
+/// Parse one /aggTrades entry: {"a": id, "p": price, "q": qty, "f": first id,
+/// "l": last id, "T": time ms, "m": buyer is maker}
+fn parse_agg_trade(value: &serde_json::Value) -> Result<AggTrade> {
+    let id = |key: &str| value[key].as_i64().ok_or_else(|| anyhow!("aggTrade without '{}': {}", key, value));
+    Ok(AggTrade {
+        id: id("a")?,
+        timestamp: Utc
+            .timestamp_millis_opt(id("T")?)
+            .single()
+            .ok_or_else(|| anyhow!("Invalid aggTrade time: {}", value))?,
+        price: parse_decimal(&value["p"])?,
+        quantity: parse_decimal(&value["q"])?,
+        first_trade_id: id("f")?,
+        last_trade_id: id("l")?,
+        is_buyer_maker: value["m"].as_bool().unwrap_or(false),
+    })
+}
+
 fn parse_decimal(value: &serde_json::Value) -> Result<Decimal> {
     let s = value.as_str().unwrap_or("0");
     Decimal::from_str(s).context("Failed to parse decimal")
 }
 
-/// Insert klines into the database
-pub async fn insert_klines(client: &Client, klines: &[Kline]) -> Result<u64> {
+/// Insert klines of one interval into the database
+pub async fn insert_klines(client: &Client, klines: &[Kline], interval: KlineInterval) -> Result<u64> {
     if klines.is_empty() {
         return Ok(0);
     }
@@ -163,7 +249,7 @@ pub async fn insert_klines(client: &Client, klines: &[Kline]) -> Result<u64> {
     // Use batched inserts for efficiency
     for chunk in klines.chunks(1000) {
         let mut query = String::from(
-            "INSERT INTO binance_klines (symbol, timestamp, open_price, high_price, low_price, close_price, volume, quote_volume, num_trades) VALUES "
+            "INSERT INTO binance_klines (symbol, kline_interval, timestamp, open_price, high_price, low_price, close_price, volume, quote_volume, num_trades) VALUES "
         );
 
         let mut params: Vec<Box<dyn tokio_postgres::types::ToSql + Sync>> = Vec::new();
@@ -174,7 +260,7 @@ pub async fn insert_klines(client: &Client, klines: &[Kline]) -> Result<u64> {
                 query.push_str(", ");
             }
             query.push_str(&format!(
-                "(${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${})",
+                "(${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${})",
                 param_idx,
                 param_idx + 1,
                 param_idx + 2,
@@ -183,11 +269,13 @@ pub async fn insert_klines(client: &Client, klines: &[Kline]) -> Result<u64> {
                 param_idx + 5,
                 param_idx + 6,
                 param_idx + 7,
-                param_idx + 8
+                param_idx + 8,
+                param_idx + 9
             ));
-            param_idx += 9;
+            param_idx += 10;
 
             params.push(Box::new(DB_SYMBOL.to_string()));
+            params.push(Box::new(interval.as_str()));
             params.push(Box::new(kline.open_time));
             params.push(Box::new(kline.open));
             params.push(Box::new(kline.high));
@@ -198,7 +286,7 @@ pub async fn insert_klines(client: &Client, klines: &[Kline]) -> Result<u64> {
             params.push(Box::new(kline.num_trades));
         }
 
-        query.push_str(" ON CONFLICT (symbol, timestamp) DO NOTHING");
+        query.push_str(" ON CONFLICT (symbol, kline_interval, timestamp) DO NOTHING");
 
         // Convert to references
         let param_refs: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> =
@@ -212,11 +300,11 @@ pub async fn insert_klines(client: &Client, klines: &[Kline]) -> Result<u64> {
 }
 
 /// Get the latest timestamp in the database
-pub async fn get_latest_timestamp(client: &Client) -> Result<Option<DateTime<Utc>>> {
+pub async fn get_latest_timestamp(client: &Client, interval: KlineInterval) -> Result<Option<DateTime<Utc>>> {
     let row = client
         .query_opt(
-            "SELECT MAX(timestamp) FROM binance_klines WHERE symbol = $1",
-            &[&DB_SYMBOL],
+            "SELECT MAX(timestamp) FROM binance_klines WHERE symbol = $1 AND kline_interval = $2",
+            &[&DB_SYMBOL, &interval.as_str()],
         )
         .await?;
 
@@ -230,11 +318,11 @@ pub async fn get_latest_timestamp(client: &Client) -> Result<Option<DateTime<Utc
 }
 
 /// Get the earliest timestamp in the database
-pub async fn get_earliest_timestamp(client: &Client) -> Result<Option<DateTime<Utc>>> {
+pub async fn get_earliest_timestamp(client: &Client, interval: KlineInterval) -> Result<Option<DateTime<Utc>>> {
     let row = client
         .query_opt(
-            "SELECT MIN(timestamp) FROM binance_klines WHERE symbol = $1",
-            &[&DB_SYMBOL],
+            "SELECT MIN(timestamp) FROM binance_klines WHERE symbol = $1 AND kline_interval = $2",
+            &[&DB_SYMBOL, &interval.as_str()],
         )
         .await?;
 
@@ -248,20 +336,21 @@ pub async fn get_earliest_timestamp(client: &Client) -> Result<Option<DateTime<U
 }
 
 /// Get count of klines in database
-pub async fn get_kline_count(client: &Client) -> Result<i64> {
+pub async fn get_kline_count(client: &Client, interval: KlineInterval) -> Result<i64> {
     let row = client
         .query_one(
-            "SELECT COUNT(*) FROM binance_klines WHERE symbol = $1",
-            &[&DB_SYMBOL],
+            "SELECT COUNT(*) FROM binance_klines WHERE symbol = $1 AND kline_interval = $2",
+            &[&DB_SYMBOL, &interval.as_str()],
         )
         .await?;
 
     Ok(row.get(0))
 }
 
-/// Find gaps in the kline data
+/// Find gaps in the kline data (more than two intervals between klines)
 /// Returns list of (gap_start, gap_end) tuples where data is missing
-pub async fn find_gaps(client: &Client) -> Result<Vec<(DateTime<Utc>, DateTime<Utc>)>> {
+pub async fn find_gaps(client: &Client, interval: KlineInterval) -> Result<Vec<(DateTime<Utc>, DateTime<Utc>)>> {
+    let max_step_secs = 2 * interval.step().num_seconds();
     let rows = client
         .query(
             r#"
@@ -270,18 +359,17 @@ pub async fn find_gaps(client: &Client) -> Result<Vec<(DateTime<Utc>, DateTime<U
                     timestamp,
                     LAG(timestamp) OVER (ORDER BY timestamp) as prev_timestamp
                 FROM binance_klines
-                WHERE symbol = $1
+                WHERE symbol = $1 AND kline_interval = $2
             )
             SELECT
                 prev_timestamp as gap_start,
-                timestamp as gap_end,
-                EXTRACT(EPOCH FROM (timestamp - prev_timestamp)) / 60 as gap_minutes
+                timestamp as gap_end
             FROM time_diffs
-            WHERE EXTRACT(EPOCH FROM (timestamp - prev_timestamp)) > 120
+            WHERE EXTRACT(EPOCH FROM (timestamp - prev_timestamp)) > $3::BIGINT
             ORDER BY prev_timestamp
             LIMIT 100
             "#,
-            &[&DB_SYMBOL],
+            &[&DB_SYMBOL, &interval.as_str(), &max_step_secs],
         )
         .await?;
 
@@ -297,12 +385,56 @@ pub async fn find_gaps(client: &Client) -> Result<Vec<(DateTime<Utc>, DateTime<U
     Ok(gaps)
 }
 
+/// Fetch klines from `start` to `end` and insert each page (1000 klines) as
+/// it arrives, so long 1s ranges are never held in memory
+pub async fn ingest_klines(
+    client: &Client,
+    binance_client: &BinanceKlinesClient,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    interval: KlineInterval,
+    progress_callback: Option<&dyn Fn(usize, usize)>,
+) -> Result<u64> {
+    let batch = interval.step() * PAGE_LIMIT as i32;
+    let total_klines = ((end - start).num_seconds() / interval.step().num_seconds()).max(0) as usize;
+    let total_requests = total_klines.div_ceil(PAGE_LIMIT);
+
+    info!("Fetching {} {} klines ({} requests)", total_klines, interval, total_requests);
+
+    let mut inserted = 0u64;
+    let mut current_start = start;
+    let mut request_count = 0;
+
+    while current_start < end {
+        let batch_end = (current_start + batch).min(end);
+
+        // endTime is inclusive, so stop just before the next batch's first kline
+        let klines = binance_client
+            .fetch_klines(current_start, batch_end - Duration::milliseconds(1), interval)
+            .await?;
+        if klines.is_empty() {
+            warn!("No klines returned for range {:?} to {:?}", current_start, batch_end);
+        }
+        inserted += insert_klines(client, &klines, interval).await?;
+
+        request_count += 1;
+        if let Some(cb) = progress_callback {
+            cb(request_count, total_requests);
+        }
+
+        current_start = batch_end;
+    }
+
+    info!("Inserted {} {} klines", inserted, interval);
+    Ok(inserted)
+}
+
 /// Fill gaps in the data
-pub async fn fill_gaps(client: &Client, binance_client: &BinanceKlinesClient) -> Result<u64> {
-    let gaps = find_gaps(client).await?;
+pub async fn fill_gaps(client: &Client, binance_client: &BinanceKlinesClient, interval: KlineInterval) -> Result<u64> {
+    let gaps = find_gaps(client, interval).await?;
 
     if gaps.is_empty() {
-        info!("No gaps found in data");
+        info!("No gaps found in {} data", interval);
         return Ok(0);
     }
 
@@ -317,33 +449,23 @@ pub async fn fill_gaps(client: &Client, binance_client: &BinanceKlinesClient) ->
             gap_start, gap_end, gap_minutes
         );
 
-        let klines = binance_client
-            .fetch_klines(gap_start + Duration::minutes(1), gap_end)
-            .await?;
-
-        let inserted = insert_klines(client, &klines).await?;
+        let inserted = ingest_klines(client, binance_client, gap_start + interval.step(), gap_end, interval, None).await?;
         total_inserted += inserted;
 
         info!("Inserted {} klines for gap", inserted);
-
-        // Rate limiting
-        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
     }
 
     Ok(total_inserted)
 }
 
 /// Update klines with new data since last fetch
-pub async fn update_klines(client: &Client, binance_client: &BinanceKlinesClient) -> Result<u64> {
-    let latest = get_latest_timestamp(client).await?;
+pub async fn update_klines(client: &Client, binance_client: &BinanceKlinesClient, interval: KlineInterval) -> Result<u64> {
+    let latest = get_latest_timestamp(client, interval).await?;
     let now = Utc::now();
 
     let start_time = match latest {
-        Some(ts) => ts + Duration::minutes(1), // Start from next minute
-        None => {
-            // No data - fetch last 6 months
-            now - Duration::days(180)
-        }
+        Some(ts) => ts + interval.step(), // Start from the next kline
+        None => now - Duration::days(interval.default_lookback_days() as i64),
     };
 
     if start_time >= now {
@@ -353,30 +475,182 @@ pub async fn update_klines(client: &Client, binance_client: &BinanceKlinesClient
 
     let gap_minutes = (now - start_time).num_minutes();
     info!(
-        "Updating klines from {:?} to {:?} ({} minutes)",
-        start_time, now, gap_minutes
+        "Updating {} klines from {:?} to {:?} ({} minutes)",
+        interval, start_time, now, gap_minutes
     );
 
-    let klines = binance_client
-        .fetch_all_klines(start_time, now, Some(&|current, total| {
-            if current % 10 == 0 || current == total {
-                info!("Progress: {}/{} requests", current, total);
+    let inserted = ingest_klines(client, binance_client, start_time, now, interval, Some(&|current, total| {
+        if current.is_multiple_of(10) || current == total {
+            info!("Progress: {}/{} requests", current, total);
+        }
+    }))
+    .await?;
+
+    // Also fill any gaps
+    let gap_filled = fill_gaps(client, binance_client, interval).await?;
+
+    Ok(inserted + gap_filled)
+}
+
+// ============================================================================
+// Aggregate Trades
+// ============================================================================
+
+/// Insert aggregate trades (duplicates are skipped)
+pub async fn insert_agg_trades(client: &Client, trades: &[AggTrade]) -> Result<u64> {
+    let mut inserted = 0u64;
+
+    for chunk in trades.chunks(1000) {
+        let mut query = String::from(
+            "INSERT INTO binance_agg_trades (symbol, agg_trade_id, timestamp, price, quantity, first_trade_id, last_trade_id, is_buyer_maker) VALUES "
+        );
+
+        let mut params: Vec<Box<dyn tokio_postgres::types::ToSql + Sync>> = Vec::new();
+
+        for (i, trade) in chunk.iter().enumerate() {
+            if i > 0 {
+                query.push_str(", ");
             }
-        }))
+            let placeholders: Vec<String> = (1..=8).map(|j| format!("${}", i * 8 + j)).collect();
+            query.push_str(&format!("({})", placeholders.join(", ")));
+
+            params.push(Box::new(DB_SYMBOL.to_string()));
+            params.push(Box::new(trade.id));
+            params.push(Box::new(trade.timestamp));
+            params.push(Box::new(trade.price));
+            params.push(Box::new(trade.quantity));
+            params.push(Box::new(trade.first_trade_id));
+            params.push(Box::new(trade.last_trade_id));
+            params.push(Box::new(trade.is_buyer_maker));
+        }
+
+        query.push_str(" ON CONFLICT (symbol, agg_trade_id) DO NOTHING");
+
+        let param_refs: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> =
+            params.iter().map(|p| p.as_ref()).collect();
+
+        inserted += client.execute(&query, &param_refs[..]).await?;
+    }
+
+    Ok(inserted)
+}
+
+/// Latest stored aggregate trade: (id, timestamp)
+pub async fn get_latest_agg_trade(client: &Client) -> Result<Option<(i64, DateTime<Utc>)>> {
+    let row = client
+        .query_opt(
+            "SELECT agg_trade_id, timestamp FROM binance_agg_trades WHERE symbol = $1 ORDER BY agg_trade_id DESC LIMIT 1",
+            &[&DB_SYMBOL],
+        )
         .await?;
 
-    let inserted = insert_klines(client, &klines).await?;
+    Ok(row.map(|row| (row.get(0), row.get(1))))
+}
 
-    // Also fill any gaps
-    let gap_filled = fill_gaps(client, binance_client).await?;
+/// Get count of aggregate trades in database
+pub async fn get_agg_trade_count(client: &Client) -> Result<i64> {
+    let row = client
+        .query_one("SELECT COUNT(*) FROM binance_agg_trades WHERE symbol = $1", &[&DB_SYMBOL])
+        .await?;
 
-    Ok(inserted + gap_filled)
+    Ok(row.get(0))
+}
+
+/// Ingest aggregate trades up to now, resuming after the latest stored trade
+/// (or starting `days` ago on an empty table). Trade IDs are consecutive, so
+/// paging by `fromId` cannot leave gaps. Pages are inserted as they arrive.
+pub async fn update_agg_trades(client: &Client, binance_client: &BinanceKlinesClient, days: u32) -> Result<u64> {
+    let now = Utc::now();
+    let mut from_id = match get_latest_agg_trade(client).await? {
+        Some((id, ts)) => {
+            info!("Resuming aggTrades after id {} ({})", id, ts);
+            id + 1
+        }
+        None => {
+            let start = now - Duration::days(days as i64);
+            match binance_client.first_agg_trade_id(start, now).await? {
+                Some(id) => id,
+                None => {
+                    info!("No aggTrades since {}", start);
+                    return Ok(0);
+                }
+            }
+        }
+    };
+
+    let mut inserted = 0u64;
+    let mut pages = 0usize;
+    loop {
+        let trades = binance_client.fetch_agg_trades(from_id).await?;
+        let Some(last) = trades.last() else {
+            break;
+        };
+        from_id = last.id + 1;
+        let reached_now = last.timestamp >= now;
+        let short_page = trades.len() < PAGE_LIMIT;
+
+        inserted += insert_agg_trades(client, &trades).await?;
+        pages += 1;
+        if pages.is_multiple_of(100) {
+            info!("Progress: {} aggTrades inserted, at {}", inserted, last.timestamp);
+        }
+
+        if reached_now || short_page {
+            break;
+        }
+    }
+
+    Ok(inserted)
 }
 
 /// Run the database migration
 pub async fn run_migration(client: &Client) -> Result<()> {
     let migration = include_str!("../migrations/004_binance_klines.sql");
     client.batch_execute(migration).await?;
+    let migration = include_str!("../migrations/011_binance_klines_interval.sql");
+    client.batch_execute(migration).await?;
     info!("Binance klines migration complete");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_kline_interval() {
+        assert_eq!("1s".parse::<KlineInterval>().unwrap(), KlineInterval::OneSecond);
+        assert_eq!("1m".parse::<KlineInterval>().unwrap(), KlineInterval::OneMinute);
+        assert!("5m".parse::<KlineInterval>().is_err());
+        assert_eq!(KlineInterval::default(), KlineInterval::OneMinute);
+        assert_eq!(KlineInterval::OneSecond.step(), Duration::seconds(1));
+        assert_eq!(KlineInterval::OneMinute.to_string(), "1m");
+    }
+
+    #[test]
+    fn test_parse_kline_and_agg_trade() {
+        let row: Vec<serde_json::Value> = serde_json::from_value(json!([
+            1765400400000i64, "97000.10", "97001.00", "96999.50", "97000.50", "1.25",
+            1765400400999i64, "121250.62", 42, "0.6", "58200.3", "0"
+        ]))
+        .unwrap();
+        let kline = parse_kline(&row).unwrap().unwrap();
+        assert_eq!(kline.open_time.timestamp(), 1765400400);
+        assert_eq!(kline.close, Decimal::from_str("97000.50").unwrap());
+        assert_eq!(kline.num_trades, 42);
+        assert!(parse_kline(&row[..5]).unwrap().is_none());
+
+        let trade = parse_agg_trade(&json!({
+            "a": 26129, "p": "97000.01", "q": "0.015", "f": 27781, "l": 27782, "T": 1765400400123i64, "m": true, "M": true
+        }))
+        .unwrap();
+        assert_eq!(trade.id, 26129);
+        assert_eq!(trade.price, Decimal::from_str("97000.01").unwrap());
+        assert_eq!(trade.quantity, Decimal::from_str("0.015").unwrap());
+        assert_eq!(trade.timestamp.timestamp_millis(), 1765400400123);
+        assert_eq!((trade.first_trade_id, trade.last_trade_id), (27781, 27782));
+        assert!(trade.is_buyer_maker);
+        assert!(parse_agg_trade(&json!({"p": "1"})).is_err());
+    }
+}
@@ -0,0 +1,527 @@
+//! Webhook alerts for fills, exits, settlements and risk/health events
+//!
+//! Events are queued on a channel and POSTed as JSON by a background task, so the
+//! trading loop never waits on the webhook. Each delivery is retried with
+//! exponential backoff. Warning/critical events of the same kind are rate limited
+//! (fills, exits and settlements are always sent) so a flapping feed or a dead
+//! database doesn't flood the channel.
+//!
+//! Postgres/Redis errors are reported through `db_error` / `redis_error`, which
+//! count the error and raise `DbUnavailable` on the alerter installed at startup.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use super::config::AlertsConfig;
+use crate::metrics;
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// Structured alert event (serialized with an `event` tag)
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AlertEvent {
+    Fill {
+        direction: String,
+        strategy: String,
+        price: f64,
+        amount_usdc: f64,
+        shares: f64,
+    },
+    Exit {
+        direction: String,
+        price: f64,
+        shares: f64,
+        profit: f64,
+    },
+    Settlement {
+        window_start: DateTime<Utc>,
+        outcome: String,
+        positions: u32,
+        pnl: f64,
+    },
+    DailyLossLimit {
+        daily_pnl: f64,
+        limit: f64,
+    },
+    FeedDown {
+        feed: String,
+        stale_secs: f64,
+    },
+    FeedRecovered {
+        feed: String,
+    },
+    OrdersRejected {
+        side: String,
+        count: u32,
+        last_error: String,
+    },
+    DbUnavailable {
+        backend: String,
+        error: String,
+    },
+}
+
+impl AlertEvent {
+    /// Rate-limit key
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AlertEvent::Fill { .. } => "fill",
+            AlertEvent::Exit { .. } => "exit",
+            AlertEvent::Settlement { .. } => "settlement",
+            AlertEvent::DailyLossLimit { .. } => "daily_loss_limit",
+            AlertEvent::FeedDown { .. } => "feed_down",
+            AlertEvent::FeedRecovered { .. } => "feed_recovered",
+            AlertEvent::OrdersRejected { .. } => "orders_rejected",
+            AlertEvent::DbUnavailable { .. } => "db_unavailable",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            AlertEvent::Fill { .. }
+            | AlertEvent::Exit { .. }
+            | AlertEvent::Settlement { .. }
+            | AlertEvent::FeedRecovered { .. } => Severity::Info,
+            AlertEvent::FeedDown { .. } | AlertEvent::OrdersRejected { .. } => Severity::Warning,
+            AlertEvent::DailyLossLimit { .. } | AlertEvent::DbUnavailable { .. } => Severity::Critical,
+        }
+    }
+
+    /// One-line human readable summary (for chat webhooks)
+    pub fn message(&self) -> String {
+        match self {
+            AlertEvent::Fill { direction, strategy, price, amount_usdc, shares } => format!(
+                "BUY {} [{}] filled: ${:.2} for {:.2} shares at {:.0}¢",
+                direction, strategy, amount_usdc, shares, price * 100.0
+            ),
+            AlertEvent::Exit { direction, price, shares, profit } => format!(
+                "SELL {} filled: {:.2} shares at {:.0}¢, P&L ${:+.2}",
+                direction, shares, price * 100.0, profit
+            ),
+            AlertEvent::Settlement { window_start, outcome, positions, pnl } => format!(
+                "Window {} settled {}: {} position(s), P&L ${:+.2}",
+                window_start.format("%H:%M"), outcome, positions, pnl
+            ),
+            AlertEvent::DailyLossLimit { daily_pnl, limit } => format!(
+                "Daily loss limit hit: ${:.2} >= ${:.2} - trading stopped",
+                daily_pnl.abs(), limit
+            ),
+            AlertEvent::FeedDown { feed, stale_secs } => {
+                format!("{} feed down: no data for {:.0}s", feed, stale_secs)
+            }
+            AlertEvent::FeedRecovered { feed } => format!("{} feed recovered", feed),
+            AlertEvent::OrdersRejected { side, count, last_error } => format!(
+                "{} consecutive {} orders rejected (last: {})",
+                count, side, last_error
+            ),
+            AlertEvent::DbUnavailable { backend, error } => {
+                format!("{} unavailable: {}", backend, error)
+            }
+        }
+    }
+}
+
+/// JSON body POSTed to the webhook
+#[derive(Debug, Serialize)]
+struct AlertPayload<'a> {
+    source: &'static str,
+    severity: Severity,
+    timestamp: DateTime<Utc>,
+    message: String,
+    /// Alerts of this kind dropped by rate limiting since the last one sent
+    suppressed: u32,
+    #[serde(flatten)]
+    event: &'a AlertEvent,
+}
+
+// ============================================================================
+// Alerter
+// ============================================================================
+
+/// Handle used by the bot to queue alerts (cheap to clone, no-op when disabled)
+#[derive(Clone)]
+pub struct Alerter {
+    tx: Option<mpsc::UnboundedSender<AlertEvent>>,
+}
+
+impl Alerter {
+    pub fn disabled() -> Self {
+        Self { tx: None }
+    }
+
+    /// Start the delivery task (returns a disabled alerter if not configured)
+    pub fn spawn(config: &AlertsConfig) -> Self {
+        if !config.enabled || config.webhook_url.is_empty() {
+            info!("Webhook alerts disabled");
+            return Self::disabled();
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let config = config.clone();
+        info!("Webhook alerts enabled");
+        tokio::spawn(run_worker(config, rx));
+
+        Self { tx: Some(tx) }
+    }
+
+    /// Queue an alert (never blocks)
+    pub fn send(&self, event: AlertEvent) {
+        if let Some(ref tx) = self.tx {
+            let _ = tx.send(event);
+        }
+    }
+
+    /// Make this the alerter `db_error` / `redis_error` report to (first call wins)
+    pub fn install_storage_alerts(&self) {
+        let _ = STORAGE_ALERTER.set(self.clone());
+    }
+}
+
+// ============================================================================
+// Storage errors
+// ============================================================================
+
+static STORAGE_ALERTER: OnceLock<Alerter> = OnceLock::new();
+
+/// Count a Postgres error and alert that the database is unavailable
+pub fn db_error(error: &dyn Display) {
+    metrics::db_error();
+    storage_alert("postgres", error);
+}
+
+/// Count a Redis error and alert that Redis is unavailable
+pub fn redis_error(error: &dyn Display) {
+    metrics::redis_error();
+    storage_alert("redis", error);
+}
+
+fn storage_alert(backend: &str, error: &dyn Display) {
+    if let Some(alerter) = STORAGE_ALERTER.get() {
+        alerter.send(AlertEvent::DbUnavailable { backend: backend.to_string(), error: error.to_string() });
+    }
+}
+
+async fn run_worker(config: AlertsConfig, mut rx: mpsc::UnboundedReceiver<AlertEvent>) {
+    let client = match Client::builder()
+        .timeout(Duration::from_millis(config.timeout_ms))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            warn!("Failed to build alert HTTP client: {}", e);
+            return;
+        }
+    };
+
+    let mut limiter = RateLimiter::new(Duration::from_secs(config.rate_limit_secs));
+
+    while let Some(event) = rx.recv().await {
+        let suppressed = match limiter.check(&event) {
+            Some(n) => n,
+            None => {
+                debug!("Alert {} rate limited", event.kind());
+                continue;
+            }
+        };
+
+        let payload = AlertPayload {
+            source: "btc-bot",
+            severity: event.severity(),
+            timestamp: Utc::now(),
+            message: event.message(),
+            suppressed,
+            event: &event,
+        };
+
+        if let Err(e) = deliver(&client, &config.webhook_url, &payload, config.max_retries, config.retry_backoff_ms).await {
+            warn!("Alert {} not delivered: {}", event.kind(), e);
+        }
+    }
+}
+
+/// POST the payload, retrying with exponential backoff on errors and non-2xx responses
+async fn deliver<T: Serialize>(
+    client: &Client,
+    url: &str,
+    payload: &T,
+    max_retries: u32,
+    backoff_ms: u64,
+) -> Result<()> {
+    let mut attempt = 0;
+    loop {
+        let err = match client.post(url).json(payload).send().await {
+            Ok(resp) if resp.status().is_success() => return Ok(()),
+            Ok(resp) => anyhow!("webhook returned {}", resp.status()),
+            Err(e) => anyhow!("webhook request failed: {}", e),
+        };
+
+        if attempt >= max_retries {
+            return Err(err);
+        }
+        attempt += 1;
+        debug!("Alert delivery failed ({}), retry {}/{}", err, attempt, max_retries);
+        tokio::time::sleep(Duration::from_millis(backoff_ms * 2u64.pow(attempt - 1))).await;
+    }
+}
+
+/// Per-kind minimum interval for warning/critical alerts
+struct RateLimiter {
+    interval: Duration,
+    last_sent: HashMap<&'static str, (Instant, u32)>,
+}
+
+impl RateLimiter {
+    fn new(interval: Duration) -> Self {
+        Self { interval, last_sent: HashMap::new() }
+    }
+
+    /// Some(suppressed count) if the event should be sent, None if rate limited
+    fn check(&mut self, event: &AlertEvent) -> Option<u32> {
+        if event.severity() == Severity::Info {
+            return Some(0);
+        }
+
+        let now = Instant::now();
+        match self.last_sent.get_mut(event.kind()) {
+            Some((last, suppressed)) if now.duration_since(*last) < self.interval => {
+                *suppressed += 1;
+                None
+            }
+            Some((last, suppressed)) => {
+                let n = *suppressed;
+                *last = now;
+                *suppressed = 0;
+                Some(n)
+            }
+            None => {
+                self.last_sent.insert(event.kind(), (now, 0));
+                Some(0)
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Trackers (turn per-tick observations into one alert per incident)
+// ============================================================================
+
+/// Tracks feed outages so FeedDown fires once per outage
+#[derive(Default)]
+pub struct FeedWatch {
+    down: HashSet<&'static str>,
+}
+
+impl FeedWatch {
+    /// `age_secs` = seconds since the feed's last update (None = never received)
+    pub fn check(&mut self, alerter: &Alerter, feed: &'static str, age_secs: Option<f64>, threshold_secs: u64) {
+        let Some(age) = age_secs else { return };
+
+        if age > threshold_secs as f64 {
+            if self.down.insert(feed) {
+                warn!("{} feed stale for {:.0}s", feed, age);
+                alerter.send(AlertEvent::FeedDown { feed: feed.to_string(), stale_secs: age });
+            }
+        } else if self.down.remove(feed) {
+            info!("{} feed recovered", feed);
+            alerter.send(AlertEvent::FeedRecovered { feed: feed.to_string() });
+        }
+    }
+}
+
+/// Counts consecutive order rejections/errors
+#[derive(Default)]
+pub struct RejectTracker {
+    consecutive: u32,
+}
+
+impl RejectTracker {
+    pub fn on_fill(&mut self) {
+        self.consecutive = 0;
+    }
+
+    /// Alerts when the streak reaches the threshold (and every threshold after)
+    pub fn on_reject(&mut self, alerter: &Alerter, side: &str, error: &str, threshold: u32) {
+        self.consecutive += 1;
+        if threshold > 0 && self.consecutive.is_multiple_of(threshold) {
+            alerter.send(AlertEvent::OrdersRejected {
+                side: side.to_string(),
+                count: self.consecutive,
+                last_error: error.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use std::sync::{Arc, Mutex};
+
+    type Received = Arc<Mutex<Vec<serde_json::Value>>>;
+
+    /// Local webhook stub: fails the first `fail_first` requests with 500, records bodies
+    async fn start_stub(fail_first: usize) -> (String, Received) {
+        let received: Received = Arc::default();
+        let calls = Arc::new(Mutex::new(0usize));
+
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(
+                    move |State((received, calls)): State<(Received, Arc<Mutex<usize>>)>,
+                     Json(body): Json<serde_json::Value>| async move {
+                        let mut n = calls.lock().unwrap();
+                        *n += 1;
+                        if *n <= fail_first {
+                            return StatusCode::INTERNAL_SERVER_ERROR;
+                        }
+                        received.lock().unwrap().push(body);
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .with_state((received.clone(), calls));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (format!("http://{}/hook", addr), received)
+    }
+
+    fn test_config(url: String) -> AlertsConfig {
+        AlertsConfig {
+            enabled: true,
+            webhook_url: url,
+            timeout_ms: 2000,
+            max_retries: 3,
+            retry_backoff_ms: 10,
+            rate_limit_secs: 300,
+            feed_down_secs: 30,
+            reject_alert_threshold: 3,
+        }
+    }
+
+    async fn wait_for(received: &Received, n: usize) {
+        for _ in 0..100 {
+            if received.lock().unwrap().len() >= n {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries_until_success() {
+        let (url, received) = start_stub(2).await;
+        let alerter = Alerter::spawn(&test_config(url));
+
+        alerter.send(AlertEvent::DbUnavailable { backend: "postgres".into(), error: "timeout".into() });
+        wait_for(&received, 1).await;
+
+        let bodies = received.lock().unwrap();
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0]["event"], "db_unavailable");
+        assert_eq!(bodies[0]["severity"], "critical");
+        assert_eq!(bodies[0]["backend"], "postgres");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits_repeated_warnings_but_not_fills() {
+        let (url, received) = start_stub(0).await;
+        let alerter = Alerter::spawn(&test_config(url));
+
+        for _ in 0..3 {
+            alerter.send(AlertEvent::FeedDown { feed: "binance".into(), stale_secs: 45.0 });
+            alerter.send(AlertEvent::Fill {
+                direction: "UP".into(),
+                strategy: "TERMINAL".into(),
+                price: 0.42,
+                amount_usdc: 5.0,
+                shares: 11.9,
+            });
+        }
+        wait_for(&received, 4).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let bodies = received.lock().unwrap();
+        let feed_down = bodies.iter().filter(|b| b["event"] == "feed_down").count();
+        let fills = bodies.iter().filter(|b| b["event"] == "fill").count();
+        assert_eq!(feed_down, 1);
+        assert_eq!(fills, 3);
+    }
+
+    #[test]
+    fn test_reject_tracker_threshold() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let alerter = Alerter { tx: Some(tx) };
+        let mut tracker = RejectTracker::default();
+
+        tracker.on_reject(&alerter, "BUY", "no liquidity", 3);
+        tracker.on_reject(&alerter, "BUY", "no liquidity", 3);
+        assert!(rx.try_recv().is_err());
+        tracker.on_reject(&alerter, "BUY", "no liquidity", 3);
+        assert!(matches!(rx.try_recv(), Ok(AlertEvent::OrdersRejected { count: 3, .. })));
+
+        tracker.on_fill();
+        tracker.on_reject(&alerter, "BUY", "no liquidity", 3);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_storage_errors_raise_db_unavailable() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        Alerter { tx: Some(tx) }.install_storage_alerts();
+
+        db_error(&"connection reset");
+        redis_error(&"timeout");
+
+        match rx.try_recv() {
+            Ok(AlertEvent::DbUnavailable { backend, error }) => {
+                assert_eq!(backend, "postgres");
+                assert_eq!(error, "connection reset");
+            }
+            other => panic!("expected DbUnavailable, got {:?}", other),
+        }
+        assert!(matches!(rx.try_recv(), Ok(AlertEvent::DbUnavailable { backend, .. }) if backend == "redis"));
+    }
+
+    #[test]
+    fn test_feed_watch_fires_once_per_outage() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let alerter = Alerter { tx: Some(tx) };
+        let mut watch = FeedWatch::default();
+
+        watch.check(&alerter, "polymarket", Some(5.0), 30);
+        watch.check(&alerter, "polymarket", Some(31.0), 30);
+        watch.check(&alerter, "polymarket", Some(40.0), 30);
+        watch.check(&alerter, "polymarket", Some(1.0), 30);
+
+        assert!(matches!(rx.try_recv(), Ok(AlertEvent::FeedDown { .. })));
+        assert!(matches!(rx.try_recv(), Ok(AlertEvent::FeedRecovered { .. })));
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,367 @@
+//! Read-only HTTP/JSON API for operators
+//!
+//! Served next to `/metrics` on the bot's HTTP server:
+//! - `GET /status`         - bankroll, positions, window counters, pending flags, last decision
+//! - `GET /market`         - live MarketState from the WebSocket feeds
+//! - `GET /query`          - probability matrix lookup (like `btc-probability-matrix query`)
+//! - `GET /first-passage`  - exit target analysis (like `btc-probability-matrix first-passage`)
+//!
+//! Nothing here mutates bot state; handlers only read shared snapshots.
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use super::hedge::{HedgedPair, PairOrigin};
+use super::strategy::{price_to_delta_bucket, BetDecision, BetDirection};
+use super::websocket::MarketState;
+use crate::edge;
+use crate::models::{delta_to_bucket, CellStats, FirstPassageMatrix, FirstPassageState, ProbabilityMatrix};
+
+// ============================================================================
+// Shared State
+// ============================================================================
+
+/// Open position as exposed by the API
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionStatus {
+    pub position_id: String,
+    pub direction: BetDirection,
+    pub strategy_type: String,
+    pub entry_price: f64,
+    pub shares: f64,
+    pub exit_target: f64,
+    pub entry_seconds_elapsed: u32,
+    pub window_start: DateTime<Utc>,
+    pub sell_pending: bool,
+}
+
+/// Hedged UP + DOWN pair as exposed by the API
+#[derive(Debug, Clone, Serialize)]
+pub struct PairStatus {
+    pub pair_id: String,
+    pub origin: PairOrigin,
+    pub window_start: DateTime<Utc>,
+    pub up_shares: f64,
+    pub up_price: f64,
+    pub down_shares: f64,
+    pub down_price: f64,
+    pub cost: f64,
+    pub guaranteed_payout: f64,
+    pub locked_pnl: f64,
+}
+
+impl From<&HedgedPair> for PairStatus {
+    fn from(p: &HedgedPair) -> Self {
+        Self {
+            pair_id: p.pair_id.clone(),
+            origin: p.origin,
+            window_start: p.window_start,
+            up_shares: p.up_shares,
+            up_price: p.up_price,
+            down_shares: p.down_shares,
+            down_price: p.down_price,
+            cost: p.cost(),
+            guaranteed_payout: p.guaranteed_payout(),
+            locked_pnl: p.locked_pnl(),
+        }
+    }
+}
+
+/// Last decision from StrategyContext::decide with the context it was made in
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionStatus {
+    pub at: DateTime<Utc>,
+    pub seconds_elapsed: u32,
+    pub btc_price: f64,
+    pub price_delta: f64,
+    pub decision: BetDecision,
+}
+
+/// Snapshot of BotState, refreshed by the main loop every tick
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BotStatus {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub dry_run: bool,
+    pub bankroll: f64,
+    pub daily_pnl: f64,
+    pub consecutive_losses: u32,
+    pub consecutive_wins: u32,
+    pub window_start: Option<DateTime<Utc>>,
+    pub terminal_bets_this_window: u32,
+    pub exit_bets_this_window: u32,
+    pub bet_pending: bool,
+    pub has_pending_sells: bool,
+    pub open_positions: Vec<PositionStatus>,
+    pub hedged_pairs: Vec<PairStatus>,
+    /// Sum of locked_pnl over hedged_pairs
+    pub locked_pnl: f64,
+    /// This pod holds the leader lease (standbys never trade)
+    pub leader: bool,
+    pub last_decision: Option<DecisionStatus>,
+}
+
+/// Everything the handlers can read
+#[derive(Clone)]
+pub struct ApiState {
+    pub status: Arc<RwLock<BotStatus>>,
+    pub market: Arc<RwLock<MarketState>>,
+    /// Matrix the bot is trading on (swapped when the session changes)
+    pub matrix: Arc<RwLock<Arc<ProbabilityMatrix>>>,
+    pub fp_matrix: Option<Arc<FirstPassageMatrix>>,
+}
+
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/status", get(status_handler))
+        .route("/market", get(market_handler))
+        .route("/query", get(query_handler))
+        .route("/first-passage", get(first_passage_handler))
+        .with_state(state)
+}
+
+fn bad_request(msg: impl Into<String>) -> Response {
+    (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": msg.into() }))).into_response()
+}
+
+/// Convert (seconds elapsed, price delta) to matrix buckets (15-second intervals)
+fn to_buckets(time_elapsed: u32, price_delta: f64) -> Result<(u8, i8), &'static str> {
+    if time_elapsed >= 900 {
+        return Err("time must be < 900 seconds");
+    }
+    let delta = Decimal::try_from(price_delta).map_err(|_| "invalid delta")?;
+    Ok(((time_elapsed / 15).min(59) as u8, delta_to_bucket(delta)))
+}
+
+/// Delta bucket where a position's token trades at `target`.
+/// DOWN targets are mapped through the UP price (1 - target), as in the CLI query.
+fn target_delta_bucket(target: f64, direction: BetDirection) -> i8 {
+    match direction {
+        BetDirection::Up => price_to_delta_bucket(target, direction),
+        BetDirection::Down => price_to_delta_bucket(1.0 - target, direction),
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+async fn status_handler(State(state): State<ApiState>) -> Json<BotStatus> {
+    Json(state.status.read().await.clone())
+}
+
+#[derive(Serialize)]
+struct MarketResponse {
+    #[serde(flatten)]
+    state: MarketState,
+    ready: bool,
+    btc_price_age_secs: Option<f64>,
+    book_age_secs: Option<f64>,
+}
+
+async fn market_handler(State(state): State<ApiState>) -> Json<MarketResponse> {
+    let ms = state.market.read().await.clone();
+    let now = Utc::now();
+    let age = |t: Option<DateTime<Utc>>| t.map(|t| (now - t).num_milliseconds() as f64 / 1000.0);
+
+    Json(MarketResponse {
+        ready: ms.is_ready(),
+        btc_price_age_secs: age(ms.btc_price_time),
+        book_age_secs: age(ms.book_time),
+        state: ms,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryParams {
+    /// Seconds elapsed in window (0-899)
+    time: u32,
+    /// BTC price delta from window open ($)
+    delta: f64,
+    /// Market price for UP (0.0-1.0)
+    price: f64,
+    /// Bankroll for sizing (defaults to the bot's current bankroll)
+    bankroll: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct QueryResponse {
+    time_bucket: u8,
+    delta_bucket: i8,
+    cell: CellStats,
+    recommendation: crate::models::BetRecommendation,
+    expected_value: f64,
+}
+
+async fn query_handler(State(state): State<ApiState>, Query(q): Query<QueryParams>) -> Response {
+    let (time_bucket, delta_bucket) = match to_buckets(q.time, q.delta) {
+        Ok(b) => b,
+        Err(msg) => return bad_request(msg),
+    };
+    if !(0.0..=1.0).contains(&q.price) {
+        return bad_request("price must be between 0.0 and 1.0");
+    }
+
+    let bankroll = match q.bankroll {
+        Some(b) => b,
+        None => state.status.read().await.bankroll,
+    };
+
+    let cell = state.matrix.read().await.get(time_bucket, delta_bucket).clone();
+    let recommendation = edge::get_recommendation(&cell, q.price, bankroll);
+    let expected_value = if recommendation.should_bet {
+        edge::calculate_expected_value(recommendation.our_probability, q.price, recommendation.bet_amount)
+    } else {
+        0.0
+    };
+
+    Json(QueryResponse {
+        time_bucket,
+        delta_bucket,
+        cell,
+        recommendation,
+        expected_value,
+    })
+    .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+struct FirstPassageParams {
+    /// Seconds elapsed in window (0-899)
+    time: u32,
+    /// BTC price delta from window open ($)
+    delta: f64,
+    /// Position direction: "up" or "down"
+    direction: String,
+    /// Entry price (0.0-1.0)
+    entry: f64,
+}
+
+#[derive(Serialize)]
+struct ExitTarget {
+    target: f64,
+    target_delta_bucket: i8,
+    p_reach: f64,
+    gain: f64,
+    exit_ev: f64,
+    vs_hold: f64,
+}
+
+#[derive(Serialize)]
+struct FirstPassageResponse {
+    time_bucket: u8,
+    delta_bucket: i8,
+    p_win: f64,
+    hold_ev: f64,
+    targets: Vec<ExitTarget>,
+    /// None = holding to settlement beats every target
+    best_target: Option<f64>,
+    state: FirstPassageState,
+}
+
+async fn first_passage_handler(State(state): State<ApiState>, Query(q): Query<FirstPassageParams>) -> Response {
+    let fp_matrix = match &state.fp_matrix {
+        Some(fp) => fp,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "first-passage matrix not loaded" })),
+            )
+                .into_response()
+        }
+    };
+
+    let (time_bucket, delta_bucket) = match to_buckets(q.time, q.delta) {
+        Ok(b) => b,
+        Err(msg) => return bad_request(msg),
+    };
+    let direction = match q.direction.to_lowercase().as_str() {
+        "up" => BetDirection::Up,
+        "down" => BetDirection::Down,
+        _ => return bad_request("direction must be 'up' or 'down'"),
+    };
+    if !(0.0..1.0).contains(&q.entry) {
+        return bad_request("entry must be between 0.0 and 1.0");
+    }
+
+    let fp_state = fp_matrix.get(time_bucket, delta_bucket);
+    let terminal_cell = state.matrix.read().await.get(time_bucket, delta_bucket).clone();
+
+    // EV of holding to settlement
+    let p_win = match direction {
+        BetDirection::Up => terminal_cell.p_up,
+        BetDirection::Down => terminal_cell.p_down,
+    };
+    let hold_ev = p_win * (1.0 - q.entry) - (1.0 - p_win) * q.entry;
+
+    let mut targets = Vec::new();
+    let mut best_ev = hold_ev;
+    let mut best_target = None;
+
+    for target in [0.40, 0.45, 0.50, 0.55, 0.60, 0.65, 0.70, 0.75, 0.80] {
+        if target <= q.entry {
+            continue; // No point exiting at a loss
+        }
+
+        let target_delta_bucket = target_delta_bucket(target, direction);
+        let p_reach = match direction {
+            BetDirection::Up => fp_state.get_up_target(target_delta_bucket).p_reach,
+            BetDirection::Down => fp_state.get_down_target(target_delta_bucket).p_reach,
+        };
+
+        // If the target is not reached we assume we hold to settlement
+        let gain = target - q.entry;
+        let exit_ev = p_reach * gain + (1.0 - p_reach) * hold_ev;
+
+        if exit_ev > best_ev {
+            best_ev = exit_ev;
+            best_target = Some(target);
+        }
+
+        targets.push(ExitTarget {
+            target,
+            target_delta_bucket,
+            p_reach,
+            gain,
+            exit_ev,
+            vs_hold: exit_ev - hold_ev,
+        });
+    }
+
+    Json(FirstPassageResponse {
+        time_bucket,
+        delta_bucket,
+        p_win,
+        hold_ev,
+        targets,
+        best_target,
+        state: fp_state.clone(),
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_buckets() {
+        assert_eq!(to_buckets(0, 0.0).ok(), Some((0, delta_to_bucket(Decimal::ZERO))));
+        assert_eq!(to_buckets(899, 0.0).ok().map(|b| b.0), Some(59));
+        assert!(to_buckets(900, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_target_delta_bucket_down() {
+        // A DOWN token at 0.60 means UP trades at 0.40
+        assert_eq!(target_delta_bucket(0.60, BetDirection::Down), price_to_delta_bucket(0.40, BetDirection::Down));
+        assert_eq!(target_delta_bucket(0.60, BetDirection::Up), price_to_delta_bucket(0.60, BetDirection::Up));
+    }
+}
@@ -1,11 +1,12 @@
 use anyhow::{anyhow, Context, Result};
-use reqwest::Client;
 use serde::Deserialize;
 use std::time::Duration;
 
+use crate::http_client::HttpClient;
+
 /// Binance API client for BTC price
 pub struct BinanceClient {
-    client: Client,
+    client: HttpClient,
     base_url: String,
 }
 
@@ -42,10 +43,7 @@ pub struct BtcPrice {
 
 impl BinanceClient {
     pub fn new(timeout_ms: u64) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_millis(timeout_ms))
-            .build()
-            .context("Failed to create HTTP client")?;
+        let client = HttpClient::new("binance", Duration::from_millis(timeout_ms));
 
         Ok(Self {
             client,
@@ -2,6 +2,9 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::path::Path;
 
+use crate::stop_loss::StopLossConfig;
+use crate::strategies::StrategySlotConfig;
+
 /// Bot configuration loaded from YAML file
 #[derive(Debug, Clone, Deserialize)]
 pub struct BotConfig {
@@ -19,7 +22,24 @@ pub struct BotConfig {
     pub terminal_strategy: TerminalStrategyConfig,
     #[serde(default)]
     pub exit_strategy: ExitStrategyConfig,
+    #[serde(default)]
+    pub session_matrices: SessionMatrixConfig,
+    #[serde(default)]
+    pub stop_loss: StopLossConfig,
+    #[serde(default)]
+    pub hedge: HedgeConfig,
+    /// Pluggable strategies (see strategies.rs), each with its own budget and caps
+    #[serde(default)]
+    pub strategies: Vec<StrategySlotConfig>,
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub http_server: HttpServerConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub decision_log: DecisionLogConfig,
+    #[serde(default)]
+    pub leader_election: LeaderElectionConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -73,7 +93,7 @@ pub struct TimingConfig {
     pub min_seconds_elapsed: u32,
     /// Don't bet after this many seconds remaining
     pub min_seconds_remaining: u32,
-    /// Minimum samples required in the time bucket
+    /// Minimum effective samples required in the cell (`CellStats::sample_size`)
     pub min_samples_in_bucket: u32,
 }
 
@@ -113,12 +133,25 @@ pub struct ExecutionConfig {
     pub limit_price_offset_cents: f64,
     /// Max slippage for market orders
     pub max_slippage_pct: f64,
-    /// Retry failed orders
+    /// Retry failed orders (network errors, 429 and 5xx only)
     pub retry_on_failure: bool,
     /// Max retries
     pub max_retries: u32,
+    /// Backoff before the first retry, doubled per retry (jittered 50-100%)
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Backoff cap
+    #[serde(default = "default_retry_max_backoff_ms")]
+    pub retry_max_backoff_ms: u64,
+    /// Never retry with less than this many seconds left in the window
+    #[serde(default = "default_retry_window_margin_secs")]
+    pub retry_window_margin_secs: u64,
 }
 
+fn default_retry_backoff_ms() -> u64 { 200 }
+fn default_retry_max_backoff_ms() -> u64 { 2000 }
+fn default_retry_window_margin_secs() -> u64 { 5 }
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct RiskConfig {
     /// Max bets per 15-min window
@@ -310,6 +343,15 @@ pub struct ExitStrategyConfig {
     /// Cooldown between exit strategy bets (milliseconds, e.g. 500 = 0.5s)
     #[serde(default = "default_exit_cooldown")]
     pub cooldown_ms: u32,
+    /// Min observed token paths in a market reach cell before its targets are used
+    #[serde(default = "default_market_reach_min_samples")]
+    pub market_reach_min_samples: u32,
+    /// Consult the optimal stopping exit policy (exit_policy.json) before the matrices
+    #[serde(default = "default_true")]
+    pub use_exit_policy: bool,
+    /// Min observed transitions in an exit policy state before it is trusted
+    #[serde(default = "default_exit_policy_min_samples")]
+    pub exit_policy_min_samples: u32,
 }
 
 fn default_exit_min_edge() -> f64 { 0.05 }          // 5% edge required
@@ -317,6 +359,8 @@ fn default_exit_max_bet() -> f64 { 5.0 }            // $5 max
 fn default_exit_max_bets() -> u32 { 10 }            // 10 per window
 fn default_exit_min_remaining() -> u32 { 300 }      // No buy in last 5 min
 fn default_exit_cooldown() -> u32 { 500 }           // 500ms (0.5s) between exit bets
+fn default_market_reach_min_samples() -> u32 { 30 } // Moderate confidence
+fn default_exit_policy_min_samples() -> u32 { 30 }  // Moderate confidence
 
 impl Default for ExitStrategyConfig {
     fn default() -> Self {
@@ -328,6 +372,64 @@ impl Default for ExitStrategyConfig {
             min_seconds_remaining: 300,
             only_strong_confidence: true,
             cooldown_ms: 500,  // 0.5s
+            market_reach_min_samples: default_market_reach_min_samples(),
+            use_exit_policy: true,
+            exit_policy_min_samples: default_exit_policy_min_samples(),
+        }
+    }
+}
+
+/// Hedged pairs (hold UP + DOWN together for a guaranteed $1 per share)
+#[derive(Debug, Clone, Deserialize)]
+pub struct HedgeConfig {
+    /// Enable pair entries, pair completion and hedging
+    #[serde(default)]
+    pub enabled: bool,
+    /// Buy both legs / complete a pair when the combined price is below this
+    #[serde(default = "default_max_pair_cost")]
+    pub max_pair_cost: f64,
+    /// USDC per new pair (both legs together)
+    #[serde(default = "default_pair_amount_usdc")]
+    pub pair_amount_usdc: f64,
+    /// Max new pairs per window
+    #[serde(default = "default_max_pairs_per_window")]
+    pub max_pairs_per_window: u32,
+    /// Buy the opposite leg of open positions when entry + opposite ask < max_pair_cost
+    #[serde(default = "default_true")]
+    pub complete_pairs: bool,
+    /// Hedge losing positions with the opposite token
+    #[serde(default = "default_true")]
+    pub hedge_losing: bool,
+    /// A position is losing when its bid is this fraction below entry
+    #[serde(default = "default_hedge_trigger_pct")]
+    pub hedge_trigger_pct: f64,
+    /// Max combined price accepted when hedging (> 1.0 locks in a small loss)
+    #[serde(default = "default_hedge_max_pair_cost")]
+    pub hedge_max_pair_cost: f64,
+    /// Don't open or hedge pairs with less time than this left
+    #[serde(default = "default_hedge_min_seconds_remaining")]
+    pub min_seconds_remaining: u32,
+}
+
+fn default_max_pair_cost() -> f64 { 0.98 }          // Lock at least 2¢/share
+fn default_pair_amount_usdc() -> f64 { 10.0 }
+fn default_max_pairs_per_window() -> u32 { 2 }
+fn default_hedge_trigger_pct() -> f64 { 0.30 }      // -30% from entry
+fn default_hedge_max_pair_cost() -> f64 { 1.05 }    // Lose at most 5¢/share
+fn default_hedge_min_seconds_remaining() -> u32 { 60 }
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_pair_cost: default_max_pair_cost(),
+            pair_amount_usdc: default_pair_amount_usdc(),
+            max_pairs_per_window: default_max_pairs_per_window(),
+            complete_pairs: true,
+            hedge_losing: true,
+            hedge_trigger_pct: default_hedge_trigger_pct(),
+            hedge_max_pair_cost: default_hedge_max_pair_cost(),
+            min_seconds_remaining: default_hedge_min_seconds_remaining(),
         }
     }
 }
@@ -349,6 +451,160 @@ pub struct LoggingConfig {
 
 fn default_true() -> bool { true }
 
+/// Embedded HTTP server (Prometheus /metrics + read-only status API)
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpServerConfig {
+    /// Enable the HTTP server
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Port to listen on (0.0.0.0)
+    #[serde(default = "default_http_port")]
+    pub port: u16,
+}
+
+fn default_http_port() -> u16 { 9100 }
+
+impl Default for HttpServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            port: default_http_port(),
+        }
+    }
+}
+
+/// Hour-of-day / weekday conditioned matrices (built with `build --sessions`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionMatrixConfig {
+    /// Use the session matrix matching the current time when one exists
+    #[serde(default)]
+    pub enabled: bool,
+    /// Session cells with fewer effective samples use the pooled matrix cell
+    #[serde(default = "default_session_min_samples")]
+    pub min_samples: f64,
+}
+
+fn default_session_min_samples() -> f64 { 30.0 }
+
+impl Default for SessionMatrixConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_samples: default_session_min_samples(),
+        }
+    }
+}
+
+/// JSONL log of every evaluated tick
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecisionLogConfig {
+    /// Write the decision log
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Directory for decisions-YYYY-MM-DD.jsonl files
+    #[serde(default = "default_decision_log_dir")]
+    pub dir: String,
+    /// Rotate when a file exceeds this size (0 = daily rotation only)
+    #[serde(default = "default_decision_log_max_file_mb")]
+    pub max_file_mb: u64,
+    /// Keep at most this many files (0 = keep all)
+    #[serde(default = "default_decision_log_max_files")]
+    pub max_files: usize,
+}
+
+fn default_decision_log_dir() -> String { "logs/decisions".to_string() }
+fn default_decision_log_max_file_mb() -> u64 { 100 }
+fn default_decision_log_max_files() -> usize { 30 }
+
+impl Default for DecisionLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: default_decision_log_dir(),
+            max_file_mb: default_decision_log_max_file_mb(),
+            max_files: default_decision_log_max_files(),
+        }
+    }
+}
+
+/// Active/standby failover across pods through a Redis lease (see leader.rs)
+#[derive(Debug, Clone, Deserialize)]
+pub struct LeaderElectionConfig {
+    /// Only the lease holder trades; without it every pod trades (single pod only)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Lease TTL: a standby takes over this long after the leader stops renewing
+    #[serde(default = "default_leader_lease_ms")]
+    pub lease_ms: u64,
+    /// How often the leader renews (and standbys try to take over)
+    #[serde(default = "default_leader_renew_interval_ms")]
+    pub renew_interval_ms: u64,
+}
+
+fn default_leader_lease_ms() -> u64 { 5000 }
+fn default_leader_renew_interval_ms() -> u64 { 1000 }
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lease_ms: default_leader_lease_ms(),
+            renew_interval_ms: default_leader_renew_interval_ms(),
+        }
+    }
+}
+
+/// Webhook alerts (fills, exits, settlements, risk and health events)
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertsConfig {
+    /// Enable webhook alerts
+    #[serde(default)]
+    pub enabled: bool,
+    /// Generic webhook URL receiving JSON POSTs (overridden by BOT_ALERT_WEBHOOK_URL)
+    #[serde(default)]
+    pub webhook_url: String,
+    /// HTTP timeout per delivery attempt (ms)
+    #[serde(default = "default_alert_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Retries after a failed delivery (exponential backoff)
+    #[serde(default = "default_alert_max_retries")]
+    pub max_retries: u32,
+    /// Initial retry backoff (ms), doubled on each retry
+    #[serde(default = "default_alert_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Minimum seconds between warning/critical alerts of the same kind
+    #[serde(default = "default_alert_rate_limit_secs")]
+    pub rate_limit_secs: u64,
+    /// Alert when a WebSocket feed has been silent for this many seconds
+    #[serde(default = "default_alert_feed_down_secs")]
+    pub feed_down_secs: u64,
+    /// Alert after this many consecutive rejected/failed orders
+    #[serde(default = "default_alert_reject_threshold")]
+    pub reject_alert_threshold: u32,
+}
+
+fn default_alert_timeout_ms() -> u64 { 5000 }
+fn default_alert_max_retries() -> u32 { 3 }
+fn default_alert_retry_backoff_ms() -> u64 { 1000 }
+fn default_alert_rate_limit_secs() -> u64 { 300 }
+fn default_alert_feed_down_secs() -> u64 { 30 }
+fn default_alert_reject_threshold() -> u32 { 5 }
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            webhook_url: String::new(),
+            timeout_ms: default_alert_timeout_ms(),
+            max_retries: default_alert_max_retries(),
+            retry_backoff_ms: default_alert_retry_backoff_ms(),
+            rate_limit_secs: default_alert_rate_limit_secs(),
+            feed_down_secs: default_alert_feed_down_secs(),
+            reject_alert_threshold: default_alert_reject_threshold(),
+        }
+    }
+}
+
 impl BotConfig {
     /// Load configuration from YAML file
     pub fn load(path: &Path) -> Result<Self> {
@@ -381,6 +637,16 @@ impl BotConfig {
         if let Ok(val) = std::env::var("BOT_DAILY_LOSS_LIMIT_PCT") {
             config.risk.daily_loss_limit_pct = val.parse().unwrap_or(config.risk.daily_loss_limit_pct);
         }
+        if let Ok(val) = std::env::var("BOT_HTTP_PORT") {
+            config.http_server.port = val.parse().unwrap_or(config.http_server.port);
+        }
+        if let Ok(val) = std::env::var("BOT_DECISION_LOG_DIR") {
+            config.decision_log.dir = val;
+        }
+        if let Ok(val) = std::env::var("BOT_ALERT_WEBHOOK_URL") {
+            config.alerts.webhook_url = val;
+            config.alerts.enabled = true;
+        }
 
         // Market token IDs from environment (required)
         if let Ok(val) = std::env::var("POLYMARKET_BTC_UP_TOKEN") {
@@ -422,6 +688,41 @@ impl BotConfig {
             _ => 0.0,
         }
     }
+
+    /// Fractional Kelly bet size in USDC for a given edge and market price
+    ///
+    /// `edge` is relative to the market probability (our_prob = market_prob * (1 + edge)).
+    /// Applies kelly_fraction, the confidence multiplier, loss reduction for the
+    /// current losing streak, then the max_bet_pct / max_bet_usdc caps.
+    pub fn kelly_bet_size(
+        &self,
+        bankroll: f64,
+        edge: f64,
+        market_prob: f64,
+        confidence: &str,
+        consecutive_losses: u32,
+    ) -> f64 {
+        // Kelly formula: (p*b - q) / b
+        // where p = our probability, b = odds - 1, q = 1 - p
+        let odds = 1.0 / market_prob;
+        let b = odds - 1.0;
+        let p = market_prob + edge * market_prob; // our probability
+        let q = 1.0 - p;
+
+        let kelly = if b > 0.0 { (p * b - q) / b } else { 0.0 };
+        let kelly = kelly.max(0.0); // Never negative
+
+        let loss_mult = self.risk.loss_reduction_factor.powi(consecutive_losses as i32);
+        let adjusted_kelly =
+            kelly * self.betting.kelly_fraction * self.confidence_multiplier(confidence) * loss_mult;
+
+        // Calculate bet amount and apply caps
+        let mut bet = bankroll * adjusted_kelly;
+        bet = bet.min(bankroll * self.betting.max_bet_pct);
+        bet = bet.min(self.betting.max_bet_usdc);
+
+        bet
+    }
 }
 
 impl Default for BotConfig {
@@ -468,6 +769,9 @@ impl Default for BotConfig {
                 max_slippage_pct: 0.02,
                 retry_on_failure: false,
                 max_retries: 1,
+                retry_backoff_ms: default_retry_backoff_ms(),
+                retry_max_backoff_ms: default_retry_max_backoff_ms(),
+                retry_window_margin_secs: default_retry_window_margin_secs(),
             },
             risk: RiskConfig {
                 max_bets_per_window: 10,  // Allow up to 10 trades per window
@@ -486,6 +790,10 @@ impl Default for BotConfig {
             cooldown: CooldownConfig::default(),
             terminal_strategy: TerminalStrategyConfig::default(),
             exit_strategy: ExitStrategyConfig::default(),
+            session_matrices: SessionMatrixConfig::default(),
+            stop_loss: StopLossConfig::default(),
+            hedge: HedgeConfig::default(),
+            strategies: Vec::new(),
             logging: LoggingConfig {
                 level: "info".to_string(),
                 log_price_checks: true,
@@ -493,6 +801,10 @@ impl Default for BotConfig {
                 log_order_book: true,
                 log_decision_reasoning: true,
             },
+            http_server: HttpServerConfig::default(),
+            alerts: AlertsConfig::default(),
+            decision_log: DecisionLogConfig::default(),
+            leader_election: LeaderElectionConfig::default(),
         }
     }
 }
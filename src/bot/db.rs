@@ -6,6 +6,8 @@ use rust_decimal::Decimal;
 use tokio_postgres::Client;
 use tracing::{info, warn};
 
+use super::hedge::HedgedPair;
+
 /// Convert f64 to Decimal for PostgreSQL NUMERIC columns
 fn to_dec(v: f64) -> Decimal {
     Decimal::try_from(v).unwrap_or_default()
@@ -202,7 +204,21 @@ impl TradeDb {
             .await
             .context("Failed to run migration v4")?;
 
-        info!("Database migrations complete (v1 + v2 + v4)");
+        // Run v9 migration (persisted risk-state snapshots + events)
+        let migration_v9 = include_str!("../../migrations/009_bot_state.sql");
+        self.client
+            .batch_execute(migration_v9)
+            .await
+            .context("Failed to run migration v9")?;
+
+        // Run v12 migration (hedged pair settlements)
+        let migration_v12 = include_str!("../../migrations/012_bot_hedged_pairs.sql");
+        self.client
+            .batch_execute(migration_v12)
+            .await
+            .context("Failed to run migration v12")?;
+
+        info!("Database migrations complete (v1 + v2 + v4 + v9 + v12)");
         Ok(())
     }
 
@@ -458,6 +474,39 @@ impl TradeDb {
         Ok(id)
     }
 
+    /// Record a settled hedged pair (idempotent on pair_id, so a failover re-settle is a no-op)
+    pub async fn insert_pair_settlement(&self, pair: &HedgedPair, outcome: Option<&str>) -> Result<()> {
+        let pnl = pair.settle(outcome);
+        let cost = pair.cost();
+        self.client
+            .execute(
+                r#"
+                INSERT INTO bot_hedged_pairs (
+                    pair_id, origin, window_start, up_shares, up_price, down_shares, down_price,
+                    cost_usdc, payout_usdc, pnl_usdc, locked_pnl_usdc, outcome
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                ON CONFLICT (pair_id) DO NOTHING
+                "#,
+                &[
+                    &pair.pair_id,
+                    &pair.origin.as_str(),
+                    &pair.window_start,
+                    &to_dec(pair.up_shares),
+                    &to_dec(pair.up_price),
+                    &to_dec(pair.down_shares),
+                    &to_dec(pair.down_price),
+                    &to_dec(cost),
+                    &to_dec(cost + pnl),
+                    &to_dec(pnl),
+                    &to_dec(pair.locked_pnl()),
+                    &outcome,
+                ],
+            )
+            .await
+            .context("Failed to insert pair settlement")?;
+        Ok(())
+    }
+
     /// Insert market outcome
     pub async fn insert_outcome(&self, outcome: &MarketOutcome) -> Result<i32> {
         let row = self.client
@@ -611,6 +660,60 @@ impl TradeDb {
         Ok(pnl)
     }
 
+    /// Store one state snapshot version and its events
+    pub async fn save_state_snapshot(
+        &self,
+        version: u64,
+        pod_id: &str,
+        state_json: &serde_json::Value,
+        events: &[(&str, serde_json::Value)],
+    ) -> Result<()> {
+        let version = version as i64;
+        self.client
+            .execute(
+                r#"
+                INSERT INTO bot_state_snapshots (version, state_json, pod_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (version) DO UPDATE SET
+                    state_json = EXCLUDED.state_json,
+                    pod_id = EXCLUDED.pod_id,
+                    created_at = NOW()
+                "#,
+                &[&version, state_json, &pod_id],
+            )
+            .await
+            .context("Failed to save state snapshot")?;
+
+        for (kind, event_json) in events {
+            self.client
+                .execute(
+                    "INSERT INTO bot_state_events (version, kind, event_json) VALUES ($1, $2, $3)",
+                    &[&version, kind, event_json],
+                )
+                .await
+                .context("Failed to save state event")?;
+        }
+
+        // Snapshots are small but written on every change: keep the last 10k
+        self.client
+            .execute("DELETE FROM bot_state_snapshots WHERE version <= $1 - 10000", &[&version])
+            .await
+            .context("Failed to prune state snapshots")?;
+        Ok(())
+    }
+
+    /// Newest state snapshot (JSON), if any
+    pub async fn load_latest_state_snapshot(&self) -> Result<Option<serde_json::Value>> {
+        let row = self.client
+            .query_opt(
+                "SELECT state_json FROM bot_state_snapshots ORDER BY version DESC LIMIT 1",
+                &[],
+            )
+            .await
+            .context("Failed to load state snapshot")?;
+        Ok(row.map(|r| r.get(0)))
+    }
+
     /// Get overall stats
     pub async fn get_overall_stats(&self) -> Result<OverallStats> {
         let row = self.client
@@ -663,6 +766,48 @@ pub struct OverallStats {
     pub net_pnl: f64,
 }
 
+/// Load the active session-conditioned matrices (empty if none were built)
+pub async fn load_session_matrices_from_db(
+    database_url: &str,
+) -> Result<Vec<(super::models::MatrixSession, super::models::ProbabilityMatrix)>> {
+    let (client, connection) = tokio_postgres::connect(database_url, tokio_postgres::NoTls)
+        .await
+        .context("Failed to connect to database for session matrices")?;
+
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            warn!("Matrix DB connection error: {}", e);
+        }
+    });
+
+    let rows = client
+        .query(
+            r#"
+            SELECT to_jsonb(m) -> 'session_json', matrix_json
+            FROM matrix_snapshots m
+            WHERE is_active = TRUE
+              AND COALESCE(to_jsonb(m) ->> 'session', 'all') <> 'all'
+            ORDER BY created_at, id  -- saved in config order, which settles overlaps
+            "#,
+            &[],
+        )
+        .await
+        .context("Failed to query session matrices")?;
+
+    let mut sessions = Vec::new();
+    for row in rows {
+        let session_json: Option<serde_json::Value> = row.get(0);
+        let Some(session_json) = session_json else { continue };
+        let session: super::models::MatrixSession =
+            serde_json::from_value(session_json).context("Failed to parse session definition")?;
+        let matrix: super::models::ProbabilityMatrix =
+            serde_json::from_value(row.get(1)).context("Failed to parse session matrix JSON")?;
+        sessions.push((session, matrix));
+    }
+
+    Ok(sessions)
+}
+
 /// Matrix snapshot info
 #[derive(Debug)]
 pub struct MatrixSnapshotInfo {
@@ -687,8 +832,9 @@ pub async fn load_matrix_from_db(database_url: &str) -> Result<Option<(super::mo
         .query_opt(
             r#"
             SELECT id, matrix_json, total_windows, created_at
-            FROM matrix_snapshots
+            FROM matrix_snapshots m
             WHERE is_active = TRUE
+              AND COALESCE(to_jsonb(m) ->> 'session', 'all') = 'all'  -- pooled (also before migration 007)
             ORDER BY created_at DESC
             LIMIT 1
             "#,
@@ -0,0 +1,293 @@
+//! Append-only JSONL log of every evaluated tick
+//!
+//! One line per call to `StrategyContext::decide`: market inputs, both quotes, the
+//! model's numbers, every gate result and a `ReasonCode` for the final action.
+//! Files are named `decisions-YYYY-MM-DD.jsonl` (UTC) and rotate at midnight or
+//! when they exceed `max_file_mb` (`decisions-YYYY-MM-DD.1.jsonl`, ...). Only the
+//! newest `max_files` files are kept.
+//!
+//! The tick loop only serializes records; file IO (open, write, rotate, prune)
+//! runs on a blocking worker fed by `DecisionLogger`.
+//!
+//! Examples:
+//!   jq 'select(.ts >= "2025-01-10T12:07:31" and .ts < "2025-01-10T12:07:32")' logs/decisions/decisions-2025-01-10.jsonl
+//!   jq -r .action logs/decisions/*.jsonl | sort | uniq -c | sort -rn
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{LineWriter, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use super::config::DecisionLogConfig;
+use super::polymarket::PriceQuote;
+use super::strategy::{BetDecision, ReasonCode};
+
+const FILE_PREFIX: &str = "decisions-";
+const FILE_SUFFIX: &str = ".jsonl";
+
+/// Top-of-book for one side
+#[derive(Debug, Clone, Serialize)]
+pub struct QuoteSnapshot {
+    pub bid: f64,
+    pub ask: f64,
+    pub spread_pct: f64,
+    pub bid_liquidity: f64,
+    pub ask_liquidity: f64,
+}
+
+impl From<&PriceQuote> for QuoteSnapshot {
+    fn from(q: &PriceQuote) -> Self {
+        Self {
+            bid: q.best_bid,
+            ask: q.best_ask,
+            spread_pct: q.spread_pct,
+            bid_liquidity: q.bid_liquidity,
+            ask_liquidity: q.ask_liquidity,
+        }
+    }
+}
+
+/// One line of the decision log
+#[derive(Debug, Serialize)]
+pub struct DecisionRecord<'a> {
+    pub ts: DateTime<Utc>,
+    pub window_start: DateTime<Utc>,
+    pub seconds_elapsed: u32,
+    pub seconds_remaining: u32,
+    pub btc_price: f64,
+    pub price_delta: f64,
+    pub up: QuoteSnapshot,
+    pub down: QuoteSnapshot,
+    /// Final outcome of the tick: BET if an order was attempted, else why not
+    pub action: ReasonCode,
+    #[serde(flatten)]
+    pub decision: &'a BetDecision,
+}
+
+impl DecisionRecord<'_> {
+    /// Serialized JSONL line (with trailing newline)
+    pub fn to_line(&self) -> Result<Vec<u8>> {
+        let mut line = serde_json::to_vec(self)?;
+        line.push(b'\n');
+        Ok(line)
+    }
+}
+
+/// Handle used by the tick loop to queue records (never blocks on IO)
+pub struct DecisionLogger {
+    tx: mpsc::UnboundedSender<(NaiveDate, Vec<u8>)>,
+}
+
+impl DecisionLogger {
+    /// Open the log and start the writer (None if disabled)
+    pub fn spawn(config: &DecisionLogConfig) -> Result<Option<Self>> {
+        let Some(mut log) = DecisionLog::open(config)? else {
+            return Ok(None);
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<(NaiveDate, Vec<u8>)>();
+        tokio::task::spawn_blocking(move || {
+            while let Some((date, line)) = rx.blocking_recv() {
+                if let Err(e) = log.write_line(date, &line) {
+                    warn!("Failed to write decision log: {}", e);
+                }
+            }
+        });
+
+        Ok(Some(Self { tx }))
+    }
+
+    /// Queue one record
+    pub fn log(&self, record: &DecisionRecord) {
+        match record.to_line() {
+            Ok(line) => {
+                let _ = self.tx.send((record.ts.date_naive(), line));
+            }
+            Err(e) => warn!("Failed to serialize decision record: {}", e),
+        }
+    }
+}
+
+/// Rotating JSONL writer
+pub struct DecisionLog {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    max_files: usize,
+    current: Option<OpenFile>,
+}
+
+struct OpenFile {
+    date: NaiveDate,
+    index: u32,
+    bytes: u64,
+    writer: LineWriter<File>,
+}
+
+impl DecisionLog {
+    /// Open the log directory (None if disabled)
+    pub fn open(config: &DecisionLogConfig) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let dir = PathBuf::from(&config.dir);
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create decision log dir {}", dir.display()))?;
+        info!("Decision log: {}", dir.display());
+
+        Ok(Some(Self {
+            dir,
+            max_file_bytes: config.max_file_mb * 1024 * 1024,
+            max_files: config.max_files,
+            current: None,
+        }))
+    }
+
+    /// Append one serialized record dated `date` (rotating first if needed)
+    pub fn write_line(&mut self, date: NaiveDate, line: &[u8]) -> Result<()> {
+        let needs_rotation = match &self.current {
+            Some(f) => f.date != date || (self.max_file_bytes > 0 && f.bytes + line.len() as u64 > self.max_file_bytes),
+            None => true,
+        };
+        if needs_rotation {
+            self.rotate(date)?;
+        }
+
+        let file = self.current.as_mut().expect("rotate opens a file");
+        file.writer.write_all(line)?;
+        file.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self, date: NaiveDate) -> Result<()> {
+        // Continue numbering after existing files for this date (e.g. after a restart)
+        let mut index = match &self.current {
+            Some(f) if f.date == date => f.index + 1,
+            _ => 0,
+        };
+        loop {
+            let path = self.path_for(date, index);
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            if self.max_file_bytes == 0 || size < self.max_file_bytes {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                self.current = Some(OpenFile { date, index, bytes: size, writer: LineWriter::new(file) });
+                break;
+            }
+            index += 1;
+        }
+
+        if let Err(e) = prune(&self.dir, self.max_files) {
+            warn!("Failed to prune decision logs: {}", e);
+        }
+        Ok(())
+    }
+
+    fn path_for(&self, date: NaiveDate, index: u32) -> PathBuf {
+        let name = if index == 0 {
+            format!("{}{}{}", FILE_PREFIX, date.format("%Y-%m-%d"), FILE_SUFFIX)
+        } else {
+            format!("{}{}.{}{}", FILE_PREFIX, date.format("%Y-%m-%d"), index, FILE_SUFFIX)
+        };
+        self.dir.join(name)
+    }
+}
+
+/// Delete the oldest log files beyond `max_files` (0 = keep everything)
+fn prune(dir: &Path, max_files: usize) -> Result<()> {
+    if max_files == 0 {
+        return Ok(());
+    }
+
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX)
+        })
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .collect();
+
+    if files.len() <= max_files {
+        return Ok(());
+    }
+
+    files.sort();
+    for (_, path) in &files[..files.len() - max_files] {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn quote() -> PriceQuote {
+        PriceQuote {
+            token_id: "t".to_string(),
+            best_bid: 0.40,
+            best_ask: 0.42,
+            mid_price: 0.41,
+            spread: 0.02,
+            spread_pct: 0.05,
+            bid_liquidity: 100.0,
+            ask_liquidity: 120.0,
+        }
+    }
+
+    #[test]
+    fn test_writes_jsonl_and_rotates_by_size_and_day() {
+        let dir = std::env::temp_dir().join(format!("decision-log-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let config = DecisionLogConfig {
+            enabled: true,
+            dir: dir.to_string_lossy().to_string(),
+            max_file_mb: 0,
+            max_files: 0,
+        };
+        let mut log = DecisionLog::open(&config).unwrap().unwrap();
+        log.max_file_bytes = 1500;
+
+        let decision = BetDecision::no_bet(ReasonCode::TooEarly, "Too early in window".to_string());
+        let day1 = Utc.with_ymd_and_hms(2025, 1, 10, 12, 7, 31).unwrap();
+        let day2 = Utc.with_ymd_and_hms(2025, 1, 11, 0, 0, 1).unwrap();
+
+        for ts in [day1, day1, day1, day2] {
+            let record = DecisionRecord {
+                ts,
+                window_start: ts,
+                seconds_elapsed: 10,
+                seconds_remaining: 890,
+                btc_price: 95000.0,
+                price_delta: 12.5,
+                up: QuoteSnapshot::from(&quote()),
+                down: QuoteSnapshot::from(&quote()),
+                action: decision.reason_code,
+                decision: &decision,
+            };
+            log.write_line(ts.date_naive(), &record.to_line().unwrap()).unwrap();
+        }
+
+        let first = fs::read_to_string(dir.join("decisions-2025-01-10.jsonl")).unwrap();
+        let line: serde_json::Value = serde_json::from_str(first.lines().next().unwrap()).unwrap();
+        assert_eq!(line["action"], "TOO_EARLY");
+        assert_eq!(line["reason_code"], "TOO_EARLY");
+        assert_eq!(line["up"]["ask"], 0.42);
+
+        assert!(dir.join("decisions-2025-01-10.1.jsonl").exists());
+        assert!(dir.join("decisions-2025-01-11.jsonl").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
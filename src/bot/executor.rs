@@ -1,21 +1,30 @@
 //! Polymarket CLOB order execution
 //!
 //! Handles EIP-712 signing and order submission to the Polymarket CLOB API.
+//! Digests are signed by a pluggable `Signer` (keystore, signing daemon, raw key).
 
 use anyhow::{anyhow, Context, Result};
 use base64::{Engine as _, engine::general_purpose::{STANDARD as BASE64, URL_SAFE as BASE64_URL}};
 use hmac::{Hmac, Mac};
-use reqwest::Client;
-use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use sha3::{Digest, Keccak256};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 
+use crate::http_client::HttpClient;
+use crate::leader::Leadership;
+use crate::metrics;
+use crate::signer::Signer;
+
 /// CTF Exchange contract address on Polygon
 const CTF_EXCHANGE: &str = "4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
 
+/// Neg Risk CTF Exchange contract address on Polygon (neg-risk markets settle here)
+const NEG_RISK_CTF_EXCHANGE: &str = "C5d563A36AE78145C45a50134d48A1215220f80a";
+
 /// Polygon chain ID
 const POLYGON_CHAIN_ID: u64 = 137;
 
@@ -62,14 +71,20 @@ pub enum OrderType {
 // Key constraint: price × shares must produce USDC with max 2 decimals
 // ============================================================================
 
+/// Floor `value` to a multiple of 1/`scale`, tolerating binary float error:
+/// 0.29 * 100.0 = 28.999999999999996 must floor to 29, not 28
+fn floor_to(value: f64, scale: f64) -> f64 {
+    (value * scale + 1e-9).floor() / scale
+}
+
+/// Polymarket minimum size for a marketable (FOK) buy is $1
+const MIN_ORDER_USDC: f64 = 1.0;
+
 /// Round shares DOWN to ensure makerAmount (USDC) has max 2 decimals for FOK BUY
 /// Returns (rounded_shares, exact_usdc) where exact_usdc = price × rounded_shares
 pub fn round_for_fok_buy(price: f64, amount_usdc: f64) -> (f64, f64) {
-    // Polymarket minimum order size is $1
-    const MIN_ORDER_USDC: f64 = 1.0;
-
     // 1. Round price to 0.01 tick (cents)
-    let rounded_price = (price * 100.0).floor() / 100.0;
+    let rounded_price = floor_to(price, 100.0);
 
     // 2. Calculate initial shares
     let shares = amount_usdc / rounded_price;
@@ -77,7 +92,7 @@ pub fn round_for_fok_buy(price: f64, amount_usdc: f64) -> (f64, f64) {
     // 3. Round shares DOWN to ensure price × shares has max 2 decimals
     // We need price × shares = XX.XX (2 decimals)
     // Strategy: iterate down from floor(shares) until we get valid USDC
-    let mut rounded_shares = (shares * 100.0).floor() / 100.0;
+    let mut rounded_shares = floor_to(shares, 100.0);
 
     // 4. Calculate exact USDC and ensure it has max 2 decimals
     let mut exact_usdc = rounded_price * rounded_shares;
@@ -97,7 +112,7 @@ pub fn round_for_fok_buy(price: f64, amount_usdc: f64) -> (f64, f64) {
     if exact_usdc > 0.0 && rounded_price > 0.0 {
         let final_shares = exact_usdc / rounded_price;
         // Round shares to 4 decimals (takerAmount precision)
-        rounded_shares = (final_shares * 10000.0).floor() / 10000.0;
+        rounded_shares = floor_to(final_shares, 10000.0);
     }
 
     (rounded_shares, exact_usdc)
@@ -112,10 +127,10 @@ pub const SELL_SLIPPAGE: f64 = 0.01; // 1 cent slippage tolerance
 /// - slippage_usdc = (price - SELL_SLIPPAGE) × rounded_shares (minimum we'll accept)
 pub fn round_for_fok_sell(price: f64, shares: f64) -> (f64, f64, f64) {
     // 1. Round price to 0.01 tick (cents)
-    let rounded_price = (price * 100.0).floor() / 100.0;
+    let rounded_price = floor_to(price, 100.0);
 
     // 2. Round shares to 2 decimals (makerAmount for SELL)
-    let rounded_shares = (shares * 100.0).floor() / 100.0;
+    let rounded_shares = floor_to(shares, 100.0);
 
     // 3. Calculate exact USDC (at bid price)
     let exact_usdc = rounded_price * rounded_shares;
@@ -135,7 +150,150 @@ pub fn limit_to_liquidity(requested_usdc: f64, price: f64, available_shares: f64
     let max_usdc_from_liquidity = price * available_shares;
     let limited = requested_usdc.min(max_usdc_from_liquidity);
     // Round down to 2 decimals
-    (limited * 100.0).floor() / 100.0
+    floor_to(limited, 100.0)
+}
+
+// ============================================================================
+// Market Parameters
+// ============================================================================
+// Every token has its own tick size, minimum order size and fee rate, and
+// neg-risk markets settle on a separate exchange contract (a different EIP-712
+// domain). Orders are built from these parameters and checked against them
+// before submission, so a mismatch fails locally instead of as an HTTP 400.
+// ============================================================================
+
+/// How long fetched market parameters are reused before refetching
+const MARKET_PARAMS_TTL: Duration = Duration::from_secs(3600);
+
+/// Trading parameters of one token, from the CLOB `/book` and `/fee-rate` endpoints
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketParams {
+    /// Minimum price increment (0.1, 0.01, 0.001 or 0.0001)
+    pub tick_size: f64,
+    /// Minimum size in shares of a resting (GTC/GTD) order
+    pub min_order_size: f64,
+    /// Fee rate every order on this market must carry (feeRateBps)
+    pub fee_rate_bps: u64,
+    /// Market settles on the Neg Risk CTF Exchange
+    pub neg_risk: bool,
+}
+
+impl MarketParams {
+    /// Parse the `/book?token_id=` and `/fee-rate?token_id=` responses
+    pub fn from_responses(book: &serde_json::Value, fee: &serde_json::Value) -> Result<Self> {
+        let tick_size = json_number(book, "tick_size")?;
+        if !(tick_size > 0.0 && tick_size < 1.0) {
+            return Err(anyhow!("Invalid tick_size: {}", tick_size));
+        }
+        let min_order_size = json_number(book, "min_order_size")?;
+        let neg_risk = book.get("neg_risk")
+            .and_then(|v| v.as_bool())
+            .ok_or_else(|| anyhow!("Missing neg_risk in order book response"))?;
+        let fee_rate_bps = json_number(fee, "base_fee")?;
+        if fee_rate_bps < 0.0 || fee_rate_bps.fract() != 0.0 {
+            return Err(anyhow!("Invalid base_fee: {}", fee_rate_bps));
+        }
+
+        Ok(Self { tick_size, min_order_size, fee_rate_bps: fee_rate_bps as u64, neg_risk })
+    }
+
+    /// Scale prices are floored to: the tick, but never finer than a cent
+    /// (order amounts are computed in cents, see the FOK helpers above)
+    fn price_scale(&self) -> f64 {
+        (1.0 / self.tick_size.max(0.01)).round()
+    }
+}
+
+/// Read a field the CLOB may send either as a JSON number or as a string
+fn json_number(value: &serde_json::Value, key: &str) -> Result<f64> {
+    match value.get(key) {
+        Some(serde_json::Value::Number(n)) => n.as_f64().ok_or_else(|| anyhow!("Invalid {}: {}", key, n)),
+        Some(serde_json::Value::String(s)) => s.parse().with_context(|| format!("Invalid {}: {}", key, s)),
+        _ => Err(anyhow!("Missing {} in market parameters", key)),
+    }
+}
+
+/// Check an order against its market's parameters before submission:
+/// - routed to the right exchange and carrying the market's fee rate
+/// - implied price on the tick grid (up to amount rounding) and inside (0, 1)
+/// - at least `min_order_size` shares for resting orders, $1 for FOK buys
+/// - FOK amounts within the decimal limits (maker 2, taker 4 decimals)
+/// - an expiration on GTD orders only
+pub fn validate_order(order: &Order, params: &MarketParams, order_type: OrderType) -> Result<()> {
+    if order.neg_risk != params.neg_risk {
+        return Err(anyhow!(
+            "Invalid order: built for the {} exchange but the market is {}neg-risk",
+            if order.neg_risk { "neg-risk" } else { "CTF" },
+            if params.neg_risk { "" } else { "not " }
+        ));
+    }
+    let fee_rate_bps = bytes32_to_u128(&order.fee_rate_bps).context("Invalid order: feeRateBps")?;
+    if fee_rate_bps != params.fee_rate_bps as u128 {
+        return Err(anyhow!(
+            "Invalid order: feeRateBps {} but the market fee rate is {} bps",
+            fee_rate_bps, params.fee_rate_bps
+        ));
+    }
+
+    let maker = bytes32_to_u128(&order.maker_amount).context("Invalid order: makerAmount")?;
+    let taker = bytes32_to_u128(&order.taker_amount).context("Invalid order: takerAmount")?;
+    if maker == 0 || taker == 0 {
+        return Err(anyhow!("Invalid order: zero amount (makerAmount={}, takerAmount={})", maker, taker));
+    }
+    // Amounts are in 6-decimal micro units
+    let (usdc, shares) = match order.side {
+        Side::Buy => (maker, taker),
+        Side::Sell => (taker, maker),
+    };
+    let price = usdc as f64 / shares as f64;
+    let tick_price = (price / params.tick_size).round() * params.tick_size;
+    // USDC is rounded to at most 0.005 (BUY: 2 decimals), which moves the implied price by 5000/shares
+    let tolerance = 5_000.0 / shares as f64 + 1e-9;
+    if (price - tick_price).abs() > tolerance {
+        return Err(anyhow!(
+            "Invalid order: price {:.6} is not on the {} tick",
+            price, params.tick_size
+        ));
+    }
+    if tick_price < params.tick_size - 1e-9 || tick_price > 1.0 - params.tick_size + 1e-9 {
+        return Err(anyhow!(
+            "Invalid order: price {:.4} outside [{}, {}]",
+            price, params.tick_size, 1.0 - params.tick_size
+        ));
+    }
+
+    match order_type {
+        OrderType::FOK => {
+            if maker % 10_000 != 0 || taker % 100 != 0 {
+                return Err(anyhow!(
+                    "Invalid order: FOK amounts exceed decimal limits (makerAmount={} max 2dp, takerAmount={} max 4dp)",
+                    maker, taker
+                ));
+            }
+            let usdc = usdc as f64 / 1_000_000.0;
+            if order.side == Side::Buy && usdc < MIN_ORDER_USDC - 1e-9 {
+                return Err(anyhow!("Invalid order: FOK buy of ${:.2} is below the ${:.2} minimum", usdc, MIN_ORDER_USDC));
+            }
+        }
+        OrderType::GTC | OrderType::GTD => {
+            let shares = shares as f64 / 1_000_000.0;
+            if shares < params.min_order_size - 1e-9 {
+                return Err(anyhow!(
+                    "Invalid order: {:.2} shares is below the market minimum of {}",
+                    shares, params.min_order_size
+                ));
+            }
+        }
+    }
+
+    let has_expiration = order.expiration != [0u8; 32];
+    match order_type {
+        OrderType::GTD if !has_expiration => Err(anyhow!("Invalid order: GTD order without an expiration")),
+        OrderType::GTC | OrderType::FOK if has_expiration => {
+            Err(anyhow!("Invalid order: {:?} order must not set an expiration", order_type))
+        }
+        _ => Ok(()),
+    }
 }
 
 /// CTF Exchange Order structure
@@ -155,10 +313,12 @@ pub struct Order {
     pub side: Side,
     pub signature_type: SignatureType,
     pub signature: Vec<u8>,
+    /// Settles on the Neg Risk CTF Exchange (selects the EIP-712 domain, not hashed)
+    pub neg_risk: bool,
 }
 
 /// Signed order ready for submission
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedOrder {
     /// Salt as JSON number (i64)
     pub salt: i64,
@@ -208,13 +368,16 @@ struct ApiKeyResponse {
 }
 
 /// Order response
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct OrderResponse {
     pub success: bool,
     #[serde(rename = "orderID")]
     pub order_id: Option<String>,
     #[serde(rename = "errorMsg")]
     pub error_msg: Option<String>,
+    /// Shares matched, when known from an order lookup rather than the POST reply
+    #[serde(skip)]
+    pub size_matched: Option<f64>,
 }
 
 /// Cancel order response
@@ -269,13 +432,19 @@ pub struct OrderDetails {
     pub side: String,
 }
 
+impl OrderDetails {
+    /// Shares matched so far (0 if missing or unparsable)
+    pub fn matched_shares(&self) -> f64 {
+        self.size_matched.parse().unwrap_or(0.0)
+    }
+}
+
 /// Polymarket order executor
 pub struct Executor {
-    client: Client,
+    client: HttpClient,
     clob_url: String,
-    secp: Secp256k1<secp256k1::All>,
-    secret_key: SecretKey,
-    /// Signer address (derived from private key)
+    signer: Arc<dyn Signer>,
+    /// Signer address
     wallet_address: [u8; 20],
     /// Funder address (Polymarket profile where USDC is held)
     /// If None, uses wallet_address
@@ -284,6 +453,15 @@ pub struct Executor {
     signature_type: SignatureType,
     credentials: Option<ApiCredentials>,
     nonce: u64,
+    /// Leader lease checked before every order (multi-pod fencing)
+    fence: Option<Leadership>,
+    /// Per-token market parameters with the time they were fetched
+    market_params: HashMap<String, (MarketParams, Instant)>,
+    retry: RetryPolicy,
+    /// End of the current market window (bounds retries)
+    window_end: Option<SystemTime>,
+    /// Recently accepted order hashes and their responses (duplicate guard)
+    accepted: VecDeque<(String, OrderResponse)>,
 }
 
 /// Convert u64 to big-endian 32-byte array
@@ -301,6 +479,14 @@ fn u128_to_bytes32(val: u128) -> [u8; 32] {
 }
 
 /// Parse a decimal string token ID to bytes32
+/// Read a big-endian 32-byte amount that must fit in u128
+fn bytes32_to_u128(bytes: &[u8; 32]) -> Result<u128> {
+    if bytes[..16].iter().any(|b| *b != 0) {
+        return Err(anyhow!("Value does not fit in u128: {}", bytes32_to_decimal(bytes)));
+    }
+    Ok(u128::from_be_bytes(bytes[16..].try_into().unwrap()))
+}
+
 fn token_id_to_bytes32(token_id: &str) -> Result<[u8; 32]> {
     // Token IDs are large decimal numbers, convert to bytes
     let mut result = [0u8; 32];
@@ -309,6 +495,9 @@ fn token_id_to_bytes32(token_id: &str) -> Result<[u8; 32]> {
     // Actually, these are uint256 values, we need to handle them as strings
     // For now, we'll use a simple approach - parse digits and convert
     let bytes = decimal_to_bytes(token_id)?;
+    if bytes.len() > 32 {
+        return Err(anyhow!("Token ID does not fit in uint256"));
+    }
     let start = 32 - bytes.len().min(32);
     result[start..].copy_from_slice(&bytes[..bytes.len().min(32)]);
     Ok(result)
@@ -341,33 +530,472 @@ fn decimal_to_bytes(s: &str) -> Result<Vec<u8>> {
     Ok(result)
 }
 
-/// Get public address from secret key
-fn get_address(secp: &Secp256k1<secp256k1::All>, secret_key: &SecretKey) -> [u8; 20] {
-    let public_key = PublicKey::from_secret_key(secp, secret_key);
-    let public_key_bytes = &public_key.serialize_uncompressed()[1..]; // Remove 0x04 prefix
+/// Convert bytes32 to decimal string
+fn bytes32_to_decimal(bytes: &[u8; 32]) -> String {
+    // Skip leading zeros and convert to decimal
+    let mut result = String::from("0");
+    for &byte in bytes.iter() {
+        // Multiply result by 256 and add byte
+        let mut carry = byte as u32;
+        let mut new_result = String::new();
+        let chars: Vec<char> = result.chars().collect();
+
+        for c in chars.iter().rev() {
+            let digit = c.to_digit(10).unwrap();
+            let val = digit * 256 + carry;
+            carry = val / 10;
+            new_result.insert(0, std::char::from_digit(val % 10, 10).unwrap());
+        }
 
-    let mut hasher = Keccak256::new();
-    hasher.update(public_key_bytes);
-    let hash = hasher.finalize();
+        while carry > 0 {
+            new_result.insert(0, std::char::from_digit(carry % 10, 10).unwrap());
+            carry /= 10;
+        }
 
-    let mut address = [0u8; 20];
-    address.copy_from_slice(&hash[12..32]);
-    address
+        result = new_result;
+    }
+
+    // Remove leading zeros, but keep at least "0"
+    let trimmed = result.trim_start_matches('0');
+    if trimmed.is_empty() {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
 }
 
-impl Executor {
-    /// Create a new executor from private key
-    pub async fn new(private_key: &str, clob_url: Option<String>) -> Result<Self> {
-        let private_key = private_key.strip_prefix("0x").unwrap_or(private_key);
-        let key_bytes = hex::decode(private_key)
-            .context("Failed to decode private key")?;
+// ============================================================================
+// EIP-712 Hashing
+// ============================================================================
+
+/// Compute ClobAuth domain separator
+fn clob_auth_domain() -> [u8; 32] {
+    let type_hash = keccak256(b"EIP712Domain(string name,string version,uint256 chainId)");
+    let name_hash = keccak256(b"ClobAuthDomain");
+    let version_hash = keccak256(b"1");
+
+    let mut encoded = Vec::new();
+    encoded.extend_from_slice(&type_hash);
+    encoded.extend_from_slice(&name_hash);
+    encoded.extend_from_slice(&version_hash);
+    encoded.extend_from_slice(&u64_to_bytes32(POLYGON_CHAIN_ID));
+
+    keccak256(&encoded)
+}
+
+/// Compute ClobAuth struct hash
+fn clob_auth_struct_hash(
+    address: &[u8; 20],
+    timestamp: &str,
+    nonce: u64,
+    message: &str,
+) -> [u8; 32] {
+    let type_hash = keccak256(
+        b"ClobAuth(address address,string timestamp,uint256 nonce,string message)"
+    );
+
+    let mut encoded = Vec::new();
+    encoded.extend_from_slice(&type_hash);
+    // address is padded to 32 bytes
+    encoded.extend_from_slice(&[0u8; 12]);
+    encoded.extend_from_slice(address);
+    encoded.extend_from_slice(&keccak256(timestamp.as_bytes()));
+    encoded.extend_from_slice(&u64_to_bytes32(nonce));
+    encoded.extend_from_slice(&keccak256(message.as_bytes()));
+
+    keccak256(&encoded)
+}
+
+/// Compute final EIP-712 hash
+fn eip712_hash(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(66);
+    encoded.extend_from_slice(&[0x19, 0x01]);
+    encoded.extend_from_slice(domain_separator);
+    encoded.extend_from_slice(struct_hash);
+    keccak256(&encoded)
+}
+
+/// Compute Order domain separator for the CTF Exchange, or the Neg Risk CTF
+/// Exchange for neg-risk markets (same name and version, different contract)
+fn order_domain(neg_risk: bool) -> [u8; 32] {
+    let type_hash = keccak256(
+        b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+    );
+
+    let name_hash = keccak256(b"Polymarket CTF Exchange");
+    let version_hash = keccak256(b"1");
+    let exchange = if neg_risk { NEG_RISK_CTF_EXCHANGE } else { CTF_EXCHANGE };
+    let contract = hex::decode(exchange).unwrap();
+
+    let mut encoded = Vec::new();
+    encoded.extend_from_slice(&type_hash);
+    encoded.extend_from_slice(&name_hash);
+    encoded.extend_from_slice(&version_hash);
+    encoded.extend_from_slice(&u64_to_bytes32(POLYGON_CHAIN_ID));
+    encoded.extend_from_slice(&[0u8; 12]);
+    encoded.extend_from_slice(&contract);
+
+    keccak256(&encoded)
+}
+
+/// Compute Order struct hash
+fn order_struct_hash(order: &Order) -> [u8; 32] {
+    let type_hash = keccak256(
+        b"Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,uint8 signatureType)"
+    );
+
+    let mut encoded = Vec::new();
+    encoded.extend_from_slice(&type_hash);
+    // Convert i64 salt to 32-byte big-endian for EIP-712
+    let mut salt_bytes = [0u8; 32];
+    salt_bytes[24..].copy_from_slice(&order.salt.to_be_bytes());
+    encoded.extend_from_slice(&salt_bytes);
+    // Addresses are padded to 32 bytes
+    encoded.extend_from_slice(&[0u8; 12]);
+    encoded.extend_from_slice(&order.maker);
+    encoded.extend_from_slice(&[0u8; 12]);
+    encoded.extend_from_slice(&order.signer);
+    encoded.extend_from_slice(&[0u8; 12]);
+    encoded.extend_from_slice(&order.taker);
+    encoded.extend_from_slice(&order.token_id);
+    encoded.extend_from_slice(&order.maker_amount);
+    encoded.extend_from_slice(&order.taker_amount);
+    encoded.extend_from_slice(&order.expiration);
+    encoded.extend_from_slice(&order.nonce);
+    encoded.extend_from_slice(&order.fee_rate_bps);
+    // side and signatureType are uint8, padded to 32 bytes
+    let mut side_bytes = [0u8; 32];
+    side_bytes[31] = order.side as u8;
+    encoded.extend_from_slice(&side_bytes);
+    let mut sig_type_bytes = [0u8; 32];
+    sig_type_bytes[31] = order.signature_type as u8;
+    encoded.extend_from_slice(&sig_type_bytes);
+
+    keccak256(&encoded)
+}
+
+/// EIP-712 digest the exchange checks an order signature against
+pub fn order_digest(order: &Order) -> [u8; 32] {
+    eip712_hash(&order_domain(order.neg_risk), &order_struct_hash(order))
+}
+
+/// Convert Order to SignedOrder for JSON serialization
+pub fn to_signed_order(order: &Order) -> SignedOrder {
+    SignedOrder {
+        salt: order.salt,
+        maker: format!("0x{}", hex::encode(order.maker)),
+        signer: format!("0x{}", hex::encode(order.signer)),
+        taker: format!("0x{}", hex::encode(order.taker)),
+        token_id: bytes32_to_decimal(&order.token_id),
+        maker_amount: bytes32_to_decimal(&order.maker_amount),
+        taker_amount: bytes32_to_decimal(&order.taker_amount),
+        expiration: bytes32_to_decimal(&order.expiration),
+        nonce: bytes32_to_decimal(&order.nonce),
+        fee_rate_bps: bytes32_to_decimal(&order.fee_rate_bps),
+        side: if order.side == Side::Buy { "BUY" } else { "SELL" }.to_string(),
+        signature_type: order.signature_type as u8,
+        signature: format!("0x{}", hex::encode(&order.signature)),
+    }
+}
 
-        let secp = Secp256k1::new();
-        let secret_key = SecretKey::from_slice(&key_bytes)
-            .context("Invalid private key")?;
+// ============================================================================
+// Offline Verification
+// ============================================================================
+// Recomputes the EIP-712 digest of a SignedOrder (as logged in "Request body")
+// and recovers who signed it, so a broken hash or signature shows up before
+// the CLOB rejects the order.
+// ============================================================================
+
+/// Parse a 0x-prefixed 20-byte address
+pub fn parse_address(value: &str) -> Result<[u8; 20]> {
+    let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .with_context(|| format!("Invalid address: {}", value))?;
+    bytes.try_into().map_err(|_| anyhow!("Address must be 20 bytes: {}", value))
+}
+
+impl SignedOrder {
+    /// Rebuild the Order that was hashed and signed (`neg_risk`: which
+    /// exchange it was signed for; the JSON does not say)
+    pub fn to_order(&self, neg_risk: bool) -> Result<Order> {
+        let side = match self.side.as_str() {
+            "BUY" => Side::Buy,
+            "SELL" => Side::Sell,
+            other => return Err(anyhow!("Unknown side: {}", other)),
+        };
+        let signature_type = match self.signature_type {
+            0 => SignatureType::Eoa,
+            1 => SignatureType::Poly,
+            other => return Err(anyhow!("Unsupported signatureType: {}", other)),
+        };
+        Ok(Order {
+            salt: self.salt,
+            maker: parse_address(&self.maker)?,
+            signer: parse_address(&self.signer)?,
+            taker: parse_address(&self.taker)?,
+            token_id: token_id_to_bytes32(&self.token_id).context("Invalid tokenId")?,
+            maker_amount: token_id_to_bytes32(&self.maker_amount).context("Invalid makerAmount")?,
+            taker_amount: token_id_to_bytes32(&self.taker_amount).context("Invalid takerAmount")?,
+            expiration: token_id_to_bytes32(&self.expiration).context("Invalid expiration")?,
+            nonce: token_id_to_bytes32(&self.nonce).context("Invalid nonce")?,
+            fee_rate_bps: token_id_to_bytes32(&self.fee_rate_bps).context("Invalid feeRateBps")?,
+            side,
+            signature_type,
+            signature: hex::decode(self.signature.strip_prefix("0x").unwrap_or(&self.signature))
+                .context("Invalid signature hex")?,
+            neg_risk,
+        })
+    }
+}
+
+/// What offline verification found for one order
+#[derive(Debug, Clone)]
+pub struct OrderVerification {
+    pub digest: [u8; 32],
+    pub recovered: [u8; 20],
+    pub signature_type: SignatureType,
+    /// Empty when the order is valid
+    pub problems: Vec<String>,
+}
+
+impl OrderVerification {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Recover the signer of `signed` and check it against the order fields and
+/// the expected maker / signer (if given):
+/// - the recovered address must be the order's `signer`
+/// - EOA orders: maker == signer; Poly (Magic/email) orders: maker is the
+///   proxy wallet, so maker != signer
+pub fn verify_signed_order(
+    signed: &SignedOrder,
+    neg_risk: bool,
+    expected_maker: Option<[u8; 20]>,
+    expected_signer: Option<[u8; 20]>,
+) -> Result<OrderVerification> {
+    let order = signed.to_order(neg_risk)?;
+    let digest = order_digest(&order);
+    let signature: [u8; 65] = order.signature.as_slice().try_into()
+        .map_err(|_| anyhow!("Signature must be 65 bytes, got {}", order.signature.len()))?;
+    let recovered = crate::signer::recover_address(&digest, &signature)?;
+
+    let mut problems = Vec::new();
+    if recovered != order.signer {
+        problems.push(format!(
+            "signature recovers to 0x{}, order signer is 0x{}",
+            hex::encode(recovered), hex::encode(order.signer)
+        ));
+    }
+    match order.signature_type {
+        SignatureType::Eoa if order.maker != order.signer => problems.push(format!(
+            "EOA order (signatureType=0) but maker 0x{} != signer 0x{}",
+            hex::encode(order.maker), hex::encode(order.signer)
+        )),
+        SignatureType::Poly if order.maker == order.signer => problems.push(
+            "Poly order (signatureType=1) but maker == signer (expected the proxy wallet as maker)".to_string()
+        ),
+        _ => {}
+    }
+    if let Some(maker) = expected_maker.filter(|m| *m != order.maker) {
+        problems.push(format!("maker 0x{} != expected 0x{}", hex::encode(order.maker), hex::encode(maker)));
+    }
+    if let Some(signer) = expected_signer.filter(|s| *s != recovered) {
+        problems.push(format!("signed by 0x{}, expected 0x{}", hex::encode(recovered), hex::encode(signer)));
+    }
+
+    Ok(OrderVerification { digest, recovered, signature_type: order.signature_type, problems })
+}
+
+// ============================================================================
+// Submission Errors and Retry
+// ============================================================================
+// Every failed submission is classified. Only network errors, 429 and 5xx are
+// retried: a retry resends the identical signed order (same salt, same hash),
+// so the exchange can never fill it twice, and after an ambiguous failure the
+// order is first looked up by hash in case the CLOB accepted it anyway.
+// Retries back off with jitter and stop short of the window end.
+// ============================================================================
+
+/// Recently accepted order hashes remembered by the duplicate guard
+const ACCEPTED_ORDERS_KEPT: usize = 256;
+
+/// Why an order submission failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderErrorKind {
+    /// Connection error or timeout (the order may or may not have arrived)
+    Network,
+    /// 429 Too Many Requests
+    RateLimited,
+    /// 5xx from the CLOB (the order may or may not have been processed)
+    Server,
+    /// Malformed order: amounts, tick, size, signature (local or CLOB 400)
+    Validation,
+    /// Not enough USDC (buy) or shares (sell), or missing allowance
+    InsufficientBalance,
+    /// FOK order could not be filled in full
+    NotFilled,
+    /// Leader lease lost: this pod must not trade
+    Fenced,
+    /// The CLOB already holds this signed order (an earlier send arrived)
+    Duplicate,
+    Other,
+}
+
+impl OrderErrorKind {
+    /// Safe to resend the identical signed order
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Network | Self::RateLimited | Self::Server)
+    }
+
+    /// The CLOB may have accepted the order even though the call failed
+    fn is_ambiguous(self) -> bool {
+        matches!(self, Self::Network | Self::Server)
+    }
+
+    /// Classify a CLOB error message (HTTP error body or `errorMsg`)
+    pub fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+        if message.contains("not enough balance") || message.contains("allowance") || message.contains("insufficient") {
+            Self::InsufficientBalance
+        } else if message.contains("fully filled") || message.contains("killed") || message.contains("no orders found to match") {
+            Self::NotFilled
+        } else if message.contains("duplicated") {
+            Self::Duplicate
+        } else if message.contains("invalid") || message.contains("tick size") || message.contains("min size")
+            || message.contains("lower than the minimum")
+        {
+            Self::Validation
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Classify a non-2xx response
+    pub fn from_status(status: u16, body: &str) -> Self {
+        match status {
+            429 => Self::RateLimited,
+            500..=599 => Self::Server,
+            _ => match Self::from_message(body) {
+                Self::Other if status == 400 => Self::Validation,
+                kind => kind,
+            },
+        }
+    }
+}
+
+/// A failed order submission (carried inside anyhow::Error; downcast to inspect)
+#[derive(Debug, Clone)]
+pub struct OrderError {
+    pub kind: OrderErrorKind,
+    pub message: String,
+    /// Retry-After sent with a 429
+    pub retry_after: Option<Duration>,
+}
+
+impl OrderError {
+    fn new(kind: OrderErrorKind, message: String) -> Self {
+        Self { kind, message, retry_after: None }
+    }
+}
+
+impl std::fmt::Display for OrderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+impl OrderResponse {
+    /// Why the CLOB refused the order (None if it was accepted)
+    pub fn error_kind(&self) -> Option<OrderErrorKind> {
+        (!self.success).then(|| OrderErrorKind::from_message(self.error_msg.as_deref().unwrap_or_default()))
+    }
+}
+
+/// Outcome of looking an order up after an ambiguous send: Some(Ok) =
+/// matched (or, once the CLOB called a resend a duplicate, accepted),
+/// Some(Err) = cancelled unfilled, None = not known to have arrived
+fn resolve_lookup(
+    order_id: &str,
+    details: Option<&OrderDetails>,
+    duplicate: bool,
+) -> Option<std::result::Result<OrderResponse, OrderError>> {
+    let accepted = |size_matched| OrderResponse {
+        success: true,
+        order_id: Some(order_id.to_string()),
+        error_msg: None,
+        size_matched,
+    };
+    match details {
+        Some(d) if d.status == OrderStatus::Filled || d.matched_shares() > 0.0 => Some(Ok(accepted(Some(d.matched_shares())))),
+        Some(d) if d.status == OrderStatus::Cancelled => Some(Err(OrderError::new(
+            OrderErrorKind::NotFilled,
+            format!("Order {} was cancelled without a fill", order_id),
+        ))),
+        // The CLOB has the order but its fill isn't visible yet
+        _ if duplicate => Some(Ok(accepted(None))),
+        _ => None,
+    }
+}
+
+/// How failed submissions are retried (from the `execution:` config)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 = never retry)
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for each further retry
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+    /// No retry is started with less than this left in the window
+    pub window_margin: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(2),
+            window_margin: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (1-based) of a failure, or None to give up.
+    /// `remaining`: time left in the window; `jitter` in [0, 1) picks a delay
+    /// between half and all of the exponential backoff.
+    pub fn next_delay(
+        &self,
+        attempt: u32,
+        err: &OrderError,
+        remaining: Option<Duration>,
+        jitter: f64,
+    ) -> Option<Duration> {
+        if !err.kind.is_retryable() || attempt > self.max_retries {
+            return None;
+        }
+        let backoff = self.base_backoff
+            .saturating_mul(1 << (attempt - 1).min(16))
+            .min(self.max_backoff);
+        let mut delay = backoff.mul_f64(0.5 + 0.5 * jitter.clamp(0.0, 1.0));
+        if let Some(retry_after) = err.retry_after {
+            delay = delay.max(retry_after);
+        }
+        match remaining {
+            Some(remaining) if delay + self.window_margin > remaining => None,
+            _ => Some(delay),
+        }
+    }
+}
 
-        let wallet_address = get_address(&secp, &secret_key);
-        info!("Executor initialized for signer: 0x{}", hex::encode(wallet_address));
+impl Executor {
+    /// Create a new executor signing with `signer` (see signer::from_env)
+    pub async fn new(signer: Box<dyn Signer>, clob_url: Option<String>) -> Result<Self> {
+        let wallet_address = signer.address();
+        info!("Executor initialized for signer: 0x{} ({})", hex::encode(wallet_address), signer.kind());
 
         // Check for separate funder address (for Magic/email wallets)
         let (funder_address, signature_type) = if let Ok(funder_str) = std::env::var("POLYMARKET_WALLET_ADDRESS") {
@@ -394,9 +1022,7 @@ impl Executor {
             (wallet_address, SignatureType::Eoa)
         };
 
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
+        let client = HttpClient::new("clob", Duration::from_secs(30));
 
         let clob_url = clob_url.unwrap_or_else(|| {
             std::env::var("POLYMARKET_CLOB_URL")
@@ -406,13 +1032,17 @@ impl Executor {
         let mut executor = Self {
             client,
             clob_url,
-            secp,
-            secret_key,
+            signer: Arc::from(signer),
             wallet_address,
             funder_address,
             signature_type,
             credentials: None,
             nonce: 0,
+            fence: None,
+            market_params: HashMap::new(),
+            retry: RetryPolicy::default(),
+            window_end: None,
+            accepted: VecDeque::new(),
         };
 
         // Derive API credentials
@@ -429,7 +1059,7 @@ impl Executor {
             .to_string();
 
         // Create L1 auth signature
-        let signature = self.create_l1_auth_signature(&timestamp, 0)?;
+        let signature = self.create_l1_auth_signature(&timestamp, 0).await?;
 
         let url = format!("{}/auth/derive-api-key", self.clob_url);
 
@@ -466,13 +1096,13 @@ impl Executor {
     }
 
     /// Create L1 authentication signature (EIP-712)
-    fn create_l1_auth_signature(&self, timestamp: &str, nonce: u64) -> Result<String> {
+    async fn create_l1_auth_signature(&self, timestamp: &str, nonce: u64) -> Result<String> {
         // ClobAuth EIP-712 domain
-        let domain_separator = self.compute_clob_auth_domain();
+        let domain_separator = clob_auth_domain();
 
         // ClobAuth message
         let message_str = "This message attests that I control the given wallet";
-        let struct_hash = self.compute_clob_auth_struct_hash(
+        let struct_hash = clob_auth_struct_hash(
             &self.wallet_address,
             timestamp,
             nonce,
@@ -480,145 +1110,31 @@ impl Executor {
         );
 
         // EIP-712 hash
-        let digest = self.compute_eip712_hash(&domain_separator, &struct_hash);
-
-        // Sign with secp256k1
-        let msg = Message::from_digest(digest);
-        let sig = self.secp.sign_ecdsa_recoverable(&msg, &self.secret_key);
-        let (rec_id, sig_bytes) = sig.serialize_compact();
-
-        // Build r, s, v format (65 bytes)
-        let mut sig_with_v = Vec::with_capacity(65);
-        sig_with_v.extend_from_slice(&sig_bytes);
-        sig_with_v.push(27 + rec_id.to_i32() as u8); // v = 27 + recovery_id
-
-        Ok(format!("0x{}", hex::encode(&sig_with_v)))
-    }
-
-    /// Compute ClobAuth domain separator
-    fn compute_clob_auth_domain(&self) -> [u8; 32] {
-        let type_hash = keccak256(b"EIP712Domain(string name,string version,uint256 chainId)");
-        let name_hash = keccak256(b"ClobAuthDomain");
-        let version_hash = keccak256(b"1");
-
-        let mut encoded = Vec::new();
-        encoded.extend_from_slice(&type_hash);
-        encoded.extend_from_slice(&name_hash);
-        encoded.extend_from_slice(&version_hash);
-        encoded.extend_from_slice(&u64_to_bytes32(POLYGON_CHAIN_ID));
-
-        keccak256(&encoded)
-    }
-
-    /// Compute ClobAuth struct hash
-    fn compute_clob_auth_struct_hash(
-        &self,
-        address: &[u8; 20],
-        timestamp: &str,
-        nonce: u64,
-        message: &str,
-    ) -> [u8; 32] {
-        let type_hash = keccak256(
-            b"ClobAuth(address address,string timestamp,uint256 nonce,string message)"
-        );
+        let digest = eip712_hash(&domain_separator, &struct_hash);
 
-        let mut encoded = Vec::new();
-        encoded.extend_from_slice(&type_hash);
-        // address is padded to 32 bytes
-        encoded.extend_from_slice(&[0u8; 12]);
-        encoded.extend_from_slice(address);
-        encoded.extend_from_slice(&keccak256(timestamp.as_bytes()));
-        encoded.extend_from_slice(&u64_to_bytes32(nonce));
-        encoded.extend_from_slice(&keccak256(message.as_bytes()));
-
-        keccak256(&encoded)
-    }
+        // Sign: r, s, v format (65 bytes, v = 27 + recovery_id)
+        let sig_with_v = self.sign_digest(digest).await?;
 
-    /// Compute final EIP-712 hash
-    fn compute_eip712_hash(&self, domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
-        let mut encoded = Vec::with_capacity(66);
-        encoded.extend_from_slice(&[0x19, 0x01]);
-        encoded.extend_from_slice(domain_separator);
-        encoded.extend_from_slice(struct_hash);
-        keccak256(&encoded)
-    }
-
-    /// Compute Order domain separator for CTF Exchange
-    fn compute_order_domain(&self) -> [u8; 32] {
-        let type_hash = keccak256(
-            b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
-        );
-
-        let name_hash = keccak256(b"Polymarket CTF Exchange");
-        let version_hash = keccak256(b"1");
-        let contract = hex::decode(CTF_EXCHANGE).unwrap();
-
-        let mut encoded = Vec::new();
-        encoded.extend_from_slice(&type_hash);
-        encoded.extend_from_slice(&name_hash);
-        encoded.extend_from_slice(&version_hash);
-        encoded.extend_from_slice(&u64_to_bytes32(POLYGON_CHAIN_ID));
-        encoded.extend_from_slice(&[0u8; 12]);
-        encoded.extend_from_slice(&contract);
-
-        keccak256(&encoded)
-    }
-
-    /// Compute Order struct hash
-    fn compute_order_struct_hash(&self, order: &Order) -> [u8; 32] {
-        let type_hash = keccak256(
-            b"Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,uint8 signatureType)"
-        );
-
-        let mut encoded = Vec::new();
-        encoded.extend_from_slice(&type_hash);
-        // Convert i64 salt to 32-byte big-endian for EIP-712
-        let mut salt_bytes = [0u8; 32];
-        salt_bytes[24..].copy_from_slice(&order.salt.to_be_bytes());
-        encoded.extend_from_slice(&salt_bytes);
-        // Addresses are padded to 32 bytes
-        encoded.extend_from_slice(&[0u8; 12]);
-        encoded.extend_from_slice(&order.maker);
-        encoded.extend_from_slice(&[0u8; 12]);
-        encoded.extend_from_slice(&order.signer);
-        encoded.extend_from_slice(&[0u8; 12]);
-        encoded.extend_from_slice(&order.taker);
-        encoded.extend_from_slice(&order.token_id);
-        encoded.extend_from_slice(&order.maker_amount);
-        encoded.extend_from_slice(&order.taker_amount);
-        encoded.extend_from_slice(&order.expiration);
-        encoded.extend_from_slice(&order.nonce);
-        encoded.extend_from_slice(&order.fee_rate_bps);
-        // side and signatureType are uint8, padded to 32 bytes
-        let mut side_bytes = [0u8; 32];
-        side_bytes[31] = order.side as u8;
-        encoded.extend_from_slice(&side_bytes);
-        let mut sig_type_bytes = [0u8; 32];
-        sig_type_bytes[31] = order.signature_type as u8;
-        encoded.extend_from_slice(&sig_type_bytes);
-
-        keccak256(&encoded)
+        Ok(format!("0x{}", hex::encode(sig_with_v)))
     }
 
     /// Sign an order
-    fn sign_order(&self, order: &mut Order) -> Result<()> {
-        let domain = self.compute_order_domain();
-        let struct_hash = self.compute_order_struct_hash(order);
-        let digest = self.compute_eip712_hash(&domain, &struct_hash);
+    async fn sign_order(&self, order: &mut Order) -> Result<()> {
+        let digest = order_digest(order);
 
-        // Sign with secp256k1
-        let msg = Message::from_digest(digest);
-        let sig = self.secp.sign_ecdsa_recoverable(&msg, &self.secret_key);
-        let (rec_id, sig_bytes) = sig.serialize_compact();
-
-        let mut sig_with_v = Vec::with_capacity(65);
-        sig_with_v.extend_from_slice(&sig_bytes);
-        sig_with_v.push(27 + rec_id.to_i32() as u8);
-
-        order.signature = sig_with_v;
+        order.signature = self.sign_digest(digest).await?.to_vec();
         Ok(())
     }
 
+    /// Sign off the async runtime: a socket signer does blocking I/O (bounded
+    /// by its timeout) that must not stall the tokio worker
+    async fn sign_digest(&self, digest: [u8; 32]) -> Result<[u8; 65]> {
+        let signer = Arc::clone(&self.signer);
+        tokio::task::spawn_blocking(move || signer.sign_digest(&digest))
+            .await
+            .context("Signing task failed")?
+    }
+
     /// Create HMAC signature for API request
     fn create_hmac_signature(
         &self,
@@ -659,19 +1175,20 @@ impl Executor {
     ) -> Result<Order> {
         // For BUY: makerAmount = USDC to spend, takerAmount = shares to receive
         // The API requires:
-        // 1. Price must be on 0.01 tick (e.g., 0.49, 0.50, 0.51)
+        // 1. Price must be on the market's tick (e.g., 0.49, 0.50, 0.51 for 0.01)
         // 2. makerAmount max 4 decimal places
         // 3. takerAmount max 2 decimal places (for BUY)
         // 4. makerAmount MUST equal price * takerAmount exactly
+        let params = self.cached_market_params(token_id)?;
 
-        // 1. Round price to 0.01 tick (cents)
-        let rounded_price = (price * 100.0).floor() / 100.0;
+        // 1. Round price to the tick (cents at most)
+        let rounded_price = floor_to(price, params.price_scale());
 
         // 2. Calculate shares from the requested USDC amount
         let shares = amount_usdc / rounded_price;
 
         // 3. Round shares to 2 decimal places (takerAmount max 2 decimals for BUY)
-        let rounded_shares = (shares * 100.0).floor() / 100.0;
+        let rounded_shares = floor_to(shares, 100.0);
 
         // 4. Recalculate exact makerAmount = price * shares (this is what Polymarket expects)
         let exact_usdc = rounded_price * rounded_shares;
@@ -692,10 +1209,10 @@ impl Executor {
             maker_amount, maker_amount as f64 / 1_000_000.0,
             taker_amount, taker_amount as f64 / 1_000_000.0);
 
-        self.create_order(token_id, maker_amount, taker_amount, Side::Buy, expiration_secs)
+        self.create_order(token_id, &params, maker_amount, taker_amount, Side::Buy, expiration_secs)
     }
 
-    /// Create an order
+    /// Create an order for a market with the given parameters
     ///
     /// # Arguments
     /// * `expiration_secs` - Optional expiration in seconds from now. If provided, order will
@@ -704,6 +1221,7 @@ impl Executor {
     fn create_order(
         &mut self,
         token_id: &str,
+        params: &MarketParams,
         maker_amount: u128,
         taker_amount: u128,
         side: Side,
@@ -742,85 +1260,147 @@ impl Executor {
             taker_amount: u128_to_bytes32(taker_amount),
             expiration: u64_to_bytes32(expiration),
             nonce: u64_to_bytes32(nonce),
-            fee_rate_bps: [0u8; 32],
+            fee_rate_bps: u64_to_bytes32(params.fee_rate_bps),
             side,
             signature_type: self.signature_type,
             signature: Vec::new(),
+            neg_risk: params.neg_risk,
         })
     }
 
-    /// Convert bytes32 to decimal string
-    fn bytes32_to_decimal(bytes: &[u8; 32]) -> String {
-        // Skip leading zeros and convert to decimal
-        let mut result = String::from("0");
-        for &byte in bytes.iter() {
-            // Multiply result by 256 and add byte
-            let mut carry = byte as u32;
-            let mut new_result = String::new();
-            let chars: Vec<char> = result.chars().collect();
-
-            for c in chars.iter().rev() {
-                let digit = c.to_digit(10).unwrap();
-                let val = digit * 256 + carry;
-                carry = val / 10;
-                new_result.insert(0, std::char::from_digit(val % 10, 10).unwrap());
-            }
+    /// Submit an order to the CLOB
+    ///
+    /// Failures come back as `OrderError` (downcast from the anyhow error).
+    /// Network errors, 429 and 5xx are retried per the retry policy by
+    /// resending this exact signed order; an order hash the CLOB already
+    /// accepted is never sent again.
+    pub async fn submit_order(
+        &mut self,
+        order: &Order,
+        order_type: OrderType,
+    ) -> Result<OrderResponse> {
+        let side = match order.side {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        };
 
-            while carry > 0 {
-                new_result.insert(0, std::char::from_digit(carry % 10, 10).unwrap());
-                carry /= 10;
-            }
+        // The CLOB order ID is the EIP-712 order hash
+        let order_id = format!("0x{}", hex::encode(order_digest(order)));
+        if let Some(previous) = self.accepted_response(&order_id) {
+            warn!("Order {} was already accepted - not submitting it again", order_id);
+            return Ok(previous);
+        }
 
-            result = new_result;
+        // Reject locally what the CLOB would reject with a 400
+        let params = self.cached_market_params(&bytes32_to_decimal(&order.token_id))?;
+        if let Err(e) = validate_order(order, &params, order_type) {
+            metrics::ORDERS.with_label_values(&[side, "invalid"]).inc();
+            warn!("{}", e);
+            return Err(OrderError::new(OrderErrorKind::Validation, e.to_string()).into());
         }
 
-        // Remove leading zeros, but keep at least "0"
-        let trimmed = result.trim_start_matches('0');
-        if trimmed.is_empty() {
-            "0".to_string()
-        } else {
-            trimmed.to_string()
+        let mut attempt = 0;
+        // An earlier attempt may have reached the CLOB
+        let mut ambiguous = false;
+        loop {
+            // Only the current leader may trade (fencing token checked against Redis)
+            if let Some(ref fence) = self.fence {
+                if let Err(e) = fence.check_fence().await {
+                    metrics::ORDERS.with_label_values(&[side, "fenced"]).inc();
+                    return Err(OrderError::new(OrderErrorKind::Fenced, e.to_string()).into());
+                }
+            }
+
+            let err = match self.post_order(order, order_type, side).await {
+                Ok(response) if !(ambiguous && response.error_kind() == Some(OrderErrorKind::Duplicate)) => {
+                    if response.success {
+                        self.remember_accepted(&order_id, &response);
+                    }
+                    return Ok(response);
+                }
+                Ok(response) => OrderError::new(OrderErrorKind::Duplicate, response.error_msg.unwrap_or_default()),
+                Err(e) => e,
+            };
+
+            // A duplicate after an ambiguous failure means an earlier send arrived
+            if ambiguous && err.kind == OrderErrorKind::Duplicate {
+                warn!("Order {} is a duplicate of an earlier send - looking it up", order_id);
+                let details = self.get_order(&order_id).await.ok();
+                if let Some(result) = self.finish_lookup(&order_id, details.as_ref(), true, side) {
+                    return result;
+                }
+            }
+
+            attempt += 1;
+            let remaining = self.window_end
+                .map(|end| end.duration_since(SystemTime::now()).unwrap_or_default());
+            let Some(delay) = self.retry.next_delay(attempt, &err, remaining, rand::random()) else {
+                return Err(err.into());
+            };
+
+            metrics::ORDERS.with_label_values(&[side, "retried"]).inc();
+            warn!("Order {} failed ({:?}): {} - retry {}/{} in {}ms",
+                order_id, err.kind, err, attempt, self.retry.max_retries, delay.as_millis());
+            tokio::time::sleep(delay).await;
+
+            // The failed request may still have reached the CLOB: look the
+            // order up before sending it again
+            if err.kind.is_ambiguous() {
+                ambiguous = true;
+                if let Ok(details) = self.get_order(&order_id).await {
+                    if let Some(result) = self.finish_lookup(&order_id, Some(&details), false, side) {
+                        return result;
+                    }
+                }
+            }
         }
     }
 
-    /// Convert Order to SignedOrder for JSON serialization
-    fn to_signed_order(&self, order: &Order) -> SignedOrder {
-        SignedOrder {
-            salt: order.salt,
-            maker: format!("0x{}", hex::encode(order.maker)),
-            signer: format!("0x{}", hex::encode(order.signer)),
-            taker: format!("0x{}", hex::encode(order.taker)),
-            token_id: Self::bytes32_to_decimal(&order.token_id),
-            maker_amount: Self::bytes32_to_decimal(&order.maker_amount),
-            taker_amount: Self::bytes32_to_decimal(&order.taker_amount),
-            expiration: Self::bytes32_to_decimal(&order.expiration),
-            nonce: Self::bytes32_to_decimal(&order.nonce),
-            fee_rate_bps: Self::bytes32_to_decimal(&order.fee_rate_bps),
-            side: if order.side == Side::Buy { "BUY" } else { "SELL" }.to_string(),
-            signature_type: order.signature_type as u8,
-            signature: format!("0x{}", hex::encode(&order.signature)),
-        }
+    /// Apply `resolve_lookup`: remember an accepted order, count a cancelled one
+    fn finish_lookup(
+        &mut self,
+        order_id: &str,
+        details: Option<&OrderDetails>,
+        duplicate: bool,
+        side: &'static str,
+    ) -> Option<Result<OrderResponse>> {
+        let result = resolve_lookup(order_id, details, duplicate)?;
+        Some(match result {
+            Ok(response) => {
+                info!("Order {} was accepted despite the error (status {:?}, {:?} shares matched)",
+                    order_id, details.map(|d| &d.status), response.size_matched);
+                self.remember_accepted(order_id, &response);
+                Ok(response)
+            }
+            Err(err) => {
+                metrics::ORDERS.with_label_values(&[side, "rejected"]).inc();
+                Err(err.into())
+            }
+        })
     }
 
-    /// Submit an order to the CLOB
-    pub async fn submit_order(
+    /// One POST /order attempt, failures classified
+    async fn post_order(
         &self,
         order: &Order,
         order_type: OrderType,
-    ) -> Result<OrderResponse> {
+        side: &'static str,
+    ) -> std::result::Result<OrderResponse, OrderError> {
+        let other = |e: anyhow::Error| OrderError::new(OrderErrorKind::Other, e.to_string());
         let creds = self.credentials.as_ref()
-            .ok_or_else(|| anyhow!("API credentials not initialized"))?;
+            .ok_or_else(|| OrderError::new(OrderErrorKind::Other, "API credentials not initialized".to_string()))?;
 
-        let signed_order = self.to_signed_order(order);
+        let signed_order = to_signed_order(order);
         let request = OrderRequest {
             order: signed_order,
             owner: creds.key.clone(),
             order_type,
         };
 
-        let body = serde_json::to_string(&request)?;
+        let body = serde_json::to_string(&request).map_err(|e| other(e.into()))?;
         let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)?
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| other(e.into()))?
             .as_secs()
             .to_string();
 
@@ -831,7 +1411,7 @@ impl Executor {
             "POST",
             path,
             &body,
-        )?;
+        ).map_err(other)?;
 
         let url = format!("{}{}", self.clob_url, path);
 
@@ -842,7 +1422,10 @@ impl Executor {
             &creds.key,
             &timestamp);
 
-        let response = self.client
+        metrics::ORDERS.with_label_values(&[side, "submitted"]).inc();
+        let submitted_at = std::time::Instant::now();
+
+        let response = match self.client
             .post(&url)
             .header("Content-Type", "application/json")
             .header("POLY_ADDRESS", format!("0x{}", hex::encode(self.wallet_address)))
@@ -851,30 +1434,76 @@ impl Executor {
             .header("POLY_SIGNATURE", &signature)
             .header("POLY_TIMESTAMP", &timestamp)
             .body(body.clone())
+            // 429s come back to submit_order's retry policy (bounded by the window)
+            .no_retry()
             .send()
             .await
-            .context("Failed to submit order")?;
+        {
+            Ok(r) => r,
+            Err(e) => {
+                metrics::ORDERS.with_label_values(&[side, "error"]).inc();
+                return Err(OrderError::new(OrderErrorKind::Network, format!("Failed to submit order: {}", e)));
+            }
+        };
 
         let status = response.status();
+        let retry_after = response.headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
         let text = response.text().await.unwrap_or_default();
+        metrics::FILL_LATENCY
+            .with_label_values(&[side])
+            .observe(submitted_at.elapsed().as_secs_f64());
 
         if !status.is_success() {
+            metrics::ORDERS.with_label_values(&[side, "rejected"]).inc();
             warn!("Order submission failed: {} - {}", status, text);
-            return Err(anyhow!("Order submission failed: {} - {}", status, text));
+            let mut err = OrderError::new(
+                OrderErrorKind::from_status(status.as_u16(), &text),
+                format!("Order submission failed: {} - {}", status, text),
+            );
+            err.retry_after = retry_after;
+            return Err(err);
         }
 
         let result: OrderResponse = serde_json::from_str(&text)
-            .context("Failed to parse order response")?;
+            .map_err(|e| OrderError::new(OrderErrorKind::Other, format!("Failed to parse order response: {}", e)))?;
 
         if result.success {
+            metrics::ORDERS.with_label_values(&[side, "filled"]).inc();
             info!("Order submitted successfully: {:?}", result.order_id);
         } else {
-            warn!("Order rejected: {:?}", result.error_msg);
+            metrics::ORDERS.with_label_values(&[side, "rejected"]).inc();
+            warn!("Order rejected ({:?}): {:?}", result.error_kind(), result.error_msg);
         }
 
         Ok(result)
     }
 
+    /// Response of an order hash the CLOB already accepted
+    fn accepted_response(&self, order_id: &str) -> Option<OrderResponse> {
+        self.accepted.iter().find(|(id, _)| id == order_id).map(|(_, r)| r.clone())
+    }
+
+    fn remember_accepted(&mut self, order_id: &str, response: &OrderResponse) {
+        if self.accepted.len() >= ACCEPTED_ORDERS_KEPT {
+            self.accepted.pop_front();
+        }
+        self.accepted.push_back((order_id.to_string(), response.clone()));
+    }
+
+    /// Retry policy for order submission (default: no retries)
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry = policy;
+    }
+
+    /// End of the current market window: retries stop short of it
+    pub fn set_window_end(&mut self, end: SystemTime) {
+        self.window_end = Some(end);
+    }
+
     /// Execute a market buy order with GTD (Good-Till-Date) - 1 second expiration
     pub async fn market_buy(
         &mut self,
@@ -882,10 +1511,11 @@ impl Executor {
         price: f64,
         amount_usdc: f64,
     ) -> Result<OrderResponse> {
+        self.market_params(token_id).await?;
         // Use GTD with 1 second expiration for better fill rates
         // Note: Polymarket adds 60s security threshold, so actual expiration is ~61s
         let mut order = self.create_buy_order(token_id, price, amount_usdc, Some(1))?;
-        self.sign_order(&mut order)?;
+        self.sign_order(&mut order).await?;
         self.submit_order(&order, OrderType::GTD).await
     }
 
@@ -913,11 +1543,16 @@ impl Executor {
             amount_usdc, price * 100.0, exact_usdc, rounded_shares);
 
         // Create order with exact amounts (no expiration for FOK)
+        self.market_params(token_id).await?;
         let mut order = self.create_buy_order(token_id, price, exact_usdc, None)?;
-        self.sign_order(&mut order)?;
+        self.sign_order(&mut order).await?;
 
         let response = self.submit_order(&order, OrderType::FOK).await?;
-        Ok((response, exact_usdc, rounded_shares))
+        // A fill recovered by lookup reports its own matched size
+        match response.size_matched.filter(|&m| m > 0.0 && m < rounded_shares) {
+            Some(matched) => Ok((response, exact_usdc * matched / rounded_shares, matched)),
+            None => Ok((response, exact_usdc, rounded_shares)),
+        }
     }
 
     /// Execute a FOK buy with liquidity check
@@ -955,8 +1590,9 @@ impl Executor {
         amount_usdc: f64,
         expiration_secs: u64,
     ) -> Result<OrderResponse> {
+        self.market_params(token_id).await?;
         let mut order = self.create_buy_order(token_id, price, amount_usdc, Some(expiration_secs))?;
-        self.sign_order(&mut order)?;
+        self.sign_order(&mut order).await?;
         self.submit_order(&order, OrderType::GTD).await
     }
 
@@ -971,13 +1607,14 @@ impl Executor {
         expiration_secs: Option<u64>,
     ) -> Result<Order> {
         // For SELL: makerAmount = shares, takerAmount = USDC
-        // Price must be on 0.01 tick (e.g., 0.49, 0.50, 0.51)
+        // Price must be on the market's tick (e.g., 0.49, 0.50, 0.51 for 0.01)
+        let params = self.cached_market_params(token_id)?;
 
-        // 1. Round price to 0.01 tick (cents)
-        let rounded_price = (price * 100.0).floor() / 100.0;
+        // 1. Round price to the tick (cents at most)
+        let rounded_price = floor_to(price, params.price_scale());
 
         // 2. Round shares to 2 decimal places
-        let rounded_shares = (shares * 100.0).floor() / 100.0;
+        let rounded_shares = floor_to(shares, 100.0);
 
         // 3. Compute exact takerAmount (USDC) = rounded_price × rounded_shares
         // Round to 4 decimal places to avoid floating point errors
@@ -994,7 +1631,7 @@ impl Executor {
             maker_amount, maker_amount as f64 / 1_000_000.0,
             taker_amount, taker_amount as f64 / 1_000_000.0);
 
-        self.create_order(token_id, maker_amount, taker_amount, Side::Sell, expiration_secs)
+        self.create_order(token_id, &params, maker_amount, taker_amount, Side::Sell, expiration_secs)
     }
 
     /// Execute a market sell order with GTD (Good-Till-Date) - 1 second expiration
@@ -1004,10 +1641,11 @@ impl Executor {
         price: f64,
         shares: f64,
     ) -> Result<OrderResponse> {
+        self.market_params(token_id).await?;
         // Use GTD with 1 second expiration for better fill rates
         // Note: Polymarket adds 60s security threshold, so actual expiration is ~61s
         let mut order = self.create_sell_order(token_id, price, shares, Some(1))?;
-        self.sign_order(&mut order)?;
+        self.sign_order(&mut order).await?;
         self.submit_order(&order, OrderType::GTD).await
     }
 
@@ -1038,11 +1676,16 @@ impl Executor {
             rounded_shares, price * 100.0, slippage_price * 100.0, slippage_usdc, exact_usdc);
 
         // Create order with slippage price (accept 1¢ less per share)
+        self.market_params(token_id).await?;
         let mut order = self.create_sell_order(token_id, slippage_price, rounded_shares, None)?;
-        self.sign_order(&mut order)?;
+        self.sign_order(&mut order).await?;
 
         let response = self.submit_order(&order, OrderType::FOK).await?;
-        Ok((response, rounded_shares, slippage_usdc))
+        // A fill recovered by lookup reports its own matched size
+        match response.size_matched.filter(|&m| m > 0.0 && m < rounded_shares) {
+            Some(matched) => Ok((response, matched, slippage_usdc * matched / rounded_shares)),
+            None => Ok((response, rounded_shares, slippage_usdc)),
+        }
     }
 
     /// Execute a FOK sell with liquidity check
@@ -1073,6 +1716,54 @@ impl Executor {
         self.fok_sell(token_id, price, rounded_shares).await
     }
 
+    /// Market parameters for `token_id`, fetched from the CLOB on first use and
+    /// then reused for MARKET_PARAMS_TTL
+    pub async fn market_params(&mut self, token_id: &str) -> Result<MarketParams> {
+        if let Some((params, fetched_at)) = self.market_params.get(token_id) {
+            if fetched_at.elapsed() < MARKET_PARAMS_TTL {
+                return Ok(*params);
+            }
+        }
+
+        let book_url = format!("{}/book?token_id={}", self.clob_url, token_id);
+        let fee_url = format!("{}/fee-rate?token_id={}", self.clob_url, token_id);
+        let (book, fee) = tokio::try_join!(self.get_json(&book_url), self.get_json(&fee_url))?;
+        let params = MarketParams::from_responses(&book, &fee)
+            .with_context(|| format!("Bad market parameters for token {}", token_id))?;
+        info!("Market params for {}...: tick={} min_size={} fee={}bps neg_risk={}",
+            &token_id[..16.min(token_id.len())],
+            params.tick_size, params.min_order_size, params.fee_rate_bps, params.neg_risk);
+
+        // Tokens rotate every window; drop expired entries so the cache stays small
+        self.market_params.retain(|_, (_, fetched_at)| fetched_at.elapsed() < MARKET_PARAMS_TTL);
+        self.market_params.insert(token_id.to_string(), (params, Instant::now()));
+        Ok(params)
+    }
+
+    /// Parameters already fetched by `market_params` (orders are built synchronously)
+    fn cached_market_params(&self, token_id: &str) -> Result<MarketParams> {
+        self.market_params.get(token_id)
+            .map(|(params, _)| *params)
+            .ok_or_else(|| anyhow!("Market parameters for token {} not loaded", token_id))
+    }
+
+    /// GET a public CLOB endpoint as JSON
+    async fn get_json(&self, url: &str) -> Result<serde_json::Value> {
+        let response = self.client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Request to {} failed: {} - {}", url, status, text));
+        }
+
+        response.json().await.with_context(|| format!("Failed to parse response from {}", url))
+    }
+
     /// Get order details by ID
     pub async fn get_order(&self, order_id: &str) -> Result<OrderDetails> {
         let creds = self.credentials.as_ref()
@@ -1182,6 +1873,11 @@ impl Executor {
         Ok(result)
     }
 
+    /// Refuse orders unless `leadership` holds the current leader lease
+    pub fn set_fence(&mut self, leadership: Leadership) {
+        self.fence = Some(leadership);
+    }
+
     /// Get signer wallet address as hex string
     pub fn wallet_address(&self) -> String {
         format!("0x{}", hex::encode(self.wallet_address))
@@ -1202,3 +1898,365 @@ fn keccak256(data: &[u8]) -> [u8; 32] {
     output.copy_from_slice(&result);
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::LocalSigner;
+
+    /// Test key and its address (Web3 Secret Storage test vector key)
+    const TEST_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+    const TEST_SIGNER: &str = "0x008aeeda4d805471df9b2a5b0f38a0c3bcba786b";
+    const PROXY_WALLET: &str = "0xd8da6bf26964af9d7eed9e03e53415d37aa96045";
+    const TOKEN_ID: &str = "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+
+    fn unsigned(maker: &str, side: &str, maker_amount: &str, taker_amount: &str, expiration: &str, signature_type: u8) -> SignedOrder {
+        SignedOrder {
+            salt: 479249096,
+            maker: maker.to_string(),
+            signer: TEST_SIGNER.to_string(),
+            taker: format!("0x{}", "00".repeat(20)),
+            token_id: TOKEN_ID.to_string(),
+            maker_amount: maker_amount.to_string(),
+            taker_amount: taker_amount.to_string(),
+            expiration: expiration.to_string(),
+            nonce: "0".to_string(),
+            fee_rate_bps: "0".to_string(),
+            side: side.to_string(),
+            signature_type,
+            signature: "0x".to_string(),
+        }
+    }
+
+    fn eoa_buy() -> SignedOrder {
+        unsigned(TEST_SIGNER, "BUY", "1000000", "2000000", "0", 0)
+    }
+
+    fn poly_sell() -> SignedOrder {
+        unsigned(PROXY_WALLET, "SELL", "5000000", "2350000", "1767225660", 1)
+    }
+
+    fn sign(signed: &SignedOrder) -> SignedOrder {
+        let signer = LocalSigner::from_hex(TEST_KEY).unwrap();
+        let mut order = signed.to_order(false).unwrap();
+        order.signature = signer.sign_digest(&order_digest(&order)).unwrap().to_vec();
+        to_signed_order(&order)
+    }
+
+    // Expected digests computed independently (Python keccak + EIP-712 encoding)
+    #[test]
+    fn test_eip712_digest_vectors() {
+        assert_eq!(hex::encode(order_domain(false)), "1a573e3617c78403b5b4b892827992f027b03d4eaf570048b8ee8cdd84d151be");
+        assert_eq!(
+            hex::encode(order_digest(&eoa_buy().to_order(false).unwrap())),
+            "f21dbeb59fc559a82b17d5e924c7e8b70f23bb533815fb4e6d53f04a183d520a"
+        );
+        assert_eq!(
+            hex::encode(order_digest(&poly_sell().to_order(false).unwrap())),
+            "b0f7013fd7f4998a8bc9009b32f24a8e641f7ab7bfbd43c6f2e2d83a092df4dc"
+        );
+
+        // Neg-risk markets: same order, Neg Risk CTF Exchange domain
+        assert_eq!(hex::encode(order_domain(true)), "82cb6aa85babb812f4b521a12b10f0cbc68d2b44be7bc02c047004f544adb49f");
+        let mut neg_risk = eoa_buy();
+        neg_risk.fee_rate_bps = "1000".to_string();
+        assert_eq!(
+            hex::encode(order_digest(&neg_risk.to_order(true).unwrap())),
+            "a0a9327d0169df421774b547e9960ac4e23a3c23b5954505f6f74ff08afc3807"
+        );
+
+        let address = parse_address(TEST_SIGNER).unwrap();
+        let auth = clob_auth_struct_hash(&address, "1767225600", 0, "This message attests that I control the given wallet");
+        assert_eq!(
+            hex::encode(eip712_hash(&clob_auth_domain(), &auth)),
+            "19e359788997ec87141fb8f9e8abc51fedf27ad1cc3e916f5826ed7876307022"
+        );
+    }
+
+    #[test]
+    fn test_uint256_decimal_round_trip() {
+        for value in [
+            "0",
+            "1",
+            "255",
+            "256",
+            "1000000",
+            "340282366920938463463374607431768211455", // u128::MAX
+            TOKEN_ID,
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935", // 2^256 - 1
+        ] {
+            assert_eq!(bytes32_to_decimal(&token_id_to_bytes32(value).unwrap()), value);
+        }
+        assert_eq!(token_id_to_bytes32("256").unwrap()[30..], [1, 0]);
+        assert!(token_id_to_bytes32("115792089237316195423570985008687907853269984665640564039457584007913129639936").is_err());
+        assert!(token_id_to_bytes32("12a").is_err());
+    }
+
+    #[test]
+    fn test_verify_signed_order_both_signature_types() {
+        let signer = parse_address(TEST_SIGNER).unwrap();
+        let proxy = parse_address(PROXY_WALLET).unwrap();
+        assert_eq!(LocalSigner::from_hex(TEST_KEY).unwrap().address(), signer);
+
+        // EOA: maker == signer
+        let eoa = sign(&eoa_buy());
+        let result = verify_signed_order(&eoa, false, Some(signer), Some(signer)).unwrap();
+        assert!(result.is_valid(), "{:?}", result.problems);
+        assert_eq!(result.signature_type, SignatureType::Eoa);
+
+        // Poly: proxy wallet is the maker, the key only signs
+        let poly = sign(&poly_sell());
+        let result = verify_signed_order(&poly, false, Some(proxy), Some(signer)).unwrap();
+        assert!(result.is_valid(), "{:?}", result.problems);
+        assert_eq!(result.recovered, signer);
+
+        // Survives the JSON the executor submits
+        let json = serde_json::to_string(&poly).unwrap();
+        let parsed: SignedOrder = serde_json::from_str(&json).unwrap();
+        assert!(verify_signed_order(&parsed, false, None, None).unwrap().is_valid());
+
+        // Tampered amount: signature no longer matches the signer
+        let mut tampered = eoa.clone();
+        tampered.maker_amount = "1000001".to_string();
+        assert!(!verify_signed_order(&tampered, false, None, None).unwrap().is_valid());
+
+        // Signed for the neg-risk exchange: only verifies against that domain
+        let mut neg_risk = eoa_buy().to_order(true).unwrap();
+        neg_risk.signature = LocalSigner::from_hex(TEST_KEY).unwrap().sign_digest(&order_digest(&neg_risk)).unwrap().to_vec();
+        let neg_risk = to_signed_order(&neg_risk);
+        assert!(verify_signed_order(&neg_risk, true, None, None).unwrap().is_valid());
+        assert!(!verify_signed_order(&neg_risk, false, None, None).unwrap().is_valid());
+
+        // Wrong maker for the signature type / expectation
+        let mut eoa_with_proxy = eoa_buy();
+        eoa_with_proxy.maker = PROXY_WALLET.to_string();
+        let result = verify_signed_order(&sign(&eoa_with_proxy), false, None, None).unwrap();
+        assert_eq!(result.problems.len(), 1);
+        assert!(result.problems[0].contains("EOA order"));
+
+        let mut poly_self_maker = poly_sell();
+        poly_self_maker.maker = TEST_SIGNER.to_string();
+        assert!(!verify_signed_order(&sign(&poly_self_maker), false, None, None).unwrap().is_valid());
+
+        assert!(!verify_signed_order(&poly, false, Some(signer), None).unwrap().is_valid());
+        assert!(!verify_signed_order(&poly, false, None, Some(proxy)).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_market_params_and_validate_order() {
+        let book = serde_json::json!({"tick_size": "0.01", "min_order_size": "5", "neg_risk": false, "bids": []});
+        let params = MarketParams::from_responses(&book, &serde_json::json!({"base_fee": 1000})).unwrap();
+        assert_eq!(params, MarketParams { tick_size: 0.01, min_order_size: 5.0, fee_rate_bps: 1000, neg_risk: false });
+        assert!(MarketParams::from_responses(&serde_json::json!({"tick_size": 0.01, "min_order_size": 5}), &serde_json::json!({"base_fee": 0})).is_err());
+        assert!(MarketParams::from_responses(&book, &serde_json::json!({})).is_err());
+        assert_eq!(MarketParams { tick_size: 0.1, ..params }.price_scale(), 10.0);
+        assert_eq!(MarketParams { tick_size: 0.001, ..params }.price_scale(), 100.0);
+
+        // $1.00 for 2 shares at 50¢ (FOK buy) / 5 shares at 47¢ (GTD sell)
+        let build = |signed: &SignedOrder| {
+            let mut signed = signed.clone();
+            signed.fee_rate_bps = "1000".to_string();
+            signed.to_order(false).unwrap()
+        };
+        let buy = build(&eoa_buy());
+        let sell = build(&poly_sell());
+        validate_order(&buy, &params, OrderType::FOK).unwrap();
+        validate_order(&sell, &params, OrderType::GTD).unwrap();
+
+        let rejects = |order: &Order, params: &MarketParams, order_type, needle: &str| {
+            let err = validate_order(order, params, order_type).unwrap_err().to_string();
+            assert!(err.contains(needle), "{}: {}", needle, err);
+        };
+        rejects(&eoa_buy().to_order(false).unwrap(), &params, OrderType::FOK, "feeRateBps 0");
+        rejects(&buy, &MarketParams { neg_risk: true, ..params }, OrderType::FOK, "neg-risk");
+        rejects(&sell, &MarketParams { tick_size: 0.1, ..params }, OrderType::GTD, "not on the 0.1 tick");
+        rejects(&sell, &MarketParams { min_order_size: 10.0, ..params }, OrderType::GTD, "below the market minimum");
+        rejects(&sell, &params, OrderType::FOK, "expiration");
+        rejects(&buy, &MarketParams { min_order_size: 1.0, ..params }, OrderType::GTD, "GTD order without an expiration");
+
+        let mut odd = buy.clone();
+        odd.maker_amount = u128_to_bytes32(1_000_100);
+        odd.taker_amount = u128_to_bytes32(2_000_200);
+        rejects(&odd, &params, OrderType::FOK, "decimal limits");
+        odd.maker_amount = u128_to_bytes32(1_000_000);
+        odd.taker_amount = u128_to_bytes32(1_000_000);
+        rejects(&odd, &params, OrderType::FOK, "outside");
+        odd.maker_amount = u128_to_bytes32(500_000);
+        rejects(&odd, &params, OrderType::FOK, "below the $1.00 minimum");
+    }
+
+    #[test]
+    fn test_resolve_lookup_after_ambiguous_send() {
+        let details = |status: &str, matched: &str| -> OrderDetails {
+            serde_json::from_value(serde_json::json!({
+                "id": "0xabc", "status": status, "size_matched": matched, "side": "BUY",
+            })).unwrap()
+        };
+        assert_eq!(
+            OrderErrorKind::from_status(400, r#"{"error":"order 0xabc is invalid. Duplicated."}"#),
+            OrderErrorKind::Duplicate
+        );
+        assert!(!OrderErrorKind::Duplicate.is_retryable());
+
+        // Matched: accepted with the matched size, duplicate or not
+        let filled = details("MATCHED", "10");
+        for duplicate in [false, true] {
+            let response = resolve_lookup("0xabc", Some(&filled), duplicate).unwrap().unwrap();
+            assert!(response.success);
+            assert_eq!(response.size_matched, Some(10.0));
+        }
+        // Cancelled unfilled: NotFilled, never resent
+        let cancelled = details("CANCELED", "0");
+        assert_eq!(resolve_lookup("0xabc", Some(&cancelled), true).unwrap().unwrap_err().kind, OrderErrorKind::NotFilled);
+        // Live or not found: resend, unless the CLOB called the resend a duplicate
+        let live = details("LIVE", "0");
+        assert!(resolve_lookup("0xabc", Some(&live), false).is_none());
+        assert!(resolve_lookup("0xabc", None, false).is_none());
+        let accepted = resolve_lookup("0xabc", None, true).unwrap().unwrap();
+        assert!(accepted.success);
+        assert_eq!(accepted.order_id.as_deref(), Some("0xabc"));
+        assert_eq!(accepted.size_matched, None);
+        assert!(resolve_lookup("0xabc", Some(&live), true).unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_order_details_matched_shares() {
+        let details: OrderDetails = serde_json::from_str(
+            r#"{"id":"0xabc","status":"CANCELED","size_matched":"0","side":"BUY"}"#).unwrap();
+        assert_eq!(details.status, OrderStatus::Cancelled);
+        assert_eq!(details.matched_shares(), 0.0);
+        let details: OrderDetails = serde_json::from_str(
+            r#"{"id":"0xabc","status":"MATCHED","size_matched":"12.5","side":"BUY"}"#).unwrap();
+        assert_eq!(details.status, OrderStatus::Filled);
+        assert_eq!(details.matched_shares(), 12.5);
+        let details: OrderDetails = serde_json::from_str(r#"{"id":"0xabc","status":"LIVE","side":"SELL"}"#).unwrap();
+        assert_eq!(details.matched_shares(), 0.0);
+    }
+
+    #[test]
+    fn test_order_error_classification_and_retry_policy() {
+        use OrderErrorKind::*;
+        assert_eq!(OrderErrorKind::from_status(429, ""), RateLimited);
+        assert_eq!(OrderErrorKind::from_status(503, "upstream"), Server);
+        assert_eq!(OrderErrorKind::from_status(400, r#"{"error":"not enough balance / allowance"}"#), InsufficientBalance);
+        assert_eq!(OrderErrorKind::from_status(400, r#"{"error":"invalid amounts, the market buy orders maker amount supports a max accuracy of 2 decimals"}"#), Validation);
+        assert_eq!(OrderErrorKind::from_status(400, r#"{"error":"something new"}"#), Validation);
+        assert_eq!(OrderErrorKind::from_status(403, "forbidden"), Other);
+        let unfilled = OrderResponse {
+            success: false,
+            order_id: None,
+            error_msg: Some("order couldn't be fully filled. FOK orders are fully filled or killed.".to_string()),
+            size_matched: None,
+        };
+        assert_eq!(unfilled.error_kind(), Some(NotFilled));
+        assert_eq!(OrderResponse { success: true, ..unfilled }.error_kind(), None);
+
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_millis(500),
+            window_margin: Duration::from_secs(5),
+        };
+        let err = |kind| OrderError::new(kind, String::new());
+        let minute = Some(Duration::from_secs(60));
+
+        // Exponential backoff, capped, jittered between half and all of it
+        assert_eq!(policy.next_delay(1, &err(Server), minute, 0.0), Some(Duration::from_millis(100)));
+        assert_eq!(policy.next_delay(1, &err(Network), minute, 1.0), Some(Duration::from_millis(200)));
+        assert_eq!(policy.next_delay(2, &err(RateLimited), None, 1.0), Some(Duration::from_millis(400)));
+        assert_eq!(policy.next_delay(3, &err(Server), minute, 1.0), Some(Duration::from_millis(500)));
+        assert_eq!(policy.next_delay(4, &err(Server), minute, 1.0), None);
+
+        // Only safe errors are retried
+        for kind in [Validation, InsufficientBalance, NotFilled, Fenced, Other] {
+            assert_eq!(policy.next_delay(1, &err(kind), minute, 0.5), None, "{:?}", kind);
+        }
+
+        // Retry-After wins when longer; the window end bounds everything
+        let mut limited = err(RateLimited);
+        limited.retry_after = Some(Duration::from_secs(2));
+        assert_eq!(policy.next_delay(1, &limited, minute, 1.0), Some(Duration::from_secs(2)));
+        assert_eq!(policy.next_delay(1, &limited, Some(Duration::from_secs(6)), 1.0), None);
+        assert_eq!(policy.next_delay(1, &err(Server), Some(Duration::from_secs(5)), 0.0), None);
+        assert_eq!(RetryPolicy::default().next_delay(1, &err(Server), minute, 0.0), None);
+    }
+
+    fn assert_close(actual: f64, expected: f64, what: &str) {
+        assert!((actual - expected).abs() < 1e-9, "{}: {} != {}", what, actual, expected);
+    }
+
+    /// Is `value` a multiple of 1/scale (within float error)?
+    fn has_decimals(value: f64, scale: f64) -> bool {
+        ((value * scale).round() - value * scale).abs() < 1e-6
+    }
+
+    #[test]
+    fn test_round_for_fok_buy_golden() {
+        // (price, amount_usdc) -> (shares, usdc)
+        let cases = [
+            ((0.50, 10.0), (20.0, 10.0)),
+            ((0.29, 5.0), (17.2413, 5.0)),      // 0.29 * 100 = 28.999…: price stays 29¢
+            ((0.57, 3.0), (5.2631, 3.0)),       // same float trap at 57¢
+            ((0.58, 2.5), (4.3103, 2.5)),       // …and 58¢
+            ((0.333, 5.0), (15.1515, 5.0)),     // price floored to the 33¢ tick
+            ((0.07, 1.0), (14.2857, 1.0)),
+            ((0.99, 0.5), (1.0202, 1.01)),      // below $1 minimum: shares rounded up
+            ((0.50, 0.99), (2.0, 1.0)),
+            ((0.015, 2.0), (200.0, 2.0)),       // 1¢ floor tick
+            ((0.999, 100.0), (101.0101, 100.0)),
+            ((0.37, 7.77), (21.0, 7.77)),
+            ((0.62, 1.0), (1.6129, 1.0)),
+        ];
+        for ((price, amount), (shares, usdc)) in cases {
+            let (got_shares, got_usdc) = round_for_fok_buy(price, amount);
+            assert_close(got_shares, shares, &format!("shares for ${} @ {}", amount, price));
+            assert_close(got_usdc, usdc, &format!("usdc for ${} @ {}", amount, price));
+        }
+
+        // Invariants over a sweep of ticks and sizes
+        for cents in 1..100 {
+            let price = cents as f64 / 100.0;
+            for amount in [0.37, 1.0, 2.5, 9.99, 25.0, 123.45] {
+                let (shares, usdc) = round_for_fok_buy(price, amount);
+                assert!(has_decimals(usdc, 100.0), "usdc {} has > 2 decimals", usdc);
+                assert!(has_decimals(shares, 10000.0), "shares {} has > 4 decimals", shares);
+                assert!(usdc >= 1.0, "below $1 minimum at {} / ${}", price, amount);
+                assert!(shares * price <= usdc + 1e-9, "pays more than usdc at {} / ${}", price, amount);
+                assert!(usdc - shares * price < price * 1e-4 + 1e-9, "loses more than one share step");
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_for_fok_sell_golden() {
+        // (price, shares) -> (shares, usdc, usdc with 1¢ slippage)
+        let cases = [
+            ((0.50, 10.0), (10.0, 5.0, 4.9)),
+            ((0.29, 17.24), (17.24, 4.9996, 4.8272)),  // 29¢ stays 29¢
+            ((0.57, 3.019), (3.01, 1.7157, 1.6856)),   // shares floored to 2 decimals
+            ((0.58, 2.5), (2.5, 1.45, 1.425)),
+            ((0.66, 13.7777), (13.77, 9.0882, 8.9505)),
+            ((0.999, 1.0), (1.0, 0.99, 0.98)),
+            ((0.015, 12.345), (12.34, 0.1234, 0.1234)), // slippage price clamped at 1¢
+            ((0.01, 5.0), (5.0, 0.05, 0.05)),
+            ((0.73, 0.005), (0.0, 0.0, 0.0)),          // dust rounds to nothing
+        ];
+        for ((price, shares), (want_shares, usdc, min_usdc)) in cases {
+            let (got_shares, got_usdc, got_min) = round_for_fok_sell(price, shares);
+            assert_close(got_shares, want_shares, &format!("shares for {} @ {}", shares, price));
+            assert_close(got_usdc, usdc, &format!("usdc for {} @ {}", shares, price));
+            assert_close(got_min, min_usdc, &format!("min usdc for {} @ {}", shares, price));
+        }
+
+        for cents in 1..100 {
+            let price = cents as f64 / 100.0;
+            for shares in [0.01, 1.0, 3.333333, 17.24, 250.129] {
+                let (rounded, usdc, min_usdc) = round_for_fok_sell(price, shares);
+                assert!(has_decimals(rounded, 100.0), "shares {} has > 2 decimals", rounded);
+                assert!(rounded <= shares && shares - rounded < 0.01 + 1e-9);
+                assert!(has_decimals(usdc, 10000.0) && has_decimals(min_usdc, 10000.0));
+                assert!(min_usdc <= usdc);
+            }
+        }
+    }
+}
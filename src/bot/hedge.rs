@@ -0,0 +1,288 @@
+//! Hedged pairs: UP + DOWN held together as one unit
+//!
+//! Exactly one token pays $1 at expiry, so holding both locks the payout at
+//! min(up_shares, down_shares) whatever BTC does (same idea as
+//! `btc-arbitrage-backtest`). Three ways into a pair:
+//! - PAIR:     buy both legs when up_ask + down_ask < max_pair_cost
+//! - COMPLETE: an open position buys the opposite leg when entry + opposite ask < max_pair_cost
+//! - HEDGE:    a losing position (bid `hedge_trigger_pct` below entry) buys the
+//!   opposite leg when entry + opposite ask <= hedge_max_pair_cost, capping the loss
+//!
+//! Once paired, the legs leave `open_positions` so stop-loss and sell-edge exits
+//! no longer touch them; the pair settles as one unit when the window closes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::config::HedgeConfig;
+use super::polymarket::PriceQuote;
+
+/// Smallest pair worth placing (shares per leg)
+const MIN_PAIR_SHARES: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PairOrigin {
+    Pair,
+    Complete,
+    Hedge,
+}
+
+impl PairOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pair => "PAIR",
+            Self::Complete => "COMPLETE",
+            Self::Hedge => "HEDGE",
+        }
+    }
+}
+
+/// UP and DOWN legs of one window, tracked as a single position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HedgedPair {
+    pub pair_id: String,
+    pub origin: PairOrigin,
+    pub window_start: DateTime<Utc>,
+    pub up_shares: f64,
+    pub up_price: f64,
+    pub down_shares: f64,
+    pub down_price: f64,
+}
+
+impl HedgedPair {
+    /// USDC paid for both legs
+    pub fn cost(&self) -> f64 {
+        self.up_shares * self.up_price + self.down_shares * self.down_price
+    }
+
+    /// Payout whichever side wins
+    pub fn guaranteed_payout(&self) -> f64 {
+        self.up_shares.min(self.down_shares)
+    }
+
+    /// P&L locked in regardless of outcome (negative for a loss-capping hedge)
+    pub fn locked_pnl(&self) -> f64 {
+        self.guaranteed_payout() - self.cost()
+    }
+
+    /// Realized P&L at settlement ("UP" / "DOWN"; unknown outcome = locked P&L)
+    pub fn settle(&self, outcome: Option<&str>) -> f64 {
+        let payout = match outcome {
+            Some("UP") => self.up_shares,
+            Some("DOWN") => self.down_shares,
+            _ => self.guaranteed_payout(),
+        };
+        payout - self.cost()
+    }
+}
+
+/// Shares on the ask side of a book (`ask_liquidity` is USDC: sum of price * size)
+fn ask_shares(quote: &PriceQuote) -> f64 {
+    if quote.best_ask > 0.0 {
+        quote.ask_liquidity / quote.best_ask
+    } else {
+        0.0
+    }
+}
+
+/// Buy both legs now
+#[derive(Debug, Clone)]
+pub struct PairSignal {
+    pub up_price: f64,
+    pub down_price: f64,
+    pub shares: f64,
+    pub pair_cost: f64,
+    pub locked_pnl: f64,
+}
+
+/// Buy the opposite leg of an open position
+#[derive(Debug, Clone)]
+pub struct LegSignal {
+    pub origin: PairOrigin,
+    pub price: f64,
+    pub shares: f64,
+    pub pair_cost: f64,
+    pub locked_pnl: f64,
+    pub reason: String,
+}
+
+/// New pair when both asks together are cheap enough
+pub fn decide_new_pair(
+    config: &HedgeConfig,
+    seconds_remaining: u32,
+    pairs_this_window: u32,
+    up: &PriceQuote,
+    down: &PriceQuote,
+) -> Option<PairSignal> {
+    if !config.enabled
+        || pairs_this_window >= config.max_pairs_per_window
+        || seconds_remaining < config.min_seconds_remaining
+        || up.best_ask <= 0.0
+        || down.best_ask <= 0.0
+    {
+        return None;
+    }
+
+    let pair_cost = up.best_ask + down.best_ask;
+    if pair_cost >= config.max_pair_cost {
+        return None;
+    }
+
+    // Same share count on both legs, capped by the thinner book
+    let shares = (config.pair_amount_usdc / pair_cost)
+        .min(ask_shares(up))
+        .min(ask_shares(down));
+    if shares < MIN_PAIR_SHARES {
+        return None;
+    }
+
+    Some(PairSignal {
+        up_price: up.best_ask,
+        down_price: down.best_ask,
+        shares,
+        pair_cost,
+        locked_pnl: shares * (1.0 - pair_cost),
+    })
+}
+
+/// Complete or hedge an open position with the opposite token. Only fires when
+/// the opposite book can take the whole position, so no unpaired remainder is left.
+pub fn decide_second_leg(
+    config: &HedgeConfig,
+    seconds_remaining: u32,
+    entry_price: f64,
+    shares: f64,
+    current_bid: f64,
+    opposite: &PriceQuote,
+) -> Option<LegSignal> {
+    if !config.enabled
+        || seconds_remaining < config.min_seconds_remaining
+        || opposite.best_ask <= 0.0
+        || ask_shares(opposite) < shares
+    {
+        return None;
+    }
+
+    let pair_cost = entry_price + opposite.best_ask;
+    let locked_pnl = shares * (1.0 - pair_cost);
+
+    if config.complete_pairs && pair_cost < config.max_pair_cost {
+        return Some(LegSignal {
+            origin: PairOrigin::Complete,
+            price: opposite.best_ask,
+            shares,
+            pair_cost,
+            locked_pnl,
+            reason: format!(
+                "PAIR COMPLETE: entry {:.0}¢ + opposite ask {:.0}¢ < {:.0}¢",
+                entry_price * 100.0,
+                opposite.best_ask * 100.0,
+                config.max_pair_cost * 100.0
+            ),
+        });
+    }
+
+    let losing = current_bid > 0.0 && current_bid <= entry_price * (1.0 - config.hedge_trigger_pct);
+    if config.hedge_losing && losing && pair_cost <= config.hedge_max_pair_cost {
+        return Some(LegSignal {
+            origin: PairOrigin::Hedge,
+            price: opposite.best_ask,
+            shares,
+            pair_cost,
+            locked_pnl,
+            reason: format!(
+                "HEDGE: bid {:.0}¢ is {:.0}% below entry {:.0}¢",
+                current_bid * 100.0,
+                (1.0 - current_bid / entry_price) * 100.0,
+                entry_price * 100.0
+            ),
+        });
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `liquidity` is USDC on each side, as in the live quotes
+    fn quote(bid: f64, ask: f64, liquidity: f64) -> PriceQuote {
+        PriceQuote {
+            token_id: "t".to_string(),
+            best_bid: bid,
+            best_ask: ask,
+            mid_price: (bid + ask) / 2.0,
+            spread: ask - bid,
+            spread_pct: (ask - bid) / ask,
+            bid_liquidity: liquidity,
+            ask_liquidity: liquidity,
+        }
+    }
+
+    fn config() -> HedgeConfig {
+        HedgeConfig { enabled: true, ..Default::default() }
+    }
+
+    #[test]
+    fn test_new_pair() {
+        let config = config();
+
+        // 45¢ + 50¢ = 95¢ < 98¢ → $10 buys 10.53 shares of each, locks 5¢/share
+        let signal = decide_new_pair(&config, 600, 0, &quote(0.44, 0.45, 100.0), &quote(0.49, 0.50, 100.0)).unwrap();
+        assert!((signal.pair_cost - 0.95).abs() < 1e-9);
+        assert!((signal.shares - 10.0 / 0.95).abs() < 1e-9);
+        assert!((signal.locked_pnl - signal.shares * 0.05).abs() < 1e-9);
+
+        // Capped by the thinner book: $2 of asks at 50¢ is 4 shares
+        let thin = decide_new_pair(&config, 600, 0, &quote(0.44, 0.45, 100.0), &quote(0.49, 0.50, 2.0)).unwrap();
+        assert!((thin.shares - 4.0).abs() < 1e-9);
+
+        // Too expensive, too late, window cap, disabled
+        assert!(decide_new_pair(&config, 600, 0, &quote(0.48, 0.49, 100.0), &quote(0.49, 0.50, 100.0)).is_none());
+        assert!(decide_new_pair(&config, 30, 0, &quote(0.44, 0.45, 100.0), &quote(0.49, 0.50, 100.0)).is_none());
+        assert!(decide_new_pair(&config, 600, config.max_pairs_per_window, &quote(0.44, 0.45, 100.0), &quote(0.49, 0.50, 100.0)).is_none());
+        let off = HedgeConfig::default();
+        assert!(decide_new_pair(&off, 600, 0, &quote(0.44, 0.45, 100.0), &quote(0.49, 0.50, 100.0)).is_none());
+    }
+
+    #[test]
+    fn test_second_leg() {
+        let config = config();
+
+        // Bought UP at 40¢, DOWN ask fell to 55¢ → 95¢ pair locks profit
+        let leg = decide_second_leg(&config, 600, 0.40, 10.0, 0.44, &quote(0.54, 0.55, 50.0)).unwrap();
+        assert_eq!(leg.origin, PairOrigin::Complete);
+        assert!((leg.locked_pnl - 0.5).abs() < 1e-9);
+
+        // Bought UP at 40¢, bid now 25¢ (-37.5%), DOWN ask 63¢ → 103¢ caps the loss at 3¢/share
+        let leg = decide_second_leg(&config, 600, 0.40, 10.0, 0.25, &quote(0.62, 0.63, 50.0)).unwrap();
+        assert_eq!(leg.origin, PairOrigin::Hedge);
+        assert!((leg.locked_pnl + 0.3).abs() < 1e-9);
+
+        // Losing but the hedge costs too much; not losing enough; book too thin
+        assert!(decide_second_leg(&config, 600, 0.40, 10.0, 0.25, &quote(0.69, 0.70, 50.0)).is_none());
+        assert!(decide_second_leg(&config, 600, 0.40, 10.0, 0.35, &quote(0.62, 0.63, 50.0)).is_none());
+        assert!(decide_second_leg(&config, 600, 0.40, 10.0, 0.25, &quote(0.62, 0.63, 5.0)).is_none());
+    }
+
+    #[test]
+    fn test_pair_settlement() {
+        let pair = HedgedPair {
+            pair_id: "p".to_string(),
+            origin: PairOrigin::Pair,
+            window_start: Utc::now(),
+            up_shares: 10.0,
+            up_price: 0.45,
+            down_shares: 9.0,
+            down_price: 0.50,
+        };
+        assert!((pair.cost() - 9.0).abs() < 1e-9);
+        assert_eq!(pair.guaranteed_payout(), 9.0);
+        assert!((pair.locked_pnl() - 0.0).abs() < 1e-9);
+        assert!((pair.settle(Some("UP")) - 1.0).abs() < 1e-9);
+        assert!((pair.settle(Some("DOWN")) - 0.0).abs() < 1e-9);
+        assert!((pair.settle(None) - pair.locked_pnl()).abs() < 1e-9);
+    }
+}
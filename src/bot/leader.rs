@@ -0,0 +1,256 @@
+//! Leader election for multi-pod deployments
+//!
+//! Exactly one pod trades: the holder of the `btc_bot:leader` lease in Redis.
+//! The leader renews the lease every `renew_interval_ms`; if it dies or
+//! stalls the key expires after `lease_ms` and a standby takes over on its
+//! next attempt. Standbys run the full loop (feeds, matrices, MarketState)
+//! but never place orders.
+//!
+//! Every acquisition gets a new, larger fencing token. The executor checks the
+//! token right before each order, so an old leader that was paused past its
+//! lease (GC, network partition) cannot trade once a standby has taken over.
+
+use anyhow::{bail, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::alerts;
+use crate::config::LeaderElectionConfig;
+use crate::metrics;
+use crate::redis_state::{LeaderLease, RedisState};
+
+/// Part of the lease we trust locally; the rest absorbs clock drift between
+/// this pod and Redis
+const LOCAL_LEASE_FRACTION: f64 = 0.8;
+
+/// Lease held by this pod and how long we may act on it without renewing
+#[derive(Debug, Clone)]
+struct HeldLease {
+    lease: LeaderLease,
+    valid_until: Instant,
+}
+
+/// Shared view of this pod's leadership (cheap to clone)
+#[derive(Clone)]
+pub struct Leadership {
+    pod_id: String,
+    /// None = no Redis: single pod, always the leader
+    redis: Option<RedisState>,
+    lease_ms: u64,
+    held: Arc<Mutex<Option<HeldLease>>>,
+}
+
+impl Leadership {
+    /// Lease-based election through Redis
+    pub fn new(redis: RedisState, pod_id: String, lease_ms: u64) -> Self {
+        Self {
+            pod_id,
+            redis: Some(redis),
+            lease_ms,
+            held: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// No coordination (Redis unavailable or election disabled): always the leader
+    pub fn standalone(pod_id: String) -> Self {
+        Self {
+            pod_id,
+            redis: None,
+            lease_ms: 0,
+            held: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Pod identity: BOT_POD_ID, else HOSTNAME (the pod name on Kubernetes)
+    pub fn pod_id_from_env() -> String {
+        std::env::var("BOT_POD_ID")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| format!("pod-{:08x}", rand::random::<u32>()))
+    }
+
+    pub fn pod_id(&self) -> &str {
+        &self.pod_id
+    }
+
+    fn valid_lease(&self) -> Option<LeaderLease> {
+        let held = self.held.lock().unwrap();
+        held.as_ref()
+            .filter(|h| Instant::now() < h.valid_until)
+            .map(|h| h.lease.clone())
+    }
+
+    /// Whether this pod may trade right now
+    pub fn is_leader(&self) -> bool {
+        self.redis.is_none() || self.valid_lease().is_some()
+    }
+
+    /// Fencing token of the lease we hold
+    pub fn token(&self) -> Option<u64> {
+        self.valid_lease().map(|l| l.token)
+    }
+
+    /// Check right before an order that we still hold the newest lease
+    ///
+    /// Fails if our local lease ran out or Redis shows another holder. If
+    /// Redis cannot be reached the local lease decides: no standby can take
+    /// the key before it expires.
+    pub async fn check_fence(&self) -> Result<u64> {
+        let Some(redis) = &self.redis else {
+            return Ok(0);
+        };
+        let Some(lease) = self.valid_lease() else {
+            bail!("Not the leader ({}) - order blocked", self.pod_id);
+        };
+
+        match redis.current_leader().await {
+            Ok(current) if current.as_ref() == Some(&lease) => Ok(lease.token),
+            Ok(current) => {
+                self.lose(&lease);
+                bail!(
+                    "Fencing token {} superseded (leader now {}) - order blocked",
+                    lease.token,
+                    current.map_or("none".to_string(), |c| format!("{} #{}", c.pod_id, c.token))
+                );
+            }
+            Err(e) => {
+                alerts::redis_error(&e);
+                warn!("Fence check failed ({}), trusting local lease #{}", e, lease.token);
+                Ok(lease.token)
+            }
+        }
+    }
+
+    /// Drop the local lease if it is still `lease`
+    fn lose(&self, lease: &LeaderLease) {
+        let mut held = self.held.lock().unwrap();
+        if held.as_ref().is_some_and(|h| &h.lease == lease) {
+            *held = None;
+            metrics::IS_LEADER.set(0);
+            warn!("Lost leadership (token #{}) - now STANDBY", lease.token);
+        }
+    }
+
+    /// One election round: renew the lease we hold, or try to take a free one.
+    /// Returns whether we are the leader afterwards.
+    pub async fn step(&self) -> Result<bool> {
+        let Some(redis) = &self.redis else {
+            return Ok(true);
+        };
+
+        let started = Instant::now();
+        let local_ttl = Duration::from_millis((self.lease_ms as f64 * LOCAL_LEASE_FRACTION) as u64);
+        let current = self.held.lock().unwrap().as_ref().map(|h| h.lease.clone());
+
+        let lease = match current {
+            Some(lease) => {
+                if redis.renew_leadership(&lease, self.lease_ms).await? {
+                    Some(lease)
+                } else {
+                    self.lose(&lease);
+                    None
+                }
+            }
+            None => redis.try_acquire_leadership(&self.pod_id, self.lease_ms).await?,
+        };
+
+        let Some(lease) = lease else {
+            return Ok(false);
+        };
+
+        let mut held = self.held.lock().unwrap();
+        if held.is_none() {
+            info!("Became LEADER: pod {} with fencing token #{}", self.pod_id, lease.token);
+            metrics::IS_LEADER.set(1);
+        }
+        *held = Some(HeldLease { lease, valid_until: started + local_ttl });
+        Ok(true)
+    }
+
+    /// Release our lease (on shutdown) so a standby takes over immediately
+    pub async fn release(&self) {
+        let Some(redis) = &self.redis else {
+            return;
+        };
+        let lease = self.held.lock().unwrap().take().map(|h| h.lease);
+        if let Some(lease) = lease {
+            metrics::IS_LEADER.set(0);
+            match redis.release_leadership(&lease).await {
+                Ok(_) => info!("Released leadership (token #{})", lease.token),
+                Err(e) => warn!("Failed to release leadership: {} (expires in {}ms)", e, self.lease_ms),
+            }
+        }
+    }
+
+    /// Run election rounds every `renew_interval_ms` until `running` clears
+    pub fn spawn(&self, config: &LeaderElectionConfig, running: Arc<AtomicBool>) {
+        let leadership = self.clone();
+        let interval = Duration::from_millis(config.renew_interval_ms);
+        tokio::spawn(async move {
+            while running.load(Ordering::SeqCst) {
+                if let Err(e) = leadership.step().await {
+                    alerts::redis_error(&e);
+                    warn!("Leader election round failed: {}", e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Needs a local Redis: REDIS_TEST_URL=redis://127.0.0.1:6379 cargo test --bin btc-bot
+    #[tokio::test]
+    async fn test_leader_failover_and_fencing() {
+        let Ok(url) = std::env::var("REDIS_TEST_URL") else {
+            eprintln!("REDIS_TEST_URL not set, skipping");
+            return;
+        };
+        let redis = RedisState::connect_url(&url).await.unwrap();
+        if let Some(stale) = redis.current_leader().await.unwrap() {
+            redis.release_leadership(&stale).await.unwrap();
+        }
+
+        let a = Leadership::new(redis.clone(), "test-pod-a".to_string(), 500);
+        let b = Leadership::new(redis.clone(), "test-pod-b".to_string(), 500);
+
+        // A takes the lease, B stays standby
+        assert!(a.step().await.unwrap());
+        assert!(!b.step().await.unwrap());
+        let token_a = a.check_fence().await.unwrap();
+        assert!(b.check_fence().await.is_err());
+
+        // A renews while alive
+        assert!(a.step().await.unwrap());
+        assert_eq!(a.token(), Some(token_a));
+
+        // A stalls past its lease: B takes over with a larger token
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert!(!a.is_leader());
+        assert!(b.step().await.unwrap());
+        let token_b = b.check_fence().await.unwrap();
+        assert!(token_b > token_a);
+
+        // A wakes up: renewal fails and its orders are fenced off
+        assert!(!a.step().await.unwrap());
+        assert!(a.check_fence().await.is_err());
+
+        // Graceful shutdown hands over immediately
+        b.release().await;
+        assert!(a.step().await.unwrap());
+        assert!(a.token().unwrap() > token_b);
+        a.release().await;
+    }
+
+    #[tokio::test]
+    async fn test_standalone_is_always_leader() {
+        let solo = Leadership::standalone("solo".to_string());
+        assert!(solo.is_leader());
+        assert!(solo.step().await.unwrap());
+        assert_eq!(solo.check_fence().await.unwrap(), 0);
+    }
+}
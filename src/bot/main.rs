@@ -10,30 +10,52 @@ mod models;
 mod edge;
 #[path = "../stats.rs"]
 mod stats;
-#[path = "../chainlink.rs"]
-mod chainlink;
-
+#[path = "../http_client.rs"]
+mod http_client;
+#[path = "../metrics.rs"]
+mod metrics;
+#[path = "../stop_loss.rs"]
+mod stop_loss;
+#[path = "../strategies.rs"]
+#[allow(dead_code)] // Plugin API: not every field is read by the built-in strategies
+mod strategies;
+
+mod alerts;
+mod api;
 mod binance;
 mod config;
 mod db;
+mod decision_log;
 mod executor;
+mod hedge;
+mod leader;
 mod polymarket;
 mod redis_state;
+mod signer;
+mod state_store;
 mod strategy;
 mod websocket;
 
+use alerts::{AlertEvent, Alerter, FeedWatch, RejectTracker};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use config::BotConfig;
 use db::{ExecutionRecord, MarketOutcome, TradeAttempt, TradeDb, TradeRecord};
-use models::{FirstPassageMatrix, PriceCrossingMatrix, ProbabilityMatrix};
-use redis_state::{RedisState, RedisPosition};
+use decision_log::{DecisionLogger, DecisionRecord, QuoteSnapshot};
+use hedge::{HedgedPair, PairOrigin};
+use models::{
+    ConfidenceLevel, ExitPolicy, FirstPassageMatrix, MarketReachMatrix, MatrixSession, PriceCrossingMatrix,
+    ProbabilityMatrix,
+};
+use redis_state::RedisState;
+use state_store::{PersistedPosition, PersistedState, StateStore};
+use stop_loss::StopTracker;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use strategy::{BetDirection, StrategyContext};
+use strategy::{BetDecision, BetDirection, ReasonCode, StrategyContext};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use tracing_subscriber::EnvFilter;
@@ -55,6 +77,38 @@ struct Args {
     /// Direction for test order: "up" or "down" (default: "up")
     #[arg(long, default_value = "up")]
     test_direction: String,
+
+    /// Run as a signing daemon on this Unix socket instead of trading: holds
+    /// the key from the keystore / POLYMARKET_PRIVATE_KEY for pods that set
+    /// POLYMARKET_SIGNER_SOCKET
+    #[arg(long, value_name = "SOCKET")]
+    signer_daemon: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check a signed order offline: recompute its EIP-712 digest, recover
+    /// the signer and compare it with the order's maker/signer
+    VerifyOrder {
+        /// JSON file with a signed order or a full order request (the
+        /// "Request body" log line); "-" reads stdin
+        file: PathBuf,
+
+        /// Expected maker (funder) address
+        #[arg(long)]
+        maker: Option<String>,
+
+        /// Expected signer address
+        #[arg(long)]
+        signer: Option<String>,
+
+        /// Order was signed for the Neg Risk CTF Exchange (neg-risk market)
+        #[arg(long)]
+        neg_risk: bool,
+    },
 }
 
 /// An open position that we're tracking for potential exit
@@ -72,6 +126,7 @@ struct OpenPosition {
     sell_pending: bool,         // true if we tried to sell but FOK failed
     strategy_type: String,      // "TERMINAL" or "EXIT"
     entry_seconds_elapsed: u32, // seconds elapsed in window when position was opened
+    stop: StopTracker,          // stop-loss level / peak bid for this position
 }
 
 /// Generate a unique position ID (simple timestamp + random)
@@ -85,6 +140,131 @@ fn generate_position_id() -> String {
     format!("{:016x}-{:016x}", now as u64, random)
 }
 
+/// One leg of a hedged pair, bought at the ask
+struct PairLeg<'a> {
+    pair_id: &'a str,
+    market_slug: &'a str,
+    token_id: &'a str,
+    direction: BetDirection,
+    window_start: DateTime<Utc>,
+    seconds_elapsed: u32,
+    price: f64,
+    shares: f64,
+    quote: &'a polymarket::PriceQuote,
+}
+
+/// FOK buy of one pair leg, recorded in bot_executions. Returns (usdc, shares) filled.
+async fn buy_pair_leg(exec: &mut executor::Executor, trade_db: Option<&TradeDb>, leg: &PairLeg<'_>) -> Result<(f64, f64)> {
+    let amount = leg.shares * leg.price;
+    let execution = ExecutionRecord {
+        position_id: leg.pair_id.to_string(),
+        side: "BUY".to_string(),
+        market_slug: Some(leg.market_slug.to_string()),
+        token_id: leg.token_id.to_string(),
+        direction: format!("{:?}", leg.direction).to_uppercase(),
+        window_start: leg.window_start,
+        order_type: "FOK".to_string(),
+        requested_price: leg.price,
+        requested_amount: amount,
+        requested_shares: Some(leg.shares),
+        filled_price: None,
+        filled_amount: None,
+        filled_shares: None,
+        status: "PENDING".to_string(),
+        error_message: None,
+        order_id: None,
+        time_elapsed_s: Some(leg.seconds_elapsed as i32),
+        btc_price: None,
+        btc_delta: None,
+        edge_pct: None,
+        our_probability: None,
+        market_probability: Some(leg.quote.best_ask),
+        best_ask: Some(leg.quote.best_ask),
+        best_bid: Some(leg.quote.best_bid),
+        ask_liquidity: Some(leg.quote.ask_liquidity),
+        bid_liquidity: Some(leg.quote.bid_liquidity),
+        sell_edge_pct: None,
+        profit_pct: None,
+        entry_price: None,
+    };
+
+    let exec_id = match trade_db {
+        Some(db) => match db.insert_execution(&execution).await {
+            Ok(id) => Some(id),
+            Err(e) => {
+                alerts::db_error(&e);
+                warn!("Failed to insert pair leg execution record: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    let result = exec.fok_buy_with_liquidity(leg.token_id, leg.price, amount, leg.quote.ask_liquidity).await;
+    let (status, filled, order_id, error_msg) = match &result {
+        Ok((response, usdc, shares)) if response.success => ("FILLED", Some((*usdc, *shares)), response.order_id.clone(), None),
+        Ok((response, _, _)) => ("CANCELLED", None, response.order_id.clone(), response.error_msg.clone()),
+        Err(e) => ("FAILED", None, None, Some(e.to_string())),
+    };
+
+    if let (Some(id), Some(db)) = (exec_id, trade_db) {
+        if let Err(e) = db.update_execution_status(
+            id,
+            status,
+            filled.map(|_| leg.price),
+            filled.map(|(usdc, _)| usdc),
+            filled.map(|(_, shares)| shares),
+            order_id.as_deref(),
+            error_msg.as_deref(),
+        ).await {
+            alerts::db_error(&e);
+            warn!("Failed to update pair leg execution status: {}", e);
+        }
+    }
+
+    filled.ok_or_else(|| anyhow::anyhow!("{} leg {}: {}", status, format!("{:?}", leg.direction).to_uppercase(),
+        error_msg.unwrap_or_else(|| "unknown".to_string())))
+}
+
+fn side_quote(q: &polymarket::PriceQuote) -> strategies::SideQuote {
+    // Quote liquidity is USDC (sum of price * size); plugins see shares
+    let shares = |usdc: f64, price: f64| if price > 0.0 { usdc / price } else { 0.0 };
+    strategies::SideQuote {
+        bid: q.best_bid,
+        ask: q.best_ask,
+        bid_size: shares(q.bid_liquidity, q.best_bid),
+        ask_size: shares(q.ask_liquidity, q.best_ask),
+    }
+}
+
+/// BetDecision for a plugin strategy's intent (keeps the core decision's trace for the log)
+fn intent_decision(intent: strategies::Intent, confidence: ConfidenceLevel, core: BetDecision) -> BetDecision {
+    BetDecision {
+        should_bet: true,
+        direction: Some(match intent.side {
+            strategies::Side::Up => BetDirection::Up,
+            strategies::Side::Down => BetDirection::Down,
+        }),
+        edge: intent.edge,
+        our_probability: intent.probability,
+        market_probability: intent.price,
+        bet_amount: intent.amount_usdc,
+        confidence,
+        reason: intent.reason,
+        exit_target: intent.exit_target,
+        strategy_type: intent.strategy,
+        reason_code: ReasonCode::Bet,
+        trace: core.trace,
+    }
+}
+
+fn fill_side(direction: BetDirection) -> strategies::Side {
+    match direction {
+        BetDirection::Up => strategies::Side::Up,
+        BetDirection::Down => strategies::Side::Down,
+    }
+}
+
 /// Load probability matrix from local file
 fn load_matrix_from_file() -> Result<ProbabilityMatrix> {
     let matrix_path = std::env::var("MATRIX_PATH")
@@ -107,6 +287,35 @@ fn load_matrix_from_file() -> Result<ProbabilityMatrix> {
     Ok(matrix)
 }
 
+/// Session matrices from the DB, each with sparse cells backed by the pooled matrix
+async fn load_session_matrices(
+    pooled: &ProbabilityMatrix,
+    min_samples: f64,
+) -> Result<Vec<(MatrixSession, ProbabilityMatrix)>> {
+    let url = std::env::var("DATABASE_URL").context("Session matrices are stored in the DB (DATABASE_URL not set)")?;
+    let sessions = db::load_session_matrices_from_db(&url).await?;
+    Ok(sessions
+        .into_iter()
+        .map(|(session, matrix)| {
+            let merged = matrix.with_fallback(pooled, min_samples);
+            (session, merged)
+        })
+        .collect())
+}
+
+/// Matrix for the session covering `time`, else the pooled matrix
+fn select_matrix<'a>(
+    pooled: &'a ProbabilityMatrix,
+    sessions: &'a [(MatrixSession, ProbabilityMatrix)],
+    time: DateTime<Utc>,
+) -> (&'a str, &'a ProbabilityMatrix) {
+    sessions
+        .iter()
+        .find(|(session, _)| session.matches(time))
+        .map(|(session, matrix)| (session.name.as_str(), matrix))
+        .unwrap_or(("all", pooled))
+}
+
 /// Load first-passage matrix from local file
 fn load_first_passage_matrix() -> Result<FirstPassageMatrix> {
     let fp_path = std::env::var("FIRST_PASSAGE_MATRIX_PATH")
@@ -151,6 +360,50 @@ fn load_crossing_matrix() -> Result<PriceCrossingMatrix> {
     Ok(crossing_matrix)
 }
 
+/// Load market reach matrix (observed Polymarket prices) from local file
+fn load_market_reach_matrix() -> Result<MarketReachMatrix> {
+    let mr_path = std::env::var("MARKET_REACH_MATRIX_PATH")
+        .unwrap_or_else(|_| "output/market_reach_matrix.json".to_string());
+    let mr_path = PathBuf::from(&mr_path);
+
+    if !mr_path.exists() {
+        anyhow::bail!(
+            "Market reach matrix not found: {}. Run 'cargo run -- build' first.",
+            mr_path.display()
+        );
+    }
+
+    info!("Loading market reach matrix from: {}", mr_path.display());
+    let mr_json = std::fs::read_to_string(&mr_path)
+        .context("Failed to read market reach matrix file")?;
+    let market_reach_matrix: MarketReachMatrix = serde_json::from_str(&mr_json)
+        .context("Failed to parse market reach matrix JSON")?;
+
+    Ok(market_reach_matrix)
+}
+
+/// Load optimal stopping exit policy from local file
+fn load_exit_policy() -> Result<ExitPolicy> {
+    let ep_path = std::env::var("EXIT_POLICY_PATH")
+        .unwrap_or_else(|_| "output/exit_policy.json".to_string());
+    let ep_path = PathBuf::from(&ep_path);
+
+    if !ep_path.exists() {
+        anyhow::bail!(
+            "Exit policy not found: {}. Run 'cargo run -- build' first.",
+            ep_path.display()
+        );
+    }
+
+    info!("Loading exit policy from: {}", ep_path.display());
+    let ep_json = std::fs::read_to_string(&ep_path)
+        .context("Failed to read exit policy file")?;
+    let exit_policy: ExitPolicy = serde_json::from_str(&ep_json)
+        .context("Failed to parse exit policy JSON")?;
+
+    Ok(exit_policy)
+}
+
 /// Bot state tracking
 struct BotState {
     bankroll: f64,
@@ -159,7 +412,11 @@ struct BotState {
     terminal_bets_this_window: u32,  // Bets from terminal strategy
     exit_bets_this_window: u32,      // Bets from exit strategy
     daily_pnl: f64,
+    trading_day: Option<chrono::NaiveDate>,  // UTC day daily_pnl belongs to
     open_positions: Vec<OpenPosition>,  // Track actual positions
+    hedged_pairs: Vec<HedgedPair>,      // UP + DOWN held together (settle as one unit)
+    pairs_this_window: u32,             // New pairs opened this window
+    strategy_fills: Vec<strategies::Fill>,  // Plugin fills this window (persisted for the registry)
     current_window_start: Option<DateTime<Utc>>,
     last_terminal_bet_time: Option<DateTime<Utc>>,  // Separate cooldown for terminal
     last_exit_bet_time: Option<DateTime<Utc>>,      // Separate cooldown for exit
@@ -177,7 +434,11 @@ impl BotState {
             terminal_bets_this_window: 0,
             exit_bets_this_window: 0,
             daily_pnl: 0.0,
+            trading_day: None,
             open_positions: Vec::new(),
+            hedged_pairs: Vec::new(),
+            pairs_this_window: 0,
+            strategy_fills: Vec::new(),
             current_window_start: None,
             last_terminal_bet_time: None,
             last_exit_bet_time: None,
@@ -210,6 +471,38 @@ impl BotState {
         self.open_positions.iter().filter(|p| p.direction == direction).count() as u32
     }
 
+    /// Snapshot for the HTTP status API
+    fn to_status(&self, dry_run: bool) -> api::BotStatus {
+        api::BotStatus {
+            updated_at: Some(Utc::now()),
+            dry_run,
+            bankroll: self.bankroll,
+            daily_pnl: self.daily_pnl,
+            consecutive_losses: self.consecutive_losses,
+            consecutive_wins: self.consecutive_wins,
+            window_start: self.current_window_start,
+            terminal_bets_this_window: self.terminal_bets_this_window,
+            exit_bets_this_window: self.exit_bets_this_window,
+            bet_pending: self.bet_pending,
+            has_pending_sells: self.has_pending_sells(),
+            open_positions: self.open_positions.iter().map(|p| api::PositionStatus {
+                position_id: p.position_id.clone(),
+                direction: p.direction,
+                strategy_type: p.strategy_type.clone(),
+                entry_price: p.entry_price,
+                shares: p.shares,
+                exit_target: p.exit_target,
+                entry_seconds_elapsed: p.entry_seconds_elapsed,
+                window_start: p.window_start,
+                sell_pending: p.sell_pending,
+            }).collect(),
+            hedged_pairs: self.hedged_pairs.iter().map(api::PairStatus::from).collect(),
+            locked_pnl: self.locked_pnl(),
+            leader: false,
+            last_decision: None,
+        }
+    }
+
     fn has_pending_sells(&self) -> bool {
         self.open_positions.iter().any(|p| p.sell_pending)
     }
@@ -242,6 +535,96 @@ impl BotState {
         self.open_positions.push(position);
     }
 
+    /// Risk state to persist (see state_store.rs)
+    fn to_persisted(&self) -> PersistedState {
+        PersistedState {
+            trading_day: self.trading_day,
+            bankroll: self.bankroll,
+            daily_pnl: self.daily_pnl,
+            consecutive_losses: self.consecutive_losses,
+            consecutive_wins: self.consecutive_wins,
+            window_start: self.current_window_start,
+            terminal_bets_this_window: self.terminal_bets_this_window,
+            exit_bets_this_window: self.exit_bets_this_window,
+            pairs_this_window: self.pairs_this_window,
+            last_terminal_bet_time: self.last_terminal_bet_time,
+            last_exit_bet_time: self.last_exit_bet_time,
+            open_positions: self.open_positions.iter().map(|p| PersistedPosition {
+                position_id: p.position_id.clone(),
+                token_id: p.token_id.clone(),
+                direction: format!("{:?}", p.direction),
+                entry_price: p.entry_price,
+                shares: p.shares,
+                entry_time_bucket: p.entry_time_bucket,
+                entry_delta_bucket: p.entry_delta_bucket,
+                exit_target: p.exit_target,
+                window_start_ts: p.window_start.timestamp(),
+                sell_pending: p.sell_pending,
+                strategy_type: p.strategy_type.clone(),
+                entry_seconds_elapsed: p.entry_seconds_elapsed,
+            }).collect(),
+            hedged_pairs: self.hedged_pairs.clone(),
+            strategy_fills: self.strategy_fills.clone(),
+        }
+    }
+
+    /// Replace the risk state with a persisted one. Positions from an older
+    /// window stay until on_new_window settles them; stop trackers restart
+    /// from the entry price.
+    fn restore(&mut self, persisted: &PersistedState, stop_config: &stop_loss::StopLossConfig) {
+        self.trading_day = persisted.trading_day;
+        self.bankroll = persisted.bankroll;
+        self.daily_pnl = persisted.daily_pnl;
+        self.consecutive_losses = persisted.consecutive_losses;
+        self.consecutive_wins = persisted.consecutive_wins;
+        self.current_window_start = persisted.window_start;
+        self.terminal_bets_this_window = persisted.terminal_bets_this_window;
+        self.exit_bets_this_window = persisted.exit_bets_this_window;
+        self.pairs_this_window = persisted.pairs_this_window;
+        self.last_terminal_bet_time = persisted.last_terminal_bet_time;
+        self.last_exit_bet_time = persisted.last_exit_bet_time;
+        self.open_positions = persisted.open_positions.iter().map(|p| OpenPosition {
+            position_id: p.position_id.clone(),
+            token_id: p.token_id.clone(),
+            direction: if p.direction == "Down" { BetDirection::Down } else { BetDirection::Up },
+            entry_price: p.entry_price,
+            shares: p.shares,
+            entry_time_bucket: p.entry_time_bucket,
+            entry_delta_bucket: p.entry_delta_bucket,
+            exit_target: p.exit_target,
+            window_start: DateTime::from_timestamp(p.window_start_ts, 0).unwrap_or_default(),
+            sell_pending: p.sell_pending,
+            strategy_type: p.strategy_type.clone(),
+            entry_seconds_elapsed: p.entry_seconds_elapsed,
+            stop: StopTracker::new(stop_config, p.entry_price),
+        }).collect();
+        self.hedged_pairs = persisted.hedged_pairs.clone();
+        self.strategy_fills = persisted.strategy_fills.clone();
+    }
+
+    /// Record a fill with the strategy registry and keep it for the snapshot
+    fn on_strategy_fill(&mut self, registry: &mut strategies::StrategyRegistry, fill: strategies::Fill) {
+        registry.on_fill(&fill);
+        if registry.contains(&fill.strategy) {
+            self.strategy_fills.push(fill);
+        }
+    }
+
+    fn add_pair(&mut self, pair: HedgedPair) {
+        info!("  [{}] Pair {} locked: UP {:.2} @ {:.0}¢ + DOWN {:.2} @ {:.0}¢ | cost=${:.2}, payout=${:.2}, locked P&L=${:+.2}",
+            pair.origin.as_str(), &pair.pair_id[..8],
+            pair.up_shares, pair.up_price * 100.0,
+            pair.down_shares, pair.down_price * 100.0,
+            pair.cost(), pair.guaranteed_payout(), pair.locked_pnl());
+        self.hedged_pairs.push(pair);
+        info!("  Locked P&L this window: ${:+.2} ({} pair(s))", self.locked_pnl(), self.hedged_pairs.len());
+    }
+
+    /// P&L guaranteed by the hedged pairs currently held
+    fn locked_pnl(&self) -> f64 {
+        self.hedged_pairs.iter().map(|p| p.locked_pnl()).sum()
+    }
+
     fn remove_position(&mut self, index: usize) -> Option<OpenPosition> {
         if index < self.open_positions.len() {
             Some(self.open_positions.remove(index))
@@ -253,6 +636,7 @@ impl BotState {
     fn clear_positions_for_window(&mut self, window_start: DateTime<Utc>) {
         // Remove positions from previous windows (they settled)
         self.open_positions.retain(|p| p.window_start == window_start);
+        self.hedged_pairs.retain(|p| p.window_start == window_start);
     }
 
     fn should_log(&mut self, cooldown_seconds: u32) -> bool {
@@ -278,13 +662,16 @@ impl BotState {
         })
     }
 
-    fn on_new_window(&mut self, window_start: DateTime<Utc>, outcome: Option<&str>) {
+    /// Returns (positions settled, settlement P&L) when a window with open positions closes
+    fn on_new_window(&mut self, window_start: DateTime<Utc>, outcome: Option<&str>) -> Option<(u32, f64)> {
+        let mut settlement = None;
         if self.current_window_start != Some(window_start) {
             // Log settlement for each unsold position BEFORE clearing
-            if !self.open_positions.is_empty() {
+            if !self.open_positions.is_empty() || !self.hedged_pairs.is_empty() {
                 let outcome_str = outcome.unwrap_or("UNKNOWN");
                 info!("╔══════════════════════════════════════════════════════════════╗");
-                info!("║  WINDOW SETTLEMENT - {} unsold position(s)                    ", self.open_positions.len());
+                info!("║  WINDOW SETTLEMENT - {} unsold position(s), {} pair(s)        ",
+                    self.open_positions.len(), self.hedged_pairs.len());
                 info!("╚══════════════════════════════════════════════════════════════╝");
                 info!("  Market outcome: {}", outcome_str);
 
@@ -323,6 +710,25 @@ impl BotState {
                     );
                 }
 
+                for pair in &self.hedged_pairs {
+                    let cost = pair.cost();
+                    let profit = pair.settle(outcome);
+                    total_cost += cost;
+                    total_payout += cost + profit;
+                    info!(
+                        "  ⇄ PAIR [{}] UP {:.2} @ {:.0}¢ + DOWN {:.2} @ {:.0}¢ | cost=${:.2}, payout=${:.2}, P&L=${:+.2} (locked ${:+.2})",
+                        pair.origin.as_str(),
+                        pair.up_shares,
+                        pair.up_price * 100.0,
+                        pair.down_shares,
+                        pair.down_price * 100.0,
+                        cost,
+                        cost + profit,
+                        profit,
+                        pair.locked_pnl()
+                    );
+                }
+
                 let total_profit = total_payout - total_cost;
                 info!("  ─────────────────────────────────────────────────────────────");
                 info!("  SETTLEMENT TOTAL: cost=${:.2}, payout=${:.2}, P&L=${:+.2}",
@@ -331,6 +737,17 @@ impl BotState {
                 // Update state
                 self.daily_pnl += total_profit;
                 self.bankroll += total_profit;
+                settlement = Some(((self.open_positions.len() + self.hedged_pairs.len()) as u32, total_profit));
+            }
+
+            // Daily P&L (and the daily loss limit) restarts each UTC day
+            let day = window_start.date_naive();
+            if self.trading_day != Some(day) {
+                if self.trading_day.is_some() {
+                    info!("═══ New trading day {} (yesterday's P&L ${:+.2}) ═══", day, self.daily_pnl);
+                }
+                self.trading_day = Some(day);
+                self.daily_pnl = 0.0;
             }
 
             info!("═══ New 15-minute window: {} ═══", window_start.format("%H:%M:%S UTC"));
@@ -339,18 +756,25 @@ impl BotState {
             self.current_window_start = Some(window_start);
             self.terminal_bets_this_window = 0;
             self.exit_bets_this_window = 0;
+            self.pairs_this_window = 0;
+            self.strategy_fills.clear();
             self.market_fetch_logged = false;
         }
+        settlement
     }
 
     fn on_bet_placed(&mut self, strategy_type: &str) {
         let now = Utc::now();
-        if strategy_type == "TERMINAL" {
-            self.terminal_bets_this_window += 1;
-            self.last_terminal_bet_time = Some(now);
-        } else {
-            self.exit_bets_this_window += 1;
-            self.last_exit_bet_time = Some(now);
+        match strategy_type {
+            "TERMINAL" => {
+                self.terminal_bets_this_window += 1;
+                self.last_terminal_bet_time = Some(now);
+            }
+            "EXIT" => {
+                self.exit_bets_this_window += 1;
+                self.last_exit_bet_time = Some(now);
+            }
+            _ => {} // Plugin strategies: counted by StrategyRegistry
         }
     }
 
@@ -394,6 +818,119 @@ impl BotState {
     }
 }
 
+/// Load the persisted risk state into `state` (Redis, else Postgres) and
+/// replay this window's plugin fills into `registry`.
+/// Returns false when there is nothing to resume from.
+async fn restore_state(
+    state: &mut BotState,
+    registry: &mut strategies::StrategyRegistry,
+    store: &mut StateStore,
+    db: Option<&TradeDb>,
+    stop_config: &stop_loss::StopLossConfig,
+) -> bool {
+    match store.load(db).await {
+        Ok(Some(persisted)) => {
+            state.restore(&persisted, stop_config);
+            registry.restore_window(&state.strategy_fills);
+            info!(
+                "Resumed state v{}: bankroll ${:.2}, daily P&L ${:+.2}, W{}/L{} streak, {} position(s), {} pair(s)",
+                store.version(), state.bankroll, state.daily_pnl, state.consecutive_wins,
+                state.consecutive_losses, state.open_positions.len(), state.hedged_pairs.len()
+            );
+            true
+        }
+        Ok(None) => {
+            // Pods from before the snapshot kept positions and bet counts under
+            // separate keys: carry the current window's over once
+            let window_start = binance::get_current_window_start();
+            match store.load_legacy(&state.to_persisted(), window_start).await {
+                Ok(Some(migrated)) => {
+                    state.restore(&migrated, stop_config);
+                    info!(
+                        "Migrated legacy Redis state: {} position(s), {} terminal / {} exit bet(s) this window",
+                        state.open_positions.len(), state.terminal_bets_this_window, state.exit_bets_this_window
+                    );
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to read legacy Redis state: {}", e),
+            }
+            false
+        }
+        Err(e) => {
+            warn!("Failed to load persisted state: {}", e);
+            false
+        }
+    }
+}
+
+/// Save the risk state right after a fill, exit or settlement rather than at
+/// the top of the next tick, so a crash in between cannot lose a position.
+/// A rejected save (newer snapshot in Redis) is reloaded on the next tick.
+async fn save_state_now(store: Option<&mut StateStore>, state: &BotState, db: Option<&TradeDb>) {
+    if let Some(store) = store {
+        if let Err(e) = store.save(state.to_persisted(), db).await {
+            warn!("Failed to save state: {}", e);
+        }
+    }
+}
+
+/// Order submission retry policy from config (no retries unless retry_on_failure)
+fn retry_policy(config: &config::ExecutionConfig) -> executor::RetryPolicy {
+    executor::RetryPolicy {
+        max_retries: if config.retry_on_failure { config.max_retries } else { 0 },
+        base_backoff: Duration::from_millis(config.retry_backoff_ms),
+        max_backoff: Duration::from_millis(config.retry_max_backoff_ms),
+        window_margin: Duration::from_secs(config.retry_window_margin_secs),
+    }
+}
+
+/// Point the executor at a new market: its window end bounds order retries,
+/// and its tick size, fee rate and neg-risk flag are fetched before the first
+/// order (failures only warn; the order path fetches them again)
+async fn prepare_market(exec: Option<&mut executor::Executor>, market: &polymarket::Btc15mMarket) {
+    let Some(exec) = exec else { return };
+    exec.set_window_end(market.window_end.into());
+    for token_id in [&market.up_token_id, &market.down_token_id] {
+        if let Err(e) = exec.market_params(token_id).await {
+            warn!("Failed to fetch market params for {}: {:#}", market.slug, e);
+        }
+    }
+}
+
+/// Verify a signed order offline (no network, no key needed)
+fn run_verify_order(file: &std::path::Path, maker: Option<&str>, signer: Option<&str>, neg_risk: bool) -> Result<()> {
+    let json = if file == std::path::Path::new("-") {
+        std::io::read_to_string(std::io::stdin()).context("Failed to read stdin")?
+    } else {
+        std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?
+    };
+    let mut value: serde_json::Value = serde_json::from_str(&json).context("Invalid order JSON")?;
+    if let Some(order) = value.get_mut("order") {
+        value = order.take();
+    }
+    let signed: executor::SignedOrder = serde_json::from_value(value).context("Not a signed order")?;
+
+    let expected_maker = maker.map(executor::parse_address).transpose()?;
+    let expected_signer = signer.map(executor::parse_address).transpose()?;
+    let result = executor::verify_signed_order(&signed, neg_risk, expected_maker, expected_signer)?;
+
+    info!("Order digest:   0x{}", hex::encode(result.digest));
+    info!("Signature type: {:?}", result.signature_type);
+    info!("Exchange:       {}", if neg_risk { "Neg Risk CTF Exchange" } else { "CTF Exchange" });
+    info!("Maker:          {}", signed.maker);
+    info!("Signer:         {}", signed.signer);
+    info!("Recovered:      0x{}", hex::encode(result.recovered));
+    if result.is_valid() {
+        info!("✓ Order signature valid");
+        Ok(())
+    } else {
+        for problem in &result.problems {
+            error!("✗ {}", problem);
+        }
+        Err(anyhow::anyhow!("Order verification failed ({} problem(s))", result.problems.len()))
+    }
+}
+
 /// Run a single test order to verify signing and order execution
 async fn run_test_order(amount: f64, direction: &str) -> Result<()> {
     info!("═══════════════════════════════════════════════════════════════");
@@ -413,11 +950,11 @@ async fn run_test_order(amount: f64, direction: &str) -> Result<()> {
     };
 
     // Initialize executor
-    let private_key = std::env::var("POLYMARKET_PRIVATE_KEY")
-        .context("POLYMARKET_PRIVATE_KEY not set - required for test order")?;
+    let signer = signer::from_env()?
+        .context("No signer configured (POLYMARKET_SIGNER_SOCKET, POLYMARKET_KEYSTORE_PATH or POLYMARKET_PRIVATE_KEY) - required for test order")?;
 
     info!("Initializing order executor...");
-    let mut exec = executor::Executor::new(&private_key, None).await
+    let mut exec = executor::Executor::new(signer, None).await
         .context("Failed to initialize executor")?;
     info!("Executor ready: {}", exec.wallet_address());
 
@@ -515,6 +1052,17 @@ async fn main() -> Result<()> {
     info!("╚══════════════════════════════════════════════════════════════╝");
     eprintln!("[btc-bot] Banner printed");
 
+    if let Some(Command::VerifyOrder { file, maker, signer, neg_risk }) = &args.command {
+        return run_verify_order(file, maker.as_deref(), signer.as_deref(), *neg_risk);
+    }
+
+    // Signing daemon mode (sidecar holding the key)
+    if let Some(ref socket) = args.signer_daemon {
+        let signer = signer::from_env()?
+            .context("Signer daemon needs POLYMARKET_KEYSTORE_PATH or POLYMARKET_PRIVATE_KEY")?;
+        return signer::serve(signer, socket).await;
+    }
+
     // Handle test order mode
     if args.test_order {
         return run_test_order(args.test_amount, &args.test_direction).await;
@@ -549,6 +1097,22 @@ async fn main() -> Result<()> {
     info!("  Kelly fraction: {:.0}%", config.betting.kelly_fraction * 100.0);
     info!("  Max bet: ${:.2} or {:.0}% of bankroll", config.betting.max_bet_usdc, config.betting.max_bet_pct * 100.0);
 
+    // Webhook alerts (started before DB/Redis so connection failures are reported)
+    let alerter = Alerter::spawn(&config.alerts);
+    alerter.install_storage_alerts();
+    let mut feed_watch = FeedWatch::default();
+    let mut reject_tracker = RejectTracker::default();
+    let mut daily_limit_alerted = false;
+
+    // Per-tick decision log (JSONL)
+    let decision_log = match DecisionLogger::spawn(&config.decision_log) {
+        Ok(log) => log,
+        Err(e) => {
+            warn!("Decision log disabled: {}", e);
+            None
+        }
+    };
+
     // Connect to database for trade tracking (uses hardcoded Qovery credentials)
     eprintln!("[btc-bot] Connecting to database...");
     info!("Connecting to trade database...");
@@ -558,12 +1122,14 @@ async fn main() -> Result<()> {
             info!("Trade database connected successfully");
             // Run migrations
             if let Err(e) = db.run_migrations().await {
+                alerts::db_error(&e);
                 warn!("Failed to run migrations: {}", e);
             }
             Some(db)
         }
         Err(e) => {
             eprintln!("[btc-bot] Database connection failed: {}", e);
+            alerts::db_error(&e);
             warn!("Failed to connect to database: {}", e);
             warn!("Running without trade tracking");
             None
@@ -580,6 +1146,7 @@ async fn main() -> Result<()> {
         }
         Err(e) => {
             eprintln!("[btc-bot] Redis connection failed: {}", e);
+            alerts::redis_error(&e);
             warn!("Failed to connect to Redis: {}", e);
             warn!("Running without multi-pod coordination - ensure only 1 pod!");
             None
@@ -632,6 +1199,24 @@ async fn main() -> Result<()> {
     };
     info!("Matrix ready: {} windows analyzed", matrix.total_windows);
 
+    // Session-conditioned matrices (pooled matrix outside any session)
+    let session_matrices: Vec<(MatrixSession, ProbabilityMatrix)> = if config.session_matrices.enabled {
+        match load_session_matrices(&matrix, config.session_matrices.min_samples).await {
+            Ok(sessions) => {
+                let names: Vec<&str> = sessions.iter().map(|(s, _)| s.name.as_str()).collect();
+                info!("Session matrices loaded: {}", if names.is_empty() { "none".to_string() } else { names.join(", ") });
+                sessions
+            }
+            Err(e) => {
+                warn!("Failed to load session matrices, using pooled matrix: {}", e);
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    let mut last_matrix_session = String::new();
+
     // Load first-passage matrix for exit strategy (legacy)
     let fp_matrix: Option<FirstPassageMatrix> = if config.exit_strategy.enabled {
         match load_first_passage_matrix() {
@@ -666,6 +1251,38 @@ async fn main() -> Result<()> {
         None
     };
 
+    // Load market reach matrix (observed token prices - preferred for exit targets)
+    let market_reach_matrix: Option<MarketReachMatrix> = if config.exit_strategy.enabled {
+        match load_market_reach_matrix() {
+            Ok(mr) => {
+                info!("Market reach matrix loaded: {} token paths (OBSERVED PRICE TARGETS)", mr.total_paths);
+                Some(mr)
+            }
+            Err(e) => {
+                warn!("Failed to load market reach matrix: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Load optimal stopping exit policy (consulted before the exit matrices)
+    let exit_policy: Option<ExitPolicy> = if config.exit_strategy.enabled && config.exit_strategy.use_exit_policy {
+        match load_exit_policy() {
+            Ok(ep) => {
+                info!("Exit policy loaded: {} windows (OPTIMAL STOPPING EXITS)", ep.total_windows);
+                Some(ep)
+            }
+            Err(e) => {
+                warn!("Failed to load exit policy: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // Get initial bankroll from environment
     let initial_bankroll: f64 = std::env::var("BOT_BANKROLL")
         .unwrap_or_else(|_| "1000".to_string())
@@ -678,12 +1295,13 @@ async fn main() -> Result<()> {
     let binance = binance::BinanceClient::new(config.polling.request_timeout_ms)?;
 
     // Initialize order executor (if credentials are available)
-    let mut order_executor = match std::env::var("POLYMARKET_PRIVATE_KEY") {
-        Ok(private_key) => {
+    let mut order_executor = match signer::from_env() {
+        Ok(Some(signer)) => {
             info!("Initializing order executor...");
-            match executor::Executor::new(&private_key, None).await {
-                Ok(exec) => {
+            match executor::Executor::new(signer, None).await {
+                Ok(mut exec) => {
                     info!("Order executor ready: {}", exec.wallet_address());
+                    exec.set_retry_policy(retry_policy(&config.execution));
                     Some(exec)
                 }
                 Err(e) => {
@@ -693,8 +1311,13 @@ async fn main() -> Result<()> {
                 }
             }
         }
-        Err(_) => {
-            warn!("POLYMARKET_PRIVATE_KEY not set - running in DRY-RUN mode");
+        Ok(None) => {
+            warn!("No signer configured (POLYMARKET_SIGNER_SOCKET / POLYMARKET_KEYSTORE_PATH / POLYMARKET_PRIVATE_KEY) - running in DRY-RUN mode");
+            None
+        }
+        Err(e) => {
+            warn!("Failed to load signer: {}", e);
+            warn!("Running in DRY-RUN mode (no orders will be placed)");
             None
         }
     };
@@ -714,10 +1337,19 @@ async fn main() -> Result<()> {
             None
         }
     };
+    if let Some(ref m) = current_market {
+        prepare_market(order_executor.as_mut(), m).await;
+    }
 
     // Initialize bot state
     let mut state = BotState::new(initial_bankroll);
 
+    // Plugin strategies from the `strategies:` config list
+    let mut registry = strategies::StrategyRegistry::from_config(&config.strategies)?;
+    if !registry.is_empty() {
+        info!("Strategy plugins: {}", registry.names().join(", "));
+    }
+
     // Set up graceful shutdown
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
@@ -728,9 +1360,76 @@ async fn main() -> Result<()> {
         r.store(false, Ordering::SeqCst);
     });
 
+    // Leader election: only the lease holder places orders, standbys stay warm
+    let pod_id = leader::Leadership::pod_id_from_env();
+    let leadership = match redis_state {
+        Some(ref rs) if config.leader_election.enabled => {
+            info!(
+                "Leader election: pod {} (lease {}ms, renew every {}ms)",
+                pod_id, config.leader_election.lease_ms, config.leader_election.renew_interval_ms
+            );
+            let leadership = leader::Leadership::new(rs.clone(), pod_id, config.leader_election.lease_ms);
+            leadership.spawn(&config.leader_election, running.clone());
+            leadership
+        }
+        _ => {
+            info!("Leader election off: pod {} always trades", pod_id);
+            metrics::IS_LEADER.set(1);
+            leader::Leadership::standalone(pod_id)
+        }
+    };
+    if let Some(ref mut exec) = order_executor {
+        exec.set_fence(leadership.clone());
+    }
+    let mut last_leader_token: Option<u64> = None;
+
+    // Persisted risk state: resume where the last pod left off. Dry runs
+    // neither load nor save it (simulated fills must not touch live state).
+    let mut state_store = order_executor
+        .is_some()
+        .then(|| StateStore::new(redis_state.clone(), leadership.pod_id().to_string()));
+    match state_store {
+        Some(ref mut store) => {
+            if !restore_state(&mut state, &mut registry, store, trade_db.as_ref(), &config.stop_loss).await {
+                info!("No persisted state - starting fresh with ${:.2}", state.bankroll);
+                // Still honour today's settled losses when the snapshots are gone
+                if let Some(ref db) = trade_db {
+                    let today = Utc::now().date_naive();
+                    if let Ok(days) = db.get_daily_pnl().await {
+                        if let Some(d) = days.iter().find(|d| d.trade_date == today) {
+                            state.daily_pnl = d.net_pnl;
+                            state.trading_day = Some(today);
+                            info!("  Daily P&L from today's settled trades: ${:+.2}", d.net_pnl);
+                        }
+                    }
+                }
+            }
+        }
+        None => info!("Dry run: risk state not persisted"),
+    }
+
     // Initialize shared market state for WebSocket data
     let market_state = Arc::new(RwLock::new(MarketState::default()));
 
+    // Start HTTP server: Prometheus /metrics + read-only status API
+    let bot_status = Arc::new(RwLock::new(state.to_status(order_executor.is_none())));
+    let live_matrix = Arc::new(RwLock::new(Arc::new(matrix.clone())));
+    if config.http_server.enabled {
+        let port = config.http_server.port;
+        let api_state = api::ApiState {
+            status: bot_status.clone(),
+            market: market_state.clone(),
+            matrix: live_matrix.clone(),
+            fp_matrix: fp_matrix.clone().map(Arc::new),
+        };
+        tokio::spawn(async move {
+            let router = metrics::router().merge(api::router(api_state));
+            if let Err(e) = metrics::serve(port, router).await {
+                error!("HTTP server failed: {}", e);
+            }
+        });
+    }
+
     // Set initial market tokens if we have them
     if let Some(ref market) = current_market {
         let mut ms = market_state.write().await;
@@ -777,9 +1476,64 @@ async fn main() -> Result<()> {
         let seconds_elapsed = binance::get_seconds_elapsed();
         let seconds_remaining = binance::get_seconds_remaining();
 
+        // Matrix for the current session (pooled when none matches)
+        let (matrix_session, matrix) = select_matrix(&matrix, &session_matrices, window_start);
+        if !session_matrices.is_empty() && matrix_session != last_matrix_session {
+            info!("Probability matrix: {} session", matrix_session);
+            last_matrix_session = matrix_session.to_string();
+            *live_matrix.write().await = Arc::new(matrix.clone());
+        }
+
+        // Persisted state: a new leader resumes from the newest snapshot before
+        // settling or trading; the leader saves whatever changed last tick
+        if let Some(ref mut store) = state_store {
+            if leadership.is_leader() && leadership.token() != last_leader_token {
+                last_leader_token = leadership.token();
+                if last_leader_token.is_some() {
+                    info!("Leader takeover: loading persisted state");
+                    restore_state(&mut state, &mut registry, store, trade_db.as_ref(), &config.stop_loss).await;
+                }
+            } else if leadership.is_leader() {
+                if let Err(e) = store.save(state.to_persisted(), trade_db.as_ref()).await {
+                    warn!("{} - reloading persisted state", e);
+                    restore_state(&mut state, &mut registry, store, trade_db.as_ref(), &config.stop_loss).await;
+                }
+            }
+        }
+
         // Check for new window (pass last outcome for settlement logging)
-        state.on_new_window(window_start, last_window_outcome.as_deref());
+        let settled_window = state.current_window_start;
+        let settled_pairs = if settled_window != Some(window_start) { state.hedged_pairs.clone() } else { Vec::new() };
+        if let Some((positions, pnl)) = state.on_new_window(window_start, last_window_outcome.as_deref()) {
+            if let Some(ref db) = trade_db {
+                for pair in &settled_pairs {
+                    if let Err(e) = db.insert_pair_settlement(pair, last_window_outcome.as_deref()).await {
+                        warn!("Failed to record pair settlement: {}", e);
+                    }
+                }
+            }
+            alerter.send(AlertEvent::Settlement {
+                window_start: settled_window.unwrap_or(window_start),
+                outcome: last_window_outcome.clone().unwrap_or_else(|| "UNKNOWN".to_string()),
+                positions,
+                pnl,
+            });
+            if leadership.is_leader() {
+                save_state_now(state_store.as_mut(), &state, trade_db.as_ref()).await;
+            }
+        }
         last_window_outcome = None; // Clear after use
+        if settled_window.is_some_and(|w| w != window_start) {
+            registry.on_window_end();
+        }
+
+        // Alert once when the daily loss limit is crossed (re-armed if P&L recovers)
+        let daily_loss_limit = state.bankroll * config.risk.daily_loss_limit_pct;
+        let limit_hit = state.daily_pnl < 0.0 && state.daily_pnl.abs() >= daily_loss_limit;
+        if limit_hit && !daily_limit_alerted {
+            alerter.send(AlertEvent::DailyLossLimit { daily_pnl: state.daily_pnl, limit: daily_loss_limit });
+        }
+        daily_limit_alerted = limit_hit;
 
         // Reset open price on new window (REST API - only once per window)
         if state.current_window_start == Some(window_start) && window_open_price.is_none() {
@@ -801,10 +1555,14 @@ async fn main() -> Result<()> {
         }
 
         // Get current BTC price from WebSocket (real-time!)
-        let btc_price = {
+        let (btc_price, btc_age, book_age) = {
             let ms = market_state.read().await;
-            ms.btc_price
+            let now = Utc::now();
+            let age = |t: Option<DateTime<Utc>>| t.map(|t| (now - t).num_milliseconds() as f64 / 1000.0);
+            (ms.btc_price, age(ms.btc_price_time), age(ms.book_time))
         };
+        feed_watch.check(&alerter, "binance", btc_age, config.alerts.feed_down_secs);
+        feed_watch.check(&alerter, "polymarket", book_age, config.alerts.feed_down_secs);
 
         if btc_price == 0.0 {
             debug!("Waiting for WebSocket BTC price...");
@@ -857,6 +1615,7 @@ async fn main() -> Result<()> {
                             ms.down_best_bid = 0.0;
                             info!("WebSocket state initialized with market tokens");
                         }
+                        prepare_market(order_executor.as_mut(), &m).await;
                         current_market = Some(m.clone());
                         m
                     }
@@ -928,6 +1687,427 @@ async fn main() -> Result<()> {
             rust_decimal::Decimal::try_from(price_delta).unwrap_or_default()
         );
 
+        // ═══════════════════════════════════════════════════════════════
+        // LEADER ELECTION
+        // Standbys stop here each tick: feeds, matrices and MarketState stay
+        // warm but only the leader trades. On takeover the new leader resumes
+        // from the persisted state (top of the loop).
+        // ═══════════════════════════════════════════════════════════════
+        if !leadership.is_leader() {
+            last_leader_token = None;
+            if state.should_log(config.cooldown.log_cooldown_seconds) {
+                info!(
+                    "[STANDBY] pod {} | BTC ${:.0} | Δ${:+.0} | t={}s | UP {:.0}¢ DOWN {:.0}¢",
+                    leadership.pod_id(), btc_price, price_delta, seconds_elapsed,
+                    up_quote.best_ask * 100.0, down_quote.best_ask * 100.0
+                );
+            }
+            *bot_status.write().await = state.to_status(order_executor.is_none());
+
+            // Window rollover: refetch market and open price at the top of the loop
+            if binance::get_current_window_start() != window_start {
+                window_open_price = None;
+                current_market = None;
+            }
+            tokio::time::sleep(poll_interval).await;
+            continue;
+        }
+        // ═══════════════════════════════════════════════════════════════
+        // HEDGED PAIRS
+        // Complete / hedge open positions with the opposite token, then buy
+        // both legs when the asks sum below max_pair_cost. Paired legs leave
+        // open_positions and settle together (see hedge.rs).
+        // ═══════════════════════════════════════════════════════════════
+        if config.hedge.enabled && !state.has_pending_sells() && !state.is_bet_pending() {
+            let mut leg_actions: Vec<(usize, hedge::LegSignal)> = Vec::new();
+            for (idx, position) in state.open_positions.iter().enumerate() {
+                if position.window_start != window_start {
+                    continue;
+                }
+                let (current_bid, opposite) = match position.direction {
+                    BetDirection::Up => (up_quote.best_bid, &down_quote),
+                    BetDirection::Down => (down_quote.best_bid, &up_quote),
+                };
+                if let Some(leg) = hedge::decide_second_leg(
+                    &config.hedge,
+                    seconds_remaining,
+                    position.entry_price,
+                    position.shares,
+                    current_bid,
+                    opposite,
+                ) {
+                    leg_actions.push((idx, leg));
+                }
+            }
+
+            // Back to front so removing a paired position keeps earlier indices valid
+            for (idx, leg) in leg_actions.into_iter().rev() {
+                let position = state.open_positions[idx].clone();
+                let (opposite_direction, opposite_token, opposite_quote) = match position.direction {
+                    BetDirection::Up => (BetDirection::Down, &market.down_token_id, &down_quote),
+                    BetDirection::Down => (BetDirection::Up, &market.up_token_id, &up_quote),
+                };
+                info!("{} → {:.2} {:?} shares at {:.0}¢, pair cost {:.0}¢ locks ${:+.2} [{}]",
+                    leg.reason, leg.shares, opposite_direction, leg.price * 100.0,
+                    leg.pair_cost * 100.0, leg.locked_pnl, &position.position_id[..8]);
+
+                let filled = match order_executor {
+                    Some(ref mut exec) => {
+                        let order = PairLeg {
+                            pair_id: &position.position_id,
+                            market_slug: &market.slug,
+                            token_id: opposite_token,
+                            direction: opposite_direction,
+                            window_start,
+                            seconds_elapsed,
+                            price: leg.price,
+                            shares: leg.shares,
+                            quote: opposite_quote,
+                        };
+                        buy_pair_leg(exec, trade_db.as_ref(), &order).await
+                    }
+                    None => {
+                        info!("[DRY-RUN] Would buy {:.2} {:?} shares at {:.0}¢",
+                            leg.shares, opposite_direction, leg.price * 100.0);
+                        Ok((leg.shares * leg.price, leg.shares))
+                    }
+                };
+
+                let (usdc, shares) = match filled {
+                    Ok(f) => f,
+                    Err(e) => {
+                        warn!("✗ {} leg not filled: {}", leg.origin.as_str(), e);
+                        reject_tracker.on_reject(&alerter, "BUY", &e.to_string(), config.alerts.reject_alert_threshold);
+                        continue;
+                    }
+                };
+                reject_tracker.on_fill();
+                alerter.send(AlertEvent::Fill {
+                    direction: format!("{:?}", opposite_direction).to_uppercase(),
+                    strategy: leg.origin.as_str().to_string(),
+                    price: leg.price,
+                    amount_usdc: usdc,
+                    shares,
+                });
+
+                let (up_shares, up_price, down_shares, down_price) = match position.direction {
+                    BetDirection::Up => (position.shares, position.entry_price, shares, leg.price),
+                    BetDirection::Down => (shares, leg.price, position.shares, position.entry_price),
+                };
+                state.remove_position(idx);
+                state.add_pair(HedgedPair {
+                    pair_id: position.position_id.clone(),
+                    origin: leg.origin,
+                    window_start,
+                    up_shares,
+                    up_price,
+                    down_shares,
+                    down_price,
+                });
+            }
+
+            // A new pair can end up as a lone UP leg, so it passes the same
+            // entry gates as plugin intents (second legs above only pair off
+            // exposure already held)
+            let pair_blocked = limit_hit.then_some(ReasonCode::DailyLossLimit).or_else(|| {
+                [BetDirection::Up, BetDirection::Down]
+                    .into_iter()
+                    .any(|dir| state.position_count_by_direction(dir) >= config.risk.max_open_positions)
+                    .then_some(ReasonCode::MaxPositions)
+            });
+            let signal = hedge::decide_new_pair(
+                &config.hedge,
+                seconds_remaining,
+                state.pairs_this_window,
+                &up_quote,
+                &down_quote,
+            )
+            .filter(|signal| {
+                if let Some(code) = pair_blocked {
+                    debug!("PAIR signal at {:.0}¢ blocked: {:?}", signal.pair_cost * 100.0, code);
+                    return false;
+                }
+                let cost = signal.shares * signal.pair_cost;
+                if cost > state.bankroll {
+                    debug!("PAIR signal blocked: ${:.2} exceeds bankroll ${:.2}", cost, state.bankroll);
+                    return false;
+                }
+                true
+            });
+            if let Some(signal) = signal {
+                let pair_id = generate_position_id();
+                info!("PAIR SIGNAL: UP {:.0}¢ + DOWN {:.0}¢ = {:.0}¢ → {:.2} shares each, locks ${:+.2} [{}]",
+                    signal.up_price * 100.0, signal.down_price * 100.0, signal.pair_cost * 100.0,
+                    signal.shares, signal.locked_pnl, &pair_id[..8]);
+
+                let legs = match order_executor {
+                    Some(ref mut exec) => {
+                        let up_leg = PairLeg {
+                            pair_id: &pair_id,
+                            market_slug: &market.slug,
+                            token_id: &market.up_token_id,
+                            direction: BetDirection::Up,
+                            window_start,
+                            seconds_elapsed,
+                            price: signal.up_price,
+                            shares: signal.shares,
+                            quote: &up_quote,
+                        };
+                        let down_leg = PairLeg {
+                            direction: BetDirection::Down,
+                            token_id: &market.down_token_id,
+                            price: signal.down_price,
+                            quote: &down_quote,
+                            ..up_leg
+                        };
+                        match buy_pair_leg(exec, trade_db.as_ref(), &up_leg).await {
+                            Ok(up) => (Ok(up), buy_pair_leg(exec, trade_db.as_ref(), &down_leg).await),
+                            Err(e) => (Err(e), Err(anyhow::anyhow!("UP leg not filled"))),
+                        }
+                    }
+                    None => {
+                        info!("[DRY-RUN] Would buy {:.2} UP + {:.2} DOWN shares", signal.shares, signal.shares);
+                        (
+                            Ok((signal.shares * signal.up_price, signal.shares)),
+                            Ok((signal.shares * signal.down_price, signal.shares)),
+                        )
+                    }
+                };
+
+                match legs {
+                    (Ok((_, up_shares)), Ok((_, down_shares))) => {
+                        reject_tracker.on_fill();
+                        state.pairs_this_window += 1;
+                        alerter.send(AlertEvent::Fill {
+                            direction: "UP+DOWN".to_string(),
+                            strategy: PairOrigin::Pair.as_str().to_string(),
+                            price: signal.pair_cost,
+                            amount_usdc: up_shares * signal.up_price + down_shares * signal.down_price,
+                            shares: up_shares.min(down_shares),
+                        });
+                        state.add_pair(HedgedPair {
+                            pair_id,
+                            origin: PairOrigin::Pair,
+                            window_start,
+                            up_shares,
+                            up_price: signal.up_price,
+                            down_shares,
+                            down_price: signal.down_price,
+                        });
+                    }
+                    (Ok((usdc, up_shares)), Err(e)) => {
+                        // Keep the UP leg as a normal position; pair completion retries DOWN
+                        warn!("✗ PAIR: DOWN leg not filled ({}), holding UP leg alone", e);
+                        reject_tracker.on_reject(&alerter, "BUY", &e.to_string(), config.alerts.reject_alert_threshold);
+                        state.pairs_this_window += 1;
+                        alerter.send(AlertEvent::Fill {
+                            direction: "UP".to_string(),
+                            strategy: PairOrigin::Pair.as_str().to_string(),
+                            price: signal.up_price,
+                            amount_usdc: usdc,
+                            shares: up_shares,
+                        });
+                        state.add_position(OpenPosition {
+                            position_id: pair_id,
+                            token_id: market.up_token_id.clone(),
+                            direction: BetDirection::Up,
+                            entry_price: signal.up_price,
+                            shares: up_shares,
+                            entry_time_bucket: time_bucket,
+                            entry_delta_bucket: delta_bucket,
+                            exit_target: 1.0,
+                            window_start,
+                            sell_pending: false,
+                            strategy_type: PairOrigin::Pair.as_str().to_string(),
+                            entry_seconds_elapsed: seconds_elapsed,
+                            stop: StopTracker::new(&config.stop_loss, signal.up_price),
+                        });
+                    }
+                    (Err(e), _) => {
+                        warn!("✗ PAIR: UP leg not filled: {}", e);
+                        reject_tracker.on_reject(&alerter, "BUY", &e.to_string(), config.alerts.reject_alert_threshold);
+                    }
+                }
+            }
+        }
+
+        save_state_now(state_store.as_mut(), &state, trade_db.as_ref()).await;
+
+        // ═══════════════════════════════════════════════════════════════
+        // CHECK STOP-LOSS FOR OPEN POSITIONS
+        // Every position tracks its own stop (fixed / percent / trailing / model).
+        // Sells are capped at the visible bid size; any remainder stays open
+        // and is retried next tick.
+        // ═══════════════════════════════════════════════════════════════
+        if !state.open_positions.is_empty() {
+            let cell = matrix.get(time_bucket, delta_bucket);
+            let our_p_up = cell.p_up_wilson_lower;
+            let our_p_down = 1.0 - cell.p_up_wilson_upper;
+
+            let mut stop_actions: Vec<(usize, OpenPosition, f64, stop_loss::StopTrigger)> = Vec::new();
+            for (idx, position) in state.open_positions.iter_mut().enumerate() {
+                if position.window_start != window_start {
+                    continue;
+                }
+                let (current_bid, our_prob) = match position.direction {
+                    BetDirection::Up => (up_quote.best_bid, our_p_up),
+                    BetDirection::Down => (down_quote.best_bid, our_p_down),
+                };
+                if let Some(trigger) = position.stop.check(current_bid, our_prob) {
+                    stop_actions.push((idx, position.clone(), current_bid, trigger));
+                }
+            }
+
+            let mut indices_to_remove: Vec<usize> = Vec::new();
+            for (idx, position, current_bid, trigger) in stop_actions {
+                let bid_liquidity = match position.direction {
+                    BetDirection::Up => up_quote.bid_liquidity,
+                    BetDirection::Down => down_quote.bid_liquidity,
+                };
+                // Quote liquidity is USDC (sum of price * size); the sell caps on shares
+                let bid_shares = if current_bid > 0.0 { bid_liquidity / current_bid } else { 0.0 };
+                let profit_pct = (current_bid - position.entry_price) / position.entry_price;
+
+                warn!("═══════════════════════════════════════════════════════════════");
+                warn!("{} - SELLING POSITION!", trigger.reason);
+                warn!("  Direction:    {:?} ({})", position.direction, position.strategy_type);
+                warn!("  Stop:         {} at {:.2}¢", trigger.mode.as_str(), trigger.level * 100.0);
+                warn!("  Entry price:  {:.2}¢", position.entry_price * 100.0);
+                warn!("  Exit price:   {:.2}¢ (bid, {:.2} shares available)", current_bid * 100.0, bid_shares);
+                warn!("  Shares:       {:.2}", position.shares);
+                warn!("  P&L:          {:+.1}%", profit_pct * 100.0);
+                warn!("═══════════════════════════════════════════════════════════════");
+
+                let Some(ref mut exec) = order_executor else {
+                    let shares = stop_loss::fillable_shares(position.shares, Some(bid_shares));
+                    info!("[DRY-RUN] {} | sell {:.2}/{:.2} shares at {:.2}¢",
+                        trigger.reason, shares, position.shares, current_bid * 100.0);
+                    state.on_position_sold((current_bid - position.entry_price) * shares);
+                    if shares + 0.01 < position.shares {
+                        state.open_positions[idx].shares -= shares;
+                    } else {
+                        indices_to_remove.push(idx);
+                    }
+                    continue;
+                };
+
+                let sell_execution = ExecutionRecord {
+                    position_id: position.position_id.clone(),
+                    side: "SELL".to_string(),
+                    market_slug: Some(market.slug.clone()),
+                    token_id: position.token_id.clone(),
+                    direction: format!("{:?}", position.direction).to_uppercase(),
+                    window_start: position.window_start,
+                    order_type: "FOK".to_string(),
+                    requested_price: current_bid,
+                    requested_amount: position.shares,
+                    requested_shares: Some(position.shares),
+                    filled_price: None,
+                    filled_amount: None,
+                    filled_shares: None,
+                    status: "PENDING".to_string(),
+                    error_message: None,
+                    order_id: None,
+                    time_elapsed_s: Some(seconds_elapsed as i32),
+                    btc_price: Some(btc_price),
+                    btc_delta: Some(price_delta),
+                    edge_pct: None,
+                    our_probability: None,
+                    market_probability: Some(current_bid),
+                    best_ask: match position.direction {
+                        BetDirection::Up => Some(up_quote.best_ask),
+                        BetDirection::Down => Some(down_quote.best_ask),
+                    },
+                    best_bid: Some(current_bid),
+                    ask_liquidity: match position.direction {
+                        BetDirection::Up => Some(up_quote.ask_liquidity),
+                        BetDirection::Down => Some(down_quote.ask_liquidity),
+                    },
+                    bid_liquidity: Some(bid_liquidity),
+                    sell_edge_pct: None,
+                    profit_pct: Some(profit_pct),
+                    entry_price: Some(position.entry_price),
+                };
+
+                let sell_exec_id = if let Some(ref db) = trade_db {
+                    match db.insert_execution(&sell_execution).await {
+                        Ok(id) => Some(id),
+                        Err(e) => {
+                            alerts::db_error(&e);
+                            warn!("Failed to insert stop-loss execution record: {}", e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                let result = exec.fok_sell_with_liquidity(
+                    &position.token_id,
+                    current_bid,
+                    position.shares,
+                    bid_shares,
+                ).await;
+
+                let (status, filled, order_id, error_msg) = match &result {
+                    Ok((response, actual_shares, _)) if response.success => {
+                        ("FILLED", Some(*actual_shares), response.order_id.clone(), None)
+                    }
+                    Ok((response, _, _)) => ("CANCELLED", None, response.order_id.clone(), response.error_msg.clone()),
+                    Err(e) => ("FAILED", None, None, Some(e.to_string())),
+                };
+
+                if let (Some(id), Some(ref db)) = (sell_exec_id, &trade_db) {
+                    if let Err(e) = db.update_execution_status(
+                        id,
+                        status,
+                        filled.map(|_| current_bid),
+                        filled,
+                        filled,
+                        order_id.as_deref(),
+                        error_msg.as_deref(),
+                    ).await {
+                        alerts::db_error(&e);
+                        warn!("Failed to update stop-loss execution status: {}", e);
+                    }
+                }
+
+                match filled {
+                    Some(actual_shares) => {
+                        info!("✓ Stop-loss FOK FILLED! ID: {:?} | {:.4} shares", order_id, actual_shares);
+                        let profit = (current_bid - position.entry_price) * actual_shares;
+                        state.on_position_sold(profit);
+                        reject_tracker.on_fill();
+                        alerter.send(AlertEvent::Exit {
+                            direction: format!("{:?}", position.direction).to_uppercase(),
+                            price: current_bid,
+                            shares: actual_shares,
+                            profit,
+                        });
+                        if actual_shares + 0.01 < position.shares {
+                            // Partial: liquidity-capped, keep the rest for the next tick
+                            state.open_positions[idx].shares -= actual_shares;
+                            state.mark_sell_pending(idx);
+                        } else {
+                            indices_to_remove.push(idx);
+                        }
+                    }
+                    None => {
+                        let error_msg = error_msg.unwrap_or_else(|| "unknown".to_string());
+                        warn!("Stop-loss sell not filled: {} - will retry next cycle", error_msg);
+                        reject_tracker.on_reject(&alerter, "SELL", &error_msg, config.alerts.reject_alert_threshold);
+                        state.mark_sell_pending(idx);
+                    }
+                }
+            }
+
+            indices_to_remove.sort();
+            for idx in indices_to_remove.into_iter().rev() {
+                state.remove_position(idx);
+            }
+            save_state_now(state_store.as_mut(), &state, trade_db.as_ref()).await;
+        }
+
         // ═══════════════════════════════════════════════════════════════
         // CHECK SELL EDGE FOR TERMINAL POSITIONS
         // Sell when (bid - our_probability) / bid >= min_sell_edge
@@ -1070,6 +2250,7 @@ async fn main() -> Result<()> {
                         match db.insert_execution(&sell_execution).await {
                             Ok(id) => Some(id),
                             Err(e) => {
+                                alerts::db_error(&e);
                                 warn!("Failed to insert sell execution record: {}", e);
                                 None
                             }
@@ -1100,15 +2281,29 @@ async fn main() -> Result<()> {
                                         response.order_id.as_deref(),
                                         None,
                                     ).await {
+                                        alerts::db_error(&e);
                                         warn!("Failed to update sell execution status: {}", e);
                                     }
                                 }
 
                                 let profit = (current_bid - position.entry_price) * actual_shares;
                                 state.on_position_sold(profit);
+                                reject_tracker.on_fill();
+                                alerter.send(AlertEvent::Exit {
+                                    direction: format!("{:?}", position.direction).to_uppercase(),
+                                    price: current_bid,
+                                    shares: actual_shares,
+                                    profit,
+                                });
                                 indices_to_remove.push(action.idx);
                             } else {
                                 warn!("Sell edge FOK rejected: {:?} - will retry next cycle", response.error_msg);
+                                reject_tracker.on_reject(
+                                    &alerter,
+                                    "SELL",
+                                    response.error_msg.as_deref().unwrap_or("unknown"),
+                                    config.alerts.reject_alert_threshold,
+                                );
 
                                 // Update execution record with rejection
                                 if let (Some(id), Some(ref db)) = (sell_exec_id, &trade_db) {
@@ -1119,6 +2314,7 @@ async fn main() -> Result<()> {
                                         response.order_id.as_deref(),
                                         response.error_msg.as_deref(),
                                     ).await {
+                                        alerts::db_error(&e);
                                         warn!("Failed to update sell execution status: {}", e);
                                     }
                                 }
@@ -1128,6 +2324,7 @@ async fn main() -> Result<()> {
                         }
                         Err(e) => {
                             error!("Sell edge execution error: {} - will retry next cycle", e);
+                            reject_tracker.on_reject(&alerter, "SELL", &e.to_string(), config.alerts.reject_alert_threshold);
 
                             // Update execution record with error
                             if let (Some(id), Some(ref db)) = (sell_exec_id, &trade_db) {
@@ -1137,6 +2334,7 @@ async fn main() -> Result<()> {
                                     None, None, None, None,
                                     Some(&e.to_string()),
                                 ).await {
+                                    alerts::db_error(&e2);
                                     warn!("Failed to update sell execution status: {}", e2);
                                 }
                             }
@@ -1161,13 +2359,26 @@ async fn main() -> Result<()> {
             for idx in indices_to_remove.into_iter().rev() {
                 state.remove_position(idx);
             }
+            save_state_now(state_store.as_mut(), &state, trade_db.as_ref()).await;
         }
 
         // ═══════════════════════════════════════════════════════════════
         // CHECK EXIT CONDITIONS FOR OPEN POSITIONS (EXIT STRATEGY)
-        // Use crossing matrix (dynamic targeting) if available, else fp_matrix
+        // Use the exit policy (optimal stopping) where it has data, else market
+        // reach matrix (observed prices), else crossing matrix (dynamic
+        // targeting), else fp_matrix. Plugin positions with their own target
+        // sell at that target instead.
         // ═══════════════════════════════════════════════════════════════
-        if crossing_matrix.is_some() || fp_matrix.is_some() {
+        let has_fixed_targets = state
+            .open_positions
+            .iter()
+            .any(|p| p.exit_target < 1.0 && registry.contains(&p.strategy_type));
+        if exit_policy.is_some()
+            || market_reach_matrix.is_some()
+            || crossing_matrix.is_some()
+            || fp_matrix.is_some()
+            || has_fixed_targets
+        {
             // First, collect all sell actions (to avoid borrow checker issues)
             struct SellAction {
                 idx: usize,
@@ -1188,13 +2399,59 @@ async fn main() -> Result<()> {
                     BetDirection::Down => down_quote.best_bid,
                 };
 
-                // Check if exit target is hit - prefer crossing matrix for dynamic targeting
-                let exit_decision = if let Some(ref cm) = crossing_matrix {
+                let current_ask = match position.direction {
+                    BetDirection::Up => up_quote.best_ask,
+                    BetDirection::Down => down_quote.best_ask,
+                };
+
+                if registry.contains(&position.strategy_type) && position.exit_target < 1.0 {
+                    let fixed = strategy::decide_exit_fixed(position.entry_price, current_bid, position.exit_target);
+                    if fixed.should_exit {
+                        info!("{} ({})", fixed.reason, position.strategy_type);
+                        sell_actions.push(SellAction {
+                            idx,
+                            position: position.clone(),
+                            current_bid,
+                        });
+                    }
+                    continue;
+                }
+
+                let policy_decision = exit_policy.as_ref().and_then(|ep| {
+                    strategy::decide_exit_policy(
+                        &config,
+                        ep,
+                        time_bucket,
+                        delta_bucket,
+                        position.direction,
+                        position.entry_price,
+                        current_bid,
+                    )
+                });
+
+                // Check if exit target is hit - prefer observed market prices, then crossing matrix
+                let exit_decision = if let Some(decision) = policy_decision {
+                    decision
+                } else if let Some(ref mr) = market_reach_matrix {
+                    strategy::decide_exit_market(
+                        &config,
+                        mr,
+                        matrix,
+                        &strategy::ExitTick {
+                            time_bucket,
+                            delta_bucket,
+                            direction: position.direction,
+                            entry_price: position.entry_price,
+                            current_bid,
+                            current_ask,
+                        },
+                    )
+                } else if let Some(ref cm) = crossing_matrix {
                     // Use crossing-based dynamic exit (recalculates target each tick)
                     strategy::decide_exit_crossing(
                         &config,
                         cm,
-                        &matrix,
+                        matrix,
                         time_bucket,
                         delta_bucket,
                         position.direction,
@@ -1207,7 +2464,7 @@ async fn main() -> Result<()> {
                     strategy::decide_exit(
                         &config,
                         fp,
-                        &matrix,
+                        matrix,
                         time_bucket,
                         delta_bucket,
                         position.direction,
@@ -1312,6 +2569,7 @@ async fn main() -> Result<()> {
                         match db.insert_execution(&sell_execution).await {
                             Ok(id) => Some(id),
                             Err(e) => {
+                                alerts::db_error(&e);
                                 warn!("Failed to insert sell execution record: {}", e);
                                 None
                             }
@@ -1342,15 +2600,29 @@ async fn main() -> Result<()> {
                                         response.order_id.as_deref(),
                                         None,
                                     ).await {
+                                        alerts::db_error(&e);
                                         warn!("Failed to update sell execution status: {}", e);
                                     }
                                 }
 
                                 let profit = (current_bid - position.entry_price) * actual_shares;
                                 state.on_position_sold(profit);
+                                reject_tracker.on_fill();
+                                alerter.send(AlertEvent::Exit {
+                                    direction: format!("{:?}", position.direction).to_uppercase(),
+                                    price: current_bid,
+                                    shares: actual_shares,
+                                    profit,
+                                });
                                 indices_to_remove.push(action.idx);
                             } else {
                                 warn!("Exit FOK rejected: {:?} - will retry next cycle", response.error_msg);
+                                reject_tracker.on_reject(
+                                    &alerter,
+                                    "SELL",
+                                    response.error_msg.as_deref().unwrap_or("unknown"),
+                                    config.alerts.reject_alert_threshold,
+                                );
 
                                 // Update execution record with rejection
                                 if let (Some(id), Some(ref db)) = (sell_exec_id, &trade_db) {
@@ -1361,6 +2633,7 @@ async fn main() -> Result<()> {
                                         response.order_id.as_deref(),
                                         response.error_msg.as_deref(),
                                     ).await {
+                                        alerts::db_error(&e);
                                         warn!("Failed to update sell execution status: {}", e);
                                     }
                                 }
@@ -1370,6 +2643,7 @@ async fn main() -> Result<()> {
                         }
                         Err(e) => {
                             error!("Exit sell execution error: {} - will retry next cycle", e);
+                            reject_tracker.on_reject(&alerter, "SELL", &e.to_string(), config.alerts.reject_alert_threshold);
 
                             // Update execution record with error
                             if let (Some(id), Some(ref db)) = (sell_exec_id, &trade_db) {
@@ -1379,6 +2653,7 @@ async fn main() -> Result<()> {
                                     None, None, None, None,
                                     Some(&e.to_string()),
                                 ).await {
+                                    alerts::db_error(&e2);
                                     warn!("Failed to update sell execution status: {}", e2);
                                 }
                             }
@@ -1400,6 +2675,7 @@ async fn main() -> Result<()> {
             for idx in indices_to_remove.into_iter().rev() {
                 state.remove_position(idx);
             }
+            save_state_now(state_store.as_mut(), &state, trade_db.as_ref()).await;
         }
 
         // ═══════════════════════════════════════════════════════════════
@@ -1409,9 +2685,10 @@ async fn main() -> Result<()> {
         // Make buy decision
         let ctx = StrategyContext {
             config: &config,
-            matrix: &matrix,
+            matrix,
             fp_matrix: fp_matrix.as_ref(),
             crossing_matrix: crossing_matrix.as_ref(),
+            market_reach_matrix: market_reach_matrix.as_ref(),
             bankroll: state.bankroll,
             consecutive_losses: state.consecutive_losses,
             terminal_bets_this_window: state.terminal_bets_this_window,
@@ -1420,7 +2697,72 @@ async fn main() -> Result<()> {
             open_positions: state.position_count(),
         };
 
-        let decision = ctx.decide(seconds_elapsed, price_delta, &up_quote, &down_quote);
+        let mut decision = ctx.decide(seconds_elapsed, price_delta, &up_quote, &down_quote);
+
+        // Aggregate plugin strategy intents with the core decision - highest edge wins.
+        // The core's reason code says nothing about plugins (it can stop at TooEarly
+        // before the loss limit is checked), so the shared gates run per intent.
+        if !registry.is_empty() && !ctx.daily_loss_limit_hit() {
+            let cell = matrix.get((seconds_elapsed / 15).min(59) as u8, delta_bucket);
+            let reliable = cell.sample_size() >= config.timing.min_samples_in_bucket as f64;
+            let view = strategies::MarketView {
+                now_ms: Utc::now().timestamp_millis(),
+                seconds_elapsed,
+                seconds_remaining,
+                price_delta,
+                up: side_quote(&up_quote),
+                down: side_quote(&down_quote),
+                model_p_up: reliable.then_some(cell.p_up_wilson_lower),
+                model_p_down: reliable.then_some(1.0 - cell.p_up_wilson_upper),
+            };
+            let open_by_strategy = |name: &str| {
+                state.open_positions.iter().filter(|p| p.strategy_type == name).count() as u32
+            };
+            let best = registry
+                .evaluate(&view, &open_by_strategy)
+                .into_iter()
+                .filter(|i| i.amount_usdc >= config.betting.min_bet_usdc)
+                .find(|i| {
+                    let (dir, quote) = match i.side {
+                        strategies::Side::Up => (BetDirection::Up, &up_quote),
+                        strategies::Side::Down => (BetDirection::Down, &down_quote),
+                    };
+                    let blocked = ctx.entry_blocked(price_delta, quote).or_else(|| {
+                        (state.position_count_by_direction(dir) >= config.risk.max_open_positions)
+                            .then_some(ReasonCode::MaxPositions)
+                    });
+                    if let Some(code) = blocked {
+                        debug!("Plugin intent [{}] {} blocked: {:?}", i.strategy, i.side.as_str(), code);
+                    }
+                    blocked.is_none()
+                });
+            if let Some(intent) = best {
+                if !decision.should_bet || intent.edge > decision.edge {
+                    info!("{} → [{}] ${:.2}", intent.reason, intent.strategy, intent.amount_usdc);
+                    decision = intent_decision(intent, cell.confidence_level, decision);
+                }
+            }
+        }
+
+        metrics::BANKROLL.set(state.bankroll);
+        metrics::DAILY_PNL.set(state.daily_pnl);
+        metrics::OPEN_POSITIONS.set(state.position_count() as i64);
+        if decision.should_bet {
+            metrics::SIGNALS.with_label_values(&[&decision.strategy_type]).inc();
+        }
+
+        {
+            let mut status = state.to_status(order_executor.is_none());
+            status.leader = true;
+            status.last_decision = Some(api::DecisionStatus {
+                at: Utc::now(),
+                seconds_elapsed,
+                btc_price,
+                price_delta,
+                decision: decision.clone(),
+            });
+            *bot_status.write().await = status;
+        }
 
         // Get matrix cell info for logging
         let cell = matrix.get(time_bucket, delta_bucket);
@@ -1474,14 +2816,14 @@ async fn main() -> Result<()> {
 
         // Check strategy-specific cooldown before betting (skip if pending sells)
         let in_cooldown = if decision.should_bet && !has_pending_sells {
-            let (last_bet_ms, cooldown_required_ms) = if decision.strategy_type == "TERMINAL" {
-                (state.millis_since_terminal_bet(), config.terminal_strategy.cooldown_ms)
-            } else {
-                (state.millis_since_exit_bet(), config.exit_strategy.cooldown_ms)
+            let cooldown = match decision.strategy_type.as_str() {
+                "TERMINAL" => Some((state.millis_since_terminal_bet(), config.terminal_strategy.cooldown_ms)),
+                "EXIT" => Some((state.millis_since_exit_bet(), config.exit_strategy.cooldown_ms)),
+                _ => None, // Plugin strategies: enforced by StrategyRegistry
             };
 
-            match last_bet_ms {
-                Some(ms) if ms < cooldown_required_ms => {
+            match cooldown {
+                Some((Some(ms), cooldown_required_ms)) if ms < cooldown_required_ms => {
                     info!("⏸ SKIP BUY: [{}] cooldown {}ms/{}ms required",
                         decision.strategy_type, ms, cooldown_required_ms);
                     true
@@ -1497,6 +2839,35 @@ async fn main() -> Result<()> {
             info!("⏸ SKIP BUY: order already pending");
         }
 
+        if let Some(ref log) = decision_log {
+            let action = if !decision.should_bet {
+                decision.reason_code
+            } else if has_pending_sells {
+                ReasonCode::PendingSells
+            } else if at_max_positions {
+                ReasonCode::MaxPositions
+            } else if in_cooldown {
+                ReasonCode::Cooldown
+            } else if state.is_bet_pending() {
+                ReasonCode::BetPending
+            } else {
+                ReasonCode::Bet
+            };
+            let record = DecisionRecord {
+                ts: Utc::now(),
+                window_start,
+                seconds_elapsed,
+                seconds_remaining,
+                btc_price,
+                price_delta,
+                up: QuoteSnapshot::from(&up_quote),
+                down: QuoteSnapshot::from(&down_quote),
+                action,
+                decision: &decision,
+            };
+            log.log(&record);
+        }
+
         if decision.should_bet && !in_cooldown && !has_pending_sells && !at_max_positions && !state.is_bet_pending() {
             let direction = decision.direction.unwrap();
 
@@ -1533,6 +2904,7 @@ async fn main() -> Result<()> {
                         continue;
                     }
                     Err(e) => {
+                        alerts::redis_error(&e);
                         warn!("Redis lock error: {}, proceeding anyway", e);
                         true // Proceed if Redis fails
                     }
@@ -1622,6 +2994,7 @@ async fn main() -> Result<()> {
                     match db.insert_execution(&buy_execution).await {
                         Ok(id) => Some(id),
                         Err(e) => {
+                            alerts::db_error(&e);
                             warn!("Failed to insert execution record: {}", e);
                             None
                         }
@@ -1643,6 +3016,14 @@ async fn main() -> Result<()> {
                             info!("✓ FOK Order FILLED! ID: {:?}", response.order_id);
                             info!("  Filled: ${:.2} for {:.4} shares at {:.2}¢",
                                 actual_usdc, actual_shares, execution_price * 100.0);
+                            reject_tracker.on_fill();
+                            alerter.send(AlertEvent::Fill {
+                                direction: format!("{:?}", direction).to_uppercase(),
+                                strategy: decision.strategy_type.clone(),
+                                price: execution_price,
+                                amount_usdc: actual_usdc,
+                                shares: actual_shares,
+                            });
 
                             // Log trade attempt (success)
                             if let Some(ref db) = trade_db {
@@ -1674,6 +3055,7 @@ async fn main() -> Result<()> {
                                     slippage_pct: Some(slippage),
                                 };
                                 if let Err(e) = db.insert_trade_attempt(&attempt).await {
+                                    alerts::db_error(&e);
                                     warn!("Failed to log trade attempt: {}", e);
                                 }
 
@@ -1696,6 +3078,7 @@ async fn main() -> Result<()> {
                                     order_id: response.order_id.clone(),
                                 };
                                 if let Err(e) = db.insert_trade(&trade).await {
+                                    alerts::db_error(&e);
                                     warn!("Failed to record trade to bot_trades: {}", e);
                                 }
                             }
@@ -1712,6 +3095,7 @@ async fn main() -> Result<()> {
                                     response.order_id.as_deref(),
                                     None,
                                 ).await {
+                                    alerts::db_error(&e);
                                     warn!("Failed to update execution status: {}", e);
                                 }
                             } else {
@@ -1720,6 +3104,14 @@ async fn main() -> Result<()> {
 
                             // Trigger cooldown
                             state.on_bet_placed(&decision.strategy_type);
+                            state.on_strategy_fill(&mut registry, strategies::Fill {
+                                strategy: decision.strategy_type.clone(),
+                                side: fill_side(direction),
+                                price: execution_price,
+                                shares: actual_shares,
+                                amount_usdc: actual_usdc,
+                                now_ms: Utc::now().timestamp_millis(),
+                            });
 
                             // Track position - use best_ask as entry price (not slippage-adjusted)
                             // FOK fills at best available price, typically the ask
@@ -1741,6 +3133,7 @@ async fn main() -> Result<()> {
                                 sell_pending: false,
                                 strategy_type: decision.strategy_type.clone(),
                                 entry_seconds_elapsed: seconds_elapsed,
+                                stop: StopTracker::new(&config.stop_loss, best_ask),
                             });
 
                             let count_after = state.position_count_by_direction(direction);
@@ -1753,31 +3146,11 @@ async fn main() -> Result<()> {
                                     direction, count_after, config.risk.max_open_positions);
                             }
 
-                            // Sync position to Redis for multi-pod awareness
+                            save_state_now(state_store.as_mut(), &state, trade_db.as_ref()).await;
                             if let Some(ref rs) = redis_state {
-                                let redis_pos = RedisPosition {
-                                    position_id: position_id.clone(),
-                                    token_id: token_id.clone(),
-                                    direction: format!("{:?}", direction),
-                                    entry_price: best_ask,
-                                    shares: actual_shares,
-                                    entry_time_bucket: time_bucket,
-                                    entry_delta_bucket: delta_bucket,
-                                    exit_target,
-                                    window_start_ts: window_start.timestamp(),
-                                    sell_pending: false,
-                                    strategy_type: decision.strategy_type.clone(),
-                                    entry_seconds_elapsed: seconds_elapsed,
-                                };
-                                if let Err(e) = rs.add_position(redis_pos).await {
-                                    warn!("Failed to sync position to Redis: {}", e);
-                                }
-                                // Increment bet counter in Redis
-                                if let Err(e) = rs.increment_bet_count(window_start.timestamp(), &decision.strategy_type).await {
-                                    warn!("Failed to increment Redis bet counter: {}", e);
-                                }
                                 // Release trade lock
                                 if let Err(e) = rs.release_trade_lock(&lock_id).await {
+                                    alerts::redis_error(&e);
                                     warn!("Failed to release Redis lock: {}", e);
                                 }
                             }
@@ -1793,6 +3166,12 @@ async fn main() -> Result<()> {
                         } else {
                             // FOK rejected (not filled)
                             warn!("✗ FOK Order rejected: {:?}", response.error_msg);
+                            reject_tracker.on_reject(
+                                &alerter,
+                                "BUY",
+                                response.error_msg.as_deref().unwrap_or("unknown"),
+                                config.alerts.reject_alert_threshold,
+                            );
 
                             // Log trade attempt (rejected)
                             if let Some(ref db) = trade_db {
@@ -1823,6 +3202,7 @@ async fn main() -> Result<()> {
                                     slippage_pct: None,
                                 };
                                 if let Err(e) = db.insert_trade_attempt(&attempt).await {
+                                    alerts::db_error(&e);
                                     warn!("Failed to log trade attempt: {}", e);
                                 }
                             }
@@ -1836,6 +3216,7 @@ async fn main() -> Result<()> {
                                     response.order_id.as_deref(),
                                     response.error_msg.as_deref(),
                                 ).await {
+                                    alerts::db_error(&e);
                                     warn!("Failed to update execution status: {}", e);
                                 }
                             }
@@ -1851,6 +3232,7 @@ async fn main() -> Result<()> {
                     }
                     Err(e) => {
                         error!("FOK order execution failed: {}", e);
+                        reject_tracker.on_reject(&alerter, "BUY", &e.to_string(), config.alerts.reject_alert_threshold);
 
                         // Release Redis lock on error
                         if let Some(ref rs) = redis_state {
@@ -1886,6 +3268,7 @@ async fn main() -> Result<()> {
                                 slippage_pct: None,
                             };
                             if let Err(e2) = db.insert_trade_attempt(&attempt).await {
+                                alerts::db_error(&e2);
                                 warn!("Failed to log trade attempt: {}", e2);
                             }
                         }
@@ -1898,6 +3281,7 @@ async fn main() -> Result<()> {
                                 None, None, None, None,
                                 Some(&e.to_string()),
                             ).await {
+                                alerts::db_error(&e2);
                                 warn!("Failed to update execution status: {}", e2);
                             }
                         }
@@ -1920,6 +3304,14 @@ async fn main() -> Result<()> {
                     BetDirection::Up => market.up_token_id.clone(),
                     BetDirection::Down => market.down_token_id.clone(),
                 };
+                state.on_strategy_fill(&mut registry, strategies::Fill {
+                    strategy: decision.strategy_type.clone(),
+                    side: fill_side(direction),
+                    price: execution_price,
+                    shares,
+                    amount_usdc: decision.bet_amount,
+                    now_ms: Utc::now().timestamp_millis(),
+                });
 
                 // Use exit target from decision
                 let exit_target = decision.exit_target.unwrap_or(1.0);
@@ -1938,6 +3330,7 @@ async fn main() -> Result<()> {
                     sell_pending: false,
                     strategy_type: decision.strategy_type.clone(),
                     entry_seconds_elapsed: seconds_elapsed,
+                    stop: StopTracker::new(&config.stop_loss, execution_price),
                 });
 
                 if exit_target < 1.0 {
@@ -1994,11 +3387,13 @@ async fn main() -> Result<()> {
                     };
 
                     if let Err(e) = db.insert_outcome(&market_outcome).await {
+                        alerts::db_error(&e);
                         warn!("Failed to record market outcome: {}", e);
                     }
 
                     // Calculate trade results for any completed trades
                     if let Err(e) = db.calculate_trade_results().await {
+                        alerts::db_error(&e);
                         warn!("Failed to calculate trade results: {}", e);
                     }
                 }
@@ -2023,6 +3418,7 @@ async fn main() -> Result<()> {
                         ms.down_best_bid = 0.0;
                         info!("Updated WebSocket state with new tokens");
                     }
+                    prepare_market(order_executor.as_mut(), &m).await;
                     current_market = Some(m);
                 }
                 Err(e) => {
@@ -2033,6 +3429,15 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Save the final state, then hand the lease to a standby right away
+    // instead of waiting for it to expire
+    if let (Some(store), true) = (state_store.as_mut(), leadership.is_leader()) {
+        if let Err(e) = store.save(state.to_persisted(), trade_db.as_ref()).await {
+            warn!("Failed to save final state: {}", e);
+        }
+    }
+    leadership.release().await;
+
     // Final stats from database
     if let Some(ref db) = trade_db {
         match db.get_overall_stats().await {
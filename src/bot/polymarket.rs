@@ -1,13 +1,14 @@
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tracing::debug;
 
+use crate::http_client::HttpClient;
+
 /// Polymarket CLOB API client
 pub struct PolymarketClient {
-    client: Client,
+    client: HttpClient,
     clob_url: String,
     gamma_url: String,
 }
@@ -95,10 +96,7 @@ pub struct OrderResponse {
 
 impl PolymarketClient {
     pub fn new(timeout_ms: u64) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_millis(timeout_ms))
-            .build()
-            .context("Failed to create HTTP client")?;
+        let client = HttpClient::new("polymarket", Duration::from_millis(timeout_ms));
 
         Ok(Self {
             client,
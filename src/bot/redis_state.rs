@@ -1,15 +1,13 @@
 //! Redis-based shared state for multi-pod coordination
 //!
 //! This module handles:
-//! - Shared position tracking across pods
-//! - Bet counters per window
+//! - The bot's versioned risk-state snapshot and its event stream (see state_store.rs)
 //! - Distributed locking to prevent duplicate orders
+//! - Leader election lease with fencing tokens (see leader.rs)
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
 use redis::AsyncCommands;
-use serde::{Deserialize, Serialize};
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
 /// Redis URL - from environment or fallback to hardcoded
 fn get_redis_url() -> String {
@@ -22,29 +20,105 @@ fn get_redis_url() -> String {
 }
 
 /// Key prefixes
-const POSITIONS_KEY: &str = "btc_bot:positions";
-const BET_COUNTER_PREFIX: &str = "btc_bot:bets:";
+const STATE_KEY: &str = "btc_bot:state";
+const STATE_EVENTS_KEY: &str = "btc_bot:state_events";
 const TRADE_LOCK_KEY: &str = "btc_bot:trade_lock";
-const LAST_BET_TIME_PREFIX: &str = "btc_bot:last_bet:";
-
-/// Position stored in Redis (serializable version)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RedisPosition {
-    pub position_id: String,
-    pub token_id: String,
-    pub direction: String,      // "Up" or "Down"
-    pub entry_price: f64,
-    pub shares: f64,
-    pub entry_time_bucket: u8,
-    pub entry_delta_bucket: i8,
-    pub exit_target: f64,
-    pub window_start_ts: i64,   // Unix timestamp
-    pub sell_pending: bool,
-    pub strategy_type: String,
-    pub entry_seconds_elapsed: u32,
+const LEADER_KEY: &str = "btc_bot:leader";
+const LEADER_FENCE_KEY: &str = "btc_bot:leader_fence";
+
+/// Keys written before the versioned snapshot existed (read by StateStore::load_legacy)
+const LEGACY_POSITIONS_KEY: &str = "btc_bot:positions";
+const LEGACY_BET_COUNTER_PREFIX: &str = "btc_bot:bets:";
+const LEGACY_LAST_BET_PREFIX: &str = "btc_bot:last_bet:";
+
+/// Delete KEYS[1] only if it still holds ARGV[1]
+const COMPARE_AND_DELETE: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// Extend the TTL of KEYS[1] (ARGV[2] ms) only if it still holds ARGV[1]
+const COMPARE_AND_EXPIRE: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// Write snapshot ARGV[1] with version ARGV[2] to KEYS[1] unless the stored
+/// snapshot is already at that version or newer, then append ARGV[3..] to the
+/// event stream KEYS[2] (trimmed to ~10k entries)
+const SAVE_STATE: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if current then
+    local stored = cjson.decode(current)
+    if stored.version and stored.version >= tonumber(ARGV[2]) then
+        return 0
+    end
+end
+redis.call('SET', KEYS[1], ARGV[1])
+for i = 3, #ARGV do
+    redis.call('XADD', KEYS[2], 'MAXLEN', '~', 10000, '*', 'event', ARGV[i])
+end
+return 1
+"#;
+
+/// Take the leader lease KEYS[1] for pod ARGV[1] (TTL ARGV[2] ms) if it is
+/// free, with a new fencing token from KEYS[2]. Re-entrant for the holder.
+/// Returns the lease value "<token>|<pod_id>", or false if another pod holds it.
+const ACQUIRE_LEASE: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if current then
+    local owner = string.match(current, '^%d+|(.*)$')
+    if owner == ARGV[1] then
+        redis.call('PEXPIRE', KEYS[1], ARGV[2])
+        return current
+    end
+    return false
+end
+local token = redis.call('INCR', KEYS[2])
+local value = token .. '|' .. ARGV[1]
+redis.call('SET', KEYS[1], value, 'PX', ARGV[2])
+return value
+"#;
+
+/// The leader lease: which pod holds it and its fencing token
+/// Tokens only ever increase, so a newer leader always has a larger one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderLease {
+    pub token: u64,
+    pub pod_id: String,
+}
+
+impl LeaderLease {
+    /// Redis value: "<token>|<pod_id>"
+    fn to_value(&self) -> String {
+        format!("{}|{}", self.token, self.pod_id)
+    }
+
+    fn from_value(value: &str) -> Option<Self> {
+        let (token, pod_id) = value.split_once('|')?;
+        Some(Self {
+            token: token.parse().ok()?,
+            pod_id: pod_id.to_string(),
+        })
+    }
+}
+
+/// Risk state under the pre-snapshot keys
+#[derive(Debug, Default)]
+pub struct LegacyState {
+    pub positions_json: Option<String>,
+    pub terminal_bets: u32,
+    pub exit_bets: u32,
+    pub last_terminal_bet_ts: Option<i64>,
+    pub last_exit_bet_ts: Option<i64>,
 }
 
 /// Redis shared state manager
+#[derive(Clone)]
 pub struct RedisState {
     client: redis::Client,
 }
@@ -52,12 +126,16 @@ pub struct RedisState {
 impl RedisState {
     /// Connect to Redis with timeout
     pub async fn connect() -> Result<Self> {
+        Self::connect_url(&get_redis_url()).await
+    }
+
+    /// Connect to a specific Redis URL (e.g. a local Redis in tests)
+    pub async fn connect_url(redis_url: &str) -> Result<Self> {
         use std::time::Duration;
         use tokio::time::timeout;
 
-        let redis_url = get_redis_url();
         eprintln!("[redis] Creating client for: {}...", &redis_url[..redis_url.len().min(60)]);
-        let client = redis::Client::open(redis_url.as_str())
+        let client = redis::Client::open(redis_url)
             .context("Failed to create Redis client")?;
 
         // Test connection with 10s timeout
@@ -91,140 +169,48 @@ impl RedisState {
             .context("Failed to get Redis connection")
     }
 
-    // ========== POSITIONS ==========
+    // ========== BOT STATE ==========
 
-    /// Get all open positions
-    pub async fn get_positions(&self) -> Result<Vec<RedisPosition>> {
+    /// Latest persisted state snapshot (JSON), if any
+    pub async fn load_state_snapshot(&self) -> Result<Option<String>> {
         let mut conn = self.conn().await?;
-        let data: Option<String> = conn.get(POSITIONS_KEY).await?;
-
-        match data {
-            Some(json) => {
-                let positions: Vec<RedisPosition> = serde_json::from_str(&json)?;
-                Ok(positions)
-            }
-            None => Ok(vec![])
-        }
+        let data: Option<String> = conn.get(STATE_KEY).await?;
+        Ok(data)
     }
 
-    /// Save all positions
-    async fn save_positions(&self, positions: &[RedisPosition]) -> Result<()> {
+    /// Store snapshot `version` and append its events to the event stream,
+    /// atomically. Returns false (nothing written) if Redis already holds this
+    /// version or a newer one, i.e. another pod wrote since we loaded.
+    pub async fn save_state_snapshot(&self, snapshot_json: &str, version: u64, events: &[String]) -> Result<bool> {
         let mut conn = self.conn().await?;
-        let json = serde_json::to_string(positions)?;
-        conn.set(POSITIONS_KEY, json).await?;
-        Ok(())
-    }
-
-    /// Add a new position
-    pub async fn add_position(&self, position: RedisPosition) -> Result<()> {
-        let mut positions = self.get_positions().await?;
-        positions.push(position.clone());
-        self.save_positions(&positions).await?;
-        info!("Redis: Added position {} ({} {})",
-            &position.position_id[..8], position.direction, position.shares);
-        Ok(())
-    }
-
-    /// Remove a position by ID
-    pub async fn remove_position(&self, position_id: &str) -> Result<bool> {
-        let mut positions = self.get_positions().await?;
-        let initial_len = positions.len();
-        positions.retain(|p| p.position_id != position_id);
-
-        if positions.len() < initial_len {
-            self.save_positions(&positions).await?;
-            info!("Redis: Removed position {}", &position_id[..8]);
-            Ok(true)
-        } else {
-            Ok(false)
+        let script = redis::Script::new(SAVE_STATE);
+        let mut invocation = script.key(STATE_KEY);
+        invocation.key(STATE_EVENTS_KEY).arg(snapshot_json).arg(version);
+        for event in events {
+            invocation.arg(event);
         }
-    }
-
-    /// Mark position as sell pending
-    pub async fn mark_sell_pending(&self, position_id: &str) -> Result<()> {
-        let mut positions = self.get_positions().await?;
-        for p in &mut positions {
-            if p.position_id == position_id {
-                p.sell_pending = true;
-            }
+        let saved: i64 = invocation.invoke_async(&mut conn).await?;
+        if saved == 1 {
+            debug!("Redis: saved state v{} ({} event(s))", version, events.len());
         }
-        self.save_positions(&positions).await?;
-        Ok(())
-    }
-
-    /// Get position count
-    pub async fn position_count(&self) -> Result<usize> {
-        let positions = self.get_positions().await?;
-        Ok(positions.len())
-    }
-
-    /// Check if any positions have pending sells
-    pub async fn has_pending_sells(&self) -> Result<bool> {
-        let positions = self.get_positions().await?;
-        Ok(positions.iter().any(|p| p.sell_pending))
+        Ok(saved == 1)
     }
 
-    /// Clear positions for a new window
-    pub async fn clear_positions_for_window(&self, window_start_ts: i64) -> Result<()> {
-        let mut positions = self.get_positions().await?;
-        let initial_len = positions.len();
-
-        // Keep only positions from the current window
-        positions.retain(|p| p.window_start_ts == window_start_ts);
-
-        if positions.len() < initial_len {
-            self.save_positions(&positions).await?;
-            info!("Redis: Cleared {} old positions", initial_len - positions.len());
-        }
-        Ok(())
-    }
-
-    // ========== BET COUNTERS ==========
-
-    /// Get bet count for a window and strategy
-    pub async fn get_bet_count(&self, window_start_ts: i64, strategy: &str) -> Result<u32> {
+    /// Pre-snapshot state: positions JSON, then per strategy ("TERMINAL",
+    /// "EXIT") the bet count for `window_start_ts` and the last bet time
+    pub async fn load_legacy_state(&self, window_start_ts: i64) -> Result<LegacyState> {
         let mut conn = self.conn().await?;
-        let key = format!("{}{}:{}", BET_COUNTER_PREFIX, window_start_ts, strategy);
-        let count: Option<u32> = conn.get(&key).await?;
-        Ok(count.unwrap_or(0))
-    }
-
-    /// Increment bet count for a window and strategy
-    pub async fn increment_bet_count(&self, window_start_ts: i64, strategy: &str) -> Result<u32> {
-        let mut conn = self.conn().await?;
-        let key = format!("{}{}:{}", BET_COUNTER_PREFIX, window_start_ts, strategy);
-        let count: u32 = conn.incr(&key, 1).await?;
-        // Set expiry to 20 minutes (longer than a window)
-        let _: () = conn.expire(&key, 1200).await?;
-        debug!("Redis: {} bets for {} in window {}", count, strategy, window_start_ts);
-        Ok(count)
-    }
-
-    // ========== COOLDOWN ==========
-
-    /// Record when a bet was placed for cooldown tracking
-    pub async fn record_bet_time(&self, strategy: &str) -> Result<()> {
-        let mut conn = self.conn().await?;
-        let key = format!("{}{}", LAST_BET_TIME_PREFIX, strategy);
-        let now = Utc::now().timestamp();
-        conn.set(&key, now).await?;
-        let _: () = conn.expire(&key, 1200).await?;
-        Ok(())
-    }
-
-    /// Get seconds since last bet for a strategy
-    pub async fn seconds_since_last_bet(&self, strategy: &str) -> Result<Option<u64>> {
-        let mut conn = self.conn().await?;
-        let key = format!("{}{}", LAST_BET_TIME_PREFIX, strategy);
-        let last_bet: Option<i64> = conn.get(&key).await?;
-
-        match last_bet {
-            Some(ts) => {
-                let now = Utc::now().timestamp();
-                Ok(Some((now - ts) as u64))
-            }
-            None => Ok(None)
+        let positions_json: Option<String> = conn.get(LEGACY_POSITIONS_KEY).await?;
+        let mut legacy = LegacyState { positions_json, ..Default::default() };
+        for (strategy, bets, last_bet) in [
+            ("TERMINAL", &mut legacy.terminal_bets, &mut legacy.last_terminal_bet_ts),
+            ("EXIT", &mut legacy.exit_bets, &mut legacy.last_exit_bet_ts),
+        ] {
+            let count: Option<u32> = conn.get(format!("{}{}:{}", LEGACY_BET_COUNTER_PREFIX, window_start_ts, strategy)).await?;
+            *bets = count.unwrap_or(0);
+            *last_bet = conn.get(format!("{}{}", LEGACY_LAST_BET_PREFIX, strategy)).await?;
         }
+        Ok(legacy)
     }
 
     // ========== TRADE LOCK ==========
@@ -251,11 +237,13 @@ impl RedisState {
     pub async fn release_trade_lock(&self, lock_id: &str) -> Result<()> {
         let mut conn = self.conn().await?;
 
-        // Only delete if we own the lock
-        let current: Option<String> = conn.get(TRADE_LOCK_KEY).await?;
-        if current.as_deref() == Some(lock_id) {
-            let _: () = conn.del(TRADE_LOCK_KEY).await?;
-        }
+        // Compare-and-delete in one script: a GET then DEL could delete a lock
+        // another pod took after ours expired in between
+        let _: i64 = redis::Script::new(COMPARE_AND_DELETE)
+            .key(TRADE_LOCK_KEY)
+            .arg(lock_id)
+            .invoke_async(&mut conn)
+            .await?;
         Ok(())
     }
 
@@ -265,4 +253,85 @@ impl RedisState {
         let exists: bool = conn.exists(TRADE_LOCK_KEY).await?;
         Ok(exists)
     }
+
+    // ========== LEADER ELECTION ==========
+
+    /// Take the leader lease for `pod_id` if it is free (or already ours)
+    /// Returns the lease, or None if another pod is the leader
+    pub async fn try_acquire_leadership(&self, pod_id: &str, ttl_ms: u64) -> Result<Option<LeaderLease>> {
+        let mut conn = self.conn().await?;
+        let value: Option<String> = redis::Script::new(ACQUIRE_LEASE)
+            .key(LEADER_KEY)
+            .key(LEADER_FENCE_KEY)
+            .arg(pod_id)
+            .arg(ttl_ms)
+            .invoke_async(&mut conn)
+            .await?;
+        Ok(value.as_deref().and_then(LeaderLease::from_value))
+    }
+
+    /// Extend our lease; false if it lapsed or another pod took over
+    pub async fn renew_leadership(&self, lease: &LeaderLease, ttl_ms: u64) -> Result<bool> {
+        let mut conn = self.conn().await?;
+        let renewed: i64 = redis::Script::new(COMPARE_AND_EXPIRE)
+            .key(LEADER_KEY)
+            .arg(lease.to_value())
+            .arg(ttl_ms)
+            .invoke_async(&mut conn)
+            .await?;
+        Ok(renewed == 1)
+    }
+
+    /// Give up our lease so a standby can take over immediately
+    pub async fn release_leadership(&self, lease: &LeaderLease) -> Result<bool> {
+        let mut conn = self.conn().await?;
+        let deleted: i64 = redis::Script::new(COMPARE_AND_DELETE)
+            .key(LEADER_KEY)
+            .arg(lease.to_value())
+            .invoke_async(&mut conn)
+            .await?;
+        Ok(deleted == 1)
+    }
+
+    /// Current leader lease, if any
+    pub async fn current_leader(&self) -> Result<Option<LeaderLease>> {
+        let mut conn = self.conn().await?;
+        let value: Option<String> = conn.get(LEADER_KEY).await?;
+        Ok(value.as_deref().and_then(LeaderLease::from_value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leader_lease_value() {
+        let lease = LeaderLease { token: 42, pod_id: "btc-bot-7f9c|a".to_string() };
+        assert_eq!(lease.to_value(), "42|btc-bot-7f9c|a");
+        assert_eq!(LeaderLease::from_value(&lease.to_value()), Some(lease));
+        assert_eq!(LeaderLease::from_value("not-a-lease"), None);
+    }
+
+    /// Needs a local Redis: REDIS_TEST_URL=redis://127.0.0.1:6379 cargo test --bin btc-bot
+    #[tokio::test]
+    async fn test_trade_lock_release_is_owner_only() {
+        let Ok(url) = std::env::var("REDIS_TEST_URL") else {
+            eprintln!("REDIS_TEST_URL not set, skipping");
+            return;
+        };
+        let rs = RedisState::connect_url(&url).await.unwrap();
+        rs.release_trade_lock("test-a").await.unwrap();
+        rs.release_trade_lock("test-b").await.unwrap();
+
+        assert!(rs.try_acquire_trade_lock("test-a", 5000).await.unwrap());
+        assert!(!rs.try_acquire_trade_lock("test-b", 5000).await.unwrap());
+
+        // Another pod's release leaves our lock alone
+        rs.release_trade_lock("test-b").await.unwrap();
+        assert!(rs.is_trade_locked().await.unwrap());
+
+        rs.release_trade_lock("test-a").await.unwrap();
+        assert!(!rs.is_trade_locked().await.unwrap());
+    }
 }
@@ -0,0 +1,487 @@
+//! Order signing backends
+//!
+//! The executor only needs an address and secp256k1 signatures over EIP-712
+//! digests, so the key sits behind the `Signer` trait:
+//!
+//! - `LocalSigner`: key in process memory, loaded from an encrypted JSON
+//!   keystore (Web3 Secret Storage v3, scrypt or pbkdf2) or a raw hex key
+//! - `SocketSigner`: key held by a signing daemon on a Unix socket (e.g. a
+//!   sidecar running `btc-bot --signer-daemon <socket>`), never in this process
+//!
+//! Selected from the environment by `from_env`, first match wins:
+//!   POLYMARKET_SIGNER_SOCKET                     → SocketSigner
+//!   POLYMARKET_KEYSTORE_PATH + password          → LocalSigner (keystore)
+//!   POLYMARKET_PRIVATE_KEY                       → LocalSigner (raw key, dev only)
+//! The keystore password comes from POLYMARKET_KEYSTORE_PASSWORD_FILE (a
+//! mounted secret) or POLYMARKET_KEYSTORE_PASSWORD.
+//!
+//! Daemon protocol: one JSON object per line in each direction.
+//!   {"method":"address"}                          → {"address":"0x…"}
+//!   {"method":"sign_digest","digest":"0x…32 bytes"} → {"signature":"0x…65 bytes"}
+//! Errors come back as {"error":"…"}.
+
+use aes::Aes128;
+use anyhow::{anyhow, bail, Context, Result};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use tracing::{info, warn};
+
+/// Signs EIP-712 digests for one address
+pub trait Signer: Send + Sync {
+    /// Signer (EOA) address
+    fn address(&self) -> [u8; 20];
+
+    /// 65-byte r || s || v signature of a 32-byte digest (v = 27 + recovery id)
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<[u8; 65]>;
+
+    /// Backend name for logs
+    fn kind(&self) -> &'static str;
+}
+
+/// Pick the signer configured in the environment (see module docs).
+/// Returns None when nothing is configured (dry run).
+pub fn from_env() -> Result<Option<Box<dyn Signer>>> {
+    if let Ok(socket) = std::env::var("POLYMARKET_SIGNER_SOCKET") {
+        return Ok(Some(Box::new(SocketSigner::connect(socket)?)));
+    }
+    if let Ok(path) = std::env::var("POLYMARKET_KEYSTORE_PATH") {
+        let password = keystore_password_from_env()?;
+        return Ok(Some(Box::new(LocalSigner::from_keystore(Path::new(&path), &password)?)));
+    }
+    if let Ok(key) = std::env::var("POLYMARKET_PRIVATE_KEY") {
+        warn!("Signing with POLYMARKET_PRIVATE_KEY from the environment - use a keystore or signer socket in production");
+        return Ok(Some(Box::new(LocalSigner::from_hex(&key)?)));
+    }
+    Ok(None)
+}
+
+fn keystore_password_from_env() -> Result<String> {
+    if let Ok(file) = std::env::var("POLYMARKET_KEYSTORE_PASSWORD_FILE") {
+        let password = std::fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read keystore password file {}", file))?;
+        return Ok(password.trim_end_matches(['\r', '\n']).to_string());
+    }
+    std::env::var("POLYMARKET_KEYSTORE_PASSWORD")
+        .context("POLYMARKET_KEYSTORE_PATH set but neither POLYMARKET_KEYSTORE_PASSWORD_FILE nor POLYMARKET_KEYSTORE_PASSWORD")
+}
+
+/// Ethereum address of a public key
+fn address_of(public_key: &PublicKey) -> [u8; 20] {
+    let hash = Keccak256::digest(&public_key.serialize_uncompressed()[1..]); // Remove 0x04 prefix
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..32]);
+    address
+}
+
+/// Address that produced `signature` over `digest`
+pub fn recover_address(digest: &[u8; 32], signature: &[u8; 65]) -> Result<[u8; 20]> {
+    let rec_id = RecoveryId::from_i32(signature[64] as i32 - 27).context("Invalid signature v")?;
+    let sig = RecoverableSignature::from_compact(&signature[..64], rec_id).context("Invalid signature")?;
+    let public_key = Secp256k1::new().recover_ecdsa(&Message::from_digest(*digest), &sig)?;
+    Ok(address_of(&public_key))
+}
+
+fn parse_hex<const N: usize>(value: &str, what: &str) -> Result<[u8; N]> {
+    let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .with_context(|| format!("Invalid {} hex", what))?;
+    bytes.try_into().map_err(|b: Vec<u8>| anyhow!("{} must be {} bytes, got {}", what, N, b.len()))
+}
+
+// ============================================================================
+// LOCAL SIGNER (keystore / raw key)
+// ============================================================================
+
+/// Key held in process memory
+pub struct LocalSigner {
+    secp: Secp256k1<secp256k1::All>,
+    secret_key: SecretKey,
+    address: [u8; 20],
+    kind: &'static str,
+}
+
+impl LocalSigner {
+    fn new(secret_key: SecretKey, kind: &'static str) -> Self {
+        let secp = Secp256k1::new();
+        let address = address_of(&PublicKey::from_secret_key(&secp, &secret_key));
+        Self { secp, secret_key, address, kind }
+    }
+
+    /// Raw hex private key (with or without 0x)
+    pub fn from_hex(private_key: &str) -> Result<Self> {
+        let key_bytes: [u8; 32] = parse_hex(private_key.trim(), "private key")?;
+        let secret_key = SecretKey::from_slice(&key_bytes).context("Invalid private key")?;
+        Ok(Self::new(secret_key, "raw-key"))
+    }
+
+    /// Encrypted JSON keystore file (geth / `cast wallet import` format)
+    pub fn from_keystore(path: &Path, password: &str) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keystore {}", path.display()))?;
+        let signer = Self::new(decrypt_keystore(&json, password)?, "keystore");
+        info!("Keystore {} unlocked for 0x{}", path.display(), hex::encode(signer.address));
+        Ok(signer)
+    }
+}
+
+impl Signer for LocalSigner {
+    fn address(&self) -> [u8; 20] {
+        self.address
+    }
+
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<[u8; 65]> {
+        let msg = Message::from_digest(*digest);
+        let sig = self.secp.sign_ecdsa_recoverable(&msg, &self.secret_key);
+        let (rec_id, sig_bytes) = sig.serialize_compact();
+
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&sig_bytes);
+        out[64] = 27 + rec_id.to_i32() as u8;
+        Ok(out)
+    }
+
+    fn kind(&self) -> &'static str {
+        self.kind
+    }
+}
+
+// ============================================================================
+// ENCRYPTED KEYSTORE (Web3 Secret Storage v3)
+// ============================================================================
+
+#[derive(Deserialize)]
+struct KeystoreFile {
+    #[serde(alias = "Crypto")]
+    crypto: KeystoreCrypto,
+}
+
+#[derive(Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: serde_json::Value,
+    mac: String,
+}
+
+#[derive(Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Deserialize)]
+struct ScryptParams {
+    dklen: usize,
+    n: u64,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+#[derive(Deserialize)]
+struct Pbkdf2Params {
+    dklen: usize,
+    c: u32,
+    prf: String,
+    salt: String,
+}
+
+/// Decrypt a v3 keystore: derive the key (scrypt/pbkdf2), check the MAC,
+/// then AES-128-CTR decrypt the private key
+pub fn decrypt_keystore(json: &str, password: &str) -> Result<SecretKey> {
+    let file: KeystoreFile = serde_json::from_str(json).context("Invalid keystore JSON")?;
+    let crypto = file.crypto;
+
+    let mut derived = match crypto.kdf.as_str() {
+        "scrypt" => {
+            let p: ScryptParams = serde_json::from_value(crypto.kdfparams).context("Invalid scrypt params")?;
+            if !p.n.is_power_of_two() {
+                bail!("scrypt n must be a power of two, got {}", p.n);
+            }
+            let params = scrypt::Params::new(p.n.trailing_zeros() as u8, p.r, p.p, p.dklen)
+                .map_err(|e| anyhow!("Invalid scrypt params: {}", e))?;
+            let mut out = vec![0u8; p.dklen];
+            scrypt::scrypt(password.as_bytes(), &hex::decode(&p.salt)?, &params, &mut out)
+                .map_err(|e| anyhow!("scrypt failed: {}", e))?;
+            out
+        }
+        "pbkdf2" => {
+            let p: Pbkdf2Params = serde_json::from_value(crypto.kdfparams).context("Invalid pbkdf2 params")?;
+            if p.prf != "hmac-sha256" {
+                bail!("Unsupported pbkdf2 prf: {}", p.prf);
+            }
+            let mut out = vec![0u8; p.dklen];
+            pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), &hex::decode(&p.salt)?, p.c, &mut out);
+            out
+        }
+        other => bail!("Unsupported keystore kdf: {}", other),
+    };
+    if derived.len() < 32 {
+        bail!("Keystore dklen must be at least 32");
+    }
+    if crypto.cipher != "aes-128-ctr" {
+        bail!("Unsupported keystore cipher: {}", crypto.cipher);
+    }
+
+    let mut key = hex::decode(&crypto.ciphertext).context("Invalid keystore ciphertext")?;
+    let mut mac_input = derived[16..32].to_vec();
+    mac_input.extend_from_slice(&key);
+    let expected_mac: [u8; 32] = parse_hex(&crypto.mac, "keystore mac")?;
+    if Keccak256::digest(&mac_input).as_slice() != expected_mac {
+        bail!("Wrong keystore password (MAC mismatch)");
+    }
+
+    let iv: [u8; 16] = parse_hex(&crypto.cipherparams.iv, "keystore iv")?;
+    let mut cipher = ctr::Ctr128BE::<Aes128>::new(derived[..16].into(), &iv.into());
+    cipher.apply_keystream(&mut key);
+    derived.fill(0);
+
+    let secret_key = SecretKey::from_slice(&key).context("Keystore does not hold a valid secp256k1 key");
+    key.fill(0);
+    secret_key
+}
+
+// ============================================================================
+// SIGNING DAEMON (Unix socket)
+// ============================================================================
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum DaemonRequest {
+    Address,
+    SignDigest { digest: String },
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct DaemonResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Timeout for one daemon round trip (local socket: normally well under 1ms)
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Key held by a signing daemon; every signature is checked against the
+/// daemon's address before use
+pub struct SocketSigner {
+    path: PathBuf,
+    address: [u8; 20],
+    conn: Mutex<Option<BufReader<UnixStream>>>,
+}
+
+impl SocketSigner {
+    /// Connect and fetch the daemon's address
+    pub fn connect(path: impl Into<PathBuf>) -> Result<Self> {
+        let mut signer = Self { path: path.into(), address: [0u8; 20], conn: Mutex::new(None) };
+        let response = signer.call(&DaemonRequest::Address)?;
+        let address = response.address.context("Signer daemon returned no address")?;
+        signer.address = parse_hex(&address, "daemon address")?;
+        info!("Signer daemon {} ready for {}", signer.path.display(), address);
+        Ok(signer)
+    }
+
+    /// One request/response on the (lazily re-opened) connection. Blocking and
+    /// bounded by SOCKET_TIMEOUT; the executor signs on tokio's blocking pool.
+    fn call(&self, request: &DaemonRequest) -> Result<DaemonResponse> {
+        let mut conn = self.conn.lock().unwrap();
+        if conn.is_none() {
+            let stream = UnixStream::connect(&self.path)
+                .with_context(|| format!("Failed to connect to signer daemon {}", self.path.display()))?;
+            stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+            stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+            *conn = Some(BufReader::new(stream));
+        }
+
+        let result = (|| {
+            let reader = conn.as_mut().unwrap();
+            let mut line = serde_json::to_string(request)?;
+            line.push('\n');
+            reader.get_mut().write_all(line.as_bytes())?;
+
+            let mut reply = String::new();
+            if reader.read_line(&mut reply)? == 0 {
+                bail!("Signer daemon closed the connection");
+            }
+            Ok(serde_json::from_str::<DaemonResponse>(&reply)?)
+        })();
+
+        // Drop a broken connection; the next call reconnects
+        match result {
+            Ok(response) => match response.error {
+                Some(e) => Err(anyhow!("Signer daemon: {}", e)),
+                None => Ok(response),
+            },
+            Err(e) => {
+                *conn = None;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Signer for SocketSigner {
+    fn address(&self) -> [u8; 20] {
+        self.address
+    }
+
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<[u8; 65]> {
+        let response = self.call(&DaemonRequest::SignDigest { digest: format!("0x{}", hex::encode(digest)) })?;
+        let signature: [u8; 65] =
+            parse_hex(&response.signature.context("Signer daemon returned no signature")?, "signature")?;
+        if recover_address(digest, &signature)? != self.address {
+            bail!("Signer daemon signature does not match 0x{}", hex::encode(self.address));
+        }
+        Ok(signature)
+    }
+
+    fn kind(&self) -> &'static str {
+        "socket"
+    }
+}
+
+fn handle_request(signer: &dyn Signer, line: &str) -> DaemonResponse {
+    let result = serde_json::from_str::<DaemonRequest>(line)
+        .map_err(anyhow::Error::from)
+        .and_then(|request| match request {
+            DaemonRequest::Address => Ok(DaemonResponse {
+                address: Some(format!("0x{}", hex::encode(signer.address()))),
+                ..Default::default()
+            }),
+            DaemonRequest::SignDigest { digest } => {
+                let digest: [u8; 32] = parse_hex(&digest, "digest")?;
+                Ok(DaemonResponse {
+                    signature: Some(format!("0x{}", hex::encode(signer.sign_digest(&digest)?))),
+                    ..Default::default()
+                })
+            }
+        });
+    result.unwrap_or_else(|e| DaemonResponse { error: Some(e.to_string()), ..Default::default() })
+}
+
+/// Serve `signer` on a Unix socket (owner-only permissions) until the task is
+/// dropped. This is the daemon side of `SocketSigner`.
+pub async fn serve(signer: Box<dyn Signer>, path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::Arc;
+
+    if path.exists() {
+        std::fs::remove_file(path).with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("Failed to bind signer socket {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    info!("Signer daemon ({}) for 0x{} listening on {}", signer.kind(), hex::encode(signer.address()), path.display());
+
+    let signer: Arc<dyn Signer> = Arc::from(signer);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let signer = signer.clone();
+        tokio::spawn(async move {
+            let (read, mut write) = stream.into_split();
+            let mut lines = tokio::io::BufReader::new(read).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let response = handle_request(signer.as_ref(), &line);
+                let Ok(mut reply) = serde_json::to_string(&response) else { break };
+                reply.push('\n');
+                if write.write_all(reply.as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Private key of the Web3 Secret Storage v3 test vectors (password "testpassword")
+    const VECTOR_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    const PBKDF2_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": "6087dab2f9fdbbfaddc31a909735c1e6"},
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    /// Same key under geth's light scrypt parameters (n=4096, r=8, p=6)
+    const SCRYPT_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": "1f2e3d4c5b6a79880f1e2d3c4b5a6978"},
+            "ciphertext": "68500dac7d539a5b506a8b99d79cc72a224087aaed5b812845bcfbdff08acbee",
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": 4096,
+                "p": 6,
+                "r": 8,
+                "salt": "4f8a1cde3a2b9076d4e1c8f2a9b3d0e7c6f5a4b3c2d1e0f9a8b7c6d5e4f3a2b1"
+            },
+            "mac": "9c51a56d5fe0943c36ca30049f43de5f7235cd85dfaa8f4d125d6cf645dc78f1"
+        },
+        "version": 3
+    }"#;
+
+    #[test]
+    fn test_keystore_vectors() {
+        for json in [PBKDF2_KEYSTORE, SCRYPT_KEYSTORE] {
+            let key = decrypt_keystore(json, "testpassword").unwrap();
+            assert_eq!(hex::encode(key.secret_bytes()), VECTOR_KEY);
+        }
+        let err = decrypt_keystore(PBKDF2_KEYSTORE, "wrong").unwrap_err();
+        assert!(err.to_string().contains("MAC mismatch"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_socket_signer_matches_local_key() {
+        let local = LocalSigner::from_hex(VECTOR_KEY).unwrap();
+        let digest = [7u8; 32];
+        let expected = local.sign_digest(&digest).unwrap();
+        assert_eq!(recover_address(&digest, &expected).unwrap(), local.address());
+
+        let path = std::env::temp_dir().join(format!("btc-bot-signer-{}.sock", std::process::id()));
+        let daemon_path = path.clone();
+        let daemon = tokio::spawn(async move {
+            serve(Box::new(LocalSigner::from_hex(VECTOR_KEY).unwrap()), &daemon_path).await
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let remote = tokio::task::spawn_blocking(move || {
+            let remote = SocketSigner::connect(&path).unwrap();
+            let signature = remote.sign_digest(&digest).unwrap();
+            (remote.address(), signature)
+        })
+        .await
+        .unwrap();
+        assert_eq!(remote, (local.address(), expected));
+        daemon.abort();
+        let _ = std::fs::remove_file(std::env::temp_dir().join(format!("btc-bot-signer-{}.sock", std::process::id())));
+    }
+}
@@ -0,0 +1,481 @@
+//! Persisted risk state
+//!
+//! Everything that must survive a restart lives in one model, `PersistedState`:
+//! bankroll, daily P&L, win/loss streaks, per-window bet counts, cooldown
+//! timestamps, open positions, hedged pairs and plugin strategy fills. Each
+//! change is saved as a new snapshot version together with the events that
+//! describe it:
+//!
+//! - Redis holds the live snapshot (`btc_bot:state`, compare-and-set on the
+//!   version so a stale pod cannot overwrite a newer one) and the event stream
+//! - Postgres keeps every version (`bot_state_snapshots` / `bot_state_events`)
+//!
+//! On startup and on leader takeover the pod loads the newest snapshot from
+//! Redis, or from Postgres when Redis is empty (and seeds Redis with it), so a
+//! restarted pod resumes with the same risk state instead of starting at zero.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::alerts;
+use crate::db::TradeDb;
+use crate::hedge::HedgedPair;
+use crate::redis_state::{LegacyState, RedisState};
+use crate::strategies::Fill;
+
+/// Open position as persisted in the snapshot (serializable version)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersistedPosition {
+    pub position_id: String,
+    pub token_id: String,
+    pub direction: String,      // "Up" or "Down"
+    pub entry_price: f64,
+    pub shares: f64,
+    pub entry_time_bucket: u8,
+    pub entry_delta_bucket: i8,
+    pub exit_target: f64,
+    pub window_start_ts: i64,   // Unix timestamp
+    pub sell_pending: bool,
+    pub strategy_type: String,
+    pub entry_seconds_elapsed: u32,
+}
+
+/// Risk state that must survive restarts and leader changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersistedState {
+    /// UTC day `daily_pnl` belongs to
+    pub trading_day: Option<NaiveDate>,
+    pub bankroll: f64,
+    pub daily_pnl: f64,
+    pub consecutive_losses: u32,
+    pub consecutive_wins: u32,
+    pub window_start: Option<DateTime<Utc>>,
+    pub terminal_bets_this_window: u32,
+    pub exit_bets_this_window: u32,
+    pub pairs_this_window: u32,
+    pub last_terminal_bet_time: Option<DateTime<Utc>>,
+    pub last_exit_bet_time: Option<DateTime<Utc>>,
+    pub open_positions: Vec<PersistedPosition>,
+    pub hedged_pairs: Vec<HedgedPair>,
+    /// Plugin strategy fills this window (replayed into the registry's budgets)
+    #[serde(default)]
+    pub strategy_fills: Vec<Fill>,
+}
+
+/// One saved version of the state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub version: u64,
+    pub saved_at: DateTime<Utc>,
+    pub pod_id: String,
+    pub state: PersistedState,
+}
+
+/// What changed between two consecutive versions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StateEvent {
+    /// First snapshot ever written
+    Initialized { bankroll: f64 },
+    /// Daily P&L reset for a new UTC day
+    DayRolled { day: NaiveDate },
+    WindowRolled { window_start: DateTime<Utc> },
+    BetPlaced { strategy_type: String },
+    PositionOpened {
+        position_id: String,
+        direction: String,
+        strategy_type: String,
+        entry_price: f64,
+        shares: f64,
+    },
+    /// Sold, paired or settled
+    PositionClosed { position_id: String },
+    PairOpened { pair_id: String, origin: String, cost: f64 },
+    /// Realized P&L (exit or settlement) with the totals after it
+    PnlRealized { amount: f64, bankroll: f64, daily_pnl: f64 },
+    /// Anything else (sell_pending flags, cooldown timestamps, ...)
+    Updated,
+}
+
+impl StateEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            StateEvent::Initialized { .. } => "initialized",
+            StateEvent::DayRolled { .. } => "day_rolled",
+            StateEvent::WindowRolled { .. } => "window_rolled",
+            StateEvent::BetPlaced { .. } => "bet_placed",
+            StateEvent::PositionOpened { .. } => "position_opened",
+            StateEvent::PositionClosed { .. } => "position_closed",
+            StateEvent::PairOpened { .. } => "pair_opened",
+            StateEvent::PnlRealized { .. } => "pnl_realized",
+            StateEvent::Updated => "updated",
+        }
+    }
+}
+
+/// Event as written to the Redis stream and Postgres
+#[derive(Serialize)]
+struct EventRecord<'a> {
+    version: u64,
+    at: DateTime<Utc>,
+    pod_id: &'a str,
+    #[serde(flatten)]
+    event: &'a StateEvent,
+}
+
+/// Events that take `old` to `new` (never empty when they differ)
+pub fn diff(old: &PersistedState, new: &PersistedState) -> Vec<StateEvent> {
+    let mut events = Vec::new();
+
+    if let Some(day) = new.trading_day.filter(|d| old.trading_day != Some(*d)) {
+        events.push(StateEvent::DayRolled { day });
+    }
+    let same_window = old.window_start == new.window_start;
+    if let Some(window_start) = new.window_start.filter(|_| !same_window) {
+        events.push(StateEvent::WindowRolled { window_start });
+    }
+
+    // Bet counters restart at 0 on a new window
+    let (terminal_before, exit_before) = if same_window {
+        (old.terminal_bets_this_window, old.exit_bets_this_window)
+    } else {
+        (0, 0)
+    };
+    for _ in terminal_before..new.terminal_bets_this_window {
+        events.push(StateEvent::BetPlaced { strategy_type: "TERMINAL".to_string() });
+    }
+    for _ in exit_before..new.exit_bets_this_window {
+        events.push(StateEvent::BetPlaced { strategy_type: "EXIT".to_string() });
+    }
+
+    for p in &old.open_positions {
+        if !new.open_positions.iter().any(|n| n.position_id == p.position_id) {
+            events.push(StateEvent::PositionClosed { position_id: p.position_id.clone() });
+        }
+    }
+    for p in &new.open_positions {
+        if !old.open_positions.iter().any(|o| o.position_id == p.position_id) {
+            events.push(StateEvent::PositionOpened {
+                position_id: p.position_id.clone(),
+                direction: p.direction.clone(),
+                strategy_type: p.strategy_type.clone(),
+                entry_price: p.entry_price,
+                shares: p.shares,
+            });
+        }
+    }
+    for pair in &new.hedged_pairs {
+        if !old.hedged_pairs.iter().any(|o| o.pair_id == pair.pair_id) {
+            events.push(StateEvent::PairOpened {
+                pair_id: pair.pair_id.clone(),
+                origin: pair.origin.as_str().to_string(),
+                cost: pair.cost(),
+            });
+        }
+    }
+
+    let realized = new.bankroll - old.bankroll;
+    if realized != 0.0 {
+        events.push(StateEvent::PnlRealized {
+            amount: realized,
+            bankroll: new.bankroll,
+            daily_pnl: new.daily_pnl,
+        });
+    }
+
+    if events.is_empty() && old != new {
+        events.push(StateEvent::Updated);
+    }
+    events
+}
+
+/// Merge the pre-snapshot Redis keys into `base`: positions and bet counts of
+/// the current window (older positions were cleared at their window's end),
+/// plus the last bet times. None when they hold nothing for this window.
+pub fn migrate_legacy(base: &PersistedState, legacy: &LegacyState, window_start: DateTime<Utc>) -> Result<Option<PersistedState>> {
+    let positions: Vec<PersistedPosition> = match &legacy.positions_json {
+        Some(json) => serde_json::from_str(json).context("Invalid legacy positions in Redis")?,
+        None => Vec::new(),
+    };
+    let open_positions: Vec<PersistedPosition> = positions
+        .into_iter()
+        .filter(|p| p.window_start_ts == window_start.timestamp())
+        .collect();
+    if open_positions.is_empty() && legacy.terminal_bets == 0 && legacy.exit_bets == 0 {
+        return Ok(None);
+    }
+
+    let at = |ts: Option<i64>| ts.and_then(|ts| DateTime::from_timestamp(ts, 0));
+    Ok(Some(PersistedState {
+        trading_day: Some(window_start.date_naive()),
+        window_start: Some(window_start),
+        terminal_bets_this_window: legacy.terminal_bets,
+        exit_bets_this_window: legacy.exit_bets,
+        last_terminal_bet_time: at(legacy.last_terminal_bet_ts),
+        last_exit_bet_time: at(legacy.last_exit_bet_ts),
+        open_positions,
+        ..base.clone()
+    }))
+}
+
+/// Loads and saves versioned snapshots (Redis live copy, Postgres durable copy)
+pub struct StateStore {
+    redis: Option<RedisState>,
+    pod_id: String,
+    last: Option<StateSnapshot>,
+}
+
+impl StateStore {
+    pub fn new(redis: Option<RedisState>, pod_id: String) -> Self {
+        Self { redis, pod_id, last: None }
+    }
+
+    /// Version of the last snapshot loaded or saved (0 = none yet)
+    pub fn version(&self) -> u64 {
+        self.last.as_ref().map_or(0, |s| s.version)
+    }
+
+    /// Newest snapshot: Redis, else Postgres (then seeds Redis with it)
+    pub async fn load(&mut self, db: Option<&TradeDb>) -> Result<Option<PersistedState>> {
+        let mut snapshot: Option<StateSnapshot> = None;
+
+        if let Some(redis) = &self.redis {
+            match redis.load_state_snapshot().await {
+                Ok(Some(json)) => {
+                    snapshot = Some(serde_json::from_str(&json).context("Invalid state snapshot in Redis")?);
+                }
+                Ok(None) => info!("No state snapshot in Redis"),
+                Err(e) => {
+                    alerts::redis_error(&e);
+                    warn!("Failed to load state from Redis: {}", e);
+                }
+            }
+        }
+
+        if snapshot.is_none() {
+            if let Some(db) = db {
+                if let Some(value) = db.load_latest_state_snapshot().await? {
+                    let rebuilt: StateSnapshot =
+                        serde_json::from_value(value).context("Invalid state snapshot in Postgres")?;
+                    info!("Rebuilt state v{} from Postgres (saved {} by {})",
+                        rebuilt.version, rebuilt.saved_at.format("%Y-%m-%d %H:%M:%S UTC"), rebuilt.pod_id);
+
+                    if let Some(redis) = &self.redis {
+                        let json = serde_json::to_string(&rebuilt)?;
+                        if let Err(e) = redis.save_state_snapshot(&json, rebuilt.version, &[]).await {
+                            alerts::redis_error(&e);
+                            warn!("Failed to seed Redis with state v{}: {}", rebuilt.version, e);
+                        }
+                    }
+                    snapshot = Some(rebuilt);
+                }
+            }
+        }
+
+        let state = snapshot.as_ref().map(|s| s.state.clone());
+        self.last = snapshot;
+        Ok(state)
+    }
+
+    /// State under the pre-snapshot Redis keys merged into `base` (see
+    /// `migrate_legacy`). Only consulted when no snapshot exists; the next save
+    /// writes the first snapshot, so the old keys are read once.
+    pub async fn load_legacy(&self, base: &PersistedState, window_start: DateTime<Utc>) -> Result<Option<PersistedState>> {
+        let Some(redis) = &self.redis else {
+            return Ok(None);
+        };
+        let legacy = redis.load_legacy_state(window_start.timestamp()).await?;
+        migrate_legacy(base, &legacy, window_start)
+    }
+
+    /// Save `state` as the next version if it changed since the last
+    /// load/save. Returns the new version, or None if nothing changed.
+    ///
+    /// Fails if Redis already holds a newer version (another pod saved since
+    /// we loaded): reload before trading on this state.
+    pub async fn save(&mut self, state: PersistedState, db: Option<&TradeDb>) -> Result<Option<u64>> {
+        let events = match &self.last {
+            Some(last) if last.state == state => return Ok(None),
+            Some(last) => diff(&last.state, &state),
+            None => vec![StateEvent::Initialized { bankroll: state.bankroll }],
+        };
+
+        let snapshot = StateSnapshot {
+            version: self.version() + 1,
+            saved_at: Utc::now(),
+            pod_id: self.pod_id.clone(),
+            state,
+        };
+        let records = events
+            .iter()
+            .map(|event| EventRecord {
+                version: snapshot.version,
+                at: snapshot.saved_at,
+                pod_id: &self.pod_id,
+                event,
+            })
+            .map(|record| serde_json::to_value(&record))
+            .collect::<serde_json::Result<Vec<_>>>()?;
+
+        if let Some(redis) = &self.redis {
+            let json = serde_json::to_string(&snapshot)?;
+            let stream: Vec<String> = records.iter().map(|r| r.to_string()).collect();
+            match redis.save_state_snapshot(&json, snapshot.version, &stream).await {
+                Ok(true) => {}
+                Ok(false) => bail!("State v{} rejected: Redis already holds a newer snapshot", snapshot.version),
+                Err(e) => {
+                    alerts::redis_error(&e);
+                    warn!("Failed to save state v{} to Redis: {}", snapshot.version, e);
+                }
+            }
+        }
+
+        if let Some(db) = db {
+            let kinds: Vec<(&str, serde_json::Value)> =
+                events.iter().map(|e| e.kind()).zip(records).collect();
+            if let Err(e) = db
+                .save_state_snapshot(snapshot.version, &self.pod_id, &serde_json::to_value(&snapshot)?, &kinds)
+                .await
+            {
+                alerts::db_error(&e);
+                warn!("Failed to save state v{} to Postgres: {}", snapshot.version, e);
+            }
+        }
+
+        let version = snapshot.version;
+        self.last = Some(snapshot);
+        Ok(Some(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hedge::PairOrigin;
+    use chrono::TimeZone;
+
+    fn position(id: &str, window: DateTime<Utc>) -> PersistedPosition {
+        PersistedPosition {
+            position_id: id.to_string(),
+            token_id: "token".to_string(),
+            direction: "Up".to_string(),
+            entry_price: 0.40,
+            shares: 10.0,
+            entry_time_bucket: 4,
+            entry_delta_bucket: 2,
+            exit_target: 1.0,
+            window_start_ts: window.timestamp(),
+            sell_pending: false,
+            strategy_type: "TERMINAL".to_string(),
+            entry_seconds_elapsed: 130,
+        }
+    }
+
+    fn state(window: DateTime<Utc>) -> PersistedState {
+        PersistedState {
+            trading_day: Some(window.date_naive()),
+            bankroll: 1000.0,
+            daily_pnl: -12.5,
+            consecutive_losses: 2,
+            consecutive_wins: 0,
+            window_start: Some(window),
+            terminal_bets_this_window: 0,
+            exit_bets_this_window: 0,
+            pairs_this_window: 0,
+            last_terminal_bet_time: None,
+            last_exit_bet_time: None,
+            open_positions: vec![],
+            hedged_pairs: vec![],
+            strategy_fills: vec![],
+        }
+    }
+
+    #[test]
+    fn test_diff_and_snapshot_round_trip() {
+        let w1 = Utc.with_ymd_and_hms(2026, 3, 9, 23, 45, 0).unwrap();
+        let w2 = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
+
+        // Bet placed: one counter step and the new position
+        let before = state(w1);
+        let mut after = before.clone();
+        after.terminal_bets_this_window = 1;
+        after.last_terminal_bet_time = Some(w1 + chrono::Duration::seconds(130));
+        after.open_positions.push(position("p1", w1));
+        assert_eq!(diff(&before, &after), vec![
+            StateEvent::BetPlaced { strategy_type: "TERMINAL".to_string() },
+            StateEvent::PositionOpened {
+                position_id: "p1".to_string(),
+                direction: "Up".to_string(),
+                strategy_type: "TERMINAL".to_string(),
+                entry_price: 0.40,
+                shares: 10.0,
+            },
+        ]);
+
+        // Settlement into the next day: position gone, P&L realized, counters restart
+        let mut settled = after.clone();
+        settled.trading_day = Some(w2.date_naive());
+        settled.window_start = Some(w2);
+        settled.terminal_bets_this_window = 0;
+        settled.open_positions.clear();
+        settled.bankroll += 6.0;
+        settled.daily_pnl = 0.0;
+        settled.hedged_pairs.push(HedgedPair {
+            pair_id: "pair1".to_string(),
+            origin: PairOrigin::Pair,
+            window_start: w2,
+            up_shares: 5.0,
+            up_price: 0.45,
+            down_shares: 5.0,
+            down_price: 0.50,
+        });
+        let events = diff(&after, &settled);
+        assert_eq!(events[0], StateEvent::DayRolled { day: w2.date_naive() });
+        assert_eq!(events[1], StateEvent::WindowRolled { window_start: w2 });
+        assert_eq!(events[2], StateEvent::PositionClosed { position_id: "p1".to_string() });
+        assert!(matches!(events[3], StateEvent::PairOpened { ref pair_id, .. } if pair_id == "pair1"));
+        assert_eq!(events[4], StateEvent::PnlRealized { amount: 6.0, bankroll: 1006.0, daily_pnl: 0.0 });
+        assert_eq!(events.len(), 5);
+
+        // Flag-only changes still produce an event; identical states none
+        let mut flagged = after.clone();
+        flagged.open_positions[0].sell_pending = true;
+        assert_eq!(diff(&after, &flagged), vec![StateEvent::Updated]);
+        assert!(diff(&after, &after).is_empty());
+
+        // Snapshot survives the JSON round trip through Redis/Postgres unchanged
+        let snapshot = StateSnapshot { version: 7, saved_at: w2, pod_id: "pod-a".to_string(), state: settled.clone() };
+        let restored: StateSnapshot = serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+        assert_eq!(restored.version, 7);
+        assert_eq!(restored.state, settled);
+    }
+
+    #[test]
+    fn test_migrate_legacy() {
+        let w1 = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
+        let w0 = w1 - chrono::Duration::minutes(15);
+        let base = PersistedState { window_start: None, ..state(w1) };
+
+        // Old positions JSON: only the current window's position carries over
+        let legacy = LegacyState {
+            positions_json: Some(serde_json::to_string(&vec![position("old", w0), position("p1", w1)]).unwrap()),
+            terminal_bets: 1,
+            exit_bets: 0,
+            last_terminal_bet_ts: Some(w1.timestamp() + 130),
+            last_exit_bet_ts: None,
+        };
+        let migrated = migrate_legacy(&base, &legacy, w1).unwrap().unwrap();
+        assert_eq!(migrated.window_start, Some(w1));
+        assert_eq!(migrated.terminal_bets_this_window, 1);
+        assert_eq!(migrated.last_terminal_bet_time, Some(w1 + chrono::Duration::seconds(130)));
+        assert_eq!(migrated.open_positions, vec![position("p1", w1)]);
+        assert_eq!(migrated.bankroll, base.bankroll);
+
+        // Nothing for this window
+        let stale = LegacyState { positions_json: Some(serde_json::to_string(&vec![position("old", w0)]).unwrap()), ..Default::default() };
+        assert!(migrate_legacy(&base, &stale, w1).unwrap().is_none());
+        assert!(migrate_legacy(&base, &LegacyState::default(), w1).unwrap().is_none());
+    }
+}
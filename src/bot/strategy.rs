@@ -1,7 +1,11 @@
 use super::config::BotConfig;
 use super::polymarket::PriceQuote;
-use crate::models::{delta_to_bucket, CellStats, ConfidenceLevel, FirstPassageMatrix, PriceCrossingMatrix, ProbabilityMatrix};
+use crate::models::{
+    delta_to_bucket, reach_level_to_price, CellStats, ConfidenceLevel, ExitPolicy, FirstPassageMatrix,
+    MarketReachMatrix, Outcome, PriceCrossingMatrix, ProbabilityMatrix, MARKET_REACH_LEVELS,
+};
 use rust_decimal::Decimal;
+use serde::Serialize;
 use tracing::{debug, info, warn};
 
 /// Map target price (0.0-1.0) to delta bucket for exit strategy
@@ -17,7 +21,7 @@ use tracing::{debug, info, warn};
 ///   75¢ → ~+$130 (bucket 10)
 ///   85¢ → ~+$200 (bucket 12)
 ///   95¢ → ~+$280 (bucket 15)
-fn price_to_delta_bucket(target_price: f64, direction: BetDirection) -> i8 {
+pub fn price_to_delta_bucket(target_price: f64, direction: BetDirection) -> i8 {
     let price_pct = (target_price * 100.0) as i32;
 
     match direction {
@@ -63,14 +67,14 @@ fn price_to_delta_bucket(target_price: f64, direction: BetDirection) -> i8 {
 }
 
 /// Direction to bet
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum BetDirection {
     Up,
     Down,
 }
 
 /// Decision from the strategy
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BetDecision {
     pub should_bet: bool,
     pub direction: Option<BetDirection>,
@@ -82,6 +86,88 @@ pub struct BetDecision {
     pub reason: String,
     pub exit_target: Option<f64>,  // None = terminal strategy (hold), Some(price) = exit strategy
     pub strategy_type: String,     // "TERMINAL" or "EXIT"
+    pub reason_code: ReasonCode,   // Machine-readable version of `reason`
+    pub trace: DecisionTrace,      // Inputs and gate results behind this decision
+}
+
+/// Why a tick did (BET) or didn't result in a buy
+///
+/// The first group is produced by `StrategyContext::decide`; the second by the
+/// bot's own checks after a positive decision (pending sells, limits, cooldown).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReasonCode {
+    Bet,
+    TooEarly,
+    DailyLossLimit,
+    PriceDeltaTooLarge,
+    InsufficientLiquidity,
+    InsufficientSamples,
+    NoStrategyAvailable,
+    ConfidenceNotAllowed,
+    DeltaAlignment,
+    SpreadTooWide,
+    NoEdge,
+    BetTooSmall,
+    // Bot-level skips
+    PendingSells,
+    MaxPositions,
+    Cooldown,
+    BetPending,
+}
+
+/// Individual filter evaluated by `decide`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Gate {
+    Timing,
+    DailyLoss,
+    PriceDelta,
+    Liquidity,
+    SampleSize,
+    StrategyAvailable,
+    Confidence,
+    TerminalEdge,
+    ExitEdge,
+    Spread,
+    DeltaAlignment,
+    MaxEntryPrice,
+    MinProbability,
+    BetSize,
+}
+
+/// Result of one gate (side = None for checks shared by both directions)
+#[derive(Debug, Clone, Serialize)]
+pub struct GateCheck {
+    pub gate: Gate,
+    pub side: Option<BetDirection>,
+    pub passed: bool,
+}
+
+/// Everything `decide` computed, filled in as far as evaluation got
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DecisionTrace {
+    pub time_bucket: Option<u8>,
+    pub delta_bucket: Option<i8>,
+    pub cell_samples: Option<f64>,
+    pub cell_p_up: Option<f64>,
+    pub our_p_up: Option<f64>,
+    pub our_p_down: Option<f64>,
+    pub terminal_up_edge: Option<f64>,
+    pub terminal_down_edge: Option<f64>,
+    pub exit_up_ev: Option<f64>,
+    pub exit_down_ev: Option<f64>,
+    pub exit_up_target: Option<f64>,
+    pub exit_down_target: Option<f64>,
+    pub gates: Vec<GateCheck>,
+}
+
+impl DecisionTrace {
+    /// Record a gate result and pass it through
+    fn gate(&mut self, gate: Gate, side: Option<BetDirection>, passed: bool) -> bool {
+        self.gates.push(GateCheck { gate, side, passed });
+        passed
+    }
 }
 
 /// Strategy context for making decisions
@@ -90,6 +176,7 @@ pub struct StrategyContext<'a> {
     pub matrix: &'a ProbabilityMatrix,
     pub fp_matrix: Option<&'a FirstPassageMatrix>,  // For exit strategy EV calculation (legacy)
     pub crossing_matrix: Option<&'a PriceCrossingMatrix>,  // For crossing-based exit strategy
+    pub market_reach_matrix: Option<&'a MarketReachMatrix>,  // Observed Polymarket prices (preferred)
     pub bankroll: f64,
     pub consecutive_losses: u32,
     pub terminal_bets_this_window: u32,  // Bets from terminal strategy
@@ -108,6 +195,29 @@ pub struct ExitStrategyResult {
 }
 
 impl<'a> StrategyContext<'a> {
+    /// Today's losses have reached `daily_loss_limit_pct` of the bankroll
+    pub fn daily_loss_limit_hit(&self) -> bool {
+        let daily_loss_limit = self.bankroll * self.config.risk.daily_loss_limit_pct;
+        self.daily_pnl < 0.0 && self.daily_pnl.abs() >= daily_loss_limit
+    }
+
+    /// Shared entry gates for buying one side outside `decide` (plugin intents):
+    /// daily loss limit, BTC move size, and that side's spread and ask liquidity.
+    /// None = the side may be bought.
+    pub fn entry_blocked(&self, price_delta: f64, quote: &PriceQuote) -> Option<ReasonCode> {
+        if self.daily_loss_limit_hit() {
+            Some(ReasonCode::DailyLossLimit)
+        } else if price_delta.abs() > self.config.price_filters.max_price_delta {
+            Some(ReasonCode::PriceDeltaTooLarge)
+        } else if quote.spread_pct > self.config.markets.max_spread_pct {
+            Some(ReasonCode::SpreadTooWide)
+        } else if quote.ask_liquidity < self.config.markets.min_liquidity_usdc {
+            Some(ReasonCode::InsufficientLiquidity)
+        } else {
+            None
+        }
+    }
+
     /// Make a betting decision based on current market state
     pub fn decide(
         &self,
@@ -115,6 +225,20 @@ impl<'a> StrategyContext<'a> {
         price_delta: f64,
         up_quote: &PriceQuote,
         down_quote: &PriceQuote,
+    ) -> BetDecision {
+        let mut trace = DecisionTrace::default();
+        let mut decision = self.evaluate(time_elapsed, price_delta, up_quote, down_quote, &mut trace);
+        decision.trace = trace;
+        decision
+    }
+
+    fn evaluate(
+        &self,
+        time_elapsed: u32,
+        price_delta: f64,
+        up_quote: &PriceQuote,
+        down_quote: &PriceQuote,
+        trace: &mut DecisionTrace,
     ) -> BetDecision {
         let time_remaining = 900u32.saturating_sub(time_elapsed);
 
@@ -123,8 +247,8 @@ impl<'a> StrategyContext<'a> {
         // ═══════════════════════════════════════════════════════════════
 
         // Check timing - too early
-        if time_elapsed < self.config.timing.min_seconds_elapsed {
-            return BetDecision::no_bet(format!(
+        if !trace.gate(Gate::Timing, None, time_elapsed >= self.config.timing.min_seconds_elapsed) {
+            return BetDecision::no_bet(ReasonCode::TooEarly, format!(
                 "Too early in window: {}s < {}s minimum",
                 time_elapsed, self.config.timing.min_seconds_elapsed
             ));
@@ -135,8 +259,8 @@ impl<'a> StrategyContext<'a> {
 
         // Check daily loss limit
         let daily_loss_limit = self.bankroll * self.config.risk.daily_loss_limit_pct;
-        if self.daily_pnl < 0.0 && self.daily_pnl.abs() >= daily_loss_limit {
-            return BetDecision::no_bet(format!(
+        if !trace.gate(Gate::DailyLoss, None, !self.daily_loss_limit_hit()) {
+            return BetDecision::no_bet(ReasonCode::DailyLossLimit, format!(
                 "Daily loss limit reached: ${:.2} >= ${:.2}",
                 self.daily_pnl.abs(),
                 daily_loss_limit
@@ -144,8 +268,8 @@ impl<'a> StrategyContext<'a> {
         }
 
         // Check price filters
-        if price_delta.abs() > self.config.price_filters.max_price_delta {
-            return BetDecision::no_bet(format!(
+        if !trace.gate(Gate::PriceDelta, None, price_delta.abs() <= self.config.price_filters.max_price_delta) {
+            return BetDecision::no_bet(ReasonCode::PriceDeltaTooLarge, format!(
                 "Price delta too large: ${:.2} > ${:.2}",
                 price_delta.abs(),
                 self.config.price_filters.max_price_delta
@@ -159,8 +283,9 @@ impl<'a> StrategyContext<'a> {
 
         // Check liquidity
         let min_liquidity = self.config.markets.min_liquidity_usdc;
-        if up_quote.ask_liquidity < min_liquidity && down_quote.ask_liquidity < min_liquidity {
-            return BetDecision::no_bet(format!(
+        let has_liquidity = up_quote.ask_liquidity >= min_liquidity || down_quote.ask_liquidity >= min_liquidity;
+        if !trace.gate(Gate::Liquidity, None, has_liquidity) {
+            return BetDecision::no_bet(ReasonCode::InsufficientLiquidity, format!(
                 "Insufficient liquidity: UP=${:.2}, DOWN=${:.2} < ${:.2}",
                 up_quote.ask_liquidity, down_quote.ask_liquidity, min_liquidity
             ));
@@ -173,12 +298,16 @@ impl<'a> StrategyContext<'a> {
         );
 
         let cell = self.matrix.get(time_bucket, delta_bucket);
-
-        // Check sample size
-        if cell.total() < self.config.timing.min_samples_in_bucket {
-            return BetDecision::no_bet(format!(
-                "Insufficient samples in bucket: {} < {}",
-                cell.total(),
+        trace.time_bucket = Some(time_bucket);
+        trace.delta_bucket = Some(delta_bucket);
+        trace.cell_samples = Some(cell.sample_size());
+        trace.cell_p_up = Some(cell.p_up);
+
+        // Check sample size (effective count: decay / regime weighting shrinks it below the raw total)
+        if !trace.gate(Gate::SampleSize, None, cell.sample_size() >= self.config.timing.min_samples_in_bucket as f64) {
+            return BetDecision::no_bet(ReasonCode::InsufficientSamples, format!(
+                "Insufficient samples in bucket: {:.1} < {}",
+                cell.sample_size(),
                 self.config.timing.min_samples_in_bucket
             ));
         }
@@ -192,14 +321,14 @@ impl<'a> StrategyContext<'a> {
             && time_remaining >= self.config.terminal_strategy.min_seconds_remaining
             && self.terminal_bets_this_window < self.config.terminal_strategy.max_bets_per_window;
 
-        // Check if exit strategy can bet (crossing_matrix preferred, fp_matrix as fallback)
+        // Check if exit strategy can bet (market_reach > crossing > fp_matrix)
         let exit_available = self.config.exit_strategy.enabled
             && time_remaining >= self.config.exit_strategy.min_seconds_remaining
             && self.exit_bets_this_window < self.config.exit_strategy.max_bets_per_window
-            && (self.crossing_matrix.is_some() || self.fp_matrix.is_some());
+            && (self.market_reach_matrix.is_some() || self.crossing_matrix.is_some() || self.fp_matrix.is_some());
 
-        if !terminal_available && !exit_available {
-            return BetDecision::no_bet(format!(
+        if !trace.gate(Gate::StrategyAvailable, None, terminal_available || exit_available) {
+            return BetDecision::no_bet(ReasonCode::NoStrategyAvailable, format!(
                 "No strategy available: terminal={} ({}s rem, {}/{} bets), exit={} ({}s rem, {}/{} bets)",
                 terminal_available, time_remaining,
                 self.terminal_bets_this_window, self.config.terminal_strategy.max_bets_per_window,
@@ -210,8 +339,8 @@ impl<'a> StrategyContext<'a> {
 
         // Get confidence-based minimum edge (used as baseline, strategies have their own)
         let confidence_str = format!("{:?}", cell.confidence_level);
-        if self.config.min_edge_for_confidence(&confidence_str).is_none() {
-            return BetDecision::no_bet(format!(
+        if !trace.gate(Gate::Confidence, None, self.config.min_edge_for_confidence(&confidence_str).is_some()) {
+            return BetDecision::no_bet(ReasonCode::ConfidenceNotAllowed, format!(
                 "Confidence level {:?} not allowed",
                 cell.confidence_level
             ));
@@ -227,6 +356,8 @@ impl<'a> StrategyContext<'a> {
         // Use Wilson lower bound for conservative probability estimate
         let our_p_up = cell.p_up_wilson_lower;
         let our_p_down = 1.0 - cell.p_up_wilson_upper;
+        trace.our_p_up = Some(our_p_up);
+        trace.our_p_down = Some(our_p_down);
 
         // ═══════════════════════════════════════════════════════════════
         // STRATEGY 1: TERMINAL EDGE (original strategy - hold to settlement)
@@ -240,9 +371,15 @@ impl<'a> StrategyContext<'a> {
 
         // ═══════════════════════════════════════════════════════════════
         // STRATEGY 2: EXIT STRATEGY EV (new strategy - sell at target)
-        // Prefer crossing_matrix (dynamic targets) over fp_matrix (legacy)
+        // Prefer market_reach_matrix (observed prices), then crossing_matrix
+        // (modeled prices), then fp_matrix (legacy)
         // ═══════════════════════════════════════════════════════════════
-        let (up_exit_result, down_exit_result) = if self.crossing_matrix.is_some() {
+        let (up_exit_result, down_exit_result) = if self.market_reach_matrix.is_some() {
+            (
+                self.calculate_market_reach_exit_ev(time_bucket, delta_bucket, BetDirection::Up, up_entry_price),
+                self.calculate_market_reach_exit_ev(time_bucket, delta_bucket, BetDirection::Down, down_entry_price),
+            )
+        } else if self.crossing_matrix.is_some() {
             // Use crossing-based calculation (more conservative, higher hit rate)
             (
                 self.calculate_crossing_exit_ev(time_bucket, delta_bucket, BetDirection::Up, up_entry_price),
@@ -259,6 +396,13 @@ impl<'a> StrategyContext<'a> {
         let up_exit_ev = up_exit_result.as_ref().map(|r| r.ev_return_pct).unwrap_or(0.0);
         let down_exit_ev = down_exit_result.as_ref().map(|r| r.ev_return_pct).unwrap_or(0.0);
 
+        trace.terminal_up_edge = Some(terminal_up_edge);
+        trace.terminal_down_edge = Some(terminal_down_edge);
+        trace.exit_up_ev = Some(up_exit_ev);
+        trace.exit_down_ev = Some(down_exit_ev);
+        trace.exit_up_target = up_exit_result.as_ref().map(|r| r.best_target);
+        trace.exit_down_target = down_exit_result.as_ref().map(|r| r.best_target);
+
         debug!(
             "Terminal edges: UP={:.2}%, DOWN={:.2}% | Exit EV: UP={:.2}%, DOWN={:.2}%",
             terminal_up_edge * 100.0, terminal_down_edge * 100.0,
@@ -301,6 +445,23 @@ impl<'a> StrategyContext<'a> {
         let prob_allows_up = our_p_up >= min_prob;
         let prob_allows_down = our_p_down >= min_prob;
 
+        // Record per-side gate results for the decision log
+        for (side, spread, delta_ok, price_ok, prob_ok, term_edge, exit_ev) in [
+            (BetDirection::Up, up_spread, delta_allows_up, price_allows_up, prob_allows_up, terminal_up_edge, up_exit_ev),
+            (BetDirection::Down, down_spread, delta_allows_down, price_allows_down, prob_allows_down, terminal_down_edge, down_exit_ev),
+        ] {
+            if terminal_available {
+                trace.gate(Gate::TerminalEdge, Some(side), term_edge >= terminal_min_edge);
+            }
+            if exit_available {
+                trace.gate(Gate::ExitEdge, Some(side), exit_ev >= exit_min_edge);
+            }
+            trace.gate(Gate::Spread, Some(side), spread <= max_spread);
+            trace.gate(Gate::DeltaAlignment, Some(side), delta_ok);
+            trace.gate(Gate::MaxEntryPrice, Some(side), price_ok);
+            trace.gate(Gate::MinProbability, Some(side), prob_ok);
+        }
+
         // Log filter states at DEBUG level (use RUST_LOG=debug to see)
         debug!(
             "FILTERS: delta={:.1} align_req={} | UP: delta={} price={} ({:.2}<={}?) prob={} ({:.1}%>={:.0}%?) | DOWN: delta={} price={} ({:.2}<={}?) prob={} ({:.1}%>={:.0}%?)",
@@ -398,7 +559,7 @@ impl<'a> StrategyContext<'a> {
                 let down_blocked_by_delta = down_has_edge && down_spread_ok && !delta_allows_down;
 
                 if up_blocked_by_delta || down_blocked_by_delta {
-                    return BetDecision::no_bet(format!(
+                    return BetDecision::no_bet(ReasonCode::DeltaAlignment, format!(
                         "Delta alignment filter: delta={:.1}$ → {} | UP edge={:.1}%{}, DOWN edge={:.1}%{}",
                         price_delta,
                         if price_delta > 0.0 { "only UP allowed" } else { "only DOWN allowed" },
@@ -410,7 +571,7 @@ impl<'a> StrategyContext<'a> {
 
             // If there was edge but spread blocked it, show that
             if (up_has_edge && !up_spread_ok) || (down_has_edge && !down_spread_ok) {
-                return BetDecision::no_bet(format!(
+                return BetDecision::no_bet(ReasonCode::SpreadTooWide, format!(
                     "Spread blocks edge: UP spread={:.1}%{} (edge={:.1}%), DOWN spread={:.1}%{} (edge={:.1}%) | max spread={:.1}%",
                     up_spread * 100.0, if up_spread_ok { "✓" } else { "✗" }, terminal_up_edge * 100.0,
                     down_spread * 100.0, if down_spread_ok { "✓" } else { "✗" }, terminal_down_edge * 100.0,
@@ -418,7 +579,7 @@ impl<'a> StrategyContext<'a> {
                 ));
             }
 
-            return BetDecision::no_bet(format!(
+            return BetDecision::no_bet(ReasonCode::NoEdge, format!(
                 "No edge: terminal UP={:.1}%/{:.1}% DOWN={:.1}%/{:.1}%, exit UP={:.1}%/{:.1}% DOWN={:.1}%/{:.1}%",
                 terminal_up_edge * 100.0, terminal_min_edge * 100.0,
                 terminal_down_edge * 100.0, terminal_min_edge * 100.0,
@@ -472,8 +633,8 @@ impl<'a> StrategyContext<'a> {
             self.config.betting.kelly_fraction * 100.0,
             self.config.confidence_multiplier(&format!("{:?}", cell.confidence_level)));
 
-        if bet_amount < self.config.betting.min_bet_usdc {
-            return BetDecision::no_bet(format!(
+        if !trace.gate(Gate::BetSize, None, bet_amount >= self.config.betting.min_bet_usdc) {
+            return BetDecision::no_bet(ReasonCode::BetTooSmall, format!(
                 "Bet size too small: ${:.2} < ${:.2}",
                 bet_amount, self.config.betting.min_bet_usdc
             ));
@@ -513,45 +674,21 @@ impl<'a> StrategyContext<'a> {
             ),
             exit_target,
             strategy_type,
+            reason_code: ReasonCode::Bet,
+            trace: DecisionTrace::default(),
         }
     }
 
     /// Calculate bet size using fractional Kelly
     fn calculate_bet_size(&self, edge: f64, market_prob: f64, cell: &CellStats) -> f64 {
-        // Kelly formula: (p*b - q) / b
-        // where p = our probability, b = odds - 1, q = 1 - p
-        let odds = 1.0 / market_prob;
-        let b = odds - 1.0;
-        let p = market_prob + edge * market_prob; // our probability
-        let q = 1.0 - p;
-
-        let kelly = if b > 0.0 { (p * b - q) / b } else { 0.0 };
-        let kelly = kelly.max(0.0); // Never negative
-
-        // Apply fractional Kelly
-        let fraction = self.config.betting.kelly_fraction;
-
-        // Apply confidence multiplier
         let confidence_str = format!("{:?}", cell.confidence_level);
-        let confidence_mult = self.config.confidence_multiplier(&confidence_str);
-
-        // Apply loss reduction
-        let loss_mult = self
-            .config
-            .risk
-            .loss_reduction_factor
-            .powi(self.consecutive_losses as i32);
-
-        let adjusted_kelly = kelly * fraction * confidence_mult * loss_mult;
-
-        // Calculate bet amount
-        let mut bet = self.bankroll * adjusted_kelly;
-
-        // Apply caps
-        bet = bet.min(self.bankroll * self.config.betting.max_bet_pct);
-        bet = bet.min(self.config.betting.max_bet_usdc);
-
-        bet
+        self.config.kelly_bet_size(
+            self.bankroll,
+            edge,
+            market_prob,
+            &confidence_str,
+            self.consecutive_losses,
+        )
     }
 
     /// Calculate the best exit strategy EV for a given entry
@@ -634,6 +771,48 @@ impl<'a> StrategyContext<'a> {
         })
     }
 
+    /// Calculate the best exit strategy EV using the MARKET REACH MATRIX
+    ///
+    /// P(reach) comes straight from historical token prices: how often the bid
+    /// reached each target from the same (time bucket, ask) state
+    pub fn calculate_market_reach_exit_ev(
+        &self,
+        time_bucket: u8,
+        delta_bucket: i8,
+        direction: BetDirection,
+        entry_price: f64,
+    ) -> Option<ExitStrategyResult> {
+        let market_matrix = self.market_reach_matrix?;
+        let terminal_cell = self.matrix.get(time_bucket, delta_bucket);
+
+        let p_win = match direction {
+            BetDirection::Up => terminal_cell.p_up,
+            BetDirection::Down => terminal_cell.p_down,
+        };
+
+        let (best_target, best_ev, best_p_reach) = best_market_reach_target(
+            market_matrix,
+            time_bucket,
+            entry_price,
+            entry_price,
+            p_win,
+            self.config.exit_strategy.market_reach_min_samples,
+        )?;
+
+        let ev_return_pct = if entry_price > 0.0 {
+            best_ev / entry_price
+        } else {
+            0.0
+        };
+
+        Some(ExitStrategyResult {
+            best_target,
+            best_ev,
+            ev_return_pct,
+            p_reach_target: best_p_reach,
+        })
+    }
+
     /// Calculate the best exit strategy EV using CROSSING MATRIX
     ///
     /// This uses P(reach) = 1 - e^(-avg_crossings) (Poisson approximation)
@@ -716,7 +895,7 @@ impl<'a> StrategyContext<'a> {
 }
 
 impl BetDecision {
-    pub fn no_bet(reason: String) -> Self {
+    pub fn no_bet(reason_code: ReasonCode, reason: String) -> Self {
         Self {
             should_bet: false,
             direction: None,
@@ -728,6 +907,8 @@ impl BetDecision {
             reason,
             exit_target: None,
             strategy_type: "NONE".to_string(),
+            reason_code,
+            trace: DecisionTrace::default(),
         }
     }
 }
@@ -1165,6 +1346,248 @@ pub fn decide_exit_crossing(
     ))
 }
 
+// ============================================================================
+// MARKET REACH EXIT STRATEGY (observed Polymarket prices)
+// ============================================================================
+
+/// Best exit target for a position from the market reach matrix
+///
+/// `current_ask` selects the state, `entry_price` sets the gain, `p_win` gives the
+/// hold-to-settlement EV. Returns (target, EV, P(reach)) - target 1.0 = hold.
+/// None if the state has fewer than `min_samples` observed paths.
+pub fn best_market_reach_target(
+    market_matrix: &MarketReachMatrix,
+    time_bucket: u8,
+    current_ask: f64,
+    entry_price: f64,
+    p_win: f64,
+    min_samples: u32,
+) -> Option<(f64, f64, f64)> {
+    let state = market_matrix.get(time_bucket, current_ask);
+    if state.count_total < min_samples {
+        return None;
+    }
+
+    let hold_ev = p_win * (1.0 - entry_price) - (1.0 - p_win) * entry_price;
+
+    let mut best_ev = hold_ev;
+    let mut best_target = 1.0; // 1.0 means hold
+    let mut best_p_reach = p_win;
+
+    for level in 0..MARKET_REACH_LEVELS {
+        let target_price = reach_level_to_price(level);
+
+        // Skip targets at or below entry (no profit)
+        if target_price <= entry_price + 0.01 || target_price >= 1.0 {
+            continue;
+        }
+
+        let p_reach = state.p_reach[level];
+        let gain = target_price - entry_price;
+        let exit_ev = p_reach * gain + (1.0 - p_reach) * hold_ev;
+
+        if exit_ev > best_ev {
+            best_ev = exit_ev;
+            best_target = target_price;
+            best_p_reach = p_reach;
+        }
+    }
+
+    Some((best_target, best_ev, best_p_reach))
+}
+
+/// Per-tick state of an open position for the exit decision
+#[derive(Debug, Clone, Copy)]
+pub struct ExitTick {
+    pub time_bucket: u8,
+    pub delta_bucket: i8,
+    pub direction: BetDirection,
+    pub entry_price: f64,
+    pub current_bid: f64,
+    pub current_ask: f64,
+}
+
+/// Dynamic exit decision using the market reach matrix
+/// Same rule as `decide_exit_crossing`: sell when the bid beats the EV of the
+/// best target from the CURRENT (time, ask) state, recalculated every tick.
+pub fn decide_exit_market(
+    config: &BotConfig,
+    market_matrix: &MarketReachMatrix,
+    terminal_matrix: &ProbabilityMatrix,
+    tick: &ExitTick,
+) -> ExitDecision {
+    let ExitTick { time_bucket, delta_bucket, direction, entry_price, current_bid, current_ask } = *tick;
+    if !config.exit_strategy.enabled {
+        return ExitDecision::no_exit("Exit strategy disabled".to_string());
+    }
+
+    let terminal_cell = terminal_matrix.get(time_bucket, delta_bucket);
+    let p_win = match direction {
+        BetDirection::Up => terminal_cell.p_up,
+        BetDirection::Down => terminal_cell.p_down,
+    };
+
+    let profit_if_sell_now = current_bid - entry_price;
+
+    let Some((best_target, best_ev, best_p_reach)) = best_market_reach_target(
+        market_matrix,
+        time_bucket,
+        current_ask,
+        entry_price,
+        p_win,
+        config.exit_strategy.market_reach_min_samples,
+    ) else {
+        return ExitDecision::no_exit(format!(
+            "bid={:.0}¢ | HOLD (no market data at ask {:.0}¢)",
+            current_bid * 100.0,
+            current_ask * 100.0
+        ));
+    };
+
+    if profit_if_sell_now > best_ev {
+        info!(
+            "SELL NOW! profit {:.1}¢ > market EV {:.1}¢ | bid={:.0}¢ entry={:.0}¢",
+            profit_if_sell_now * 100.0,
+            best_ev * 100.0,
+            current_bid * 100.0,
+            entry_price * 100.0
+        );
+
+        return ExitDecision {
+            should_exit: true,
+            exit_price: current_bid,
+            ev_exit: profit_if_sell_now,
+            ev_hold: best_ev,
+            ev_improvement: (profit_if_sell_now - best_ev) * 100.0,
+            reason: format!(
+                "Profit {:.1}¢ > MarketEV {:.1}¢",
+                profit_if_sell_now * 100.0,
+                best_ev * 100.0
+            ),
+        };
+    }
+
+    let target_str = if best_target < 1.0 {
+        format!("{:.0}¢", best_target * 100.0)
+    } else {
+        "HOLD".to_string()
+    };
+
+    ExitDecision::no_exit(format!(
+        "bid={:.0}¢ | target={} P={:.0}% | fwd_ev={:.1}¢ | P(win)={:.0}%",
+        current_bid * 100.0,
+        target_str,
+        best_p_reach * 100.0,
+        best_ev * 100.0,
+        p_win * 100.0
+    ))
+}
+
+// ============================================================================
+// OPTIMAL STOPPING EXIT POLICY
+// ============================================================================
+
+/// Exit decision from the precomputed optimal stopping policy
+///
+/// Sells when the current bid is worth at least the continuation value of the
+/// (time, delta, bid) state. Entry price is sunk and plays no part in the rule.
+/// None if the state has fewer than `exit_policy_min_samples` transitions, so
+/// the caller can fall back to the target-based exits.
+pub fn decide_exit_policy(
+    config: &BotConfig,
+    policy: &ExitPolicy,
+    time_bucket: u8,
+    delta_bucket: i8,
+    direction: BetDirection,
+    entry_price: f64,
+    current_bid: f64,
+) -> Option<ExitDecision> {
+    if !config.exit_strategy.enabled {
+        return Some(ExitDecision::no_exit("Exit strategy disabled".to_string()));
+    }
+
+    let token = match direction {
+        BetDirection::Up => Outcome::Up,
+        BetDirection::Down => Outcome::Down,
+    };
+    let cell = policy.get(token, time_bucket, delta_bucket);
+
+    // Last bucket has no transitions but its hold value is exact (settlement)
+    if cell.samples < config.exit_strategy.exit_policy_min_samples && time_bucket < 59 {
+        return None;
+    }
+
+    let hold_value = cell.continuation_at(current_bid);
+    let ev_exit = current_bid - entry_price;
+    let ev_hold = hold_value - entry_price;
+
+    if cell.should_sell(current_bid) {
+        info!(
+            "SELL NOW! bid {:.0}¢ >= hold value {:.1}¢ | entry={:.0}¢ P(win)={:.0}%",
+            current_bid * 100.0,
+            hold_value * 100.0,
+            entry_price * 100.0,
+            cell.p_win * 100.0
+        );
+
+        return Some(ExitDecision {
+            should_exit: true,
+            exit_price: current_bid,
+            ev_exit,
+            ev_hold,
+            ev_improvement: (ev_exit - ev_hold) * 100.0,
+            reason: format!(
+                "Policy: bid {:.0}¢ >= hold value {:.1}¢",
+                current_bid * 100.0,
+                hold_value * 100.0
+            ),
+        });
+    }
+
+    let threshold_str = match cell.sell_threshold {
+        Some(t) => format!("{:.0}¢", t * 100.0),
+        None => "HOLD".to_string(),
+    };
+
+    Some(ExitDecision::no_exit(format!(
+        "bid={:.0}¢ | policy sell at {} | hold value={:.1}¢ | P(win)={:.0}%",
+        current_bid * 100.0,
+        threshold_str,
+        hold_value * 100.0,
+        cell.p_win * 100.0
+    )))
+}
+
+// ============================================================================
+// FIXED TARGET EXIT (PLUGIN STRATEGIES)
+// ============================================================================
+
+/// Sell as soon as the bid reaches the target the strategy set at entry.
+/// Used for plugin positions (e.g. cheap_buy) that own their exit rule.
+pub fn decide_exit_fixed(entry_price: f64, current_bid: f64, exit_target: f64) -> ExitDecision {
+    if exit_target >= 1.0 || current_bid < exit_target {
+        return ExitDecision::no_exit(format!(
+            "bid={:.0}¢ | fixed target={:.0}¢",
+            current_bid * 100.0,
+            exit_target * 100.0
+        ));
+    }
+
+    let ev_exit = current_bid - entry_price;
+    ExitDecision {
+        should_exit: true,
+        exit_price: current_bid,
+        ev_exit,
+        ev_hold: 0.0,
+        ev_improvement: 0.0,
+        reason: format!(
+            "Fixed target reached: {:.0}¢ >= {:.0}¢",
+            current_bid * 100.0,
+            exit_target * 100.0
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1174,4 +1597,68 @@ mod tests {
         assert_eq!(BetDirection::Up, BetDirection::Up);
         assert_ne!(BetDirection::Up, BetDirection::Down);
     }
+
+    #[test]
+    fn test_kelly_bet_size_caps_and_loss_reduction() {
+        let config = BotConfig::default();
+
+        // No edge -> no bet
+        assert_eq!(config.kelly_bet_size(1000.0, 0.0, 0.5, "Strong", 0), 0.0);
+
+        // Huge edge is capped by max_bet_pct / max_bet_usdc
+        let capped = config.kelly_bet_size(1000.0, 0.8, 0.5, "Strong", 0);
+        let cap = (1000.0 * config.betting.max_bet_pct).min(config.betting.max_bet_usdc);
+        assert!((capped - cap).abs() < 1e-9);
+
+        // Losing streak shrinks the bet
+        let fresh = config.kelly_bet_size(1000.0, 0.05, 0.5, "Moderate", 0);
+        let after_losses = config.kelly_bet_size(1000.0, 0.05, 0.5, "Moderate", 2);
+        assert!(after_losses < fresh);
+
+        // Unreliable cells never get sized
+        assert_eq!(config.kelly_bet_size(1000.0, 0.2, 0.5, "Unreliable", 0), 0.0);
+    }
+
+    #[test]
+    fn test_best_market_reach_target() {
+        // 40 paths from a 40¢ ask at t=0: half see the bid touch 60¢, none win
+        let mut matrix = MarketReachMatrix::new(0.0);
+        let state = matrix.get_mut(0, crate::models::price_to_ask_level(0.40));
+        for i in 0..40 {
+            state.record(if i % 2 == 0 { 0.60 } else { 0.42 }, false);
+        }
+        state.compute_probabilities();
+
+        // Holding a 40¢ token that wins 30% of the time loses money; 60¢ exit is best
+        let (target, ev, p_reach) = best_market_reach_target(&matrix, 0, 0.40, 0.40, 0.30, 30).unwrap();
+        assert!((target - 0.60).abs() < 1e-9);
+        assert!((p_reach - 0.5).abs() < 1e-9);
+        assert!(ev > 0.30 * 0.60 - 0.70 * 0.40);
+
+        // Not enough samples
+        assert!(best_market_reach_target(&matrix, 0, 0.40, 0.40, 0.30, 100).is_none());
+    }
+
+    #[test]
+    fn test_decide_exit_policy() {
+        let config = BotConfig::default();
+        let mut policy = ExitPolicy::new(0.8, 10);
+
+        // Holding an UP token at (t=30, +$5..10) is worth 70¢ whatever the bid
+        let cell = policy.get_mut(Outcome::Up, 30, 1);
+        cell.samples = 100;
+        cell.p_win = 0.70;
+        cell.continuation = vec![0.70; crate::models::EXIT_POLICY_BID_LEVELS];
+        cell.sell_threshold = Some(0.70);
+
+        let sell = decide_exit_policy(&config, &policy, 30, 1, BetDirection::Up, 0.50, 0.72).unwrap();
+        assert!(sell.should_exit);
+        assert!((sell.ev_hold - 0.20).abs() < 1e-9);
+
+        let hold = decide_exit_policy(&config, &policy, 30, 1, BetDirection::Up, 0.50, 0.65).unwrap();
+        assert!(!hold.should_exit);
+
+        // Too few transitions for the DOWN token: fall back to the matrices
+        assert!(decide_exit_policy(&config, &policy, 30, 1, BetDirection::Down, 0.50, 0.65).is_none());
+    }
 }
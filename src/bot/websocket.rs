@@ -6,7 +6,7 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -15,12 +15,13 @@ use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
 
 use super::polymarket::PriceQuote;
+use crate::metrics;
 
 // ============================================================================
 // Shared State
 // ============================================================================
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct MarketState {
     // BTC price from Binance
     pub btc_price: f64,
@@ -108,9 +109,14 @@ pub async fn binance_ws_task(
     running: Arc<AtomicBool>,
 ) -> Result<()> {
     let url = "wss://stream.binance.com:9443/ws/btcusdt@trade";
+    let mut first_attempt = true;
 
     while running.load(Ordering::SeqCst) {
         info!("Connecting to Binance WebSocket...");
+        if !first_attempt {
+            metrics::feed_reconnect("binance");
+        }
+        first_attempt = false;
 
         match connect_async(url).await {
             Ok((ws_stream, _)) => {
@@ -120,6 +126,7 @@ pub async fn binance_ws_task(
                 while running.load(Ordering::SeqCst) {
                     match tokio::time::timeout(Duration::from_secs(30), read.next()).await {
                         Ok(Some(Ok(Message::Text(text)))) => {
+                            metrics::feed_message("binance");
                             if let Ok(trade) = serde_json::from_str::<BinanceTrade>(&text) {
                                 if let Ok(price) = trade.price.parse::<f64>() {
                                     let mut s = state.write().await;
@@ -228,6 +235,9 @@ pub async fn polymarket_ws_task(
 
         connection_attempts += 1;
         info!("Connecting to Polymarket WebSocket (attempt #{})...", connection_attempts);
+        if connection_attempts > 1 {
+            metrics::feed_reconnect("polymarket");
+        }
 
         match connect_async(url).await {
             Ok((ws_stream, _)) => {
@@ -283,6 +293,7 @@ pub async fn polymarket_ws_task(
                         msg = read.next() => {
                             match msg {
                                 Some(Ok(Message::Text(text))) => {
+                                    metrics::feed_message("polymarket");
                                     messages_received += 1;
                                     if messages_received == 1 {
                                         info!("First WebSocket message received ({} bytes)", text.len());
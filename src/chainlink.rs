@@ -1,15 +1,20 @@
 //! Chainlink Oracle Price Fetcher
 //!
 //! Fetches BTC/USD price data from Chainlink oracle on Polygon mainnet
-//! using raw JSON-RPC calls.
+//! using raw JSON-RPC calls: round by round through the proxy, or in bulk
+//! from the aggregators' event logs (`backfill_from_logs`).
 
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, TimeZone, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use tokio_postgres::Client;
 
+use crate::http_client::HttpClient;
+
 // Polygon RPC endpoints (free public RPCs work fine for latestRoundData/getRoundData calls)
 const POLYGON_RPCS: &[&str] = &[
     "https://polygon-rpc.com",
@@ -25,6 +30,20 @@ const BTC_USD_FEED: &str = "0xc907E116054Ad103354f2D350FD2514433D57F6f";
 const LATEST_ROUND_DATA_SELECTOR: &str = "feaf968c"; // latestRoundData()
 const GET_ROUND_DATA_SELECTOR: &str = "9a6fc8f5";   // getRoundData(uint80)
 const DECIMALS_SELECTOR: &str = "313ce567";          // decimals()
+const PHASE_ID_SELECTOR: &str = "58303b10";          // phaseId()
+const PHASE_AGGREGATORS_SELECTOR: &str = "c1597304"; // phaseAggregators(uint16)
+
+// Aggregator events (topic0 = keccak256 of the event signature)
+// AnswerUpdated(int256 indexed current, uint256 indexed roundId, uint256 updatedAt)
+const ANSWER_UPDATED_TOPIC: &str = "0x0559884fd3a460db3073b7fc896cc77986f16e378210ded43186175bf646fc5f";
+// NewRound(uint256 indexed roundId, address indexed startedBy, uint256 startedAt)
+const NEW_ROUND_TOPIC: &str = "0x0109fc6f55cf40689f02fbaad7af7fe7bbac8a3d2186600afc7d3e10cac60271";
+
+/// Default eth_getLogs block span (halved while the RPC rejects a range)
+pub const DEFAULT_LOG_BLOCK_RANGE: u64 = 2000;
+
+/// Missing rounds looked up with getRoundData before they are reported
+const MAX_ROUND_REFILLS: usize = 500;
 
 /// Chainlink round data
 #[derive(Debug, Clone)]
@@ -58,7 +77,7 @@ struct JsonRpcError {
 
 /// Chainlink client for fetching price data
 pub struct ChainlinkClient {
-    http: reqwest::Client,
+    http: HttpClient,
     rpc_url: String,
     decimals: u8,
 }
@@ -66,9 +85,7 @@ pub struct ChainlinkClient {
 impl ChainlinkClient {
     /// Create a new Chainlink client with custom timeout (in milliseconds)
     pub async fn new_with_timeout(timeout_ms: u64) -> Result<Self> {
-        let http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_millis(timeout_ms))
-            .build()?;
+        let http = HttpClient::new("chainlink", std::time::Duration::from_millis(timeout_ms));
 
         // Find a working RPC
         let mut rpc_url = String::new();
@@ -95,9 +112,7 @@ impl ChainlinkClient {
 
     /// Create a new Chainlink client with default timeout
     pub async fn new() -> Result<Self> {
-        let http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
+        let http = HttpClient::new("chainlink", std::time::Duration::from_secs(30));
 
         // Find a working RPC
         let mut rpc_url = String::new();
@@ -123,7 +138,19 @@ impl ChainlinkClient {
         })
     }
 
-    async fn test_rpc(http: &reqwest::Client, rpc: &str) -> bool {
+    /// Create a client for one RPC endpoint (e.g. an archive node for old logs)
+    pub async fn with_rpc_url(rpc_url: &str, timeout_ms: u64) -> Result<Self> {
+        let http = HttpClient::new("chainlink", std::time::Duration::from_millis(timeout_ms));
+        let decimals = Self::fetch_decimals_static(&http, rpc_url).await?;
+
+        Ok(Self {
+            http,
+            rpc_url: rpc_url.to_string(),
+            decimals,
+        })
+    }
+
+    async fn test_rpc(http: &HttpClient, rpc: &str) -> bool {
         let request = JsonRpcRequest {
             jsonrpc: "2.0",
             method: "eth_blockNumber",
@@ -137,7 +164,7 @@ impl ChainlinkClient {
         }
     }
 
-    async fn fetch_decimals_static(http: &reqwest::Client, rpc_url: &str) -> Result<u8> {
+    async fn fetch_decimals_static(http: &HttpClient, rpc_url: &str) -> Result<u8> {
         let data = format!("0x{}", DECIMALS_SELECTOR);
         let result = Self::eth_call_static(http, rpc_url, &data).await?;
 
@@ -147,7 +174,7 @@ impl ChainlinkClient {
     }
 
     async fn eth_call_static(
-        http: &reqwest::Client,
+        http: &HttpClient,
         rpc_url: &str,
         data: &str,
     ) -> Result<String> {
@@ -320,11 +347,6 @@ impl ChainlinkClient {
                     }
                 }
             }
-
-            // Rate limiting
-            if rounds.len() % 100 == 0 {
-                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-            }
         }
 
         // Sort by timestamp (oldest first)
@@ -336,6 +358,354 @@ impl ChainlinkClient {
     }
 }
 
+// ============================================================================
+// Event Log Backfill
+// ============================================================================
+
+/// One phase of the proxy: the aggregator that served it. Proxy round IDs are
+/// (phase_id << 64) | aggregator round ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    pub phase_id: u16,
+    /// Lowercase 0x address
+    pub aggregator: String,
+}
+
+/// Aggregator rounds of one phase that are missing from the backfill
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundGap {
+    pub phase_id: u16,
+    pub first_missing: u64,
+    pub last_missing: u64,
+}
+
+/// Result of a log backfill
+#[derive(Debug)]
+pub struct LogBackfill {
+    /// Rounds ordered by round ID (phase, then aggregator round)
+    pub rounds: Vec<RoundData>,
+    pub from_block: u64,
+    pub to_block: u64,
+    /// Rounds missing from the logs that getRoundData filled in
+    pub refilled: usize,
+    /// Events of a phase logged after the next phase's first round (the
+    /// proxy no longer served them)
+    pub superseded: usize,
+    /// Rows written, chunk by chunk, when a database was given
+    pub inserted: u64,
+    /// Rounds still missing after the refill
+    pub gaps: Vec<RoundGap>,
+}
+
+/// A decoded aggregator event
+#[derive(Debug, Clone, PartialEq)]
+enum RoundEvent {
+    AnswerUpdated { phase_id: u16, round: u64, answer: i128, updated_at: i64 },
+    NewRound { phase_id: u16, round: u64, started_at: i64 },
+}
+
+impl RoundEvent {
+    fn phase_id(&self) -> u16 {
+        match self {
+            RoundEvent::AnswerUpdated { phase_id, .. } | RoundEvent::NewRound { phase_id, .. } => *phase_id,
+        }
+    }
+}
+
+/// Proxy round ID of an aggregator round
+pub fn proxy_round_id(phase_id: u16, aggregator_round: u64) -> u128 {
+    ((phase_id as u128) << 64) | aggregator_round as u128
+}
+
+fn split_round_id(round_id: u128) -> (u16, u64) {
+    ((round_id >> 64) as u16, round_id as u64)
+}
+
+fn hex_digits(value: &str) -> &str {
+    value.strip_prefix("0x").unwrap_or(value)
+}
+
+/// Parse a hex quantity ("0x1a") or a 32-byte word into a u64
+fn hex_u64(value: &str) -> Result<u64> {
+    let digits = hex_digits(value).trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(digits, 16).map_err(|e| anyhow!("Invalid hex quantity '{}': {}", value, e))
+}
+
+/// Signed 32-byte word; Chainlink answers fit in the low 128 bits (two's complement)
+fn hex_i128(value: &str) -> Result<i128> {
+    let digits = hex_digits(value);
+    let low = &digits[digits.len().saturating_sub(32)..];
+    Ok(u128::from_str_radix(low, 16).map_err(|e| anyhow!("Invalid int256 '{}': {}", value, e))? as i128)
+}
+
+/// Decode an AnswerUpdated/NewRound log, attributing it to the phase whose
+/// aggregator emitted it
+fn parse_round_event(log: &Value, phases: &HashMap<String, u16>) -> Result<Option<RoundEvent>> {
+    let address = log["address"].as_str().unwrap_or_default().to_lowercase();
+    let phase_id = *phases
+        .get(&address)
+        .ok_or_else(|| anyhow!("Log from unknown aggregator {}", address))?;
+    let topics: Vec<&str> = log["topics"]
+        .as_array()
+        .map(|t| t.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    let data = log["data"].as_str().unwrap_or("0x");
+
+    match topics.first().map(|t| t.to_lowercase()) {
+        Some(topic) if topic == ANSWER_UPDATED_TOPIC && topics.len() >= 3 => Ok(Some(RoundEvent::AnswerUpdated {
+            phase_id,
+            round: hex_u64(topics[2])?,
+            answer: hex_i128(topics[1])?,
+            updated_at: hex_u64(data)? as i64,
+        })),
+        Some(topic) if topic == NEW_ROUND_TOPIC && topics.len() >= 2 => Ok(Some(RoundEvent::NewRound {
+            phase_id,
+            round: hex_u64(topics[1])?,
+            started_at: hex_u64(data)? as i64,
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// Whether an event of `phase_id` at `block` comes after a later phase's
+/// first block (`phase_starts`: first block of each phase seen so far)
+fn superseded(phase_starts: &BTreeMap<u16, u64>, phase_id: u16, block: u64) -> bool {
+    phase_starts.range(phase_id + 1..).any(|(_, &first)| first < block)
+}
+
+/// Rounds missing within each phase. `rounds` must be sorted by round ID.
+/// A phase that starts after another phase in the same backfill must start
+/// at aggregator round 1.
+pub fn find_round_gaps(rounds: &[RoundData]) -> Vec<RoundGap> {
+    let mut gaps = Vec::new();
+    for pair in rounds.windows(2) {
+        let (prev_phase, prev_round) = split_round_id(pair[0].round_id);
+        let (phase_id, round) = split_round_id(pair[1].round_id);
+        let first_expected = if phase_id == prev_phase { prev_round + 1 } else { 1 };
+        if round > first_expected {
+            gaps.push(RoundGap { phase_id, first_missing: first_expected, last_missing: round - 1 });
+        }
+    }
+    gaps
+}
+
+impl ChainlinkClient {
+    /// Generic JSON-RPC call returning the `result` value
+    async fn rpc(&self, method: &'static str, params: Vec<Value>) -> Result<Value> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            method,
+            params,
+            id: 1,
+        };
+
+        let response: Value = self.http.post(&self.rpc_url).json(&request).send().await?.json().await?;
+
+        if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+            let message = error["message"].as_str().map(str::to_string).unwrap_or_else(|| error.to_string());
+            return Err(anyhow!("RPC error in {}: {}", method, message));
+        }
+
+        Ok(response["result"].clone())
+    }
+
+    pub async fn block_number(&self) -> Result<u64> {
+        let result = self.rpc("eth_blockNumber", vec![]).await?;
+        hex_u64(result.as_str().ok_or_else(|| anyhow!("Invalid eth_blockNumber result"))?)
+    }
+
+    async fn block_timestamp(&self, block: u64) -> Result<i64> {
+        let result = self.rpc("eth_getBlockByNumber", vec![json!(format!("{:#x}", block)), json!(false)]).await?;
+        let timestamp = result["timestamp"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Block {} not available", block))?;
+        Ok(hex_u64(timestamp)? as i64)
+    }
+
+    /// First block at or after `timestamp` (binary search up to `latest`)
+    pub async fn block_at_or_after(&self, timestamp: i64, latest: u64) -> Result<u64> {
+        let (mut low, mut high) = (0u64, latest);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.block_timestamp(mid).await? < timestamp {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// All phases of the proxy, oldest first
+    pub async fn phases(&self) -> Result<Vec<Phase>> {
+        let current = hex_u64(&self.eth_call(&format!("0x{}", PHASE_ID_SELECTOR)).await?)?;
+
+        let mut phases = Vec::new();
+        for phase_id in 1..=current as u16 {
+            let data = format!("0x{}{:0>64x}", PHASE_AGGREGATORS_SELECTOR, phase_id);
+            let word = self.eth_call(&data).await?;
+            let digits = hex_digits(&word);
+            if digits.len() < 40 {
+                return Err(anyhow!("Invalid phaseAggregators({}) result: {}", phase_id, word));
+            }
+            let address = digits[digits.len() - 40..].to_lowercase();
+            if address.chars().all(|c| c == '0') {
+                continue; // phase never set
+            }
+            phases.push(Phase { phase_id, aggregator: format!("0x{}", address) });
+        }
+        Ok(phases)
+    }
+
+    /// First block of each phase after the first, from its round 1 through
+    /// the proxy (phases whose round 1 can't be read are left out)
+    async fn phase_start_blocks(&self, phases: &[Phase], latest: u64) -> Result<BTreeMap<u16, u64>> {
+        let mut starts = BTreeMap::new();
+        for phase in phases.iter().skip(1) {
+            if let Some(first) = self.get_round_data(proxy_round_id(phase.phase_id, 1)).await? {
+                starts.insert(phase.phase_id, self.block_at_or_after(first.updated_at.timestamp(), latest).await?);
+            }
+        }
+        Ok(starts)
+    }
+
+    /// Backfill rounds since `start` from the AnswerUpdated/NewRound logs of
+    /// every phase aggregator. Each log is attributed to its phase by the
+    /// emitting aggregator, and a phase's logs after the block of the next
+    /// phase's first round are dropped (the old aggregator may keep running).
+    /// Round continuity is checked per phase and missing rounds are looked up
+    /// with getRoundData before being reported as gaps. With `db`, each block
+    /// chunk is inserted as soon as it is read, so an aborted run keeps its
+    /// progress.
+    pub async fn backfill_from_logs(&self, start: DateTime<Utc>, max_block_range: u64, db: Option<&Client>) -> Result<LogBackfill> {
+        let phases = self.phases().await?;
+        let mut phase_by_aggregator = HashMap::new();
+        for phase in &phases {
+            if let Some(other) = phase_by_aggregator.insert(phase.aggregator.clone(), phase.phase_id) {
+                return Err(anyhow!(
+                    "Aggregator {} serves phases {} and {}; rounds cannot be attributed",
+                    phase.aggregator,
+                    other,
+                    phase.phase_id
+                ));
+            }
+        }
+        let addresses: Vec<&str> = phases.iter().map(|p| p.aggregator.as_str()).collect();
+        println!("  Phases: {}", phases.iter().map(|p| format!("{}={}", p.phase_id, p.aggregator)).collect::<Vec<_>>().join(", "));
+
+        let to_block = self.block_number().await?;
+        let from_block = self.block_at_or_after(start.timestamp(), to_block).await?;
+        println!("  Blocks {} to {} ({} blocks)", from_block, to_block, to_block.saturating_sub(from_block) + 1);
+
+        let mut phase_starts = self.phase_start_blocks(&phases, to_block).await?;
+        let to_time = |ts: i64| Utc.timestamp_opt(ts, 0).single().unwrap_or_default();
+        let mut rounds: Vec<RoundData> = Vec::new();
+        let mut started: HashMap<(u16, u64), i64> = HashMap::new();
+        let (mut refilled, mut superseded_events, mut inserted) = (0usize, 0usize, 0u64);
+        let max_block_range = max_block_range.max(1);
+        let mut range = max_block_range;
+        let mut current = from_block;
+        let mut queries = 0usize;
+
+        while current <= to_block {
+            let end = (current + range - 1).min(to_block);
+            let params = json!({
+                "fromBlock": format!("{:#x}", current),
+                "toBlock": format!("{:#x}", end),
+                "address": addresses,
+                "topics": [[ANSWER_UPDATED_TOPIC, NEW_ROUND_TOPIC]],
+            });
+            let logs = match self.rpc("eth_getLogs", vec![params]).await {
+                Ok(Value::Array(logs)) => logs,
+                Ok(other) => return Err(anyhow!("Unexpected eth_getLogs result: {}", other)),
+                // Providers cap the block span or result size: retry smaller
+                Err(e) if range > 1 => {
+                    range = (range / 2).max(1);
+                    tracing::debug!("eth_getLogs {}..{} failed ({}), retrying with {} blocks", current, end, e, range);
+                    continue;
+                }
+                Err(e) => return Err(e.context(format!("eth_getLogs failed at block {} (rerun to resume)", current))),
+            };
+
+            let mut events = Vec::with_capacity(logs.len());
+            for log in &logs {
+                if let Some(event) = parse_round_event(log, &phase_by_aggregator)? {
+                    events.push((hex_u64(log["blockNumber"].as_str().unwrap_or("0x0"))?, event));
+                }
+            }
+            events.sort_by_key(|(block, _)| *block);
+
+            let mut answers: BTreeMap<(u16, u64), (i128, i64)> = BTreeMap::new();
+            for (block, event) in events {
+                let phase_id = event.phase_id();
+                let first = phase_starts.entry(phase_id).or_insert(block);
+                *first = (*first).min(block);
+                if superseded(&phase_starts, phase_id, block) {
+                    superseded_events += 1;
+                    continue;
+                }
+                match event {
+                    RoundEvent::AnswerUpdated { phase_id, round, answer, updated_at } => {
+                        answers.insert((phase_id, round), (answer, updated_at));
+                    }
+                    RoundEvent::NewRound { phase_id, round, started_at } => {
+                        started.insert((phase_id, round), started_at);
+                    }
+                }
+            }
+
+            let mut chunk: Vec<RoundData> = answers
+                .into_iter()
+                .map(|((phase_id, round), (answer, updated_at))| RoundData {
+                    round_id: proxy_round_id(phase_id, round),
+                    price: Decimal::from_i128_with_scale(answer, self.decimals as u32),
+                    started_at: to_time(*started.get(&(phase_id, round)).unwrap_or(&updated_at)),
+                    updated_at: to_time(updated_at),
+                })
+                .collect();
+
+            // Fill holes in the round sequence (from the last round already
+            // read) through the proxy
+            if let Some(first) = chunk.first() {
+                let mut check: Vec<RoundData> = rounds.last().filter(|last| last.round_id < first.round_id).cloned().into_iter().collect();
+                check.extend(chunk.iter().cloned());
+                let missing: Vec<u128> = find_round_gaps(&check)
+                    .iter()
+                    .flat_map(|gap| (gap.first_missing..=gap.last_missing).map(move |r| proxy_round_id(gap.phase_id, r)))
+                    .take(MAX_ROUND_REFILLS.saturating_sub(refilled))
+                    .collect();
+                for round_id in missing {
+                    if let Some(round) = self.get_round_data(round_id).await? {
+                        chunk.push(round);
+                        refilled += 1;
+                    }
+                }
+                chunk.sort_by_key(|r| r.round_id);
+            }
+
+            if let Some(db) = db {
+                inserted += insert_chainlink_prices(db, &chunk).await?;
+            }
+            rounds.extend(chunk);
+
+            queries += 1;
+            if queries.is_multiple_of(100) {
+                println!("  Block {} of {}: {} rounds", end, to_block, rounds.len());
+            }
+            current = end + 1;
+            range = (range * 2).min(max_block_range);
+        }
+
+        rounds.sort_by_key(|r| r.round_id);
+        let gaps = find_round_gaps(&rounds);
+
+        Ok(LogBackfill { rounds, from_block, to_block, refilled, superseded: superseded_events, inserted, gaps })
+    }
+}
+
 // ============================================================================
 // Database Operations
 // ============================================================================
@@ -401,6 +771,27 @@ pub async fn get_latest_chainlink_timestamp(client: &Client) -> Result<Option<Da
     Ok(row.and_then(|r| r.get(0)))
 }
 
+/// Newest stored round with a round ID: (round ID, updated_at)
+pub async fn get_latest_chainlink_round(client: &Client) -> Result<Option<(u128, DateTime<Utc>)>> {
+    let row = client
+        .query_opt(
+            "SELECT round_id, timestamp FROM chainlink_prices
+             WHERE symbol = 'BTCUSD' AND round_id IS NOT NULL
+             ORDER BY round_id DESC LIMIT 1",
+            &[],
+        )
+        .await?;
+
+    match row {
+        Some(row) => {
+            let round_id: Decimal = row.get(0);
+            let round_id = round_id.to_string().parse().map_err(|e| anyhow!("Invalid round_id {}: {}", round_id, e))?;
+            Ok(Some((round_id, row.get(1))))
+        }
+        None => Ok(None),
+    }
+}
+
 /// Get statistics about stored Chainlink data
 pub async fn get_chainlink_stats(client: &Client) -> Result<ChainlinkStats> {
     let row = client
@@ -434,3 +825,211 @@ pub struct ChainlinkStats {
     pub min_price: Option<Decimal>,
     pub max_price: Option<Decimal>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn round(phase_id: u16, aggregator_round: u64) -> RoundData {
+        RoundData {
+            round_id: proxy_round_id(phase_id, aggregator_round),
+            price: Decimal::ONE,
+            started_at: Utc.timestamp_opt(0, 0).unwrap(),
+            updated_at: Utc.timestamp_opt(0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_round_ids_and_gaps() {
+        assert_eq!(proxy_round_id(2, 5), (2u128 << 64) | 5);
+        assert_eq!(split_round_id(proxy_round_id(3, 1234)), (3, 1234));
+        assert_eq!(hex_u64("0x1a").unwrap(), 26);
+        assert_eq!(hex_u64("0x0").unwrap(), 0);
+        assert_eq!(hex_i128(&format!("0x{}", "f".repeat(64))).unwrap(), -1);
+        assert_eq!(hex_i128(&format!("0x{:0>64x}", 9_705_000_000_000i64)).unwrap(), 9_705_000_000_000);
+
+        // Continuous within phase 1, then phase 2 from round 1
+        assert!(find_round_gaps(&[round(1, 7), round(1, 8), round(2, 1), round(2, 2)]).is_empty());
+        // Holes inside a phase, and a later phase that does not start at round 1
+        assert_eq!(
+            find_round_gaps(&[round(1, 7), round(1, 10), round(2, 3)]),
+            vec![
+                RoundGap { phase_id: 1, first_missing: 8, last_missing: 9 },
+                RoundGap { phase_id: 2, first_missing: 1, last_missing: 2 },
+            ]
+        );
+        // The first phase of a backfill may start mid-phase
+        assert!(find_round_gaps(&[round(2, 500)]).is_empty());
+    }
+
+    const AGGREGATOR_1: &str = "0x00000000000000000000000000000000000000aa";
+    const AGGREGATOR_2: &str = "0x00000000000000000000000000000000000000bb";
+    const GENESIS: u64 = 1_700_000_000;
+
+    fn word(value: u128) -> String {
+        format!("0x{:0>64x}", value)
+    }
+
+    fn answer_log(aggregator: &str, block: u64, aggregator_round: u64, answer: u128) -> Value {
+        json!({
+            "address": aggregator,
+            "blockNumber": format!("{:#x}", block),
+            "topics": [ANSWER_UPDATED_TOPIC, word(answer), word(aggregator_round as u128)],
+            "data": word((GENESIS + 2 * block) as u128),
+        })
+    }
+
+    fn new_round_log(aggregator: &str, block: u64, aggregator_round: u64, started_at: u64) -> Value {
+        json!({
+            "address": aggregator,
+            "blockNumber": format!("{:#x}", block),
+            "topics": [NEW_ROUND_TOPIC, word(aggregator_round as u128), word(0xcafe)],
+            "data": word(started_at as u128),
+        })
+    }
+
+    /// Phase 1 rounds 40-44 (43 never logged), phase 2 rounds 1-3, and a phase 1
+    /// round 45 after phase 2 took over; blocks are 2s apart
+    fn fixture_logs() -> Vec<Value> {
+        vec![
+            answer_log(AGGREGATOR_1, 5, 40, 9_690_000_000_000),
+            answer_log(AGGREGATOR_1, 20, 41, 9_700_000_000_000),
+            new_round_log(AGGREGATOR_1, 29, 42, GENESIS + 57),
+            answer_log(AGGREGATOR_1, 30, 42, 9_701_000_000_000),
+            answer_log(AGGREGATOR_1, 50, 44, 9_703_000_000_000),
+            answer_log(AGGREGATOR_2, 70, 1, 9_704_000_000_000),
+            answer_log(AGGREGATOR_1, 75, 45, 9_800_000_000_000),
+            answer_log(AGGREGATOR_2, 80, 2, 9_705_000_000_000),
+            answer_log(AGGREGATOR_2, 95, 3, 9_706_000_000_000),
+        ]
+    }
+
+    /// Stub node: a proxy with two phases, 100 blocks and a 16-block eth_getLogs cap
+    fn stub_node(method: &str, params: &Value) -> std::result::Result<Value, String> {
+        match method {
+            "eth_blockNumber" => Ok(json!("0x64")),
+            "eth_getBlockByNumber" => {
+                let block = hex_u64(params[0].as_str().unwrap()).unwrap();
+                Ok(json!({ "timestamp": format!("{:#x}", GENESIS + 2 * block) }))
+            }
+            "eth_call" => {
+                let data = params[0]["data"].as_str().unwrap();
+                let (selector, argument) = data[2..].split_at(8);
+                match selector {
+                    DECIMALS_SELECTOR => Ok(json!(word(8))),
+                    PHASE_ID_SELECTOR => Ok(json!(word(2))),
+                    PHASE_AGGREGATORS_SELECTOR => match hex_u64(argument).unwrap() {
+                        1 => Ok(json!(format!("0x{:0>64}", &AGGREGATOR_1[2..]))),
+                        2 => Ok(json!(format!("0x{:0>64}", &AGGREGATOR_2[2..]))),
+                        _ => Ok(json!(word(0))),
+                    },
+                    GET_ROUND_DATA_SELECTOR if argument == &word(proxy_round_id(1, 43))[2..] => {
+                        let fields = [proxy_round_id(1, 43), 9_702_000_000_000, 1_700_000_078, 1_700_000_080, proxy_round_id(1, 43)];
+                        Ok(json!(format!("0x{}", fields.iter().map(|f| format!("{:0>64x}", f)).collect::<String>())))
+                    }
+                    _ => Err("execution reverted".to_string()),
+                }
+            }
+            "eth_getLogs" => {
+                let from = hex_u64(params[0]["fromBlock"].as_str().unwrap()).unwrap();
+                let to = hex_u64(params[0]["toBlock"].as_str().unwrap()).unwrap();
+                if to - from + 1 > 16 {
+                    return Err("block range is too wide".to_string());
+                }
+                Ok(Value::Array(
+                    fixture_logs()
+                        .into_iter()
+                        .filter(|log| (from..=to).contains(&hex_u64(log["blockNumber"].as_str().unwrap()).unwrap()))
+                        .collect(),
+                ))
+            }
+            other => Err(format!("method {} not supported", other)),
+        }
+    }
+
+    /// Serve JSON-RPC requests (one per connection) with `handler`
+    async fn serve_rpc(handler: fn(&str, &Value) -> std::result::Result<Value, String>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 8192];
+                let body_start = loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_lowercase();
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length: usize = text
+                            .lines()
+                            .find_map(|l| l.strip_prefix("content-length:"))
+                            .map_or(0, |l| l.trim().parse().unwrap());
+                        if request.len() >= end + 4 + length || n == 0 {
+                            break end + 4;
+                        }
+                    }
+                };
+                let call: Value = serde_json::from_slice(&request[body_start..]).unwrap();
+                let reply = match handler(call["method"].as_str().unwrap(), &call["params"]) {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": call["id"], "result": result }),
+                    Err(message) => json!({ "jsonrpc": "2.0", "id": call["id"], "error": { "code": -32000, "message": message } }),
+                };
+                let body = reply.to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                let _ = socket.shutdown().await;
+            }
+        });
+        format!("http://{}/", addr)
+    }
+
+    #[test]
+    fn test_superseded_phase_events() {
+        let starts = BTreeMap::from([(1, 5), (2, 70), (3, 200)]);
+        assert!(!superseded(&starts, 1, 70));
+        assert!(superseded(&starts, 1, 71));
+        assert!(!superseded(&starts, 2, 150));
+        assert!(superseded(&starts, 2, 201));
+        assert!(!superseded(&starts, 3, 1_000));
+    }
+
+    #[tokio::test]
+    async fn test_backfill_from_logs() {
+        let url = serve_rpc(stub_node).await;
+        let client = ChainlinkClient::with_rpc_url(&url, 5000).await.unwrap();
+
+        let phases = client.phases().await.unwrap();
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[1], Phase { phase_id: 2, aggregator: AGGREGATOR_2.to_string() });
+
+        // Start at block 10: round 40 (block 5) is out of range
+        let start = Utc.timestamp_opt((GENESIS + 20) as i64, 0).unwrap();
+        let backfill = client.backfill_from_logs(start, 40, None).await.unwrap();
+        assert_eq!((backfill.from_block, backfill.to_block), (10, 100));
+
+        // Phase 1 round 45 (block 75) comes after phase 2's first round (block 70)
+        let ids: Vec<(u16, u64)> = backfill.rounds.iter().map(|r| split_round_id(r.round_id)).collect();
+        assert_eq!(ids, vec![(1, 41), (1, 42), (1, 43), (1, 44), (2, 1), (2, 2), (2, 3)]);
+        assert_eq!(backfill.superseded, 1);
+        assert_eq!(backfill.refilled, 1);
+        assert_eq!(backfill.inserted, 0);
+        assert!(backfill.gaps.is_empty());
+
+        let rounds = &backfill.rounds;
+        assert_eq!(rounds[0].price, Decimal::from(97000));
+        assert_eq!(rounds[0].updated_at.timestamp() as u64, GENESIS + 40);
+        // started_at comes from NewRound when logged, else equals updated_at
+        assert_eq!(rounds[1].started_at.timestamp() as u64, GENESIS + 57);
+        assert_eq!(rounds[0].started_at, rounds[0].updated_at);
+        // Round 43 came from getRoundData through the proxy
+        assert_eq!(rounds[2].price, Decimal::from(97020));
+        assert_eq!(rounds[2].updated_at.timestamp(), 1_700_000_080);
+        assert_eq!(rounds[6].price, Decimal::from(97060));
+    }
+}
@@ -2,10 +2,14 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use native_tls::TlsConnector;
 use postgres_native_tls::MakeTlsConnector;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use tokio_postgres::{Client, NoTls, Row};
 
-use crate::models::{PricePoint, ProbabilityMatrix};
+use crate::binance_klines::KlineInterval;
+use crate::models::{MatrixSession, PricePoint, ProbabilityMatrix, TokenPricePath};
 
 /// Database configuration
 pub struct DbConfig {
@@ -67,49 +71,24 @@ pub async fn connect(config: &DbConfig) -> Result<Client> {
     Ok(client)
 }
 
-/// Fetch all BTC price data from the database
-/// Returns data ordered by timestamp ascending
-pub async fn fetch_all_prices(client: &Client) -> Result<Vec<PricePoint>> {
-    let query = r#"
-        SELECT timestamp, close_price
-        FROM binance_klines
-        WHERE symbol = 'BTCUSDT'
-        ORDER BY timestamp ASC
-    "#;
-
-    let rows = client.query(query, &[]).await?;
-
-    let prices: Vec<PricePoint> = rows
-        .iter()
-        .map(|row| {
-            let timestamp: DateTime<Utc> = row.get(0);
-            let close_price: Decimal = row.get(1);
-            PricePoint {
-                timestamp,
-                close_price,
-            }
-        })
-        .collect();
-
-    Ok(prices)
-}
-
-/// Fetch price data for a specific date range
+/// Fetch price data in `[start, end)`, ordered by timestamp ascending
 pub async fn fetch_prices_range(
     client: &Client,
     start: DateTime<Utc>,
     end: DateTime<Utc>,
+    interval: KlineInterval,
 ) -> Result<Vec<PricePoint>> {
     let query = r#"
         SELECT timestamp, close_price
         FROM binance_klines
         WHERE symbol = 'BTCUSDT'
+          AND kline_interval = $3
           AND timestamp >= $1
-          AND timestamp <= $2
+          AND timestamp < $2
         ORDER BY timestamp ASC
     "#;
 
-    let rows = client.query(query, &[&start, &end]).await?;
+    let rows = client.query(query, &[&start, &end, &interval.as_str()]).await?;
 
     let prices: Vec<PricePoint> = rows
         .iter()
@@ -127,41 +106,130 @@ pub async fn fetch_prices_range(
 }
 
 /// Get the date range of available data
-pub async fn get_data_range(client: &Client) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+pub async fn get_data_range(client: &Client, interval: KlineInterval) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
     let query = r#"
         SELECT MIN(timestamp), MAX(timestamp)
         FROM binance_klines
-        WHERE symbol = 'BTCUSDT'
+        WHERE symbol = 'BTCUSDT' AND kline_interval = $1
     "#;
 
-    let row = client.query_one(query, &[]).await?;
-    let min: DateTime<Utc> = row.get(0);
-    let max: DateTime<Utc> = row.get(1);
+    let row = client.query_one(query, &[&interval.as_str()]).await?;
+    let (Some(min), Some(max)) = (row.get::<_, Option<DateTime<Utc>>>(0), row.get::<_, Option<DateTime<Utc>>>(1)) else {
+        anyhow::bail!("No {} klines in binance_klines - run 'binance fetch --interval {}' first", interval, interval);
+    };
 
     Ok((min, max))
 }
 
 /// Get total count of price records
-pub async fn get_price_count(client: &Client) -> Result<i64> {
+pub async fn get_price_count(client: &Client, interval: KlineInterval) -> Result<i64> {
     let query = r#"
         SELECT COUNT(*)
         FROM binance_klines
-        WHERE symbol = 'BTCUSDT'
+        WHERE symbol = 'BTCUSDT' AND kline_interval = $1
     "#;
 
-    let row = client.query_one(query, &[]).await?;
+    let row = client.query_one(query, &[&interval.as_str()]).await?;
     let count: i64 = row.get(0);
 
     Ok(count)
 }
 
+/// Fetch Polymarket UP/DOWN token price histories of the windows starting at
+/// or after `since`, one path per (window, token)
+/// Only points inside the window [start, start + 15min] are kept
+pub async fn fetch_polymarket_price_paths(client: &Client, since: DateTime<Utc>) -> Result<Vec<TokenPricePath>> {
+    let query = r#"
+        SELECT window_timestamp,
+               token_type,
+               EXTRACT(EPOCH FROM (timestamp - TO_TIMESTAMP(window_timestamp)))::INT AS secs,
+               price
+        FROM polymarket_prices
+        WHERE window_timestamp >= $1
+          AND timestamp >= TO_TIMESTAMP(window_timestamp)
+          AND timestamp <= TO_TIMESTAMP(window_timestamp + 900)
+        ORDER BY window_timestamp, token_type, timestamp
+    "#;
+
+    let rows = client.query(query, &[&since.timestamp()]).await?;
+
+    let mut paths: Vec<TokenPricePath> = Vec::new();
+    let mut current_key: Option<(i64, String)> = None;
+
+    for row in &rows {
+        let window_ts: i64 = row.get(0);
+        let token_type: String = row.get(1);
+        let secs: i32 = row.get(2);
+        let price: Decimal = row.get(3);
+        let price = price.to_f64().unwrap_or(0.0);
+
+        let key = (window_ts, token_type);
+        if current_key.as_ref() != Some(&key) {
+            let window_start = DateTime::from_timestamp(window_ts, 0).unwrap_or_default();
+            paths.push(TokenPricePath { window_start, points: Vec::new() });
+            current_key = Some(key);
+        }
+        if let Some(path) = paths.last_mut() {
+            path.points.push((secs.max(0) as u32, price));
+        }
+    }
+
+    Ok(paths)
+}
+
 /// Run migrations for matrix snapshots table
 pub async fn run_matrix_migrations(client: &Client) -> Result<()> {
     let migration = include_str!("../migrations/002_matrix_snapshots.sql");
     client.batch_execute(migration).await?;
+    let sessions = include_str!("../migrations/007_matrix_sessions.sql");
+    client.batch_execute(sessions).await?;
+    let counts = include_str!("../migrations/008_matrix_counts.sql");
+    client.batch_execute(counts).await?;
+    Ok(())
+}
+
+/// Save the raw-count state of a matrix for incremental updates
+/// `data_end` is the start of the last window it contains
+pub async fn save_matrix_counts<T: Serialize>(
+    client: &Client,
+    name: &str,
+    matrix: &T,
+    data_end: Option<DateTime<Utc>>,
+) -> Result<()> {
+    let matrix_json = serde_json::to_value(matrix)?;
+
+    client
+        .execute(
+            r#"
+            INSERT INTO matrix_counts (name, matrix_json, data_end, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (name) DO UPDATE
+            SET matrix_json = EXCLUDED.matrix_json,
+                data_end = EXCLUDED.data_end,
+                updated_at = NOW()
+            "#,
+            &[&name, &matrix_json, &data_end],
+        )
+        .await?;
+
     Ok(())
 }
 
+/// Load the raw-count state saved by `save_matrix_counts`
+pub async fn load_matrix_counts<T: DeserializeOwned>(client: &Client, name: &str) -> Result<Option<T>> {
+    let row = client
+        .query_opt("SELECT matrix_json FROM matrix_counts WHERE name = $1", &[&name])
+        .await?;
+
+    match row {
+        Some(row) => {
+            let matrix_json: serde_json::Value = row.get(0);
+            Ok(Some(serde_json::from_value(matrix_json)?))
+        }
+        None => Ok(None),
+    }
+}
+
 /// Save a probability matrix to the database
 /// Returns the ID of the saved snapshot
 pub async fn save_matrix(client: &Client, matrix: &ProbabilityMatrix) -> Result<i32> {
@@ -183,6 +251,30 @@ pub async fn save_matrix(client: &Client, matrix: &ProbabilityMatrix) -> Result<
     Ok(id)
 }
 
+/// Save a session-conditioned matrix (replaces that session's active snapshot)
+/// Returns the ID of the saved snapshot
+pub async fn save_session_matrix(client: &Client, session: &MatrixSession, matrix: &ProbabilityMatrix) -> Result<i32> {
+    let session_json = serde_json::to_value(session)?;
+    let matrix_json = serde_json::to_value(matrix)?;
+
+    let row = client
+        .query_one(
+            "SELECT save_session_matrix($1, $2, $3, $4, $5, $6)",
+            &[
+                &session.name,
+                &session_json,
+                &matrix_json,
+                &(matrix.total_windows as i32),
+                &matrix.data_start,
+                &matrix.data_end,
+            ],
+        )
+        .await?;
+
+    let id: i32 = row.get(0);
+    Ok(id)
+}
+
 /// Load the latest active probability matrix from the database
 pub async fn load_latest_matrix(client: &Client) -> Result<Option<ProbabilityMatrix>> {
     let row = client
@@ -190,7 +282,7 @@ pub async fn load_latest_matrix(client: &Client) -> Result<Option<ProbabilityMat
             r#"
             SELECT matrix_json, total_windows, data_start, data_end, created_at
             FROM matrix_snapshots
-            WHERE is_active = TRUE
+            WHERE is_active = TRUE AND session = 'all'
             ORDER BY created_at DESC
             LIMIT 1
             "#,
@@ -215,7 +307,7 @@ pub async fn get_matrix_info(client: &Client) -> Result<Option<MatrixInfo>> {
             r#"
             SELECT id, total_windows, data_start, data_end, created_at
             FROM matrix_snapshots
-            WHERE is_active = TRUE
+            WHERE is_active = TRUE AND session = 'all'
             ORDER BY created_at DESC
             LIMIT 1
             "#,
@@ -0,0 +1,385 @@
+//! Shared HTTP client with per-host rate limits
+//!
+//! Every REST client (Polymarket, Binance, Chainlink RPC, order executor) sends
+//! through one connection pool and one token bucket per host. A 429 (or
+//! Binance's 418 ban) pauses the whole host for its `Retry-After` and the
+//! request is retried. Limits are per process: give a backfill job a smaller
+//! share with HTTP_RATE_LIMITS so it can run next to the live bot, e.g.
+//!
+//!   HTTP_RATE_LIMITS="api.binance.com=4/8,polygon-rpc.com=5/10"
+//!
+//! (`host=requests_per_second/burst`, comma separated).
+//!
+//! Each binary includes this file as its own module, so builder methods that
+//! some binary doesn't call carry `#[allow(dead_code)]`.
+
+use prometheus::{register_histogram_vec, register_int_counter_vec, HistogramVec, IntCounterVec};
+use reqwest::{IntoUrl, Method, Response, StatusCode};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Throttled (429/418) requests are retried at most this many times
+const MAX_THROTTLE_RETRIES: u32 = 3;
+
+/// Don't wait out a Retry-After longer than this; return the 429 instead
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+
+// ============================================================================
+// Metrics
+// ============================================================================
+
+/// Requests by client, host and HTTP status ("timeout"/"error" if none)
+static REQUESTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("http_client_requests_total", "Outgoing HTTP requests", &["client", "host", "status"])
+        .unwrap()
+});
+
+/// Request latency (excluding time spent waiting for the rate limiter)
+static LATENCY: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "http_client_request_seconds",
+        "Outgoing HTTP request latency",
+        &["client", "host"],
+        vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0]
+    )
+    .unwrap()
+});
+
+/// Time requests waited for a rate-limit token or a host pause
+static THROTTLE_WAIT: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "http_client_throttle_wait_seconds",
+        "Time spent waiting on the per-host rate limiter",
+        &["client", "host"],
+        vec![0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 30.0, 60.0]
+    )
+    .unwrap()
+});
+
+// ============================================================================
+// Per-host Token Buckets
+// ============================================================================
+
+/// Sustained rate and burst allowed for one host
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HostLimit {
+    pub per_second: f64,
+    pub burst: f64,
+}
+
+/// Defaults sized so the live bot plus one backfill stay under the exchange
+/// limits (Binance: 6000 weight/min per IP, a 1000-row klines call weighs 5)
+fn default_limit(host: &str) -> HostLimit {
+    let (per_second, burst) = match host {
+        "api.binance.com" => (10.0, 20.0),
+        "clob.polymarket.com" => (20.0, 40.0),
+        "gamma-api.polymarket.com" => (10.0, 20.0),
+        _ => (10.0, 20.0),
+    };
+    HostLimit { per_second, burst }
+}
+
+/// Parse HTTP_RATE_LIMITS (`host=rate/burst,...`); bad entries are skipped with a warning
+fn parse_limits(spec: &str) -> HashMap<String, HostLimit> {
+    let mut limits = HashMap::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let parsed = entry.split_once('=').and_then(|(host, limit)| {
+            let (rate, burst) = limit.split_once('/').unwrap_or((limit, limit));
+            let limit = HostLimit { per_second: rate.trim().parse().ok()?, burst: burst.trim().parse().ok()? };
+            (limit.per_second > 0.0 && limit.burst >= 1.0).then(|| (host.trim().to_string(), limit))
+        });
+        match parsed {
+            Some((host, limit)) => {
+                limits.insert(host, limit);
+            }
+            None => warn!("Ignoring invalid HTTP_RATE_LIMITS entry: {}", entry),
+        }
+    }
+    limits
+}
+
+struct TokenBucket {
+    limit: HostLimit,
+    tokens: f64,
+    updated: Instant,
+    /// Set by a 429/418: nothing goes out before this
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(limit: HostLimit, now: Instant) -> Self {
+        Self { limit, tokens: limit.burst, updated: now, paused_until: None }
+    }
+
+    /// Take a token and return how long to wait before using it. Tokens can
+    /// go negative, so concurrent callers queue up behind each other.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.per_second).min(self.limit.burst);
+        self.updated = self.updated.max(now);
+        self.tokens -= 1.0;
+
+        let refill = if self.tokens < 0.0 {
+            Duration::from_secs_f64(-self.tokens / self.limit.per_second)
+        } else {
+            Duration::ZERO
+        };
+        let paused = self.paused_until.map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+        refill.max(paused)
+    }
+
+    fn pause_until(&mut self, until: Instant) {
+        self.paused_until = Some(self.paused_until.map_or(until, |current| current.max(until)));
+        // Restart from an empty bucket after the pause instead of bursting:
+        // no tokens accrue until it ends
+        self.tokens = self.tokens.min(0.0);
+        self.updated = self.updated.max(until);
+    }
+}
+
+struct Limiter {
+    overrides: HashMap<String, HostLimit>,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl Limiter {
+    fn reserve(&self, host: &str, now: Instant) -> Duration {
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::new(self.overrides.get(host).copied().unwrap_or_else(|| default_limit(host)), now))
+            .reserve(now)
+    }
+
+    fn pause(&self, host: &str, until: Instant) {
+        if let Some(bucket) = self.buckets.lock().unwrap().get_mut(host) {
+            bucket.pause_until(until);
+        }
+    }
+}
+
+static LIMITER: LazyLock<Limiter> = LazyLock::new(|| Limiter {
+    overrides: std::env::var("HTTP_RATE_LIMITS").map(|spec| parse_limits(&spec)).unwrap_or_default(),
+    buckets: Mutex::new(HashMap::new()),
+});
+
+/// One connection pool for the whole process
+static SHARED: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder().build().expect("Failed to create HTTP client")
+});
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// Rate-limited handle on the shared HTTP client. `name` labels its metrics;
+/// `timeout` applies to requests that don't set their own.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    name: &'static str,
+    timeout: Duration,
+}
+
+impl HttpClient {
+    pub fn new(name: &'static str, timeout: Duration) -> Self {
+        Self { name, timeout }
+    }
+
+    pub fn get(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    #[allow(dead_code)]
+    pub fn post(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    #[allow(dead_code)]
+    pub fn delete(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+
+    pub fn request(&self, method: Method, url: impl IntoUrl) -> RequestBuilder {
+        RequestBuilder {
+            client: self.clone(),
+            inner: SHARED.request(method, url),
+            retry_throttled: true,
+        }
+    }
+}
+
+/// Request under construction (a thin wrapper over reqwest's builder)
+pub struct RequestBuilder {
+    client: HttpClient,
+    inner: reqwest::RequestBuilder,
+    retry_throttled: bool,
+}
+
+impl RequestBuilder {
+    #[allow(dead_code)]
+    pub fn header(self, key: &'static str, value: impl AsRef<str>) -> Self {
+        Self { inner: self.inner.header(key, value.as_ref()), ..self }
+    }
+
+    #[allow(dead_code)]
+    pub fn json<T: Serialize + ?Sized>(self, json: &T) -> Self {
+        Self { inner: self.inner.json(json), ..self }
+    }
+
+    #[allow(dead_code)]
+    pub fn body(self, body: impl Into<reqwest::Body>) -> Self {
+        Self { inner: self.inner.body(body), ..self }
+    }
+
+    /// Per-call timeout (overrides the client default)
+    #[allow(dead_code)]
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { inner: self.inner.timeout(timeout), ..self }
+    }
+
+    /// Return a 429 to the caller instead of retrying it (for callers with
+    /// their own retry policy, e.g. order submission)
+    #[allow(dead_code)]
+    pub fn no_retry(self) -> Self {
+        Self { retry_throttled: false, ..self }
+    }
+
+    /// Wait for the host's rate limit, send, and retry 429/418 responses
+    /// after pausing the host for their Retry-After
+    pub async fn send(self) -> reqwest::Result<Response> {
+        let Self { client, inner, retry_throttled } = self;
+        let mut request = inner.build()?;
+        if request.timeout().is_none() {
+            *request.timeout_mut() = Some(client.timeout);
+        }
+        let host = request.url().host_str().unwrap_or_default().to_string();
+
+        let mut throttled = 0;
+        loop {
+            let wait = LIMITER.reserve(&host, Instant::now());
+            if !wait.is_zero() {
+                THROTTLE_WAIT.with_label_values(&[client.name, &host]).observe(wait.as_secs_f64());
+                debug!("{}: waiting {}ms for {} rate limit", client.name, wait.as_millis(), host);
+                tokio::time::sleep(wait).await;
+            }
+
+            let retry = if retry_throttled && throttled < MAX_THROTTLE_RETRIES { request.try_clone() } else { None };
+            let started = Instant::now();
+            let result = SHARED.execute(request).await;
+            let status = match &result {
+                Ok(response) => response.status().as_str().to_string(),
+                Err(e) if e.is_timeout() => "timeout".to_string(),
+                Err(_) => "error".to_string(),
+            };
+            REQUESTS.with_label_values(&[client.name, &host, &status]).inc();
+            LATENCY.with_label_values(&[client.name, &host]).observe(started.elapsed().as_secs_f64());
+
+            let response = result?;
+            if !is_throttled(response.status()) {
+                return Ok(response);
+            }
+
+            throttled += 1;
+            let pause = retry_after(&response).unwrap_or_else(|| throttle_backoff(throttled));
+            LIMITER.pause(&host, Instant::now() + pause);
+            warn!("{}: {} from {}, pausing host for {}s", client.name, response.status(), host, pause.as_secs_f64());
+
+            match retry {
+                Some(next) if pause <= MAX_RETRY_WAIT => request = next,
+                _ => return Ok(response),
+            }
+        }
+    }
+}
+
+/// 429 Too Many Requests, or 418 (Binance's IP ban after ignoring 429s)
+fn is_throttled(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::IM_A_TEAPOT
+}
+
+/// Retry-After in seconds (the HTTP-date form is not used by these APIs)
+fn retry_after(response: &Response) -> Option<Duration> {
+    response.headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Pause after the n-th throttled response without a Retry-After: 1s, 2s, 4s...
+fn throttle_backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1 << (attempt.saturating_sub(1)).min(6))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_token_bucket_and_limit_parsing() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(HostLimit { per_second: 10.0, burst: 2.0 }, start);
+
+        // Burst goes out immediately, then one token every 100ms
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start).as_millis(), 100);
+        assert_eq!(bucket.reserve(start).as_millis(), 200);
+        let later = start + Duration::from_secs(1);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+
+        // A 429 pause holds everything back and restarts without a burst
+        bucket.pause_until(later + Duration::from_secs(5));
+        assert_eq!(bucket.reserve(later), Duration::from_secs(5));
+        let resumed = later + Duration::from_secs(5);
+        assert!(bucket.reserve(resumed) > Duration::ZERO);
+
+        let limits = parse_limits(" api.binance.com=4/8, polygon-rpc.com=2 ,bad, x=0/1, y=1/z ");
+        assert_eq!(limits.len(), 2);
+        assert_eq!(limits["api.binance.com"], HostLimit { per_second: 4.0, burst: 8.0 });
+        assert_eq!(limits["polygon-rpc.com"], HostLimit { per_second: 2.0, burst: 2.0 });
+        assert_eq!(throttle_backoff(1), Duration::from_secs(1));
+        assert_eq!(throttle_backoff(3), Duration::from_secs(4));
+    }
+
+    /// Serve the given raw HTTP responses, one per connection
+    async fn serve(responses: Vec<&'static str>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                socket.write_all(response.as_bytes()).await.unwrap();
+                let _ = socket.shutdown().await;
+            }
+        });
+        format!("http://{}/", addr)
+    }
+
+    const TOO_MANY: &str = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok";
+
+    #[tokio::test]
+    async fn test_retries_after_429() {
+        let client = HttpClient::new("test", Duration::from_secs(5));
+
+        let url = serve(vec![TOO_MANY, TOO_MANY, OK]).await;
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "ok");
+
+        // Callers with their own retry policy see the 429
+        let url = serve(vec![TOO_MANY, OK]).await;
+        let response = client.post(&url).body("{}").no_retry().send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+}
@@ -1,18 +1,24 @@
+mod backfill;
 mod binance_klines;
 mod chainlink;
 mod db;
 mod edge;
+mod http_client;
 mod models;
 mod output;
 mod processor;
 mod stats;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use serde::Deserialize;
 use std::path::PathBuf;
 
 use crate::db::DbConfig;
-use crate::models::{FirstPassageMatrix, ProbabilityMatrix};
+use crate::models::{
+    ExitPolicy, FirstPassageMatrix, MarketReachMatrix, MatrixSession, Outcome, PriceCrossingMatrix, PriceReachMatrix,
+    ProbabilityMatrix,
+};
 
 #[derive(Parser)]
 #[command(name = "btc-probability-matrix")]
@@ -29,6 +35,98 @@ enum Commands {
         /// Output directory for generated files
         #[arg(short, long, default_value = "output")]
         output_dir: PathBuf,
+
+        /// Assumed Polymarket half spread in cents for the market reach matrix
+        /// (ask = recorded price + h, bid = recorded price - h)
+        #[arg(long, default_value = "1.0")]
+        half_spread_cents: f64,
+
+        /// Days of Polymarket token prices used for the market reach matrix
+        #[arg(long, default_value = "28")]
+        market_reach_days: u32,
+
+        /// Exit policy: fraction of a bid's mispricing vs fair value that
+        /// carries over to the next 15s bucket (0 = snaps to fair, 1 = persists)
+        #[arg(long, default_value = "0.8")]
+        bid_persistence: f64,
+
+        /// Exit policy: min observed transitions before a state uses them
+        #[arg(long, default_value = "10")]
+        policy_min_samples: u32,
+
+        /// Shrink sparse cells towards a prior from neighbouring time/delta
+        /// buckets (Brownian digital fallback) instead of a flat Beta(1, 1)
+        #[arg(long)]
+        empirical_bayes: bool,
+
+        /// Empirical Bayes: prior weight in pseudo-observations
+        #[arg(long, default_value = "10.0")]
+        prior_strength: f64,
+
+        /// Empirical Bayes: BTC volatility for the parametric prior ($ per √second)
+        #[arg(long, default_value = "5.0")]
+        sigma_per_sqrt_s: f64,
+
+        /// Time decay: a window this many days old counts half (default: no decay)
+        #[arg(long)]
+        half_life_days: Option<f64>,
+
+        /// Regime: favour windows whose realized volatility is close to the
+        /// median of the last day
+        #[arg(long)]
+        vol_weighting: bool,
+
+        /// Regime: favour windows starting near this UTC hour (0-23)
+        #[arg(long)]
+        regime_hour: Option<u32>,
+
+        /// Regime: favour windows on this weekday (mon, tue, ...)
+        #[arg(long)]
+        regime_weekday: Option<chrono::Weekday>,
+
+        /// Also build one matrix per session defined in this YAML file
+        /// (e.g. config/matrix_sessions.yaml)
+        #[arg(long)]
+        sessions: Option<PathBuf>,
+
+        /// Kline interval to build from (1s gives second-level paths)
+        #[arg(long, default_value = "1m")]
+        resolution: binance_klines::KlineInterval,
+    },
+
+    /// Add the windows completed since the last build/update to the saved
+    /// raw counts and recompute the matrices (exit policy and market reach
+    /// are only refreshed by `build`)
+    Update {
+        /// Output directory for generated files
+        #[arg(short, long, default_value = "output")]
+        output_dir: PathBuf,
+
+        /// Shrink sparse cells towards a prior from neighbouring time/delta
+        /// buckets (Brownian digital fallback) instead of a flat Beta(1, 1)
+        #[arg(long)]
+        empirical_bayes: bool,
+
+        /// Empirical Bayes: prior weight in pseudo-observations
+        #[arg(long, default_value = "10.0")]
+        prior_strength: f64,
+
+        /// Empirical Bayes: BTC volatility for the parametric prior ($ per √second)
+        #[arg(long, default_value = "5.0")]
+        sigma_per_sqrt_s: f64,
+
+        /// Time decay: decay the saved weights by the time elapsed since the
+        /// last update (a window this many days old counts half)
+        #[arg(long)]
+        half_life_days: Option<f64>,
+
+        /// Also update the session matrices defined in this YAML file
+        #[arg(long)]
+        sessions: Option<PathBuf>,
+
+        /// Kline interval to read (use the one the saved counts were built from)
+        #[arg(long, default_value = "1m")]
+        resolution: binance_klines::KlineInterval,
     },
 
     /// Query the probability for a specific situation
@@ -76,6 +174,36 @@ enum Commands {
         entry_price: f64,
     },
 
+    /// Query market reach probabilities (P(bid reaches X¢) from observed Polymarket prices)
+    MarketReach {
+        /// Time elapsed in seconds since window start (0-899)
+        #[arg(short, long)]
+        time_elapsed: u32,
+
+        /// Current ask of the token (e.g., 0.40 for 40 cents)
+        #[arg(short, long)]
+        ask: f64,
+    },
+
+    /// Query the optimal stopping exit policy (sell now or hold at a given bid)
+    ExitPolicy {
+        /// Time elapsed in seconds since window start (0-899)
+        #[arg(short, long)]
+        time_elapsed: u32,
+
+        /// Current price delta from window open (in dollars)
+        #[arg(short, long)]
+        price_delta: f64,
+
+        /// Token held (up or down)
+        #[arg(short = 'd', long)]
+        direction: String,
+
+        /// Current bid of the token (e.g., 0.55 for 55 cents)
+        #[arg(short, long)]
+        bid: f64,
+    },
+
     /// Fetch Chainlink BTC/USD price data (deprecated - use binance)
     Chainlink {
         #[command(subcommand)]
@@ -87,6 +215,12 @@ enum Commands {
         #[command(subcommand)]
         action: BinanceAction,
     },
+
+    /// Backfill Polymarket history tables (resumable, per-window checkpoints)
+    Backfill {
+        #[command(subcommand)]
+        action: BackfillAction,
+    },
 }
 
 #[derive(Subcommand)]
@@ -103,25 +237,96 @@ enum ChainlinkAction {
 
     /// Show statistics about stored Chainlink data
     Stats,
+
+    /// Backfill from AnswerUpdated/NewRound event logs of every phase aggregator
+    /// (set CHAINLINK_RPC_URL to an archive node for older blocks)
+    Backfill {
+        /// Number of days of history to fetch (default: 180 = 6 months)
+        #[arg(short, long, default_value = "180")]
+        days: u32,
+
+        /// Max blocks per eth_getLogs request (halved when the RPC rejects it)
+        #[arg(long, default_value_t = chainlink::DEFAULT_LOG_BLOCK_RANGE)]
+        block_range: u64,
+    },
 }
 
 #[derive(Subcommand)]
 enum BinanceAction {
     /// Fetch historical klines from Binance
     Fetch {
-        /// Number of days of history to fetch (default: 180 = 6 months)
-        #[arg(short, long, default_value = "180")]
-        days: u32,
+        /// Number of days of history to fetch (default: 180 for 1m, 7 for 1s)
+        #[arg(short, long)]
+        days: Option<u32>,
+
+        /// Kline interval (1m or 1s)
+        #[arg(short, long, default_value = "1m")]
+        interval: binance_klines::KlineInterval,
     },
 
     /// Fetch only new data since last update (with gap detection)
-    Update,
+    Update {
+        /// Kline interval (1m or 1s)
+        #[arg(short, long, default_value = "1m")]
+        interval: binance_klines::KlineInterval,
+    },
 
     /// Show statistics about stored Binance data
     Stats,
 
     /// Find and fill gaps in the data
-    FillGaps,
+    FillGaps {
+        /// Kline interval (1m or 1s)
+        #[arg(short, long, default_value = "1m")]
+        interval: binance_klines::KlineInterval,
+    },
+
+    /// Fetch aggregate trades since the latest stored one
+    AggTrades {
+        /// Days of history to fetch when the table is empty
+        #[arg(short, long, default_value = "1")]
+        days: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackfillAction {
+    /// Backfill UP/DOWN price history into polymarket_prices
+    PolymarketPrices {
+        /// Days of history to cover (default: 28)
+        #[arg(short, long, default_value = "28")]
+        days: u32,
+
+        /// Cover only the last N hours instead (hourly sync)
+        #[arg(long)]
+        hours: Option<u32>,
+
+        /// Refetch checkpointed windows too
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Backfill individual trades into polymarket_trades
+    PolymarketTrades {
+        /// Days of history to cover (default: 28)
+        #[arg(short, long, default_value = "28")]
+        days: u32,
+
+        /// Cover only the last N hours instead (hourly sync)
+        #[arg(long)]
+        hours: Option<u32>,
+
+        /// Refetch checkpointed windows too
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Show checkpoints and missing windows per job
+    Status {
+        /// Days of history to check (default: 28)
+        #[arg(short, long, default_value = "28")]
+        days: u32,
+    },
 }
 
 #[tokio::main]
@@ -129,8 +334,63 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Build { output_dir } => {
-            build_matrix(output_dir).await?;
+        Commands::Build {
+            output_dir,
+            half_spread_cents,
+            market_reach_days,
+            bid_persistence,
+            policy_min_samples,
+            empirical_bayes,
+            prior_strength,
+            sigma_per_sqrt_s,
+            half_life_days,
+            vol_weighting,
+            regime_hour,
+            regime_weekday,
+            sessions,
+            resolution,
+        } => {
+            let smoothing = empirical_bayes.then(|| stats::SmoothingConfig {
+                prior_strength,
+                sigma_per_sqrt_s,
+                ..Default::default()
+            });
+            let weighting = BuildWeighting {
+                half_life_days,
+                vol_weighting,
+                hour: regime_hour,
+                weekday: regime_weekday,
+            };
+            let sessions = sessions.map(|path| load_sessions(&path)).transpose()?;
+            build_matrix(
+                output_dir,
+                half_spread_cents / 100.0,
+                market_reach_days,
+                bid_persistence,
+                policy_min_samples,
+                smoothing,
+                weighting,
+                sessions.unwrap_or_default(),
+                resolution,
+            )
+            .await?;
+        }
+        Commands::Update {
+            output_dir,
+            empirical_bayes,
+            prior_strength,
+            sigma_per_sqrt_s,
+            half_life_days,
+            sessions,
+            resolution,
+        } => {
+            let smoothing = empirical_bayes.then(|| stats::SmoothingConfig {
+                prior_strength,
+                sigma_per_sqrt_s,
+                ..Default::default()
+            });
+            let sessions = sessions.map(|path| load_sessions(&path)).transpose()?;
+            update_matrices(output_dir, smoothing, half_life_days, sessions.unwrap_or_default(), resolution).await?;
         }
         Commands::Query {
             time_elapsed,
@@ -151,18 +411,123 @@ async fn main() -> Result<()> {
         } => {
             query_first_passage(time_elapsed, price_delta, &direction, entry_price).await?;
         }
+        Commands::MarketReach { time_elapsed, ask } => {
+            query_market_reach(time_elapsed, ask)?;
+        }
+        Commands::ExitPolicy {
+            time_elapsed,
+            price_delta,
+            direction,
+            bid,
+        } => {
+            query_exit_policy(time_elapsed, price_delta, &direction, bid)?;
+        }
         Commands::Chainlink { action } => {
             handle_chainlink(action).await?;
         }
         Commands::Binance { action } => {
             handle_binance(action).await?;
         }
+        Commands::Backfill { action } => {
+            handle_backfill(action).await?;
+        }
     }
 
     Ok(())
 }
 
-async fn build_matrix(output_dir: PathBuf) -> Result<()> {
+/// Session definitions file for `build --sessions`
+#[derive(Deserialize)]
+struct SessionsFile {
+    sessions: Vec<MatrixSession>,
+}
+
+fn load_sessions(path: &std::path::Path) -> Result<Vec<MatrixSession>> {
+    let yaml = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let file: SessionsFile = serde_yaml::from_str(&yaml).with_context(|| format!("Failed to parse {}", path.display()))?;
+    if let Some(bad) = file.sessions.iter().find(|s| s.start_hour > 23 || s.end_hour > 24) {
+        anyhow::bail!("Session '{}': hours must be 0-23 (start) and 0-24 (end)", bad.name);
+    }
+    Ok(file.sessions)
+}
+
+/// Sample weighting options from the CLI (vol target resolved from the data)
+struct BuildWeighting {
+    half_life_days: Option<f64>,
+    vol_weighting: bool,
+    hour: Option<u32>,
+    weekday: Option<chrono::Weekday>,
+}
+
+impl BuildWeighting {
+    /// Windows in the last day, whose median realized volatility is the target
+    const RECENT_WINDOWS: usize = 96;
+
+    fn resolve(&self, windows: &[models::FifteenMinWindow]) -> processor::SampleWeighting {
+        let vol_target = if self.vol_weighting {
+            let mut recent: Vec<f64> = windows
+                .iter()
+                .rev()
+                .take(Self::RECENT_WINDOWS)
+                .map(processor::realized_volatility)
+                .collect();
+            recent.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            recent.get(recent.len() / 2).copied()
+        } else {
+            None
+        };
+
+        processor::SampleWeighting {
+            half_life_days: self.half_life_days,
+            vol_target,
+            vol_bandwidth: 0.5,
+            hour: self.hour,
+            hour_bandwidth: 2.0,
+            weekday: self.weekday,
+            other_weekday_weight: 0.3,
+        }
+    }
+}
+
+/// 15-minute windows from `start` to `last` (inclusive), fetched one UTC day
+/// at a time so a 1s build never holds more than a day of prices. Day
+/// boundaries are window boundaries, so no window is split across fetches.
+async fn load_windows(
+    client: &tokio_postgres::Client,
+    start: chrono::DateTime<chrono::Utc>,
+    last: chrono::DateTime<chrono::Utc>,
+    resolution: binance_klines::KlineInterval,
+) -> Result<Vec<models::FifteenMinWindow>> {
+    use chrono::Datelike;
+
+    let end = last + resolution.step();
+    let mut windows = Vec::new();
+    let mut chunk_start = start;
+    while chunk_start < end {
+        let next_day = (chunk_start.date_naive() + chrono::Days::new(1)).and_time(chrono::NaiveTime::MIN).and_utc();
+        let chunk_end = next_day.min(end);
+        let prices = db::fetch_prices_range(client, chunk_start, chunk_end, resolution).await?;
+        windows.extend(processor::process_into_windows(&prices));
+        if next_day.date_naive().day() == 1 {
+            println!("  {} windows up to {}", windows.len(), next_day.format("%Y-%m-%d"));
+        }
+        chunk_start = chunk_end;
+    }
+    Ok(windows)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn build_matrix(
+    output_dir: PathBuf,
+    half_spread: f64,
+    market_reach_days: u32,
+    bid_persistence: f64,
+    policy_min_samples: u32,
+    smoothing: Option<stats::SmoothingConfig>,
+    weighting: BuildWeighting,
+    sessions: Vec<MatrixSession>,
+    resolution: binance_klines::KlineInterval,
+) -> Result<()> {
     println!("🔌 Connecting to database...");
     let config = DbConfig::default();
     let client = db::connect(&config).await?;
@@ -170,33 +535,36 @@ async fn build_matrix(output_dir: PathBuf) -> Result<()> {
     // Run migrations for matrix snapshots table
     println!("📋 Running migrations...");
     db::run_matrix_migrations(&client).await?;
+    binance_klines::run_migration(&client).await?;
 
     // Get data range info
-    let (start, end) = db::get_data_range(&client).await?;
-    let count = db::get_price_count(&client).await?;
-    println!("📊 Data range: {} to {}", start, end);
+    let (start, end) = db::get_data_range(&client, resolution).await?;
+    let count = db::get_price_count(&client, resolution).await?;
+    println!("📊 Data range: {} to {} ({} klines)", start, end, resolution);
     println!("📊 Total records: {}", count);
 
-    println!("\n📥 Fetching price data (this may take a moment)...");
-    let prices = db::fetch_all_prices(&client).await?;
-    println!("✅ Fetched {} price points", prices.len());
-
-    println!("\n🔄 Processing into 15-minute windows...");
-    let windows = processor::process_into_windows(&prices);
+    println!("\n🔄 Fetching prices and processing into 15-minute windows (one day at a time)...");
+    let mut windows = load_windows(&client, start, end, resolution).await?;
+    processor::drop_incomplete_windows(&mut windows, end);
     println!("✅ Created {} windows", windows.len());
 
     println!("\n📈 Building probability matrix...");
+    let weighting = weighting.resolve(&windows);
     let mut matrix = ProbabilityMatrix::new();
-    processor::populate_matrix(&windows, &mut matrix);
+    if weighting.is_uniform() {
+        processor::populate_matrix(&windows, &mut matrix);
+    } else {
+        println!("⚖️  Sample weighting: {:?}", weighting);
+        processor::populate_matrix_weighted(&windows, &mut matrix, &weighting);
+    }
 
     // Compute statistics for all cells
-    println!("📊 Computing statistical significance...");
-    for time_bucket in 0u8..60 {
-        for delta_bucket in -17i8..=16i8 {
-            let cell = matrix.get_mut(time_bucket, delta_bucket);
-            stats::compute_cell_stats(cell);
-        }
+    if smoothing.is_some() {
+        println!("📊 Computing statistical significance (empirical Bayes priors)...");
+    } else {
+        println!("📊 Computing statistical significance...");
     }
+    stats::compute_matrix_stats(&mut matrix, smoothing.as_ref());
 
     // Build First-Passage Matrix
     println!("\n📈 Building first-passage matrix...");
@@ -286,6 +654,59 @@ async fn build_matrix(output_dir: PathBuf) -> Result<()> {
     std::fs::write(&pc_report_path, &pc_report)?;
     println!("  ✅ Report: {}", pc_report_path.display());
 
+    // Build Exit Policy (backward induction over delta transitions, uses terminal matrix)
+    println!("\n📈 Solving optimal stopping exit policy...");
+    let exit_policy = processor::build_exit_policy(&windows, &matrix, bid_persistence, policy_min_samples);
+
+    // Export Exit Policy files
+    let ep_json_path = output_dir.join("exit_policy.json");
+    let ep_csv_path = output_dir.join("exit_policy.csv");
+    let ep_report_path = output_dir.join("exit_policy_report.txt");
+
+    println!("\n💾 Exporting exit policy...");
+
+    output::export_exit_policy_to_json(&exit_policy, &ep_json_path)?;
+    println!("  ✅ JSON: {}", ep_json_path.display());
+
+    output::export_exit_policy_to_csv(&exit_policy, &ep_csv_path)?;
+    println!("  ✅ CSV: {}", ep_csv_path.display());
+
+    let ep_report = output::generate_exit_policy_report(&exit_policy);
+    std::fs::write(&ep_report_path, &ep_report)?;
+    println!("  ✅ Report: {}", ep_report_path.display());
+
+    // Build Market Reach Matrix (from recorded Polymarket token prices)
+    let reach_since = chrono::Utc::now() - chrono::Duration::days(market_reach_days as i64);
+    println!("\n📥 Fetching Polymarket token price history since {}...", reach_since.format("%Y-%m-%d"));
+    let price_paths = db::fetch_polymarket_price_paths(&client, reach_since).await?;
+    println!("✅ Fetched {} token price paths", price_paths.len());
+
+    let mr_report = if price_paths.is_empty() {
+        println!("⚠️  polymarket_prices is empty - skipping market reach matrix");
+        None
+    } else {
+        println!("\n📈 Building market reach matrix...");
+        let market_reach_matrix = processor::build_market_reach_matrix(&price_paths, half_spread);
+
+        let mr_json_path = output_dir.join("market_reach_matrix.json");
+        let mr_csv_path = output_dir.join("market_reach_matrix.csv");
+        let mr_report_path = output_dir.join("market_reach_report.txt");
+
+        println!("\n💾 Exporting market reach results...");
+
+        output::export_market_reach_to_json(&market_reach_matrix, &mr_json_path)?;
+        println!("  ✅ JSON: {}", mr_json_path.display());
+
+        output::export_market_reach_to_csv(&market_reach_matrix, &mr_csv_path)?;
+        println!("  ✅ CSV: {}", mr_csv_path.display());
+
+        let mr_report = output::generate_market_reach_report(&market_reach_matrix);
+        std::fs::write(&mr_report_path, &mr_report)?;
+        println!("  ✅ Report: {}", mr_report_path.display());
+
+        Some(mr_report)
+    };
+
     // Print summaries
     println!("\n{}", report);
     output::print_matrix_summary(&matrix);
@@ -296,16 +717,263 @@ async fn build_matrix(output_dir: PathBuf) -> Result<()> {
 
     println!("\n{}", pc_report);
 
+    println!("\n{}", ep_report);
+
+    if let Some(mr_report) = mr_report {
+        println!("\n{}", mr_report);
+    }
+
     // Save to database for bot access
     println!("\n💾 Saving matrix to database...");
     let snapshot_id = db::save_matrix(&client, &matrix).await?;
     println!("  ✅ Saved as snapshot #{}", snapshot_id);
 
+    println!("💾 Saving raw counts for incremental updates...");
+    save_counts(&client, &matrix, &fp_matrix, &price_reach_matrix, &crossing_matrix).await?;
+
+    // Session-conditioned matrices: same pipeline on each session's windows
+    if !sessions.is_empty() {
+        println!("\n🕐 Building session matrices...");
+    }
+    for session in &sessions {
+        let session_windows: Vec<_> = windows.iter().filter(|w| session.matches(w.start_time)).cloned().collect();
+
+        let mut session_matrix = ProbabilityMatrix::new();
+        if weighting.is_uniform() {
+            processor::populate_matrix(&session_windows, &mut session_matrix);
+        } else {
+            processor::populate_matrix_weighted(&session_windows, &mut session_matrix, &weighting);
+        }
+        stats::compute_matrix_stats(&mut session_matrix, smoothing.as_ref());
+
+        let path = output_dir.join(format!("matrix_{}.json", session.name));
+        output::export_to_json(&session_matrix, &path)?;
+        let snapshot_id = db::save_session_matrix(&client, session, &session_matrix).await?;
+        db::save_matrix_counts(&client, &session_counts_name(session), &session_matrix, session_matrix.data_end).await?;
+        println!(
+            "  ✅ {}: {} windows → {} (snapshot #{})",
+            session.name,
+            session_windows.len(),
+            path.display(),
+            snapshot_id
+        );
+    }
+
     println!("\n✅ Build complete!");
 
     Ok(())
 }
 
+// Names of the raw-count states in `matrix_counts`
+const COUNTS_TERMINAL: &str = "terminal";
+const COUNTS_FIRST_PASSAGE: &str = "first_passage";
+const COUNTS_PRICE_REACH: &str = "price_reach";
+const COUNTS_PRICE_CROSSING: &str = "price_crossing";
+
+fn session_counts_name(session: &MatrixSession) -> String {
+    format!("session:{}", session.name)
+}
+
+/// Save the raw counts of the four window-based matrices
+async fn save_counts(
+    client: &tokio_postgres::Client,
+    matrix: &ProbabilityMatrix,
+    fp_matrix: &FirstPassageMatrix,
+    pr_matrix: &PriceReachMatrix,
+    pc_matrix: &PriceCrossingMatrix,
+) -> Result<()> {
+    db::save_matrix_counts(client, COUNTS_TERMINAL, matrix, matrix.data_end).await?;
+    db::save_matrix_counts(client, COUNTS_FIRST_PASSAGE, fp_matrix, fp_matrix.data_end).await?;
+    db::save_matrix_counts(client, COUNTS_PRICE_REACH, pr_matrix, pr_matrix.data_end).await?;
+    db::save_matrix_counts(client, COUNTS_PRICE_CROSSING, pc_matrix, pc_matrix.data_end).await?;
+    Ok(())
+}
+
+/// Incremental build: load the saved raw counts, fetch only the prices since
+/// the last ingested window and add the newly completed windows.
+///
+/// Price reach and crossing map new windows through the updated terminal
+/// matrix; older windows keep the P(UP) they were mapped with, so run a full
+/// `build` now and then to re-map them.
+async fn update_matrices(
+    output_dir: PathBuf,
+    smoothing: Option<stats::SmoothingConfig>,
+    half_life_days: Option<f64>,
+    sessions: Vec<MatrixSession>,
+    resolution: binance_klines::KlineInterval,
+) -> Result<()> {
+    println!("🔌 Connecting to database...");
+    let config = DbConfig::default();
+    let client = db::connect(&config).await?;
+
+    println!("📋 Running migrations...");
+    db::run_matrix_migrations(&client).await?;
+
+    println!("📥 Loading saved matrix counts...");
+    let (Some(mut matrix), Some(mut fp_matrix), Some(mut pr_matrix), Some(mut pc_matrix)) = (
+        db::load_matrix_counts::<ProbabilityMatrix>(&client, COUNTS_TERMINAL).await?,
+        db::load_matrix_counts::<FirstPassageMatrix>(&client, COUNTS_FIRST_PASSAGE).await?,
+        db::load_matrix_counts::<PriceReachMatrix>(&client, COUNTS_PRICE_REACH).await?,
+        db::load_matrix_counts::<PriceCrossingMatrix>(&client, COUNTS_PRICE_CROSSING).await?,
+    ) else {
+        anyhow::bail!("No saved matrix counts - run 'build' first");
+    };
+
+    // Resume from the matrix that is furthest behind
+    let Some(last_ingested) = [matrix.data_end, fp_matrix.data_end, pr_matrix.data_end, pc_matrix.data_end]
+        .into_iter()
+        .min()
+        .flatten()
+    else {
+        anyhow::bail!("Saved matrix counts are empty - run 'build' first");
+    };
+    println!("📊 Last ingested window: {}", last_ingested);
+
+    binance_klines::run_migration(&client).await?;
+    let (_, latest) = db::get_data_range(&client, resolution).await?;
+    let since = last_ingested + chrono::Duration::minutes(15);
+    println!("\n📥 Fetching {} prices from {} to {}...", resolution, since, latest);
+    let mut windows = load_windows(&client, since, latest, resolution).await?;
+    processor::drop_incomplete_windows(&mut windows, latest);
+    if windows.is_empty() {
+        println!("\n✅ No newly completed windows - matrices are up to date");
+        return Ok(());
+    }
+    println!("✅ {} new windows", windows.len());
+
+    println!("\n📈 Updating probability matrix...");
+    processor::ingest_matrix_windows(processor::windows_after(&windows, matrix.data_end), &mut matrix, half_life_days);
+    stats::compute_matrix_stats(&mut matrix, smoothing.as_ref());
+
+    println!("📈 Updating first-passage matrix...");
+    processor::populate_first_passage_matrix(processor::windows_after(&windows, fp_matrix.data_end), &mut fp_matrix);
+    stats::compute_first_passage_matrix_stats(&mut fp_matrix);
+
+    println!("📈 Updating price reach matrix...");
+    processor::populate_price_reach_matrix(processor::windows_after(&windows, pr_matrix.data_end), &matrix, &mut pr_matrix);
+    processor::compute_price_reach_probabilities(&mut pr_matrix);
+
+    println!("📈 Updating price crossing matrix...");
+    processor::populate_price_crossing_matrix(
+        processor::windows_after(&windows, pc_matrix.data_end),
+        &matrix,
+        &mut pc_matrix,
+    );
+    processor::compute_crossing_averages(&mut pc_matrix);
+
+    // Export
+    std::fs::create_dir_all(&output_dir)?;
+    println!("\n💾 Exporting to {}...", output_dir.display());
+
+    output::export_to_json(&matrix, &output_dir.join("matrix.json"))?;
+    output::export_to_csv(&matrix, &output_dir.join("matrix.csv"))?;
+    std::fs::write(output_dir.join("report.txt"), output::generate_report(&matrix))?;
+
+    output::export_first_passage_to_json(&fp_matrix, &output_dir.join("first_passage_matrix.json"))?;
+    output::export_first_passage_to_csv(&fp_matrix, &output_dir.join("first_passage_matrix.csv"))?;
+    std::fs::write(
+        output_dir.join("first_passage_report.txt"),
+        output::generate_first_passage_report(&fp_matrix),
+    )?;
+
+    output::export_price_reach_to_json(&pr_matrix, &output_dir.join("price_reach_matrix.json"))?;
+    output::export_price_reach_to_csv(&pr_matrix, &output_dir.join("price_reach_matrix.csv"))?;
+    std::fs::write(
+        output_dir.join("price_reach_report.txt"),
+        output::generate_price_reach_report(&pr_matrix),
+    )?;
+
+    output::export_price_crossing_to_json(&pc_matrix, &output_dir.join("price_crossing_matrix.json"))?;
+    output::export_price_crossing_to_csv(&pc_matrix, &output_dir.join("price_crossing_matrix.csv"))?;
+    std::fs::write(
+        output_dir.join("price_crossing_report.txt"),
+        output::generate_price_crossing_report(&pc_matrix),
+    )?;
+
+    output::print_matrix_summary(&matrix);
+
+    println!("\n💾 Saving matrix to database...");
+    let snapshot_id = db::save_matrix(&client, &matrix).await?;
+    println!("  ✅ Saved as snapshot #{}", snapshot_id);
+    save_counts(&client, &matrix, &fp_matrix, &pr_matrix, &pc_matrix).await?;
+
+    if !sessions.is_empty() {
+        println!("\n🕐 Updating session matrices...");
+    }
+    for session in &sessions {
+        let name = session_counts_name(session);
+        let Some(mut session_matrix) = db::load_matrix_counts::<ProbabilityMatrix>(&client, &name).await? else {
+            println!("  ⚠️  {}: no saved counts - run 'build --sessions' first", session.name);
+            continue;
+        };
+
+        let session_windows: Vec<_> = processor::windows_after(&windows, session_matrix.data_end)
+            .iter()
+            .filter(|w| session.matches(w.start_time))
+            .cloned()
+            .collect();
+        processor::ingest_matrix_windows(&session_windows, &mut session_matrix, half_life_days);
+        stats::compute_matrix_stats(&mut session_matrix, smoothing.as_ref());
+
+        let path = output_dir.join(format!("matrix_{}.json", session.name));
+        output::export_to_json(&session_matrix, &path)?;
+        let snapshot_id = db::save_session_matrix(&client, session, &session_matrix).await?;
+        db::save_matrix_counts(&client, &name, &session_matrix, session_matrix.data_end).await?;
+        println!(
+            "  ✅ {}: +{} windows → {} (snapshot #{})",
+            session.name,
+            session_windows.len(),
+            path.display(),
+            snapshot_id
+        );
+    }
+
+    println!("\n✅ Update complete! ({} windows total)", matrix.total_windows);
+
+    Ok(())
+}
+
+fn query_market_reach(time_elapsed: u32, ask: f64) -> Result<()> {
+    let path = PathBuf::from("output/market_reach_matrix.json");
+
+    if !path.exists() {
+        println!("❌ Market reach matrix not found. Run 'build' first (needs polymarket_prices data).");
+        return Ok(());
+    }
+
+    let json = std::fs::read_to_string(&path)?;
+    let matrix: MarketReachMatrix = serde_json::from_str(&json)?;
+
+    let time_bucket = (time_elapsed / 15).min(59) as u8;
+    output::print_market_reach_from_state(&matrix, time_bucket, ask);
+
+    Ok(())
+}
+
+fn query_exit_policy(time_elapsed: u32, price_delta: f64, direction: &str, bid: f64) -> Result<()> {
+    let path = PathBuf::from("output/exit_policy.json");
+
+    if !path.exists() {
+        println!("❌ Exit policy not found. Run 'build' first to generate it.");
+        return Ok(());
+    }
+
+    let token = match direction.to_lowercase().as_str() {
+        "up" => Outcome::Up,
+        "down" => Outcome::Down,
+        _ => anyhow::bail!("direction must be 'up' or 'down'"),
+    };
+
+    let json = std::fs::read_to_string(&path)?;
+    let policy: ExitPolicy = serde_json::from_str(&json)?;
+
+    let time_bucket = (time_elapsed / 15).min(59) as u8;
+    let delta_bucket = models::delta_to_bucket(rust_decimal::Decimal::try_from(price_delta)?);
+    output::print_exit_policy_from_state(&policy, token, time_bucket, delta_bucket, bid);
+
+    Ok(())
+}
+
 async fn query_probability(
     time_elapsed: u32,
     price_delta: f64,
@@ -564,6 +1232,9 @@ async fn handle_chainlink(action: ChainlinkAction) -> Result<()> {
         ChainlinkAction::Stats => {
             chainlink_stats().await?;
         }
+        ChainlinkAction::Backfill { days, block_range } => {
+            chainlink_backfill(days, block_range).await?;
+        }
     }
     Ok(())
 }
@@ -671,6 +1342,58 @@ async fn chainlink_update() -> Result<()> {
     Ok(())
 }
 
+async fn chainlink_backfill(days: u32, block_range: u64) -> Result<()> {
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("        CHAINLINK BTC/USD EVENT LOG BACKFILL");
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    println!("📡 Connecting to Polygon RPC...");
+    let client = match std::env::var("CHAINLINK_RPC_URL") {
+        Ok(url) => chainlink::ChainlinkClient::with_rpc_url(&url, 60000).await?,
+        Err(_) => chainlink::ChainlinkClient::new().await?,
+    };
+
+    println!("🔌 Connecting to database...");
+    let config = DbConfig::default();
+    let db_client = db::connect(&config).await?;
+
+    // Resume from the newest stored round (its block is read again, upserts are idempotent)
+    let mut start = chrono::Utc::now() - chrono::Duration::days(days as i64);
+    if let Some((round_id, updated_at)) = chainlink::get_latest_chainlink_round(&db_client).await? {
+        if updated_at > start {
+            println!("📅 Resuming from stored round {} ({})", round_id, updated_at);
+            start = updated_at;
+        }
+    }
+
+    println!("\n📥 Fetching AnswerUpdated/NewRound logs since {} (inserting each block chunk)...", start);
+    let backfill = client.backfill_from_logs(start, block_range, Some(&db_client)).await?;
+
+    println!(
+        "\n📊 Rounds: {} in blocks {} to {} ({} filled with getRoundData, {} superseded events dropped)",
+        backfill.rounds.len(),
+        backfill.from_block,
+        backfill.to_block,
+        backfill.refilled,
+        backfill.superseded
+    );
+    if backfill.gaps.is_empty() {
+        println!("📊 Round continuity: OK");
+    } else {
+        let missing: u64 = backfill.gaps.iter().map(|g| g.last_missing - g.first_missing + 1).sum();
+        println!("⚠️  {} rounds still missing in {} gaps:", missing, backfill.gaps.len());
+        for gap in backfill.gaps.iter().take(5) {
+            println!("    phase {}: rounds {} to {}", gap.phase_id, gap.first_missing, gap.last_missing);
+        }
+        if backfill.gaps.len() > 5 {
+            println!("    ... and {} more", backfill.gaps.len() - 5);
+        }
+    }
+    println!("✅ Inserted {} records", backfill.inserted);
+
+    chainlink_stats().await
+}
+
 async fn chainlink_stats() -> Result<()> {
     println!("═══════════════════════════════════════════════════════════════");
     println!("              CHAINLINK PRICES DATABASE STATS");
@@ -709,23 +1432,26 @@ async fn chainlink_stats() -> Result<()> {
 
 async fn handle_binance(action: BinanceAction) -> Result<()> {
     match action {
-        BinanceAction::Fetch { days } => {
-            binance_fetch(days).await?;
+        BinanceAction::Fetch { days, interval } => {
+            binance_fetch(days.unwrap_or(interval.default_lookback_days()), interval).await?;
         }
-        BinanceAction::Update => {
-            binance_update().await?;
+        BinanceAction::Update { interval } => {
+            binance_update(interval).await?;
         }
         BinanceAction::Stats => {
             binance_stats().await?;
         }
-        BinanceAction::FillGaps => {
-            binance_fill_gaps().await?;
+        BinanceAction::FillGaps { interval } => {
+            binance_fill_gaps(interval).await?;
+        }
+        BinanceAction::AggTrades { days } => {
+            binance_agg_trades(days).await?;
         }
     }
     Ok(())
 }
 
-async fn binance_fetch(days: u32) -> Result<()> {
+async fn binance_fetch(days: u32, interval: binance_klines::KlineInterval) -> Result<()> {
     println!("═══════════════════════════════════════════════════════════════");
     println!("        BINANCE BTC/USDT HISTORICAL KLINES FETCH");
     println!("═══════════════════════════════════════════════════════════════\n");
@@ -744,25 +1470,19 @@ async fn binance_fetch(days: u32) -> Result<()> {
     let start_time = end_time - chrono::Duration::days(days as i64);
 
     println!("📅 Fetching from {} to {}", start_time.format("%Y-%m-%d"), end_time.format("%Y-%m-%d"));
-    println!("📊 Expected: ~{} klines (1-minute intervals)\n", days * 24 * 60);
-
-    let klines = binance_client
-        .fetch_all_klines(start_time, end_time, Some(&|current, total| {
-            if current % 50 == 0 || current == total {
-                println!("  Progress: {}/{} requests ({:.1}%)", current, total, (current as f64 / total as f64) * 100.0);
-            }
-        }))
-        .await?;
-
-    println!("\n📊 Fetched {} klines", klines.len());
-
-    if !klines.is_empty() {
-        println!("  First: {} @ ${:.2}", klines.first().unwrap().open_time, klines.first().unwrap().close);
-        println!("  Last:  {} @ ${:.2}", klines.last().unwrap().open_time, klines.last().unwrap().close);
-    }
+    println!(
+        "📊 Expected: ~{} klines ({} intervals)\n",
+        (end_time - start_time).num_seconds() / interval.step().num_seconds(),
+        interval
+    );
 
-    println!("\n💾 Inserting into database...");
-    let inserted = binance_klines::insert_klines(&db_client, &klines).await?;
+    println!("💾 Inserting each page as it arrives...");
+    let inserted = binance_klines::ingest_klines(&db_client, &binance_client, start_time, end_time, interval, Some(&|current, total| {
+        if current.is_multiple_of(50) || current == total {
+            println!("  Progress: {}/{} requests ({:.1}%)", current, total, (current as f64 / total as f64) * 100.0);
+        }
+    }))
+    .await?;
     println!("✅ Inserted {} new records", inserted);
 
     // Show stats
@@ -771,7 +1491,7 @@ async fn binance_fetch(days: u32) -> Result<()> {
     Ok(())
 }
 
-async fn binance_update() -> Result<()> {
+async fn binance_update(interval: binance_klines::KlineInterval) -> Result<()> {
     println!("═══════════════════════════════════════════════════════════════");
     println!("         BINANCE BTC/USDT KLINES UPDATE");
     println!("═══════════════════════════════════════════════════════════════\n");
@@ -782,13 +1502,13 @@ async fn binance_update() -> Result<()> {
 
     binance_klines::run_migration(&db_client).await?;
 
-    let latest = binance_klines::get_latest_timestamp(&db_client).await?;
+    let latest = binance_klines::get_latest_timestamp(&db_client, interval).await?;
 
     match latest {
-        Some(ts) => println!("📅 Last stored timestamp: {}", ts),
+        Some(ts) => println!("📅 Last stored {} timestamp: {}", interval, ts),
         None => {
-            println!("⚠️  No existing data. Running full fetch instead...");
-            return binance_fetch(180).await;
+            println!("⚠️  No existing {} data. Running full fetch instead...", interval);
+            return binance_fetch(interval.default_lookback_days(), interval).await;
         }
     }
 
@@ -796,7 +1516,7 @@ async fn binance_update() -> Result<()> {
     let binance_client = binance_klines::BinanceKlinesClient::new(30000)?;
 
     println!("\n📥 Fetching new data...");
-    let inserted = binance_klines::update_klines(&db_client, &binance_client).await?;
+    let inserted = binance_klines::update_klines(&db_client, &binance_client, interval).await?;
     println!("✅ Total inserted: {} records", inserted);
 
     // Show updated stats
@@ -815,44 +1535,54 @@ async fn binance_stats() -> Result<()> {
 
     binance_klines::run_migration(&db_client).await?;
 
-    let count = binance_klines::get_kline_count(&db_client).await?;
-    let earliest = binance_klines::get_earliest_timestamp(&db_client).await?;
-    let latest = binance_klines::get_latest_timestamp(&db_client).await?;
+    for interval in [binance_klines::KlineInterval::OneMinute, binance_klines::KlineInterval::OneSecond] {
+        let count = binance_klines::get_kline_count(&db_client, interval).await?;
+        let earliest = binance_klines::get_earliest_timestamp(&db_client, interval).await?;
+        let latest = binance_klines::get_latest_timestamp(&db_client, interval).await?;
 
-    println!("  Total rows:   {:>12}", count);
+        println!("  {} klines", interval);
+        println!("  Total rows:   {:>12}", count);
 
-    if count > 0 {
-        if let (Some(e), Some(l)) = (earliest, latest) {
-            println!("  Earliest:     {}", e);
-            println!("  Latest:       {}", l);
-            let duration = l - e;
-            println!("  Span:         {} days, {} hours", duration.num_days(), duration.num_hours() % 24);
-        }
-
-        // Check for gaps
-        let gaps = binance_klines::find_gaps(&db_client).await?;
-        if gaps.is_empty() {
-            println!("  Gaps:         None detected");
-        } else {
-            println!("  Gaps:         {} gaps found (run 'binance fill-gaps' to fix)", gaps.len());
-            for (i, (start, end)) in gaps.iter().take(5).enumerate() {
-                let minutes = (*end - *start).num_minutes();
-                println!("    {}. {} to {} ({} min)", i + 1, start, end, minutes);
+        if count > 0 {
+            if let (Some(e), Some(l)) = (earliest, latest) {
+                println!("  Earliest:     {}", e);
+                println!("  Latest:       {}", l);
+                let duration = l - e;
+                println!("  Span:         {} days, {} hours", duration.num_days(), duration.num_hours() % 24);
             }
-            if gaps.len() > 5 {
-                println!("    ... and {} more", gaps.len() - 5);
+
+            // Check for gaps
+            let gaps = binance_klines::find_gaps(&db_client, interval).await?;
+            if gaps.is_empty() {
+                println!("  Gaps:         None detected");
+            } else {
+                println!("  Gaps:         {} gaps found (run 'binance fill-gaps --interval {}' to fix)", gaps.len(), interval);
+                for (i, (start, end)) in gaps.iter().take(5).enumerate() {
+                    let minutes = (*end - *start).num_minutes();
+                    println!("    {}. {} to {} ({} min)", i + 1, start, end, minutes);
+                }
+                if gaps.len() > 5 {
+                    println!("    ... and {} more", gaps.len() - 5);
+                }
             }
+        } else {
+            println!("  No data. Run 'binance fetch --interval {}' to populate.", interval);
         }
-    } else {
-        println!("\n  No data in database. Run 'binance fetch' to populate.");
+        println!();
     }
 
+    let agg_trades = binance_klines::get_agg_trade_count(&db_client).await?;
+    println!("  aggTrades");
+    println!("  Total rows:   {:>12}", agg_trades);
+    if let Some((id, ts)) = binance_klines::get_latest_agg_trade(&db_client).await? {
+        println!("  Latest:       {} (id {})", ts, id);
+    }
     println!("\n═══════════════════════════════════════════════════════════════\n");
 
     Ok(())
 }
 
-async fn binance_fill_gaps() -> Result<()> {
+async fn binance_fill_gaps(interval: binance_klines::KlineInterval) -> Result<()> {
     println!("═══════════════════════════════════════════════════════════════");
     println!("         BINANCE KLINES GAP FILL");
     println!("═══════════════════════════════════════════════════════════════\n");
@@ -867,7 +1597,7 @@ async fn binance_fill_gaps() -> Result<()> {
     let binance_client = binance_klines::BinanceKlinesClient::new(30000)?;
 
     println!("\n🔍 Scanning for gaps...");
-    let filled = binance_klines::fill_gaps(&db_client, &binance_client).await?;
+    let filled = binance_klines::fill_gaps(&db_client, &binance_client, interval).await?;
     println!("✅ Filled {} records", filled);
 
     // Show updated stats
@@ -875,3 +1605,141 @@ async fn binance_fill_gaps() -> Result<()> {
 
     Ok(())
 }
+
+async fn binance_agg_trades(days: u32) -> Result<()> {
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("         BINANCE BTC/USDT AGGREGATE TRADES");
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    println!("🔌 Connecting to database...");
+    let config = DbConfig::default();
+    let db_client = db::connect(&config).await?;
+
+    binance_klines::run_migration(&db_client).await?;
+
+    println!("📡 Creating Binance client...");
+    let binance_client = binance_klines::BinanceKlinesClient::new(30000)?;
+
+    println!("\n📥 Fetching aggregate trades...");
+    let inserted = binance_klines::update_agg_trades(&db_client, &binance_client, days).await?;
+    println!("✅ Inserted {} aggregate trades", inserted);
+
+    // Show updated stats
+    binance_stats().await?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Backfill Commands
+// ============================================================================
+
+async fn handle_backfill(action: BackfillAction) -> Result<()> {
+    let lookback_secs = |days: u32, hours: Option<u32>| match hours {
+        Some(h) => h as i64 * 3600,
+        None => days as i64 * 86400,
+    };
+    match action {
+        BackfillAction::PolymarketPrices { days, hours, force } => {
+            backfill_run(backfill::Job::PolymarketPrices, lookback_secs(days, hours), force).await?;
+        }
+        BackfillAction::PolymarketTrades { days, hours, force } => {
+            backfill_run(backfill::Job::PolymarketTrades, lookback_secs(days, hours), force).await?;
+        }
+        BackfillAction::Status { days } => {
+            backfill_status(days).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn backfill_run(job: backfill::Job, lookback_secs: i64, force: bool) -> Result<()> {
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("         BACKFILL {}", job.table().to_uppercase());
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    println!("🔌 Connecting to database...");
+    let config = DbConfig::default();
+    let mut db_client = db::connect(&config).await?;
+
+    println!("📋 Running migrations and schema checks...");
+    backfill::run_migrations(&db_client).await?;
+
+    let api = backfill::PolymarketHistoryClient::new(30000);
+    println!(
+        "📅 Covering the last {:.1} hours{}\n",
+        lookback_secs as f64 / 3600.0,
+        if force { " (forced refetch)" } else { "" }
+    );
+
+    let summary = backfill::run(&mut db_client, &api, job, lookback_secs, force).await?;
+
+    println!("\n📊 Windows fetched: {}", summary.windows);
+    println!("  With data:    {:>8}", summary.with_data);
+    println!("  Empty:        {:>8}", summary.empty);
+    println!("  Failed:       {:>8}", summary.failed);
+    println!("  Truncated:    {:>8}", summary.truncated);
+    println!("  Rows written: {:>8}", summary.rows);
+    if summary.failed > 0 {
+        println!("\n⚠️  Failed windows stay as gaps and are retried on the next run");
+    }
+    if summary.truncated > 0 {
+        println!("⚠️  Truncated windows hit the trade page cap; they are listed by 'backfill status'");
+    }
+
+    Ok(())
+}
+
+async fn backfill_status(days: u32) -> Result<()> {
+    println!("\n═══════════════════════════════════════════════════════════════");
+    println!("              BACKFILL STATUS (last {} days)", days);
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    let config = DbConfig::default();
+    let db_client = db::connect(&config).await?;
+
+    backfill::run_migrations(&db_client).await?;
+
+    let (first, last) = backfill::window_range(chrono::Utc::now().timestamp(), days as i64 * 86400);
+    for job in backfill::Job::ALL {
+        let stats = backfill::checkpoint_stats(&db_client, job).await?;
+        println!("  {}", job.table());
+        println!("    Checkpoints:  {} with data, {} empty, {} truncated", stats.done, stats.empty, stats.truncated);
+        if let Some(completed) = stats.last_completed {
+            println!("    Last run:     {}", completed);
+        }
+
+        if first > last {
+            continue;
+        }
+        let truncated = backfill::find_truncated_windows(&db_client, job, first, last).await?;
+        if !truncated.is_empty() {
+            println!("    Truncated:    {} windows missing trades past the page cap (e.g. {})",
+                truncated.len(), backfill::window_slug(truncated[0]));
+        }
+        let missing = backfill::find_missing_windows(&db_client, job, first, last).await?;
+        let gaps = backfill::group_gaps(&missing);
+        if gaps.is_empty() {
+            println!("    Gaps:         None detected");
+        } else {
+            println!(
+                "    Gaps:         {} missing windows in {} gaps (run 'backfill {}' to fix)",
+                missing.len(),
+                gaps.len(),
+                job.table().replace('_', "-")
+            );
+            for (i, (start, end)) in gaps.iter().take(5).enumerate() {
+                let windows = (end - start) / backfill::WINDOW_SECS + 1;
+                println!("      {}. {} to {} ({} windows)", i + 1, backfill::window_slug(*start), backfill::window_slug(*end), windows);
+            }
+            if gaps.len() > 5 {
+                println!("      ... and {} more", gaps.len() - 5);
+            }
+        }
+        println!();
+    }
+
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    Ok(())
+}
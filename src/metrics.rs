@@ -0,0 +1,170 @@
+//! Prometheus metrics shared by btc-bot and btc-logger-ws
+//!
+//! Metrics live in the default prometheus registry and are exposed as text on
+//! `GET /metrics` by a small embedded HTTP server. Feed staleness is computed at
+//! scrape time from the last message seen on each WebSocket feed.
+
+use anyhow::{Context, Result};
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use prometheus::{
+    register_gauge, register_gauge_vec, register_histogram_vec, register_int_counter_vec,
+    register_int_gauge, Encoder, Gauge, GaugeVec, HistogramVec, IntCounterVec, IntGauge, TextEncoder,
+};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+use tracing::info;
+
+// ============================================================================
+// Trading
+// ============================================================================
+
+/// Bet signals produced by the strategy, by strategy type (TERMINAL/EXIT)
+pub static SIGNALS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("btc_bot_signals_total", "Bet signals by strategy", &["strategy"]).unwrap()
+});
+
+/// Orders by side (BUY/SELL) and status (submitted/filled/rejected/error)
+pub static ORDERS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("btc_bot_orders_total", "Orders by side and status", &["side", "status"]).unwrap()
+});
+
+/// Time from order submission to CLOB response (FOK orders fill or die in this window)
+pub static FILL_LATENCY: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "btc_bot_fill_latency_seconds",
+        "Order submission to CLOB response latency",
+        &["side"],
+        vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0]
+    )
+    .unwrap()
+});
+
+pub static OPEN_POSITIONS: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!("btc_bot_open_positions", "Currently open positions").unwrap()
+});
+
+pub static BANKROLL: LazyLock<Gauge> = LazyLock::new(|| {
+    register_gauge!("btc_bot_bankroll_usdc", "Current bankroll in USDC").unwrap()
+});
+
+pub static DAILY_PNL: LazyLock<Gauge> = LazyLock::new(|| {
+    register_gauge!("btc_bot_daily_pnl_usdc", "Realized P&L today in USDC").unwrap()
+});
+
+/// 1 while this pod holds the leader lease (only the leader trades)
+pub static IS_LEADER: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!("btc_bot_is_leader", "1 if this pod is the trading leader, 0 if standby").unwrap()
+});
+
+// ============================================================================
+// Feeds
+// ============================================================================
+
+/// WebSocket reconnects by feed (binance/polymarket)
+pub static WS_RECONNECTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("btc_ws_reconnects_total", "WebSocket reconnects by feed", &["feed"]).unwrap()
+});
+
+/// WebSocket text messages received by feed (use rate() for message rate)
+pub static WS_MESSAGES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("btc_ws_messages_total", "WebSocket messages received by feed", &["feed"]).unwrap()
+});
+
+/// Seconds since the last message on each feed (updated on scrape)
+pub static FEED_STALENESS: LazyLock<GaugeVec> = LazyLock::new(|| {
+    register_gauge_vec!("btc_feed_staleness_seconds", "Seconds since last message by feed", &["feed"]).unwrap()
+});
+
+/// Last message time per feed, used to compute FEED_STALENESS
+static FEED_LAST_SEEN: LazyLock<Mutex<HashMap<&'static str, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+/// Errors talking to storage backends (postgres/redis)
+pub static STORAGE_ERRORS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("btc_storage_errors_total", "Storage errors by backend", &["backend"]).unwrap()
+});
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Record a message received on a WebSocket feed
+pub fn feed_message(feed: &'static str) {
+    WS_MESSAGES.with_label_values(&[feed]).inc();
+    if let Ok(mut seen) = FEED_LAST_SEEN.lock() {
+        seen.insert(feed, Instant::now());
+    }
+}
+
+/// Record a WebSocket (re)connect attempt after the first one
+pub fn feed_reconnect(feed: &'static str) {
+    WS_RECONNECTS.with_label_values(&[feed]).inc();
+}
+
+pub fn db_error() {
+    STORAGE_ERRORS.with_label_values(&["postgres"]).inc();
+}
+
+pub fn redis_error() {
+    STORAGE_ERRORS.with_label_values(&["redis"]).inc();
+}
+
+/// Encode all registered metrics in the Prometheus text format
+pub fn render() -> String {
+    if let Ok(seen) = FEED_LAST_SEEN.lock() {
+        for (feed, t) in seen.iter() {
+            FEED_STALENESS.with_label_values(&[feed]).set(t.elapsed().as_secs_f64());
+        }
+    }
+
+    let mut buf = Vec::new();
+    let _ = TextEncoder::new().encode(&prometheus::gather(), &mut buf);
+    String::from_utf8(buf).unwrap_or_default()
+}
+
+// ============================================================================
+// HTTP Server
+// ============================================================================
+
+async fn metrics_handler() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], render())
+}
+
+/// Router exposing `GET /metrics` (merge into a larger router if needed)
+pub fn router() -> Router {
+    Router::new().route("/metrics", get(metrics_handler))
+}
+
+/// Serve a router on 0.0.0.0:port until the process exits
+pub async fn serve(port: u16, router: Router) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
+        .await
+        .with_context(|| format!("Failed to bind HTTP server on port {}", port))?;
+    info!("HTTP server listening on :{}", port);
+    axum::serve(listener, router).await.context("HTTP server failed")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_counters_and_staleness() {
+        SIGNALS.with_label_values(&["TERMINAL"]).inc();
+        feed_message("binance");
+
+        let text = render();
+        assert!(text.contains("btc_bot_signals_total{strategy=\"TERMINAL\"}"));
+        assert!(text.contains("btc_ws_messages_total{feed=\"binance\"}"));
+        assert!(text.contains("btc_feed_staleness_seconds{feed=\"binance\"}"));
+    }
+}
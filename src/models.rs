@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -36,6 +36,10 @@ pub struct PriceSnapshot {
     pub price: Decimal,
     /// Delta from window open price (can be negative)
     pub delta_from_open: Decimal,
+    /// Highest and lowest delta from open within this bucket (both equal
+    /// delta_from_open unless the bucket holds several prices, e.g. 1s klines)
+    pub high_delta: Decimal,
+    pub low_delta: Decimal,
 }
 
 /// Time bucket (0-59, representing 15-second intervals within 15 minutes)
@@ -147,6 +151,21 @@ pub fn bucket_to_label(bucket: i8) -> &'static str {
     }
 }
 
+/// Representative price delta (in dollars) for a bucket: its midpoint, or
+/// ±$330 for the open-ended extremes
+#[allow(dead_code)]
+pub fn bucket_to_midpoint(bucket: i8) -> f64 {
+    const EDGES: [f64; 17] = [
+        0.0, 5.0, 10.0, 15.0, 20.0, 30.0, 40.0, 50.0, 70.0, 90.0, 110.0, 140.0, 170.0, 200.0, 230.0, 260.0, 300.0,
+    ];
+    let magnitude = |i: usize| if i >= 16 { 330.0 } else { (EDGES[i] + EDGES[i + 1]) / 2.0 };
+    if bucket >= 0 {
+        magnitude(bucket.min(DELTA_BUCKET_MAX) as usize)
+    } else {
+        -magnitude((-(bucket.max(DELTA_BUCKET_MIN) as i16) - 1) as usize)
+    }
+}
+
 /// Confidence level for a probability estimate
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConfidenceLevel {
@@ -174,6 +193,14 @@ impl ConfidenceLevel {
     }
 }
 
+fn default_flat_prior() -> f64 {
+    1.0
+}
+
+fn default_credible_upper() -> f64 {
+    1.0
+}
+
 /// Statistics for a single cell in the probability matrix
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CellStats {
@@ -187,6 +214,19 @@ pub struct CellStats {
     /// Number of times outcome was DOWN
     pub count_down: u32,
 
+    /// Sample-weighted counts (time decay / regime weighting). Zero when the
+    /// matrix was built without weighting.
+    #[serde(default)]
+    pub weight_up: f64,
+    #[serde(default)]
+    pub weight_down: f64,
+    /// Sum of squared weights, for the Kish effective sample size
+    #[serde(default)]
+    pub weight_sq_sum: f64,
+    /// Effective sample size (Σw)² / Σw² - what intervals and confidence use
+    #[serde(default)]
+    pub effective_samples: f64,
+
     /// Raw probability of UP
     pub p_up: f64,
     /// Raw probability of DOWN
@@ -202,6 +242,18 @@ pub struct CellStats {
     /// Bayesian posterior beta (for Beta distribution)
     pub beta_beta: f64,
 
+    /// Prior the posterior was built from (Beta(1, 1) unless empirical-Bayes smoothed)
+    #[serde(default = "default_flat_prior")]
+    pub prior_alpha: f64,
+    #[serde(default = "default_flat_prior")]
+    pub prior_beta: f64,
+
+    /// 95% credible interval for P(UP) from the Beta posterior
+    #[serde(default)]
+    pub p_up_credible_lower: f64,
+    #[serde(default = "default_credible_upper")]
+    pub p_up_credible_upper: f64,
+
     /// Confidence level based on sample size
     pub confidence_level: ConfidenceLevel,
 }
@@ -213,12 +265,20 @@ impl CellStats {
             price_delta_bucket,
             count_up: 0,
             count_down: 0,
+            weight_up: 0.0,
+            weight_down: 0.0,
+            weight_sq_sum: 0.0,
+            effective_samples: 0.0,
             p_up: 0.5,
             p_down: 0.5,
             p_up_wilson_lower: 0.0,
             p_up_wilson_upper: 1.0,
             beta_alpha: 1.0,  // Uniform prior
             beta_beta: 1.0,
+            prior_alpha: 1.0,
+            prior_beta: 1.0,
+            p_up_credible_lower: 0.0,
+            p_up_credible_upper: 1.0,
             confidence_level: ConfidenceLevel::Unreliable,
         }
     }
@@ -227,12 +287,60 @@ impl CellStats {
         self.count_up + self.count_down
     }
 
+    /// Effective sample size once stats are computed, else the raw count
+    #[allow(dead_code)]
+    pub fn sample_size(&self) -> f64 {
+        if self.effective_samples > 0.0 {
+            self.effective_samples
+        } else {
+            self.total() as f64
+        }
+    }
+
     pub fn increment(&mut self, outcome: Outcome) {
         match outcome {
             Outcome::Up => self.count_up += 1,
             Outcome::Down => self.count_down += 1,
         }
     }
+
+    /// Count an observation that carries `weight` (time decay / regime)
+    #[allow(dead_code)]
+    pub fn increment_weighted(&mut self, outcome: Outcome, weight: f64) {
+        self.increment(outcome);
+        match outcome {
+            Outcome::Up => self.weight_up += weight,
+            Outcome::Down => self.weight_down += weight,
+        }
+        self.weight_sq_sum += weight * weight;
+    }
+
+    /// Scale the recorded weights by `factor` (time decay between updates).
+    /// An unweighted cell first gets a weight of 1 per observation.
+    #[allow(dead_code)]
+    pub fn decay(&mut self, factor: f64) {
+        if self.weight_sq_sum <= 0.0 {
+            self.weight_up = self.count_up as f64;
+            self.weight_down = self.count_down as f64;
+            self.weight_sq_sum = self.total() as f64;
+        }
+        self.weight_up *= factor;
+        self.weight_down *= factor;
+        self.weight_sq_sum *= factor * factor;
+    }
+
+    /// (UP, DOWN) counts scaled to the effective sample size, so a weighted
+    /// cell keeps its weighted P(UP) without overstating how much data it has.
+    /// Unweighted cells (no weights recorded) use the raw counts.
+    pub fn effective_counts(&self) -> (f64, f64) {
+        let weight = self.weight_up + self.weight_down;
+        if weight <= 0.0 || self.weight_sq_sum <= 0.0 {
+            return (self.count_up as f64, self.count_down as f64);
+        }
+        let n_eff = weight * weight / self.weight_sq_sum;
+        let p_up = self.weight_up / weight;
+        (p_up * n_eff, (1.0 - p_up) * n_eff)
+    }
 }
 
 /// The complete probability matrix: 60 time buckets x 34 price delta buckets
@@ -285,6 +393,90 @@ impl ProbabilityMatrix {
         let delta_bucket = delta_to_bucket(price_delta);
         self.get_mut(time_bucket, delta_bucket).increment(outcome);
     }
+
+    /// Record an observation that counts `weight` times (time decay / regime)
+    #[allow(dead_code)]
+    pub fn record_weighted(&mut self, time_bucket: u8, price_delta: Decimal, outcome: Outcome, weight: f64) {
+        let delta_bucket = delta_to_bucket(price_delta);
+        self.get_mut(time_bucket, delta_bucket).increment_weighted(outcome, weight);
+    }
+
+    /// Whether any cell carries sample weights (built with decay / regime weighting)
+    #[allow(dead_code)]
+    pub fn is_weighted(&self) -> bool {
+        self.cells.iter().flatten().any(|cell| cell.weight_sq_sum > 0.0)
+    }
+
+    /// Decay every cell's weights by `factor`
+    #[allow(dead_code)]
+    pub fn decay(&mut self, factor: f64) {
+        for cell in self.cells.iter_mut().flatten() {
+            cell.decay(factor);
+        }
+    }
+
+    /// Copy of this (session) matrix where every cell with fewer than
+    /// `min_samples` effective samples is replaced by the pooled matrix cell
+    #[allow(dead_code)]
+    pub fn with_fallback(&self, pooled: &ProbabilityMatrix, min_samples: f64) -> ProbabilityMatrix {
+        let mut merged = self.clone();
+        for (row, pooled_row) in merged.cells.iter_mut().zip(&pooled.cells) {
+            for (cell, pooled_cell) in row.iter_mut().zip(pooled_row) {
+                if cell.sample_size() < min_samples {
+                    *cell = pooled_cell.clone();
+                }
+            }
+        }
+        merged
+    }
+}
+
+// ============================================================================
+// SESSION-CONDITIONED MATRICES
+// ============================================================================
+
+/// Days a session covers
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionDays {
+    #[default]
+    All,
+    Weekdays,
+    Weekends,
+}
+
+/// A trading session: windows starting in [start_hour, end_hour) UTC (wrapping
+/// past midnight when start > end) on the selected days
+#[allow(dead_code)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatrixSession {
+    pub name: String,
+    pub start_hour: u32,
+    pub end_hour: u32,
+    #[serde(default)]
+    pub days: SessionDays,
+}
+
+impl MatrixSession {
+    #[allow(dead_code)]
+    pub fn matches(&self, time: DateTime<Utc>) -> bool {
+        let weekend = matches!(time.weekday(), Weekday::Sat | Weekday::Sun);
+        let day_ok = match self.days {
+            SessionDays::All => true,
+            SessionDays::Weekdays => !weekend,
+            SessionDays::Weekends => weekend,
+        };
+
+        let hour = time.hour();
+        let hour_ok = if self.start_hour <= self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        };
+
+        day_ok && hour_ok
+    }
 }
 
 /// Recommendation from the bot API
@@ -929,6 +1121,273 @@ pub fn count_crossings(prev_price: f64, curr_price: f64) -> [u32; 25] {
     crossings
 }
 
+// ============================================================================
+// MARKET REACH MATRIX (Matrix 5)
+// Built directly from historical Polymarket token prices (polymarket_prices),
+// not from modeled prices: P(token bid reaches X¢ before expiry | time, current ask)
+// Ask levels: 0-4¢, 4-8¢, ..., 96-100¢ (25 levels); targets: 4¢, 8¢, ..., 100¢
+// ============================================================================
+
+/// Number of ask levels / target levels in the market reach matrix
+pub const MARKET_REACH_LEVELS: usize = 25;
+
+/// Convert a token price (0.0-1.0) to its ask level (0 = 0-4¢, ..., 24 = 96-100¢)
+pub fn price_to_ask_level(price: f64) -> usize {
+    ((price * 100.0).floor().max(0.0) as usize / 4).min(MARKET_REACH_LEVELS - 1)
+}
+
+/// Target price for a target level (0 = 4¢, ..., 24 = 100¢)
+pub fn reach_level_to_price(level: usize) -> f64 {
+    crossing_level_to_cents(level) as f64 / 100.0
+}
+
+/// One token's recorded price history within a window (from polymarket_prices)
+#[derive(Debug, Clone)]
+pub struct TokenPricePath {
+    pub window_start: DateTime<Utc>,
+    /// (seconds into window, price) sorted by time
+    pub points: Vec<(u32, f64)>,
+}
+
+/// Observed outcomes from a (time bucket, current ask) state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketReachState {
+    /// Time bucket (0-59)
+    pub time_bucket: u8,
+    /// Ask level (0-24, 4¢ wide)
+    pub ask_level: u8,
+    /// Token paths observed in this state
+    pub count_total: u32,
+    /// Paths where the token settled as the winner
+    pub count_win: u32,
+    /// reached[i] = paths where the bid later reached (i+1)*4¢
+    pub reached: [u32; MARKET_REACH_LEVELS],
+    /// Computed P(bid reaches target) (filled after processing)
+    pub p_reach: [f64; MARKET_REACH_LEVELS],
+    /// Computed P(token wins at settlement)
+    pub p_win: f64,
+    pub confidence_level: ConfidenceLevel,
+}
+
+impl MarketReachState {
+    pub fn new(time_bucket: u8, ask_level: u8) -> Self {
+        Self {
+            time_bucket,
+            ask_level,
+            count_total: 0,
+            count_win: 0,
+            reached: [0; MARKET_REACH_LEVELS],
+            p_reach: [0.0; MARKET_REACH_LEVELS],
+            p_win: 0.0,
+            confidence_level: ConfidenceLevel::Unreliable,
+        }
+    }
+
+    /// Record one path: the highest bid seen after this state and whether the token won
+    pub fn record(&mut self, max_future_bid: f64, won: bool) {
+        self.count_total += 1;
+        if won {
+            self.count_win += 1;
+        }
+        for level in 0..MARKET_REACH_LEVELS {
+            if max_future_bid + 1e-9 >= reach_level_to_price(level) {
+                self.reached[level] += 1;
+            }
+        }
+    }
+
+    /// Compute probabilities from counts
+    pub fn compute_probabilities(&mut self) {
+        self.confidence_level = ConfidenceLevel::from_sample_count(self.count_total);
+        if self.count_total == 0 {
+            return;
+        }
+        let total = self.count_total as f64;
+        for level in 0..MARKET_REACH_LEVELS {
+            self.p_reach[level] = self.reached[level] as f64 / total;
+        }
+        self.p_win = self.count_win as f64 / total;
+    }
+
+    /// P(bid reaches target price) - uses the lowest level at or above the
+    /// target, so a target between levels is never overstated
+    pub fn p_reach_price(&self, target: f64) -> f64 {
+        let cents = (target * 100.0).round() as usize;
+        if cents == 0 {
+            return 1.0;
+        }
+        self.p_reach[(cents.div_ceil(4) - 1).min(MARKET_REACH_LEVELS - 1)]
+    }
+}
+
+/// The complete Market Reach Matrix
+/// Dimensions: [time_bucket][ask_level] -> MarketReachState
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketReachMatrix {
+    /// 2D array: states[time_bucket][ask_level]
+    pub states: Vec<Vec<MarketReachState>>,
+    /// Token paths (window x UP/DOWN) used
+    pub total_paths: u32,
+    /// Half spread assumed around the recorded price (ask = price + h, bid = price - h)
+    pub half_spread: f64,
+    /// Date range
+    pub data_start: Option<DateTime<Utc>>,
+    pub data_end: Option<DateTime<Utc>>,
+}
+
+impl MarketReachMatrix {
+    pub fn new(half_spread: f64) -> Self {
+        let states = (0..TIME_BUCKETS)
+            .map(|time_bucket| {
+                (0..MARKET_REACH_LEVELS as u8)
+                    .map(|ask_level| MarketReachState::new(time_bucket, ask_level))
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            states,
+            total_paths: 0,
+            half_spread,
+            data_start: None,
+            data_end: None,
+        }
+    }
+
+    /// Get state by time bucket and current ask price
+    pub fn get(&self, time_bucket: u8, ask: f64) -> &MarketReachState {
+        &self.states[time_bucket.min(TIME_BUCKETS - 1) as usize][price_to_ask_level(ask)]
+    }
+
+    /// Get mutable state by time bucket and ask level
+    pub fn get_mut(&mut self, time_bucket: u8, ask_level: usize) -> &mut MarketReachState {
+        &mut self.states[time_bucket.min(TIME_BUCKETS - 1) as usize][ask_level.min(MARKET_REACH_LEVELS - 1)]
+    }
+}
+
+// ============================================================================
+// EXIT POLICY (optimal stopping)
+// Solved by backward induction over (time bucket, delta bucket, token bid):
+// for every state, is selling at the bid worth more than holding on?
+// Bid levels: 1¢, 2¢, ..., 99¢ (99 levels)
+// ============================================================================
+
+/// Number of token bid levels in the exit policy (1¢ steps)
+pub const EXIT_POLICY_BID_LEVELS: usize = 99;
+
+/// Convert a token bid (0.0-1.0) to its policy level (0 = 1¢, ..., 98 = 99¢)
+pub fn bid_to_policy_level(bid: f64) -> usize {
+    ((bid * 100.0).round().max(1.0) as usize - 1).min(EXIT_POLICY_BID_LEVELS - 1)
+}
+
+/// Bid for a policy level (0 = 1¢, ..., 98 = 99¢)
+pub fn policy_level_to_bid(level: usize) -> f64 {
+    (level + 1) as f64 / 100.0
+}
+
+/// Optimal stopping solution for one (time bucket, delta bucket) state of one token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitPolicyCell {
+    /// Time bucket (0-59)
+    pub time_bucket: u8,
+    /// Price delta bucket (-17 to +16)
+    pub delta_bucket: i8,
+    /// Observed transitions out of this state (0 at the last bucket)
+    pub samples: u32,
+    /// P(token wins at settlement) from the terminal matrix
+    pub p_win: f64,
+    /// continuation[level] = expected value per share of holding at that bid
+    /// (the best of selling later or settling), from the next state onward
+    pub continuation: Vec<f64>,
+    /// Lowest bid at which selling now beats holding (None = always hold)
+    pub sell_threshold: Option<f64>,
+}
+
+impl ExitPolicyCell {
+    pub fn new(time_bucket: u8, delta_bucket: i8) -> Self {
+        Self {
+            time_bucket,
+            delta_bucket,
+            samples: 0,
+            p_win: 0.5,
+            continuation: vec![0.0; EXIT_POLICY_BID_LEVELS],
+            sell_threshold: None,
+        }
+    }
+
+    /// Expected value of holding when the current bid is `bid`
+    pub fn continuation_at(&self, bid: f64) -> f64 {
+        self.continuation[bid_to_policy_level(bid)]
+    }
+
+    /// Does selling at `bid` beat holding?
+    pub fn should_sell(&self, bid: f64) -> bool {
+        bid >= self.continuation_at(bid)
+    }
+}
+
+/// Exit policy table for both tokens
+/// Dimensions: [time_bucket][delta_bucket + 17] -> ExitPolicyCell
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitPolicy {
+    /// Policy for a held UP token
+    pub up: Vec<Vec<ExitPolicyCell>>,
+    /// Policy for a held DOWN token
+    pub down: Vec<Vec<ExitPolicyCell>>,
+    /// Fraction of the current bid's mispricing (bid - fair value) that persists
+    /// into the next bucket (0 = bid snaps to fair value, 1 = gap never closes)
+    pub bid_persistence: f64,
+    /// States with fewer transitions than this are treated as hold-to-settlement
+    pub min_samples: u32,
+    /// Windows used for the transition statistics
+    pub total_windows: u32,
+    /// Date range
+    pub data_start: Option<DateTime<Utc>>,
+    pub data_end: Option<DateTime<Utc>>,
+}
+
+impl ExitPolicy {
+    pub fn new(bid_persistence: f64, min_samples: u32) -> Self {
+        let table = || -> Vec<Vec<ExitPolicyCell>> {
+            (0..TIME_BUCKETS)
+                .map(|time_bucket| {
+                    (DELTA_BUCKET_MIN..=DELTA_BUCKET_MAX)
+                        .map(|delta_bucket| ExitPolicyCell::new(time_bucket, delta_bucket))
+                        .collect()
+                })
+                .collect()
+        };
+
+        Self {
+            up: table(),
+            down: table(),
+            bid_persistence,
+            min_samples,
+            total_windows: 0,
+            data_start: None,
+            data_end: None,
+        }
+    }
+
+    /// Get the policy cell for a held token
+    pub fn get(&self, token: Outcome, time_bucket: u8, delta_bucket: i8) -> &ExitPolicyCell {
+        let table = match token {
+            Outcome::Up => &self.up,
+            Outcome::Down => &self.down,
+        };
+        &table[time_bucket.min(TIME_BUCKETS - 1) as usize][(delta_bucket + 17) as usize]
+    }
+
+    /// Get a mutable policy cell
+    pub fn get_mut(&mut self, token: Outcome, time_bucket: u8, delta_bucket: i8) -> &mut ExitPolicyCell {
+        let table = match token {
+            Outcome::Up => &mut self.up,
+            Outcome::Down => &mut self.down,
+        };
+        &mut table[time_bucket.min(TIME_BUCKETS - 1) as usize][(delta_bucket + 17) as usize]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -979,4 +1438,34 @@ mod tests {
         assert_eq!(cell.count_down, 1);
         assert_eq!(cell.total(), 3);
     }
+
+    #[test]
+    fn test_session_matrices() {
+        use chrono::TimeZone;
+
+        let sessions = [
+            MatrixSession { name: "weekend".to_string(), start_hour: 0, end_hour: 24, days: SessionDays::Weekends },
+            MatrixSession { name: "asia".to_string(), start_hour: 23, end_hour: 7, days: SessionDays::Weekdays },
+            MatrixSession { name: "us".to_string(), start_hour: 13, end_hour: 21, days: SessionDays::Weekdays },
+        ];
+        // Thu 2025-12-04, Sat 2025-12-06
+        let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2025, 12, day, hour, 0, 0).unwrap();
+        // First match wins, as in the bot
+        let session_for = |time| sessions.iter().find(|s| s.matches(time));
+        assert_eq!(session_for(at(4, 14)).unwrap().name, "us");
+        assert_eq!(session_for(at(4, 23)).unwrap().name, "asia");
+        assert_eq!(session_for(at(4, 3)).unwrap().name, "asia");
+        assert_eq!(session_for(at(6, 14)).unwrap().name, "weekend");
+        assert!(session_for(at(4, 10)).is_none());
+
+        // Sparse session cells fall back to the pooled cell
+        let mut pooled = ProbabilityMatrix::new();
+        pooled.get_mut(0, 0).count_up = 100;
+        let mut session = ProbabilityMatrix::new();
+        session.get_mut(0, 0).count_down = 5;
+        session.get_mut(1, 0).count_down = 50;
+        let merged = session.with_fallback(&pooled, 30.0);
+        assert_eq!(merged.get(0, 0).count_up, 100);
+        assert_eq!(merged.get(1, 0).count_down, 50);
+    }
 }
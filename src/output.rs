@@ -14,8 +14,9 @@ pub fn export_to_json(matrix: &ProbabilityMatrix, path: &Path) -> Result<()> {
 }
 
 /// Export the probability matrix to CSV
-/// Format: time_bucket, price_delta_bucket, price_delta_label, count_up, count_down, total,
-///         p_up, p_down, p_up_wilson_lower, p_up_wilson_upper, confidence_level
+/// Format: time_bucket, price_delta_bucket, price_delta_label, count_up, count_down, total, effective_samples,
+///         p_up, p_down, p_up_wilson_lower, p_up_wilson_upper, beta_alpha, beta_beta,
+///         p_up_credible_lower, p_up_credible_upper, confidence_level
 pub fn export_to_csv(matrix: &ProbabilityMatrix, path: &Path) -> Result<()> {
     let mut wtr = csv::Writer::from_path(path)?;
 
@@ -28,12 +29,15 @@ pub fn export_to_csv(matrix: &ProbabilityMatrix, path: &Path) -> Result<()> {
         "count_up",
         "count_down",
         "total",
+        "effective_samples",
         "p_up",
         "p_down",
         "p_up_wilson_lower",
         "p_up_wilson_upper",
         "beta_alpha",
         "beta_beta",
+        "p_up_credible_lower",
+        "p_up_credible_upper",
         "confidence_level",
     ])?;
 
@@ -60,12 +64,15 @@ pub fn export_to_csv(matrix: &ProbabilityMatrix, path: &Path) -> Result<()> {
                 cell.count_up.to_string(),
                 cell.count_down.to_string(),
                 cell.total().to_string(),
+                format!("{:.1}", cell.effective_samples),
                 format!("{:.4}", cell.p_up),
                 format!("{:.4}", cell.p_down),
                 format!("{:.4}", cell.p_up_wilson_lower),
                 format!("{:.4}", cell.p_up_wilson_upper),
                 format!("{:.2}", cell.beta_alpha),
                 format!("{:.2}", cell.beta_beta),
+                format!("{:.4}", cell.p_up_credible_lower),
+                format!("{:.4}", cell.p_up_credible_upper),
                 format!("{:?}", cell.confidence_level),
             ])?;
         }
@@ -872,6 +879,333 @@ pub fn print_price_crossing_from_state(
     }
 }
 
+// ============================================================================
+// MARKET REACH MATRIX OUTPUT
+// ============================================================================
+
+use crate::models::{reach_level_to_price, MarketReachMatrix, MARKET_REACH_LEVELS};
+
+/// Export market reach matrix to JSON
+pub fn export_market_reach_to_json(matrix: &MarketReachMatrix, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(matrix)?;
+    let mut file = File::create(path)?;
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+/// Export market reach matrix to CSV
+/// Format: time_bucket, time_range, ask_range, paths, p_win, confidence, p_4c, p_8c, ..., p_100c
+pub fn export_market_reach_to_csv(matrix: &MarketReachMatrix, path: &Path) -> Result<()> {
+    let mut wtr = csv::Writer::from_path(path)?;
+
+    let mut header = vec![
+        "time_bucket".to_string(),
+        "time_range".to_string(),
+        "ask_range".to_string(),
+        "paths".to_string(),
+        "p_win".to_string(),
+        "confidence".to_string(),
+    ];
+    for level in 0..MARKET_REACH_LEVELS {
+        header.push(format!("p_{}c", crossing_level_to_cents(level)));
+    }
+    wtr.write_record(&header)?;
+
+    for (time_bucket, row) in matrix.states.iter().enumerate() {
+        let time_start_secs = time_bucket as u32 * 15;
+        let time_end_secs = time_start_secs + 15;
+        let time_range = format!(
+            "{}:{:02}-{}:{:02}",
+            time_start_secs / 60,
+            time_start_secs % 60,
+            time_end_secs / 60,
+            time_end_secs % 60
+        );
+
+        for state in row {
+            let mut record = vec![
+                time_bucket.to_string(),
+                time_range.clone(),
+                format!("{}-{}c", state.ask_level as u32 * 4, state.ask_level as u32 * 4 + 4),
+                state.count_total.to_string(),
+                format!("{:.4}", state.p_win),
+                format!("{:?}", state.confidence_level),
+            ];
+            for level in 0..MARKET_REACH_LEVELS {
+                record.push(format!("{:.4}", state.p_reach[level]));
+            }
+            wtr.write_record(&record)?;
+        }
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Generate a human-readable report for the market reach matrix
+pub fn generate_market_reach_report(matrix: &MarketReachMatrix) -> String {
+    let mut report = String::new();
+
+    report.push_str("╔══════════════════════════════════════════════════════════════════╗\n");
+    report.push_str("║       MARKET REACH MATRIX (POLYMARKET PRICES) - REPORT           ║\n");
+    report.push_str("╚══════════════════════════════════════════════════════════════════╝\n\n");
+
+    report.push_str("📊 DATA SUMMARY\n");
+    report.push_str("─────────────────────────────────────────\n");
+    report.push_str(&format!("Token paths analyzed: {}\n", matrix.total_paths));
+    report.push_str(&format!(
+        "Assumed spread: {:.1}¢ (ask = price + {:.1}¢, bid = price - {:.1}¢)\n",
+        matrix.half_spread * 200.0,
+        matrix.half_spread * 100.0,
+        matrix.half_spread * 100.0
+    ));
+    if let (Some(start), Some(end)) = (matrix.data_start, matrix.data_end) {
+        report.push_str(&format!(
+            "Date range: {} to {}\n",
+            start.format("%Y-%m-%d %H:%M"),
+            end.format("%Y-%m-%d %H:%M")
+        ));
+    }
+    report.push('\n');
+
+    report.push_str("🎯 SAMPLE P(BID REACHES TARGET BEFORE EXPIRY)\n");
+    report.push_str("─────────────────────────────────────────\n");
+    report.push_str("From state (time, current ask) → P(reach) for targets\n\n");
+
+    for time_bucket in [6u8, 20, 40] {
+        let time_secs = time_bucket as u32 * 15;
+        report.push_str(&format!(
+            "Time {:2}:{:02} ({}:{:02} remaining):\n",
+            time_secs / 60,
+            time_secs % 60,
+            (900 - time_secs) / 60,
+            (900 - time_secs) % 60
+        ));
+
+        for ask in [0.30, 0.40, 0.50, 0.60] {
+            let state = matrix.get(time_bucket, ask);
+            if state.count_total == 0 {
+                continue;
+            }
+            report.push_str(&format!(
+                "  ask {:.0}¢ (n={}): P(win)={:.0}% | +8¢={:.0}% +16¢={:.0}% +24¢={:.0}%\n",
+                ask * 100.0,
+                state.count_total,
+                state.p_win * 100.0,
+                state.p_reach_price(ask + 0.08) * 100.0,
+                state.p_reach_price(ask + 0.16) * 100.0,
+                state.p_reach_price(ask + 0.24) * 100.0,
+            ));
+        }
+        report.push('\n');
+    }
+
+    report.push_str("═══════════════════════════════════════════════════════════════════\n");
+
+    report
+}
+
+/// Print P(reach) for every target from a (time, ask) state
+pub fn print_market_reach_from_state(matrix: &MarketReachMatrix, time_bucket: u8, ask: f64) {
+    let state = matrix.get(time_bucket, ask);
+    let time_secs = time_bucket as u32 * 15;
+
+    println!("\n📊 MARKET REACH STATISTICS");
+    println!(
+        "From: Time {}:{:02} | Ask: {:.0}¢ (level {}-{}¢)",
+        time_secs / 60,
+        time_secs % 60,
+        ask * 100.0,
+        state.ask_level as u32 * 4,
+        state.ask_level as u32 * 4 + 4
+    );
+    println!(
+        "Paths: {} | P(win): {:.1}% | Confidence: {:?}",
+        state.count_total,
+        state.p_win * 100.0,
+        state.confidence_level
+    );
+
+    if state.count_total == 0 {
+        println!("\n⚠️  No data for this state");
+        return;
+    }
+
+    println!("\n{:>6} {:>10} {:>10} {:>12}", "Target", "Reached", "P(reach)", "Exit EV");
+    let hold_ev = state.p_win - ask;
+    for level in 0..MARKET_REACH_LEVELS {
+        let target = reach_level_to_price(level);
+        if target <= ask {
+            continue;
+        }
+        let p = state.p_reach[level];
+        let exit_ev = p * (target - ask) + (1.0 - p) * hold_ev;
+        println!(
+            "{:>5}¢ {:>10} {:>9.1}% {:>+11.1}¢",
+            crossing_level_to_cents(level),
+            state.reached[level],
+            p * 100.0,
+            exit_ev * 100.0
+        );
+    }
+    println!("\nHold to settlement EV: {:+.1}¢", hold_ev * 100.0);
+}
+
+// ============================================================================
+// EXIT POLICY OUTPUT
+// ============================================================================
+
+use crate::models::{ExitPolicy, ExitPolicyCell, Outcome};
+
+/// Export exit policy to JSON
+/// Written compact: the continuation vectors make the pretty form ~10x larger
+pub fn export_exit_policy_to_json(policy: &ExitPolicy, path: &Path) -> Result<()> {
+    let json = serde_json::to_string(policy)?;
+    let mut file = File::create(path)?;
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+/// Export exit policy sell thresholds to CSV
+/// Format: token, time_bucket, time_range, delta_bucket, delta_range, samples, p_win, sell_threshold
+pub fn export_exit_policy_to_csv(policy: &ExitPolicy, path: &Path) -> Result<()> {
+    let mut wtr = csv::Writer::from_path(path)?;
+
+    wtr.write_record([
+        "token",
+        "time_bucket",
+        "time_range",
+        "delta_bucket",
+        "delta_range",
+        "samples",
+        "p_win",
+        "sell_threshold",
+    ])?;
+
+    for (token, table) in [("UP", &policy.up), ("DOWN", &policy.down)] {
+        for (time_bucket, row) in table.iter().enumerate() {
+            let time_start_secs = time_bucket as u32 * 15;
+            let time_end_secs = time_start_secs + 15;
+            let time_range = format!(
+                "{}:{:02}-{}:{:02}",
+                time_start_secs / 60,
+                time_start_secs % 60,
+                time_end_secs / 60,
+                time_end_secs % 60
+            );
+
+            for cell in row {
+                wtr.write_record(&[
+                    token.to_string(),
+                    time_bucket.to_string(),
+                    time_range.clone(),
+                    cell.delta_bucket.to_string(),
+                    bucket_to_label(cell.delta_bucket).to_string(),
+                    cell.samples.to_string(),
+                    format!("{:.4}", cell.p_win),
+                    cell.sell_threshold.map(|t| format!("{:.2}", t)).unwrap_or_default(),
+                ])?;
+            }
+        }
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+fn format_threshold(cell: &ExitPolicyCell) -> String {
+    match cell.sell_threshold {
+        Some(t) => format!("{:>3.0}¢", t * 100.0),
+        None => "HOLD".to_string(),
+    }
+}
+
+/// Generate a human-readable report for the exit policy
+pub fn generate_exit_policy_report(policy: &ExitPolicy) -> String {
+    let mut report = String::new();
+
+    report.push_str("╔══════════════════════════════════════════════════════════════════╗\n");
+    report.push_str("║         EXIT POLICY (OPTIMAL STOPPING) - ANALYSIS REPORT         ║\n");
+    report.push_str("╚══════════════════════════════════════════════════════════════════╝\n\n");
+
+    report.push_str("📊 DATA SUMMARY\n");
+    report.push_str("─────────────────────────────────────────\n");
+    report.push_str(&format!("Windows analyzed: {}\n", policy.total_windows));
+    report.push_str(&format!("Bid persistence: {:.2}\n", policy.bid_persistence));
+    report.push_str(&format!("Min transitions per state: {}\n", policy.min_samples));
+    if let (Some(start), Some(end)) = (policy.data_start, policy.data_end) {
+        report.push_str(&format!(
+            "Date range: {} to {}\n",
+            start.format("%Y-%m-%d %H:%M"),
+            end.format("%Y-%m-%d %H:%M")
+        ));
+    }
+    report.push('\n');
+
+    report.push_str("🎯 SELL THRESHOLDS FOR A HELD UP TOKEN (sell when bid >= threshold)\n");
+    report.push_str("─────────────────────────────────────────\n");
+    report.push_str(&format!("{:>18}", "Delta \\ Time"));
+    for time_bucket in [0u8, 12, 24, 36, 48, 56, 59] {
+        let secs = time_bucket as u32 * 15;
+        report.push_str(&format!(" {:>6}", format!("{}:{:02}", secs / 60, secs % 60)));
+    }
+    report.push('\n');
+
+    for delta_bucket in [-8i8, -5, -3, -1, 0, 2, 4, 7] {
+        report.push_str(&format!("{:>18}", bucket_to_label(delta_bucket)));
+        for time_bucket in [0u8, 12, 24, 36, 48, 56, 59] {
+            let cell = policy.get(Outcome::Up, time_bucket, delta_bucket);
+            report.push_str(&format!(" {:>6}", format_threshold(cell)));
+        }
+        report.push('\n');
+    }
+    report.push('\n');
+
+    report.push_str("═══════════════════════════════════════════════════════════════════\n");
+
+    report
+}
+
+/// Print the policy for one state: continuation value and decision at a few bids
+pub fn print_exit_policy_from_state(policy: &ExitPolicy, token: Outcome, time_bucket: u8, delta_bucket: i8, bid: f64) {
+    let cell = policy.get(token, time_bucket, delta_bucket);
+    let time_secs = time_bucket as u32 * 15;
+
+    println!("\n📊 EXIT POLICY");
+    println!(
+        "Token: {:?} | Time {}:{:02} | Delta: {}",
+        token,
+        time_secs / 60,
+        time_secs % 60,
+        bucket_to_label(delta_bucket)
+    );
+    println!(
+        "Transitions: {} | P(win): {:.1}% | Sell threshold: {}",
+        cell.samples,
+        cell.p_win * 100.0,
+        format_threshold(cell).trim()
+    );
+
+    println!("\n{:>6} {:>12} {:>8}", "Bid", "Hold value", "Action");
+    for cents in (5..=95).step_by(5) {
+        let b = cents as f64 / 100.0;
+        println!(
+            "{:>5}¢ {:>11.1}¢ {:>8}",
+            cents,
+            cell.continuation_at(b) * 100.0,
+            if cell.should_sell(b) { "SELL" } else { "HOLD" }
+        );
+    }
+
+    let hold = cell.continuation_at(bid);
+    println!(
+        "\nAt bid {:.0}¢: hold value {:.1}¢ → {}",
+        bid * 100.0,
+        hold * 100.0,
+        if cell.should_sell(bid) { "SELL NOW" } else { "HOLD" }
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,13 @@
-use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc, Weekday};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 
 use crate::models::{
-    count_crossings_directional, delta_to_bucket, FifteenMinWindow, FirstPassageMatrix, Outcome, PricePoint,
-    PriceCrossingMatrix, PriceReachMatrix, PriceSnapshot, ProbabilityMatrix,
+    bid_to_policy_level, count_crossings_directional, delta_to_bucket, policy_level_to_bid, price_to_ask_level,
+    ExitPolicy, FifteenMinWindow, FirstPassageMatrix, MarketReachMatrix, Outcome, PricePoint, PriceCrossingMatrix,
+    PriceReachMatrix, PriceSnapshot, ProbabilityMatrix, TokenPricePath, DELTA_BUCKET_MAX, DELTA_BUCKET_MIN,
+    EXIT_POLICY_BID_LEVELS, PRICE_DELTA_BUCKETS, TIME_BUCKETS,
 };
 
 /// Process raw price points into 15-minute windows
@@ -92,20 +95,23 @@ fn build_window(start_time: DateTime<Utc>, points: &[&PricePoint]) -> Option<Fif
         Outcome::Down
     };
 
-    // Build snapshots for each 15-second bucket
+    // Build snapshots for each 15-second bucket in one pass over the points
     let mut snapshots = Vec::with_capacity(60);
+    let mut remaining = sorted_points.iter().peekable();
+    let mut price_at_bucket = open_price;
 
     for bucket in 0u8..60 {
-        let bucket_start = start_time + Duration::seconds(bucket as i64 * 15);
-        let bucket_end = bucket_start + Duration::seconds(15);
-
-        // Find the last price point in this bucket (or before it)
-        let price_at_bucket = sorted_points
-            .iter()
-            .filter(|p| p.timestamp < bucket_end)
-            .last()
-            .map(|p| p.close_price)
-            .unwrap_or(open_price);
+        let bucket_end = start_time + Duration::seconds((bucket as i64 + 1) * 15);
+
+        // The last price in this bucket is the snapshot (carried over from
+        // earlier buckets when the bucket is empty); all of them give the extremes
+        let mut high: Option<Decimal> = None;
+        let mut low: Option<Decimal> = None;
+        while let Some(point) = remaining.next_if(|p| p.timestamp < bucket_end) {
+            price_at_bucket = point.close_price;
+            high = Some(high.map_or(price_at_bucket, |h| h.max(price_at_bucket)));
+            low = Some(low.map_or(price_at_bucket, |l| l.min(price_at_bucket)));
+        }
 
         let delta_from_open = price_at_bucket - open_price;
 
@@ -113,6 +119,8 @@ fn build_window(start_time: DateTime<Utc>, points: &[&PricePoint]) -> Option<Fif
             time_bucket: bucket,
             price: price_at_bucket,
             delta_from_open,
+            high_delta: high.unwrap_or(price_at_bucket) - open_price,
+            low_delta: low.unwrap_or(price_at_bucket) - open_price,
         });
     }
 
@@ -149,6 +157,38 @@ pub fn populate_matrix(windows: &[FifteenMinWindow], matrix: &mut ProbabilityMat
     }
 }
 
+/// Populate the probability matrix, each window counting `window_weight` times
+pub fn populate_matrix_weighted(
+    windows: &[FifteenMinWindow],
+    matrix: &mut ProbabilityMatrix,
+    weighting: &SampleWeighting,
+) {
+    let newest = windows.iter().map(|w| w.start_time).max();
+
+    for window in windows {
+        matrix.total_windows += 1;
+        let weight = newest.map_or(1.0, |newest| window_weight(window, weighting, newest));
+
+        // Update data range
+        if matrix.data_start.is_none() || window.start_time < matrix.data_start.unwrap() {
+            matrix.data_start = Some(window.start_time);
+        }
+        if matrix.data_end.is_none() || window.start_time > matrix.data_end.unwrap() {
+            matrix.data_end = Some(window.start_time);
+        }
+
+        // Record each snapshot
+        for snapshot in &window.snapshots {
+            matrix.record_weighted(
+                snapshot.time_bucket,
+                snapshot.delta_from_open,
+                window.outcome,
+                weight,
+            );
+        }
+    }
+}
+
 /// Process all data and return a populated probability matrix
 pub fn build_probability_matrix(prices: &[PricePoint]) -> ProbabilityMatrix {
     let windows = process_into_windows(prices);
@@ -159,6 +199,79 @@ pub fn build_probability_matrix(prices: &[PricePoint]) -> ProbabilityMatrix {
     matrix
 }
 
+// ============================================================================
+// SAMPLE WEIGHTING
+// ============================================================================
+
+/// How much each historical window counts in the probability matrix.
+/// The default weighs every window 1.0.
+#[derive(Debug, Clone, Default)]
+pub struct SampleWeighting {
+    /// Exponential time decay: a window this many days older than the newest
+    /// one counts half
+    pub half_life_days: Option<f64>,
+    /// Realized volatility ($) to favour; windows are weighted by a Gaussian
+    /// in log(vol / target) with `vol_bandwidth`
+    pub vol_target: Option<f64>,
+    pub vol_bandwidth: f64,
+    /// Hour of day (UTC) to favour; Gaussian in circular hour distance
+    pub hour: Option<u32>,
+    pub hour_bandwidth: f64,
+    /// Weekday to favour; other days count `other_weekday_weight`
+    pub weekday: Option<Weekday>,
+    pub other_weekday_weight: f64,
+}
+
+impl SampleWeighting {
+    pub fn is_uniform(&self) -> bool {
+        self.half_life_days.is_none() && self.vol_target.is_none() && self.hour.is_none() && self.weekday.is_none()
+    }
+}
+
+/// Realized volatility of a window: root sum of squared 15s price changes ($)
+pub fn realized_volatility(window: &FifteenMinWindow) -> f64 {
+    let mut previous = window.open_price;
+    let mut sum_sq = 0.0;
+    for snapshot in &window.snapshots {
+        let change = (snapshot.price - previous).to_f64().unwrap_or(0.0);
+        sum_sq += change * change;
+        previous = snapshot.price;
+    }
+    sum_sq.sqrt()
+}
+
+/// Weight of one window: product of the enabled decay / regime factors
+pub fn window_weight(window: &FifteenMinWindow, weighting: &SampleWeighting, newest: DateTime<Utc>) -> f64 {
+    let mut weight = 1.0;
+
+    if let Some(half_life) = weighting.half_life_days.filter(|h| *h > 0.0) {
+        let age_days = newest.signed_duration_since(window.start_time).num_seconds().max(0) as f64 / 86_400.0;
+        weight *= 0.5f64.powf(age_days / half_life);
+    }
+
+    if let Some(target) = weighting.vol_target.filter(|t| *t > 0.0) {
+        // Flat windows (no moves) count as a very low-vol regime
+        let vol = realized_volatility(window).max(target * 1e-3);
+        let z = (vol / target).ln() / weighting.vol_bandwidth.max(1e-6);
+        weight *= (-0.5 * z * z).exp();
+    }
+
+    if let Some(hour) = weighting.hour {
+        let diff = (window.start_time.hour() as i32 - hour as i32).rem_euclid(24);
+        let distance = diff.min(24 - diff) as f64;
+        let z = distance / weighting.hour_bandwidth.max(1e-6);
+        weight *= (-0.5 * z * z).exp();
+    }
+
+    if let Some(weekday) = weighting.weekday {
+        if window.start_time.weekday() != weekday {
+            weight *= weighting.other_weekday_weight;
+        }
+    }
+
+    weight
+}
+
 // ============================================================================
 // FIRST-PASSAGE MATRIX PROCESSING
 // ============================================================================
@@ -180,27 +293,21 @@ pub fn populate_first_passage_matrix(
 
         let snapshots = &window.snapshots;
 
-        // For each snapshot, look at remaining snapshots to find max/min
+        // For each snapshot, look at the rest of the path to find max/min
+        // (intra-bucket extremes count, so second-level data catches spikes
+        // that revert before the next bucket boundary)
         for (i, snapshot) in snapshots.iter().enumerate() {
-            // Get remaining snapshots (including current for consistency)
-            let remaining = &snapshots[i..];
+            let later = &snapshots[i + 1..];
 
-            if remaining.is_empty() {
-                continue;
-            }
-
-            // Find max and min delta in remaining time
-            let max_delta = remaining
+            let max_delta = later
                 .iter()
-                .map(|s| s.delta_from_open)
-                .max()
-                .unwrap_or(snapshot.delta_from_open);
+                .map(|s| s.high_delta)
+                .fold(snapshot.delta_from_open, Decimal::max);
 
-            let min_delta = remaining
+            let min_delta = later
                 .iter()
-                .map(|s| s.delta_from_open)
-                .min()
-                .unwrap_or(snapshot.delta_from_open);
+                .map(|s| s.low_delta)
+                .fold(snapshot.delta_from_open, Decimal::min);
 
             // Record this observation
             matrix.record(
@@ -411,6 +518,259 @@ pub fn build_price_crossing_matrix(
     matrix
 }
 
+// ============================================================================
+// MARKET REACH MATRIX PROCESSING
+// Uses recorded Polymarket token prices instead of terminal-matrix P(UP)
+// ============================================================================
+
+/// Paths whose last recorded price is earlier than this have no usable settlement
+const MIN_SETTLEMENT_SECS: u32 = 840;
+
+/// Populate the market reach matrix from token price paths
+///
+/// Each path contributes at most one observation per time bucket (the first
+/// price in that bucket) so dense price histories don't dominate a cell.
+pub fn populate_market_reach_matrix(paths: &[TokenPricePath], matrix: &mut MarketReachMatrix) {
+    let half_spread = matrix.half_spread;
+
+    for path in paths {
+        let points = &path.points;
+        if points.len() < 2 {
+            continue;
+        }
+
+        // Settlement: the token resolved to ~$1 or ~$0 at expiry
+        let (last_secs, last_price) = points[points.len() - 1];
+        if last_secs < MIN_SETTLEMENT_SECS {
+            continue;
+        }
+        let won = last_price >= 0.5;
+
+        if matrix.data_start.is_none() || path.window_start < matrix.data_start.unwrap() {
+            matrix.data_start = Some(path.window_start);
+        }
+        if matrix.data_end.is_none() || path.window_start > matrix.data_end.unwrap() {
+            matrix.data_end = Some(path.window_start);
+        }
+
+        // max_after[i] = highest bid strictly after point i
+        let mut max_after = vec![0.0f64; points.len()];
+        for i in (0..points.len() - 1).rev() {
+            max_after[i] = max_after[i + 1].max(points[i + 1].1 - half_spread);
+        }
+
+        let mut seen = [false; 60];
+        for (i, &(secs, price)) in points[..points.len() - 1].iter().enumerate() {
+            if secs >= 900 {
+                break;
+            }
+            let time_bucket = (secs / 15).min(59) as u8;
+            if seen[time_bucket as usize] {
+                continue;
+            }
+            seen[time_bucket as usize] = true;
+
+            let ask = (price + half_spread).min(1.0);
+            matrix
+                .get_mut(time_bucket, price_to_ask_level(ask))
+                .record(max_after[i], won);
+        }
+
+        matrix.total_paths += 1;
+    }
+}
+
+/// Compute probabilities for all states in the market reach matrix
+pub fn compute_market_reach_probabilities(matrix: &mut MarketReachMatrix) {
+    for row in matrix.states.iter_mut() {
+        for state in row.iter_mut() {
+            state.compute_probabilities();
+        }
+    }
+}
+
+/// Build market reach matrix from Polymarket token price paths
+pub fn build_market_reach_matrix(paths: &[TokenPricePath], half_spread: f64) -> MarketReachMatrix {
+    let mut matrix = MarketReachMatrix::new(half_spread);
+    populate_market_reach_matrix(paths, &mut matrix);
+    compute_market_reach_probabilities(&mut matrix);
+    matrix
+}
+
+// ============================================================================
+// EXIT POLICY (OPTIMAL STOPPING)
+// Backward induction over the bucket-to-bucket delta transitions of the
+// historical windows, valued with the terminal matrix
+// ============================================================================
+
+/// Count delta bucket transitions between consecutive time buckets
+/// counts[time_bucket][from_delta + 17][to_delta + 17] for time buckets 0-58
+pub fn count_delta_transitions(windows: &[FifteenMinWindow]) -> Vec<Vec<Vec<u32>>> {
+    let n = PRICE_DELTA_BUCKETS as usize;
+    let mut counts = vec![vec![vec![0u32; n]; n]; TIME_BUCKETS as usize - 1];
+
+    for window in windows {
+        for pair in window.snapshots.windows(2) {
+            let time_bucket = pair[0].time_bucket as usize;
+            if time_bucket >= counts.len() {
+                continue;
+            }
+            let from = (delta_to_bucket(pair[0].delta_from_open) + 17) as usize;
+            let to = (delta_to_bucket(pair[1].delta_from_open) + 17) as usize;
+            counts[time_bucket][from][to] += 1;
+        }
+    }
+
+    counts
+}
+
+/// Solve the exit policy for one token by backward induction
+///
+/// V(t, d, b) = max(b, C(t, d, b)) where C is the value of holding:
+/// - last bucket: C = P(win), the token settles at $1 or $0
+/// - otherwise:   C = Σ P(d' | t, d) × V(t+1, d', b'), with the next bid
+///   b' = fair(t+1, d') + persistence × (b - fair(t, d))
+///
+/// fair(t, d) is P(win) from the terminal matrix. States with fewer than
+/// `min_samples` transitions fall back to C = fair(t, d) (hold to settlement).
+fn solve_token_policy(
+    policy: &mut ExitPolicy,
+    token: Outcome,
+    transitions: &[Vec<Vec<u32>>],
+    terminal_matrix: &ProbabilityMatrix,
+) {
+    let fair = |time_bucket: u8, delta_bucket: i8| {
+        let cell = terminal_matrix.get(time_bucket, delta_bucket);
+        match token {
+            Outcome::Up => cell.p_up,
+            Outcome::Down => cell.p_down,
+        }
+    };
+    let persistence = policy.bid_persistence;
+    let min_samples = policy.min_samples.max(1);
+
+    // next_value[delta + 17][level] = V(t+1, delta, bid)
+    let mut next_value: Vec<Vec<f64>> = Vec::new();
+
+    for time_bucket in (0..TIME_BUCKETS).rev() {
+        let mut value = vec![vec![0.0; EXIT_POLICY_BID_LEVELS]; PRICE_DELTA_BUCKETS as usize];
+
+        for delta_bucket in DELTA_BUCKET_MIN..=DELTA_BUCKET_MAX {
+            let idx = (delta_bucket + 17) as usize;
+            let p_win = fair(time_bucket, delta_bucket);
+            let row = transitions.get(time_bucket as usize).map(|r| &r[idx]);
+            let samples: u32 = row.map(|r| r.iter().sum()).unwrap_or(0);
+
+            let cell = policy.get_mut(token, time_bucket, delta_bucket);
+            cell.samples = samples;
+            cell.p_win = p_win;
+
+            let slots = cell.continuation.iter_mut().zip(value[idx].iter_mut());
+            for (level, (continuation, state_value)) in slots.enumerate() {
+                let bid = policy_level_to_bid(level);
+
+                let hold = match row {
+                    Some(row) if samples >= min_samples => {
+                        let mispricing = bid - p_win;
+                        let mut total = 0.0;
+                        for (next_idx, &count) in row.iter().enumerate() {
+                            if count == 0 {
+                                continue;
+                            }
+                            let next_delta = next_idx as i8 - 17;
+                            let next_bid = fair(time_bucket + 1, next_delta) + persistence * mispricing;
+                            total += count as f64 * next_value[next_idx][bid_to_policy_level(next_bid)];
+                        }
+                        total / samples as f64
+                    }
+                    _ => p_win,
+                };
+
+                *continuation = hold;
+                *state_value = bid.max(hold);
+            }
+
+            cell.sell_threshold = cell
+                .continuation
+                .iter()
+                .enumerate()
+                .map(|(level, &hold)| (policy_level_to_bid(level), hold))
+                .find(|&(bid, hold)| bid >= hold)
+                .map(|(bid, _)| bid);
+        }
+
+        next_value = value;
+    }
+}
+
+/// Build the exit policy table for both tokens
+pub fn build_exit_policy(
+    windows: &[FifteenMinWindow],
+    terminal_matrix: &ProbabilityMatrix,
+    bid_persistence: f64,
+    min_samples: u32,
+) -> ExitPolicy {
+    let mut policy = ExitPolicy::new(bid_persistence, min_samples);
+
+    policy.total_windows = windows.len() as u32;
+    policy.data_start = windows.iter().map(|w| w.start_time).min();
+    policy.data_end = windows.iter().map(|w| w.start_time).max();
+
+    let transitions = count_delta_transitions(windows);
+    solve_token_policy(&mut policy, Outcome::Up, &transitions, terminal_matrix);
+    solve_token_policy(&mut policy, Outcome::Down, &transitions, terminal_matrix);
+
+    policy
+}
+
+// ============================================================================
+// INCREMENTAL UPDATES
+// Extend saved raw-count matrices with the windows completed since they were
+// built instead of rebuilding from all of binance_klines
+// ============================================================================
+
+/// Drop the trailing windows still in progress at `latest` (newest price
+/// timestamp): a window is complete once the next window has data
+pub fn drop_incomplete_windows(windows: &mut Vec<FifteenMinWindow>, latest: DateTime<Utc>) {
+    let complete = windows.partition_point(|w| w.start_time + Duration::minutes(15) <= latest);
+    windows.truncate(complete);
+}
+
+/// Windows (sorted) that started after `last_ingested`, a saved matrix's `data_end`
+pub fn windows_after(windows: &[FifteenMinWindow], last_ingested: Option<DateTime<Utc>>) -> &[FifteenMinWindow] {
+    match last_ingested {
+        Some(last) => &windows[windows.partition_point(|w| w.start_time <= last)..],
+        None => windows,
+    }
+}
+
+/// Add new windows to a saved probability matrix
+///
+/// With a half-life the saved weights are decayed from the matrix's last
+/// window to the newest new one, so the result matches a full rebuild with
+/// the same half-life. A weighted matrix without a half-life gets the new
+/// windows at weight 1 (regime weights are not reapplied).
+pub fn ingest_matrix_windows(windows: &[FifteenMinWindow], matrix: &mut ProbabilityMatrix, half_life_days: Option<f64>) {
+    let weighting = SampleWeighting {
+        half_life_days: half_life_days.filter(|h| *h > 0.0),
+        ..Default::default()
+    };
+
+    if weighting.is_uniform() && !matrix.is_weighted() {
+        populate_matrix(windows, matrix);
+        return;
+    }
+
+    if let (Some(half_life), Some(newest), Some(last)) =
+        (weighting.half_life_days, windows.last().map(|w| w.start_time), matrix.data_end)
+    {
+        let age_days = newest.signed_duration_since(last).num_seconds().max(0) as f64 / 86_400.0;
+        matrix.decay(0.5f64.powf(age_days / half_life));
+    }
+
+    populate_matrix_weighted(windows, matrix, &weighting);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,4 +840,250 @@ mod tests {
         assert_eq!(windows.len(), 1);
         assert_eq!(windows[0].outcome, Outcome::Up);
     }
+
+    #[test]
+    fn test_intra_bucket_extremes() {
+        let window_start = Utc.with_ymd_and_hms(2025, 12, 4, 8, 0, 0).unwrap();
+
+        // 1s path, flat at 100 except a +$40 spike at 62-63s that reverts
+        // before the bucket 4 boundary (75s)
+        let points: Vec<PricePoint> = (0..900)
+            .map(|i| PricePoint {
+                timestamp: window_start + Duration::seconds(i),
+                close_price: if (62..64).contains(&i) { dec!(140) } else { dec!(100) },
+            })
+            .collect();
+
+        let windows = process_into_windows(&points);
+        let snapshots = &windows[0].snapshots;
+        assert_eq!(snapshots.len(), 60);
+        assert_eq!(snapshots[4].delta_from_open, dec!(0));
+        assert_eq!(snapshots[4].high_delta, dec!(40));
+        assert_eq!(snapshots[4].low_delta, dec!(0));
+        assert_eq!(snapshots[5].high_delta, dec!(0));
+
+        // First-passage from bucket 3 sees the spike; from bucket 4 on it has passed
+        let mut fp_matrix = FirstPassageMatrix::new();
+        populate_first_passage_matrix(&windows, &mut fp_matrix);
+        let reached = |bucket: u8| {
+            let target = fp_matrix.get(bucket, delta_to_bucket(dec!(0))).get_up_target(delta_to_bucket(dec!(35)));
+            (target.count_reached, target.count_total)
+        };
+        assert_eq!(reached(3), (1, 1));
+        assert_eq!(reached(4), (0, 1));
+    }
+
+    #[test]
+    fn test_market_reach_matrix() {
+        let window_start = Utc.with_ymd_and_hms(2025, 12, 4, 8, 0, 0).unwrap();
+
+        // Token rises from 40¢ to 70¢, then settles as the winner
+        let winner = TokenPricePath {
+            window_start,
+            points: vec![(0, 0.40), (30, 0.50), (300, 0.70), (600, 0.60), (895, 0.99)],
+        };
+        // Token drifts down from 40¢ and loses
+        let loser = TokenPricePath {
+            window_start,
+            points: vec![(0, 0.40), (30, 0.35), (300, 0.20), (895, 0.01)],
+        };
+        // No settlement price - ignored
+        let truncated = TokenPricePath {
+            window_start,
+            points: vec![(0, 0.40), (60, 0.45)],
+        };
+
+        let matrix = build_market_reach_matrix(&[winner, loser, truncated], 0.01);
+        assert_eq!(matrix.total_paths, 2);
+
+        // Both paths start in the 40-44¢ ask level at t=0 (ask = 41¢)
+        let state = matrix.get(0, 0.41);
+        assert_eq!(state.count_total, 2);
+        assert_eq!(state.count_win, 1);
+        assert!((state.p_win - 0.5).abs() < 1e-9);
+        // Winner's bid later hits 98¢, loser's best is 34¢
+        assert!((state.p_reach_price(0.68) - 0.5).abs() < 1e-9);
+        assert!((state.p_reach_price(0.32) - 1.0).abs() < 1e-9);
+        assert_eq!(state.p_reach_price(1.00), 0.0);
+        // Between levels the next level up is read: 34¢ (loser's best) and
+        // 97¢ (winner's 98¢ is below the 100¢ level) count as not reached
+        assert!((state.p_reach_price(0.34) - 0.5).abs() < 1e-9);
+        assert_eq!(state.p_reach_price(0.97), 0.0);
+    }
+
+    #[test]
+    fn test_time_decayed_matrix() {
+        let newest = Utc.with_ymd_and_hms(2025, 12, 4, 8, 0, 0).unwrap();
+        let window = |start_time: DateTime<Utc>, outcome: Outcome| FifteenMinWindow {
+            start_time,
+            open_price: dec!(100000),
+            close_price: dec!(100000),
+            outcome,
+            snapshots: vec![PriceSnapshot { time_bucket: 0, price: dec!(100000), delta_from_open: dec!(1), high_delta: dec!(1), low_delta: dec!(1) }],
+        };
+        // DOWN 30 days ago, UP today
+        let windows = vec![window(newest - Duration::days(30), Outcome::Down), window(newest, Outcome::Up)];
+
+        let weighting = SampleWeighting { half_life_days: Some(30.0), ..Default::default() };
+        assert!((window_weight(&windows[0], &weighting, newest) - 0.5).abs() < 1e-9);
+
+        let mut matrix = ProbabilityMatrix::new();
+        populate_matrix_weighted(&windows, &mut matrix, &weighting);
+        let cell = matrix.get_mut(0, 0);
+        crate::stats::compute_cell_stats(cell);
+
+        // P(UP) = 1 / 1.5, effective n = 1.5² / 1.25 = 1.8 (not 2)
+        assert_eq!(cell.total(), 2);
+        assert!((cell.p_up - 2.0 / 3.0).abs() < 1e-9);
+        assert!((cell.effective_samples - 1.8).abs() < 1e-9);
+
+        // Uniform weighting reproduces the raw counts
+        let mut flat = ProbabilityMatrix::new();
+        populate_matrix(&windows, &mut flat);
+        let cell = flat.get_mut(0, 0);
+        crate::stats::compute_cell_stats(cell);
+        assert!((cell.p_up - 0.5).abs() < 1e-9);
+        assert!((cell.effective_samples - 2.0).abs() < 1e-9);
+
+        // Regime: 8h away from the favoured hour, other weekday
+        let regime = SampleWeighting {
+            hour: Some(16),
+            hour_bandwidth: 2.0,
+            weekday: Some(Weekday::Mon),
+            other_weekday_weight: 0.5,
+            ..Default::default()
+        };
+        let w = window_weight(&windows[1], &regime, newest);
+        assert!((w - 0.5 * (-8.0f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_incremental_update_matches_rebuild() {
+        let start = Utc.with_ymd_and_hms(2025, 12, 4, 8, 0, 0).unwrap();
+        let window = |i: i64, jump: Decimal| {
+            let snapshots = (0u8..60)
+                .map(|time_bucket| {
+                    let delta = jump * Decimal::from(time_bucket) / dec!(59);
+                    PriceSnapshot { time_bucket, price: dec!(100000) + delta, delta_from_open: delta, high_delta: delta, low_delta: delta }
+                })
+                .collect();
+            FifteenMinWindow {
+                start_time: start + Duration::minutes(15 * i),
+                open_price: dec!(100000),
+                close_price: dec!(100000) + jump,
+                outcome: if jump >= Decimal::ZERO { Outcome::Up } else { Outcome::Down },
+                snapshots,
+            }
+        };
+        let mut windows = vec![window(0, dec!(40)), window(1, dec!(-25)), window(2, dec!(10)), window(3, dec!(-60))];
+
+        // The last window is still in progress 10 minutes in
+        drop_incomplete_windows(&mut windows, start + Duration::minutes(55));
+        assert_eq!(windows.len(), 3);
+
+        // Full rebuild vs. saved matrices from the first window + update
+        let mut full = ProbabilityMatrix::new();
+        populate_matrix(&windows, &mut full);
+        let mut fp_full = FirstPassageMatrix::new();
+        populate_first_passage_matrix(&windows, &mut fp_full);
+
+        let mut saved = ProbabilityMatrix::new();
+        populate_matrix(&windows[..1], &mut saved);
+        let mut fp_saved = FirstPassageMatrix::new();
+        populate_first_passage_matrix(&windows[..1], &mut fp_saved);
+
+        let new = windows_after(&windows, saved.data_end);
+        assert_eq!(new.len(), 2);
+        ingest_matrix_windows(new, &mut saved, None);
+        populate_first_passage_matrix(windows_after(&windows, fp_saved.data_end), &mut fp_saved);
+
+        // Already ingested windows are skipped
+        assert!(windows_after(&windows, saved.data_end).is_empty());
+        assert_eq!(saved.total_windows, full.total_windows);
+        assert_eq!(saved.data_start, full.data_start);
+        assert_eq!(saved.data_end, full.data_end);
+        for (row, full_row) in saved.cells.iter().zip(&full.cells) {
+            for (cell, full_cell) in row.iter().zip(full_row) {
+                assert_eq!((cell.count_up, cell.count_down), (full_cell.count_up, full_cell.count_down));
+            }
+        }
+        assert_eq!(fp_saved.total_observations, fp_full.total_observations);
+        let (state, full_state) = (fp_saved.get(30, 0), fp_full.get(30, 0));
+        for (cell, full_cell) in state.up_targets.iter().zip(&full_state.up_targets) {
+            assert_eq!((cell.count_reached, cell.count_total), (full_cell.count_reached, full_cell.count_total));
+        }
+
+        // Decayed update reproduces the weights of a decayed rebuild
+        let weighting = SampleWeighting { half_life_days: Some(0.01), ..Default::default() };
+        let mut decayed_full = ProbabilityMatrix::new();
+        populate_matrix_weighted(&windows, &mut decayed_full, &weighting);
+
+        let mut decayed = ProbabilityMatrix::new();
+        populate_matrix(&windows[..1], &mut decayed);
+        ingest_matrix_windows(windows_after(&windows, decayed.data_end), &mut decayed, Some(0.01));
+
+        let (cell, full_cell) = (decayed.get(0, 0), decayed_full.get(0, 0));
+        assert_eq!(cell.total(), 3);
+        assert!((cell.weight_up - full_cell.weight_up).abs() < 1e-12);
+        assert!((cell.weight_down - full_cell.weight_down).abs() < 1e-12);
+        assert!((cell.weight_sq_sum - full_cell.weight_sq_sum).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_exit_policy_backward_induction() {
+        let start = Utc.with_ymd_and_hms(2025, 12, 4, 8, 0, 0).unwrap();
+
+        // Flat until the last bucket, then a +$12 or -$12 jump
+        let window = |jump: Decimal| {
+            let snapshots = (0u8..60)
+                .map(|time_bucket| {
+                    let delta = if time_bucket == 59 { jump } else { Decimal::ZERO };
+                    PriceSnapshot { time_bucket, price: dec!(100000) + delta, delta_from_open: delta, high_delta: delta, low_delta: delta }
+                })
+                .collect();
+            FifteenMinWindow {
+                start_time: start,
+                open_price: dec!(100000),
+                close_price: dec!(100000) + jump,
+                outcome: if jump >= Decimal::ZERO { Outcome::Up } else { Outcome::Down },
+                snapshots,
+            }
+        };
+        let windows: Vec<_> = (0..20).map(|i| window(if i % 2 == 0 { dec!(12) } else { dec!(-12) })).collect();
+
+        let mut terminal = ProbabilityMatrix::new();
+        let mut set_p_up = |time_bucket: u8, delta_bucket: i8, p_up: f64| {
+            let cell = terminal.get_mut(time_bucket, delta_bucket);
+            cell.p_up = p_up;
+            cell.p_down = 1.0 - p_up;
+        };
+        set_p_up(59, 2, 0.9);
+        set_p_up(59, -3, 0.1);
+
+        let policy = build_exit_policy(&windows, &terminal, 0.5, 10);
+        assert_eq!(policy.total_windows, 20);
+
+        // Last bucket: holding is worth P(win), so sell at or above it
+        let last = policy.get(Outcome::Up, 59, 2);
+        assert_eq!(last.samples, 0);
+        assert!((last.continuation_at(0.50) - 0.9).abs() < 1e-9);
+        assert_eq!(last.sell_threshold, Some(0.9));
+
+        // One bucket earlier: 50/50 jump to 90¢ or 10¢ fair value. A rich bid
+        // (60¢ vs 50¢ fair) keeps half its premium, so holding is worth 55¢.
+        let before = policy.get(Outcome::Up, 58, 0);
+        assert_eq!(before.samples, 20);
+        assert!((before.continuation_at(0.60) - 0.55).abs() < 1e-9);
+        assert!(before.should_sell(0.60));
+        assert!(!before.should_sell(0.45));
+        assert_eq!(before.sell_threshold, Some(0.5));
+
+        // Symmetric for the DOWN token
+        assert_eq!(policy.get(Outcome::Down, 58, 0).sell_threshold, Some(0.5));
+
+        // No data: hold to settlement
+        let unseen = policy.get(Outcome::Up, 58, 10);
+        assert_eq!(unseen.samples, 0);
+        assert!((unseen.continuation_at(0.70) - unseen.p_win).abs() < 1e-9);
+    }
 }
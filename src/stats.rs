@@ -1,4 +1,7 @@
-use crate::models::{CellStats, ConfidenceLevel, FirstPassageCell, FirstPassageMatrix};
+use crate::models::{
+    bucket_to_midpoint, CellStats, ConfidenceLevel, FirstPassageCell, FirstPassageMatrix, ProbabilityMatrix,
+    DELTA_BUCKET_MAX, DELTA_BUCKET_MIN, PRICE_DELTA_BUCKETS, TIME_BUCKETS,
+};
 
 /// Z-score for 95% confidence interval
 const Z_95: f64 = 1.96;
@@ -11,8 +14,16 @@ pub fn wilson_score_interval(successes: u32, total: u32) -> (f64, f64) {
         return (0.0, 1.0);
     }
 
-    let n = total as f64;
-    let p_hat = successes as f64 / n;
+    wilson_interval(successes as f64 / total as f64, total as f64)
+}
+
+/// Wilson Score interval for an observed proportion over `n` (possibly
+/// effective, non-integer) samples
+pub fn wilson_interval(p_hat: f64, n: f64) -> (f64, f64) {
+    if n <= 0.0 {
+        return (0.0, 1.0);
+    }
+
     let z = Z_95;
     let z_squared = z * z;
 
@@ -33,15 +44,17 @@ pub fn wilson_score_interval(successes: u32, total: u32) -> (f64, f64) {
 /// Prior: Beta(alpha_prior, beta_prior) - default is Beta(1, 1) = uniform
 /// Posterior: Beta(alpha_prior + successes, beta_prior + failures)
 ///
+/// Counts may be effective (weighted) sample counts.
+///
 /// Returns (posterior_alpha, posterior_beta)
 pub fn beta_posterior(
-    successes: u32,
-    failures: u32,
+    successes: impl Into<f64>,
+    failures: impl Into<f64>,
     alpha_prior: f64,
     beta_prior: f64,
 ) -> (f64, f64) {
-    let alpha = alpha_prior + successes as f64;
-    let beta = beta_prior + failures as f64;
+    let alpha = alpha_prior + successes.into();
+    let beta = beta_prior + failures.into();
     (alpha, beta)
 }
 
@@ -78,10 +91,14 @@ pub fn beta_credible_interval(alpha: f64, beta: f64, credible_level: f64) -> (f6
 }
 
 /// Update a CellStats with calculated statistics
+///
+/// Uses the weighted counts at their effective sample size, so a time-decayed
+/// cell reports the weighted P(UP) with intervals as wide as its real information.
 pub fn compute_cell_stats(cell: &mut CellStats) {
     let total = cell.total();
 
     if total == 0 {
+        cell.effective_samples = 0.0;
         cell.p_up = 0.5;
         cell.p_down = 0.5;
         cell.p_up_wilson_lower = 0.0;
@@ -92,22 +109,179 @@ pub fn compute_cell_stats(cell: &mut CellStats) {
         return;
     }
 
-    // Raw probabilities
-    cell.p_up = cell.count_up as f64 / total as f64;
-    cell.p_down = cell.count_down as f64 / total as f64;
+    let (up, down) = cell.effective_counts();
+    let n_eff = up + down;
+    cell.effective_samples = n_eff;
+
+    // Raw (weighted) probabilities
+    cell.p_up = up / n_eff;
+    cell.p_down = down / n_eff;
 
     // Wilson Score CI
-    let (lower, upper) = wilson_score_interval(cell.count_up, total);
+    let (lower, upper) = wilson_interval(cell.p_up, n_eff);
     cell.p_up_wilson_lower = lower;
     cell.p_up_wilson_upper = upper;
 
     // Bayesian posterior (uniform prior)
-    let (alpha, beta) = beta_posterior(cell.count_up, cell.count_down, 1.0, 1.0);
+    let (alpha, beta) = beta_posterior(up, down, 1.0, 1.0);
     cell.beta_alpha = alpha;
     cell.beta_beta = beta;
+    cell.prior_alpha = 1.0;
+    cell.prior_beta = 1.0;
+    let (lower, upper) = beta_credible_interval(alpha, beta, 0.95);
+    cell.p_up_credible_lower = lower;
+    cell.p_up_credible_upper = upper;
 
     // Confidence level
-    cell.confidence_level = ConfidenceLevel::from_sample_count(total);
+    cell.confidence_level = ConfidenceLevel::from_sample_count(n_eff.round() as u32);
+}
+
+// ============================================================================
+// EMPIRICAL BAYES SMOOTHING
+// ============================================================================
+
+/// Settings for deriving each cell's prior from the rest of the matrix
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct SmoothingConfig {
+    /// Neighbours within ± this many time buckets are pooled into the prior
+    pub time_radius: u8,
+    /// Neighbours within ± this many delta buckets are pooled into the prior
+    pub delta_radius: i8,
+    /// Prior weight in pseudo-observations (how hard a sparse cell is pulled)
+    pub prior_strength: f64,
+    /// Pooled neighbour samples at which the neighbours and the parametric
+    /// fit get equal weight in the prior mean
+    pub neighbor_half_weight: f64,
+    /// BTC volatility for the parametric prior, in $ per √second
+    pub sigma_per_sqrt_s: f64,
+}
+
+impl Default for SmoothingConfig {
+    fn default() -> Self {
+        Self {
+            time_radius: 2,
+            delta_radius: 1,
+            prior_strength: 10.0,
+            neighbor_half_weight: 30.0,
+            sigma_per_sqrt_s: 5.0,
+        }
+    }
+}
+
+/// Parametric P(UP): Brownian digital, P(delta + W(remaining) > 0) = Φ(delta / σ√remaining)
+pub fn brownian_digital_p_up(delta: f64, seconds_remaining: f64, sigma_per_sqrt_s: f64) -> f64 {
+    use statrs::distribution::{ContinuousCDF, Normal};
+
+    let scale = sigma_per_sqrt_s * seconds_remaining.max(1.0).sqrt();
+    match Normal::new(0.0, 1.0) {
+        Ok(normal) => normal.cdf(delta / scale),
+        Err(_) => 0.5,
+    }
+}
+
+/// Beta prior for one cell from its neighbours' raw counts (closer buckets weigh
+/// more, the cell itself is excluded), blended with the Brownian digital at the
+/// bucket midpoint where the neighbourhood is sparse.
+///
+/// Returns (alpha_prior, beta_prior) summing to `prior_strength`.
+#[allow(dead_code)]
+pub fn empirical_bayes_prior(
+    matrix: &ProbabilityMatrix,
+    time_bucket: u8,
+    delta_bucket: i8,
+    config: &SmoothingConfig,
+) -> (f64, f64) {
+    let mut weighted_up = 0.0;
+    let mut weighted_total = 0.0;
+
+    let t_lo = time_bucket.saturating_sub(config.time_radius);
+    let t_hi = time_bucket.saturating_add(config.time_radius).min(TIME_BUCKETS - 1);
+    let d_lo = delta_bucket.saturating_sub(config.delta_radius).max(DELTA_BUCKET_MIN);
+    let d_hi = delta_bucket.saturating_add(config.delta_radius).min(DELTA_BUCKET_MAX);
+
+    for t in t_lo..=t_hi {
+        for d in d_lo..=d_hi {
+            if t == time_bucket && d == delta_bucket {
+                continue;
+            }
+            let distance = (t as i32 - time_bucket as i32).abs() + (d as i32 - delta_bucket as i32).abs();
+            let weight = 1.0 / (1.0 + distance as f64);
+            let (up, down) = matrix.get(t, d).effective_counts();
+            weighted_up += weight * up;
+            weighted_total += weight * (up + down);
+        }
+    }
+
+    let seconds_remaining = 900.0 - (time_bucket as f64 * 15.0 + 7.5);
+    let parametric = brownian_digital_p_up(bucket_to_midpoint(delta_bucket), seconds_remaining, config.sigma_per_sqrt_s);
+
+    let mean = if weighted_total > 0.0 {
+        let w = weighted_total / (weighted_total + config.neighbor_half_weight);
+        w * (weighted_up / weighted_total) + (1.0 - w) * parametric
+    } else {
+        parametric
+    };
+
+    // Keep the prior proper: never a point mass at 0 or 1
+    let mean = mean.clamp(0.01, 0.99);
+    (mean * config.prior_strength, (1.0 - mean) * config.prior_strength)
+}
+
+/// Update a CellStats using an informative prior: P(UP) becomes the posterior
+/// mean (shrunk towards the prior) with a credible interval, and the Wilson
+/// bounds the bot enters on are taken around the posterior mean over
+/// observations + prior weight. Confidence stays rated on the real observations
+/// and empty cells get the prior mean but stay Unreliable. Raw counts are
+/// unchanged.
+#[allow(dead_code)]
+pub fn compute_cell_stats_with_prior(cell: &mut CellStats, alpha_prior: f64, beta_prior: f64) {
+    compute_cell_stats(cell);
+
+    let (up, down) = cell.effective_counts();
+    let (alpha, beta) = beta_posterior(up, down, alpha_prior, beta_prior);
+    cell.prior_alpha = alpha_prior;
+    cell.prior_beta = beta_prior;
+    cell.beta_alpha = alpha;
+    cell.beta_beta = beta;
+    cell.p_up = beta_mean(alpha, beta);
+    cell.p_down = 1.0 - cell.p_up;
+
+    let (lower, upper) = beta_credible_interval(alpha, beta, 0.95);
+    cell.p_up_credible_lower = lower;
+    cell.p_up_credible_upper = upper;
+
+    if cell.total() > 0 {
+        let (lower, upper) = wilson_interval(cell.p_up, cell.effective_samples + alpha_prior + beta_prior);
+        cell.p_up_wilson_lower = lower;
+        cell.p_up_wilson_upper = upper;
+    }
+}
+
+/// Compute statistics for all cells in the probability matrix, with a flat
+/// prior or (if `smoothing` is set) empirical-Bayes priors from the raw counts
+#[allow(dead_code)]
+pub fn compute_matrix_stats(matrix: &mut ProbabilityMatrix, smoothing: Option<&SmoothingConfig>) {
+    let Some(config) = smoothing else {
+        for time_bucket in 0..TIME_BUCKETS {
+            for delta_bucket in DELTA_BUCKET_MIN..=DELTA_BUCKET_MAX {
+                compute_cell_stats(matrix.get_mut(time_bucket, delta_bucket));
+            }
+        }
+        return;
+    };
+
+    // Priors from raw counts first, so no cell's prior sees smoothed neighbours
+    let mut priors = Vec::with_capacity(TIME_BUCKETS as usize * PRICE_DELTA_BUCKETS as usize);
+    for time_bucket in 0..TIME_BUCKETS {
+        for delta_bucket in DELTA_BUCKET_MIN..=DELTA_BUCKET_MAX {
+            priors.push((time_bucket, delta_bucket, empirical_bayes_prior(matrix, time_bucket, delta_bucket, config)));
+        }
+    }
+
+    for (time_bucket, delta_bucket, (alpha, beta)) in priors {
+        compute_cell_stats_with_prior(matrix.get_mut(time_bucket, delta_bucket), alpha, beta);
+    }
 }
 
 // ============================================================================
@@ -209,4 +383,49 @@ mod tests {
         assert!(cell.p_up_wilson_upper < 0.75);
         assert_eq!(cell.confidence_level, ConfidenceLevel::Moderate);
     }
+
+    #[test]
+    fn test_empirical_bayes_smoothing() {
+        let config = SmoothingConfig::default();
+        let mut matrix = ProbabilityMatrix::new();
+
+        // Neighbours of (40, +8) are 80% UP with plenty of data; the cell itself
+        // has 2 UP / 1 DOWN
+        for t in 38u8..=42 {
+            for d in 7i8..=9 {
+                let cell = matrix.get_mut(t, d);
+                cell.count_up = 80;
+                cell.count_down = 20;
+            }
+        }
+        let cell = matrix.get_mut(40, 8);
+        cell.count_up = 2;
+        cell.count_down = 1;
+
+        let (alpha, beta) = empirical_bayes_prior(&matrix, 40, 8, &config);
+        assert!((alpha + beta - config.prior_strength).abs() < 1e-9);
+        assert!(alpha / (alpha + beta) > 0.75);
+
+        compute_matrix_stats(&mut matrix, Some(&config));
+        let cell = matrix.get(40, 8);
+        assert!(cell.p_up > 0.7 && cell.p_up < 0.8);
+        assert!((cell.p_up + cell.p_down - 1.0).abs() < 1e-9);
+        assert!(cell.p_up_credible_lower < cell.p_up && cell.p_up < cell.p_up_credible_upper);
+        assert!(cell.p_up_credible_upper - cell.p_up_credible_lower < 0.5);
+
+        // The bot's entry bounds follow the posterior; confidence counts only
+        // the 3 real observations
+        assert!(cell.p_up_wilson_lower < cell.p_up && cell.p_up < cell.p_up_wilson_upper);
+        assert!(cell.p_up_wilson_lower > 0.4);
+        assert_eq!(cell.confidence_level, ConfidenceLevel::Unreliable);
+
+        // No data anywhere: the Brownian digital decides the direction
+        let empty = ProbabilityMatrix::new();
+        let (a_up, b_up) = empirical_bayes_prior(&empty, 55, 12, &config);
+        let (a_down, b_down) = empirical_bayes_prior(&empty, 55, -12, &config);
+        assert!(a_up / (a_up + b_up) > 0.9);
+        assert!(a_down / (a_down + b_down) < 0.1);
+        let (a_flat, b_flat) = empirical_bayes_prior(&empty, 0, 0, &config);
+        assert!((a_flat / (a_flat + b_flat) - 0.5).abs() < 0.05);
+    }
 }
@@ -0,0 +1,252 @@
+//! Stop-loss rules for open positions
+//!
+//! Shared by btc-bot (live exits) and btc-strategy-sim (backtests) so both fire
+//! on exactly the same condition. Every position carries its own `StopTracker`,
+//! created at entry from `StopLossConfig`, which fixes the stop level and
+//! remembers the peak bid for trailing stops.
+//!
+//! Modes:
+//! - `fixed`:    sell when bid <= stop_price
+//! - `percent`:  sell when bid <= entry × (1 - max_loss_pct)
+//! - `trailing`: sell when bid <= peak bid since entry × (1 - trailing_pct)
+//! - `model`:    sell when our probability < bid - model_margin
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", try_from = "String")]
+pub enum StopLossMode {
+    Off,
+    Fixed,
+    Percent,
+    Trailing,
+    Model,
+}
+
+impl StopLossMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "none" => Some(Self::Off),
+            "fixed" => Some(Self::Fixed),
+            "percent" | "pct" => Some(Self::Percent),
+            "trailing" => Some(Self::Trailing),
+            "model" => Some(Self::Model),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "OFF",
+            Self::Fixed => "FIXED",
+            Self::Percent => "PERCENT",
+            Self::Trailing => "TRAILING",
+            Self::Model => "MODEL",
+        }
+    }
+}
+
+/// Config files accept the same names (and aliases) as the CLI
+impl TryFrom<String> for StopLossMode {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s).ok_or_else(|| format!("unknown stop-loss mode: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StopLossConfig {
+    /// off, fixed, percent, trailing or model
+    #[serde(default = "default_mode")]
+    pub mode: StopLossMode,
+    /// fixed: sell when the bid drops to this price (e.g. 0.15 = 15¢)
+    #[serde(default = "default_stop_price")]
+    pub stop_price: f64,
+    /// percent: sell when the bid is this fraction below entry (0.50 = -50%)
+    #[serde(default = "default_max_loss_pct")]
+    pub max_loss_pct: f64,
+    /// trailing: sell when the bid falls this fraction below its peak since entry
+    #[serde(default = "default_trailing_pct")]
+    pub trailing_pct: f64,
+    /// model: sell when our probability is below the bid by at least this much
+    #[serde(default = "default_model_margin")]
+    pub model_margin: f64,
+}
+
+fn default_mode() -> StopLossMode { StopLossMode::Off }
+fn default_stop_price() -> f64 { 0.15 }   // 15¢
+fn default_max_loss_pct() -> f64 { 0.50 } // -50% from entry
+fn default_trailing_pct() -> f64 { 0.30 } // -30% from peak bid
+fn default_model_margin() -> f64 { 0.05 } // 5¢ below bid
+
+impl Default for StopLossConfig {
+    fn default() -> Self {
+        Self {
+            mode: default_mode(),
+            stop_price: default_stop_price(),
+            max_loss_pct: default_max_loss_pct(),
+            trailing_pct: default_trailing_pct(),
+            model_margin: default_model_margin(),
+        }
+    }
+}
+
+/// Why a stop fired
+#[derive(Debug, Clone)]
+pub struct StopTrigger {
+    pub mode: StopLossMode,
+    /// Bid level (or model-implied price for `model`) that was breached
+    pub level: f64,
+    pub reason: String,
+}
+
+/// Per-position stop state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopTracker {
+    pub mode: StopLossMode,
+    pub entry_price: f64,
+    /// Highest bid seen since entry (starts at the entry price)
+    pub peak_bid: f64,
+    /// Stop level for fixed / percent modes
+    pub stop_price: f64,
+    pub trailing_pct: f64,
+    pub model_margin: f64,
+}
+
+impl StopTracker {
+    pub fn new(config: &StopLossConfig, entry_price: f64) -> Self {
+        let stop_price = match config.mode {
+            StopLossMode::Fixed => config.stop_price,
+            StopLossMode::Percent => entry_price * (1.0 - config.max_loss_pct),
+            _ => 0.0,
+        };
+
+        Self {
+            mode: config.mode,
+            entry_price,
+            peak_bid: entry_price,
+            stop_price,
+            trailing_pct: config.trailing_pct,
+            model_margin: config.model_margin,
+        }
+    }
+
+    /// Current stop level in bid terms (None for off / model)
+    pub fn stop_level(&self) -> Option<f64> {
+        match self.mode {
+            StopLossMode::Fixed | StopLossMode::Percent => Some(self.stop_price),
+            StopLossMode::Trailing => Some(self.peak_bid * (1.0 - self.trailing_pct)),
+            StopLossMode::Off | StopLossMode::Model => None,
+        }
+    }
+
+    /// Feed the latest bid (and our probability for the token); returns a
+    /// trigger if the stop is hit. Call once per tick.
+    pub fn check(&mut self, bid: f64, our_prob: f64) -> Option<StopTrigger> {
+        if bid <= 0.0 {
+            return None; // Empty book - nothing to sell into
+        }
+        self.peak_bid = self.peak_bid.max(bid);
+
+        match self.mode {
+            StopLossMode::Off => None,
+            StopLossMode::Fixed | StopLossMode::Percent | StopLossMode::Trailing => {
+                let level = self.stop_level()?;
+                if bid > level {
+                    return None;
+                }
+                let reason = match self.mode {
+                    StopLossMode::Trailing => format!(
+                        "TRAILING STOP: bid {:.0}¢ <= {:.0}¢ ({:.0}% off peak {:.0}¢)",
+                        bid * 100.0,
+                        level * 100.0,
+                        self.trailing_pct * 100.0,
+                        self.peak_bid * 100.0
+                    ),
+                    _ => format!(
+                        "STOP-LOSS: bid {:.0}¢ <= stop {:.0}¢ (entry {:.0}¢)",
+                        bid * 100.0,
+                        level * 100.0,
+                        self.entry_price * 100.0
+                    ),
+                };
+                Some(StopTrigger { mode: self.mode, level, reason })
+            }
+            StopLossMode::Model => {
+                let level = our_prob + self.model_margin;
+                if bid < level {
+                    return None;
+                }
+                Some(StopTrigger {
+                    mode: self.mode,
+                    level,
+                    reason: format!(
+                        "MODEL STOP: P(win) {:.0}% < bid {:.0}¢ - {:.0}¢",
+                        our_prob * 100.0,
+                        bid * 100.0,
+                        self.model_margin * 100.0
+                    ),
+                })
+            }
+        }
+    }
+}
+
+/// Shares a FOK sell can fill against the visible bid size (same cap as
+/// `Executor::fok_sell_with_liquidity`); None = bid size unknown
+pub fn fillable_shares(shares: f64, bid_liquidity: Option<f64>) -> f64 {
+    match bid_liquidity {
+        Some(available) => shares.min(available.max(0.0)),
+        None => shares,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_modes() {
+        let config = |mode| StopLossConfig { mode, ..Default::default() };
+
+        // Fixed: 15¢
+        let mut fixed = StopTracker::new(&config(StopLossMode::Fixed), 0.40);
+        assert!(fixed.check(0.20, 0.5).is_none());
+        assert!(fixed.check(0.15, 0.5).is_some());
+
+        // Percent: -50% from a 40¢ entry = 20¢
+        let mut pct = StopTracker::new(&config(StopLossMode::Percent), 0.40);
+        assert!(pct.check(0.21, 0.5).is_none());
+        assert!(pct.check(0.20, 0.5).is_some());
+
+        // Trailing: peak 80¢ → stop at 56¢, even though still above entry
+        let mut trailing = StopTracker::new(&config(StopLossMode::Trailing), 0.40);
+        assert!(trailing.check(0.80, 0.9).is_none());
+        assert!(trailing.check(0.60, 0.9).is_none());
+        let trigger = trailing.check(0.55, 0.9).unwrap();
+        assert!((trigger.level - 0.56).abs() < 1e-9);
+
+        // Model: sell when P(win) + 5¢ <= bid
+        let mut model = StopTracker::new(&config(StopLossMode::Model), 0.40);
+        assert!(model.check(0.30, 0.28).is_none());
+        assert!(model.check(0.30, 0.20).is_some());
+
+        // Off never fires; empty book never fires
+        let mut off = StopTracker::new(&config(StopLossMode::Off), 0.40);
+        assert!(off.check(0.01, 0.0).is_none());
+        assert!(fixed.check(0.0, 0.0).is_none());
+
+        assert_eq!(fillable_shares(10.0, Some(4.0)), 4.0);
+        assert_eq!(fillable_shares(10.0, None), 10.0);
+    }
+
+    #[test]
+    fn test_mode_config_aliases() {
+        let mode: StopLossMode = serde_json::from_str("\"pct\"").unwrap();
+        assert_eq!(mode, StopLossMode::Percent);
+        let mode: StopLossMode = serde_json::from_str("\"Trailing\"").unwrap();
+        assert_eq!(mode, StopLossMode::Trailing);
+        assert!(serde_json::from_str::<StopLossMode>("\"sometimes\"").is_err());
+    }
+}
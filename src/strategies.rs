@@ -0,0 +1,623 @@
+//! Pluggable entry strategies
+//!
+//! Shared by btc-bot and the simulators. Each strategy implements `Strategy`
+//! and is enabled by name in the `strategies:` list of the bot YAML, with its
+//! own budget, cooldown and position caps (`StrategySlotConfig`). The
+//! `StrategyRegistry` builds the enabled strategies, enforces those limits on
+//! their intents and forwards fills and window ends.
+//!
+//! The core TERMINAL / EXIT strategies still run through
+//! `StrategyContext::decide`; the bot aggregates its decision with the
+//! registry's intents and takes the highest edge.
+//!
+//! Adding a strategy: implement `Strategy`, give it a `params` struct
+//! (deserialized from the slot's `params:` map) and add it to `build`.
+
+use anyhow::{anyhow, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Token side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Side {
+    Up,
+    Down,
+}
+
+impl Side {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Side::Up => "UP",
+            Side::Down => "DOWN",
+        }
+    }
+}
+
+/// Top of book for one token
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SideQuote {
+    pub bid: f64,
+    pub ask: f64,
+    pub bid_size: f64,
+    pub ask_size: f64,
+}
+
+/// Everything a strategy sees on one tick
+#[derive(Debug, Clone)]
+pub struct MarketView {
+    /// Wall clock (live) or simulated clock (backtests), for cooldowns
+    pub now_ms: i64,
+    pub seconds_elapsed: u32,
+    pub seconds_remaining: u32,
+    pub price_delta: f64,
+    pub up: SideQuote,
+    pub down: SideQuote,
+    /// Conservative model P(UP) / P(DOWN) for the current cell (None if unavailable)
+    pub model_p_up: Option<f64>,
+    pub model_p_down: Option<f64>,
+}
+
+impl MarketView {
+    pub fn quote(&self, side: Side) -> &SideQuote {
+        match side {
+            Side::Up => &self.up,
+            Side::Down => &self.down,
+        }
+    }
+
+    pub fn model_p(&self, side: Side) -> Option<f64> {
+        match side {
+            Side::Up => self.model_p_up,
+            Side::Down => self.model_p_down,
+        }
+    }
+}
+
+/// A buy a strategy wants to make
+#[derive(Debug, Clone)]
+pub struct Intent {
+    pub strategy: String,
+    pub side: Side,
+    /// Price to pay (normally the ask)
+    pub price: f64,
+    pub amount_usdc: f64,
+    /// Used to rank intents across strategies
+    pub edge: f64,
+    pub probability: f64,
+    /// Sell target (None = hold to settlement)
+    pub exit_target: Option<f64>,
+    pub reason: String,
+}
+
+/// A filled intent (persisted by the bot so budgets survive a restart)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fill {
+    pub strategy: String,
+    pub side: Side,
+    pub price: f64,
+    pub shares: f64,
+    pub amount_usdc: f64,
+    pub now_ms: i64,
+}
+
+pub trait Strategy: Send {
+    fn name(&self) -> &str;
+
+    /// Intents for this tick (the registry applies budget and caps)
+    fn evaluate(&mut self, view: &MarketView) -> Vec<Intent>;
+
+    fn on_fill(&mut self, _fill: &Fill) {}
+
+    /// Called once when a window closes (before the next window's first tick)
+    fn on_window_end(&mut self) {}
+}
+
+// ============================================================================
+// Config
+// ============================================================================
+
+/// One entry of the `strategies:` list
+#[derive(Debug, Clone, Deserialize)]
+pub struct StrategySlotConfig {
+    /// Strategy name (see `build`)
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Max USDC per bet
+    #[serde(default = "default_max_bet_usdc")]
+    pub max_bet_usdc: f64,
+    /// Max USDC spent per window
+    #[serde(default = "default_budget_per_window_usdc")]
+    pub budget_per_window_usdc: f64,
+    #[serde(default = "default_max_bets_per_window")]
+    pub max_bets_per_window: u32,
+    /// Min time between fills (milliseconds)
+    #[serde(default = "default_cooldown_ms")]
+    pub cooldown_ms: u32,
+    /// Max open positions opened by this strategy
+    #[serde(default = "default_max_open_positions")]
+    pub max_open_positions: u32,
+    /// Strategy-specific parameters
+    #[serde(default)]
+    pub params: serde_yaml::Value,
+}
+
+fn default_enabled() -> bool { true }
+fn default_max_bet_usdc() -> f64 { 5.0 }
+fn default_budget_per_window_usdc() -> f64 { 20.0 }
+fn default_max_bets_per_window() -> u32 { 2 }
+fn default_cooldown_ms() -> u32 { 1000 }
+fn default_max_open_positions() -> u32 { 2 }
+
+impl StrategySlotConfig {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            enabled: default_enabled(),
+            max_bet_usdc: default_max_bet_usdc(),
+            budget_per_window_usdc: default_budget_per_window_usdc(),
+            max_bets_per_window: default_max_bets_per_window(),
+            cooldown_ms: default_cooldown_ms(),
+            max_open_positions: default_max_open_positions(),
+            params: serde_yaml::Value::Null,
+        }
+    }
+}
+
+/// Deserialize a slot's params (missing = defaults)
+fn params<T: DeserializeOwned + Default>(slot: &StrategySlotConfig) -> Result<T> {
+    if slot.params.is_null() {
+        return Ok(T::default());
+    }
+    serde_yaml::from_value(slot.params.clone())
+        .with_context(|| format!("Invalid params for strategy '{}'", slot.name))
+}
+
+/// Known strategies by name
+pub fn build(slot: &StrategySlotConfig) -> Result<Box<dyn Strategy>> {
+    match slot.name.as_str() {
+        "momentum" => Ok(Box::new(MomentumStrategy::new(params(slot)?))),
+        "cheap_buy" => Ok(Box::new(CheapBuyStrategy::new(params(slot)?))),
+        other => Err(anyhow!("Unknown strategy '{}' (known: momentum, cheap_buy)", other)),
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Per-window spend of one strategy
+#[derive(Debug, Clone, Default)]
+struct SlotWindow {
+    spent_usdc: f64,
+    bets: u32,
+    last_fill_ms: Option<i64>,
+}
+
+struct Slot {
+    config: StrategySlotConfig,
+    strategy: Box<dyn Strategy>,
+    window: SlotWindow,
+}
+
+pub struct StrategyRegistry {
+    slots: Vec<Slot>,
+}
+
+impl StrategyRegistry {
+    /// Build every enabled strategy (unknown names are an error)
+    pub fn from_config(slots: &[StrategySlotConfig]) -> Result<Self> {
+        let mut built = Vec::new();
+        for config in slots.iter().filter(|s| s.enabled) {
+            if built.iter().any(|s: &Slot| s.config.name == config.name) {
+                return Err(anyhow!("Strategy '{}' listed twice", config.name));
+            }
+            built.push(Slot {
+                strategy: build(config)?,
+                config: config.clone(),
+                window: SlotWindow::default(),
+            });
+        }
+        Ok(Self { slots: built })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.slots.iter().map(|s| s.config.name.as_str()).collect()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.slots.iter().any(|s| s.config.name == name)
+    }
+
+    /// Intents from every strategy within its limits, best edge first.
+    /// `open_positions(name)` = positions currently held for that strategy.
+    pub fn evaluate(&mut self, view: &MarketView, open_positions: &dyn Fn(&str) -> u32) -> Vec<Intent> {
+        let mut intents = Vec::new();
+
+        for slot in &mut self.slots {
+            let limits = &slot.config;
+            let window = &slot.window;
+            let mut open = open_positions(&limits.name);
+
+            let cooling_down = window
+                .last_fill_ms
+                .is_some_and(|t| view.now_ms - t < limits.cooldown_ms as i64);
+            if cooling_down || window.bets >= limits.max_bets_per_window || open >= limits.max_open_positions {
+                continue;
+            }
+
+            let mut remaining = limits.budget_per_window_usdc - window.spent_usdc;
+            let mut bets_left = limits.max_bets_per_window - window.bets;
+            for mut intent in slot.strategy.evaluate(view) {
+                if remaining <= 0.0 || bets_left == 0 || open >= limits.max_open_positions {
+                    break;
+                }
+                intent.strategy = limits.name.clone();
+                intent.amount_usdc = intent.amount_usdc.min(limits.max_bet_usdc).min(remaining);
+                if intent.amount_usdc <= 0.0 || intent.price <= 0.0 {
+                    continue;
+                }
+                remaining -= intent.amount_usdc;
+                bets_left -= 1;
+                open += 1;
+                intents.push(intent);
+            }
+        }
+
+        intents.sort_by(|a, b| b.edge.partial_cmp(&a.edge).unwrap_or(std::cmp::Ordering::Equal));
+        intents
+    }
+
+    /// Record a fill (ignored for strategies not in the registry, e.g. TERMINAL)
+    pub fn on_fill(&mut self, fill: &Fill) {
+        if let Some(slot) = self.slots.iter_mut().find(|s| s.config.name == fill.strategy) {
+            slot.window.spent_usdc += fill.amount_usdc;
+            slot.window.bets += 1;
+            slot.window.last_fill_ms = Some(fill.now_ms);
+            slot.strategy.on_fill(fill);
+        }
+    }
+
+    /// Rebuild the current window's budgets and strategy state from its fills
+    /// (after a restart or leader takeover)
+    pub fn restore_window(&mut self, fills: &[Fill]) {
+        self.on_window_end();
+        for fill in fills {
+            self.on_fill(fill);
+        }
+    }
+
+    /// Reset per-window budgets and notify every strategy
+    pub fn on_window_end(&mut self) {
+        for slot in &mut self.slots {
+            slot.window = SlotWindow::default();
+            slot.strategy.on_window_end();
+        }
+    }
+}
+
+// ============================================================================
+// Momentum
+// ============================================================================
+
+/// Follow a BTC move that is already large, if the model still shows edge
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MomentumParams {
+    /// Min |BTC delta| from window open ($)
+    pub min_delta: f64,
+    pub min_seconds_elapsed: u32,
+    pub max_seconds_elapsed: u32,
+    /// Don't pay more than this for the token
+    pub max_entry_price: f64,
+    /// Min (model P - ask) / ask
+    pub min_edge: f64,
+    pub bet_usdc: f64,
+}
+
+impl Default for MomentumParams {
+    fn default() -> Self {
+        Self {
+            min_delta: 50.0,
+            min_seconds_elapsed: 120,
+            max_seconds_elapsed: 720,
+            max_entry_price: 0.65,
+            min_edge: 0.05,
+            bet_usdc: 5.0,
+        }
+    }
+}
+
+pub struct MomentumStrategy {
+    params: MomentumParams,
+}
+
+impl MomentumStrategy {
+    pub fn new(params: MomentumParams) -> Self {
+        Self { params }
+    }
+}
+
+impl Strategy for MomentumStrategy {
+    fn name(&self) -> &str {
+        "momentum"
+    }
+
+    fn evaluate(&mut self, view: &MarketView) -> Vec<Intent> {
+        let p = &self.params;
+        if view.seconds_elapsed < p.min_seconds_elapsed
+            || view.seconds_elapsed > p.max_seconds_elapsed
+            || view.price_delta.abs() < p.min_delta
+        {
+            return Vec::new();
+        }
+
+        let side = if view.price_delta > 0.0 { Side::Up } else { Side::Down };
+        let ask = view.quote(side).ask;
+        let Some(prob) = view.model_p(side) else {
+            return Vec::new();
+        };
+        if ask <= 0.0 || ask > p.max_entry_price {
+            return Vec::new();
+        }
+
+        let edge = (prob - ask) / ask;
+        if edge < p.min_edge {
+            return Vec::new();
+        }
+
+        vec![Intent {
+            strategy: self.name().to_string(),
+            side,
+            price: ask,
+            amount_usdc: p.bet_usdc,
+            edge,
+            probability: prob,
+            exit_target: None,
+            reason: format!(
+                "MOMENTUM: delta ${:+.0}, {} ask {:.0}¢ vs P={:.1}% (edge {:.1}%)",
+                view.price_delta,
+                side.as_str(),
+                ask * 100.0,
+                prob * 100.0,
+                edge * 100.0
+            ),
+        }]
+    }
+}
+
+// ============================================================================
+// Cheap buy (lottery tickets)
+// ============================================================================
+
+/// Buy a very cheap token while the model still gives it a real chance, and
+/// sell into any rebound (formerly the btc-cheap-buy scenario)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CheapBuyParams {
+    /// Price ceiling: buy when ask <= this
+    pub max_price: f64,
+    pub min_seconds_elapsed: u32,
+    pub max_seconds_elapsed: u32,
+    /// Model P must be at least this multiple of the ask (0 = no model check)
+    pub min_prob_ratio: f64,
+    /// Sell when the bid reaches this (>= 1.0 = hold to settlement)
+    pub exit_target: f64,
+    pub bet_usdc: f64,
+}
+
+impl Default for CheapBuyParams {
+    fn default() -> Self {
+        Self {
+            max_price: 0.10,
+            min_seconds_elapsed: 0,
+            max_seconds_elapsed: 180,
+            min_prob_ratio: 1.0,
+            exit_target: 0.70,
+            bet_usdc: 5.0,
+        }
+    }
+}
+
+/// One ticket per side per window
+pub struct CheapBuyStrategy {
+    params: CheapBuyParams,
+    bought_up: bool,
+    bought_down: bool,
+}
+
+impl CheapBuyStrategy {
+    pub fn new(params: CheapBuyParams) -> Self {
+        Self { params, bought_up: false, bought_down: false }
+    }
+}
+
+impl Strategy for CheapBuyStrategy {
+    fn name(&self) -> &str {
+        "cheap_buy"
+    }
+
+    fn evaluate(&mut self, view: &MarketView) -> Vec<Intent> {
+        let p = &self.params;
+        if view.seconds_elapsed < p.min_seconds_elapsed || view.seconds_elapsed > p.max_seconds_elapsed {
+            return Vec::new();
+        }
+
+        let mut intents = Vec::new();
+        for (side, bought) in [(Side::Up, self.bought_up), (Side::Down, self.bought_down)] {
+            let ask = view.quote(side).ask;
+            if bought || ask <= 0.0 || ask > p.max_price {
+                continue;
+            }
+
+            let prob = view.model_p(side);
+            if p.min_prob_ratio > 0.0 && !prob.is_some_and(|prob| prob >= ask * p.min_prob_ratio) {
+                continue;
+            }
+
+            let edge = prob.map(|prob| (prob - ask) / ask).unwrap_or(0.0);
+            intents.push(Intent {
+                strategy: self.name().to_string(),
+                side,
+                price: ask,
+                amount_usdc: p.bet_usdc,
+                edge,
+                probability: prob.unwrap_or(0.0),
+                exit_target: (p.exit_target < 1.0).then_some(p.exit_target),
+                reason: format!(
+                    "CHEAP BUY: {} ask {:.0}¢ <= {:.0}¢, P={} at {}s, exit {}",
+                    side.as_str(),
+                    ask * 100.0,
+                    p.max_price * 100.0,
+                    prob.map(|prob| format!("{:.1}%", prob * 100.0)).unwrap_or_else(|| "n/a".to_string()),
+                    view.seconds_elapsed,
+                    if p.exit_target < 1.0 { format!("{:.0}¢", p.exit_target * 100.0) } else { "HOLD".to_string() }
+                ),
+            });
+        }
+        intents
+    }
+
+    fn on_fill(&mut self, fill: &Fill) {
+        match fill.side {
+            Side::Up => self.bought_up = true,
+            Side::Down => self.bought_down = true,
+        }
+    }
+
+    fn on_window_end(&mut self) {
+        self.bought_up = false;
+        self.bought_down = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(now_ms: i64, delta: f64, up_ask: f64, p_up: f64) -> MarketView {
+        MarketView {
+            now_ms,
+            seconds_elapsed: 300,
+            seconds_remaining: 600,
+            price_delta: delta,
+            up: SideQuote { bid: up_ask - 0.01, ask: up_ask, bid_size: 100.0, ask_size: 100.0 },
+            down: SideQuote { bid: 0.98 - up_ask, ask: 1.0 - up_ask, bid_size: 100.0, ask_size: 100.0 },
+            model_p_up: Some(p_up),
+            model_p_down: Some(1.0 - p_up),
+        }
+    }
+
+    #[test]
+    fn test_momentum_intent() {
+        let mut momentum = MomentumStrategy::new(MomentumParams::default());
+
+        // +$80, UP at 55¢ with P=70% → 27% edge
+        let intents = momentum.evaluate(&view(0, 80.0, 0.55, 0.70));
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].side, Side::Up);
+        assert!((intents[0].edge - (0.70 - 0.55) / 0.55).abs() < 1e-9);
+
+        // Delta too small, price too high, no edge
+        assert!(momentum.evaluate(&view(0, 20.0, 0.55, 0.70)).is_empty());
+        assert!(momentum.evaluate(&view(0, 80.0, 0.70, 0.90)).is_empty());
+        assert!(momentum.evaluate(&view(0, 80.0, 0.55, 0.56)).is_empty());
+    }
+
+    #[test]
+    fn test_registry_limits() {
+        let mut slot = StrategySlotConfig::new("momentum");
+        slot.max_bet_usdc = 4.0;
+        slot.budget_per_window_usdc = 6.0;
+        slot.max_bets_per_window = 5;
+        slot.cooldown_ms = 1000;
+        let mut registry = StrategyRegistry::from_config(&[slot]).unwrap();
+        let none_open = |_: &str| 0;
+
+        // Capped by max_bet
+        let intents = registry.evaluate(&view(0, 80.0, 0.55, 0.70), &none_open);
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].strategy, "momentum");
+        assert_eq!(intents[0].amount_usdc, 4.0);
+
+        let fill = Fill {
+            strategy: "momentum".to_string(),
+            side: Side::Up,
+            price: 0.55,
+            shares: 4.0 / 0.55,
+            amount_usdc: 4.0,
+            now_ms: 0,
+        };
+        registry.on_fill(&fill);
+
+        // Cooldown, then capped by the remaining $2 of budget
+        assert!(registry.evaluate(&view(500, 80.0, 0.55, 0.70), &none_open).is_empty());
+        let intents = registry.evaluate(&view(1500, 80.0, 0.55, 0.70), &none_open);
+        assert_eq!(intents[0].amount_usdc, 2.0);
+
+        // Position cap
+        assert!(registry.evaluate(&view(1500, 80.0, 0.55, 0.70), &|_: &str| 2).is_empty());
+
+        // Budget spent, then reset by the window end
+        let last = Fill { amount_usdc: 2.0, now_ms: 1500, ..fill.clone() };
+        registry.on_fill(&last);
+        assert!(registry.evaluate(&view(5000, 80.0, 0.55, 0.70), &none_open).is_empty());
+        registry.on_window_end();
+        assert_eq!(registry.evaluate(&view(5000, 80.0, 0.55, 0.70), &none_open).len(), 1);
+
+        // Replaying the window's fills restores the spent budget
+        registry.restore_window(&[fill, last]);
+        assert!(registry.evaluate(&view(5000, 80.0, 0.55, 0.70), &none_open).is_empty());
+    }
+
+    #[test]
+    fn test_cheap_buy() {
+        let mut cheap = CheapBuyStrategy::new(CheapBuyParams { min_prob_ratio: 1.5, ..Default::default() });
+
+        // UP at 8¢ with P(UP)=15% (1.9×) → ticket with a 70¢ exit
+        let mut v = view(0, -60.0, 0.08, 0.15);
+        v.seconds_elapsed = 100;
+        let intents = cheap.evaluate(&v);
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].side, Side::Up);
+        assert_eq!(intents[0].exit_target, Some(0.70));
+
+        // Model doesn't back it (P=10% < 1.5 × 8¢), too late in the window
+        assert!(cheap.evaluate(&view(0, -60.0, 0.08, 0.10)).is_empty());
+        v.seconds_elapsed = 200;
+        assert!(cheap.evaluate(&v).is_empty());
+
+        // One ticket per side until the window ends
+        v.seconds_elapsed = 100;
+        cheap.on_fill(&Fill {
+            strategy: "cheap_buy".to_string(),
+            side: Side::Up,
+            price: 0.08,
+            shares: 62.5,
+            amount_usdc: 5.0,
+            now_ms: 0,
+        });
+        assert!(cheap.evaluate(&v).is_empty());
+        cheap.on_window_end();
+        assert_eq!(cheap.evaluate(&v).len(), 1);
+    }
+
+    #[test]
+    fn test_registry_config() {
+        let yaml = "- name: momentum\n  max_bet_usdc: 3.0\n  params:\n    min_delta: 100.0\n- name: momentum\n  enabled: false\n";
+        let slots: Vec<StrategySlotConfig> = serde_yaml::from_str(yaml).unwrap();
+        let registry = StrategyRegistry::from_config(&slots).unwrap();
+        assert_eq!(registry.names(), vec!["momentum"]);
+
+        let unknown = StrategySlotConfig::new("nope");
+        assert!(StrategyRegistry::from_config(&[unknown]).is_err());
+    }
+}